@@ -0,0 +1,68 @@
+//! Measures the cost of collecting column statistics while fetching. Runs against an in-memory
+//! SQLite database, so it requires the SQLite ODBC driver to be installed. See
+//! `tests/integration_sqlite.rs`.
+//!
+//! ```shell
+//! cargo bench --bench column_statistics --features integration-tests
+//! ```
+
+use arrow_odbc::{
+    odbc_api::{Connection, ConnectionOptions, Environment},
+    OdbcReaderBuilder,
+};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+/// Each connection to this data source creates a fresh, empty database living in memory.
+const SQLITE: &str = "Driver={SQLite3};Database=:memory:;";
+
+const NUM_ROWS: usize = 1_000_000;
+
+/// Creates a table with a column of each type statistics are collected for.
+fn setup_table(conn: &Connection<'_>) {
+    conn.execute(
+        "CREATE TABLE Measurements (a INTEGER, b BIGINT, c DOUBLE, d DATE, e VARCHAR(20))",
+        (),
+    )
+    .unwrap();
+    let insert = format!(
+        "WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < {NUM_ROWS}) \
+        INSERT INTO Measurements SELECT n % 1000, n * 7919, n / 3.0, \
+        date('2000-01-01', printf('+%d days', n % 10000)), \
+        CASE WHEN n % 10 = 0 THEN NULL ELSE printf('value %d', n) END FROM seq"
+    );
+    conn.execute(&insert, ()).unwrap();
+}
+
+fn fetch_all(conn: &Connection<'_>, column_statistics: bool) {
+    let cursor = conn
+        .execute("SELECT * FROM Measurements", ())
+        .unwrap()
+        .unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .with_column_statistics(column_statistics)
+        .build(cursor)
+        .unwrap();
+    for batch in reader.by_ref() {
+        batch.unwrap();
+    }
+}
+
+fn column_statistics(c: &mut Criterion) {
+    let env = Environment::new().unwrap();
+    let conn = env
+        .connect_with_connection_string(SQLITE, ConnectionOptions::default())
+        .unwrap();
+    setup_table(&conn);
+
+    let mut group = c.benchmark_group("column_statistics");
+    // Each iteration fetches a million rows, so fewer samples suffice.
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+    for (name, column_statistics) in [("without", false), ("with", true)] {
+        group.bench_function(name, |b| b.iter(|| fetch_all(&conn, column_statistics)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, column_statistics);
+criterion_main!(benches);
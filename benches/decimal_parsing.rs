@@ -0,0 +1,48 @@
+//! Compares parsing decimal text as fetched from the database with and without the `simd` feature.
+//!
+//! ```shell
+//! cargo bench --bench decimal_parsing
+//! cargo bench --bench decimal_parsing --features simd
+//! ```
+
+use arrow_odbc::parse_decimal_text;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+
+/// Decimal texts, like a driver would return them for a `DECIMAL(precision, 2)` column.
+fn decimal_texts(num_rows: usize, precision: usize) -> Vec<Vec<u8>> {
+    (0..num_rows)
+        .map(|row| {
+            let digits: String = (0..precision)
+                .map(|index| char::from(b'0' + ((row + index) % 10) as u8))
+                .collect();
+            let (integer, fraction) = digits.split_at(precision - 2);
+            let sign = if row % 3 == 0 { "-" } else { "" };
+            format!("{sign}{integer}.{fraction}").into_bytes()
+        })
+        .collect()
+}
+
+fn decimal_parsing(c: &mut Criterion) {
+    const NUM_ROWS: usize = 100_000;
+    let mut group = c.benchmark_group("decimal_parsing");
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+    for precision in [10, 18, 38] {
+        let texts = decimal_texts(NUM_ROWS, precision);
+        group.bench_function(format!("precision_{precision}"), |b| {
+            b.iter_batched(
+                || texts.clone(),
+                |texts| {
+                    texts
+                        .iter()
+                        .map(|text| parse_decimal_text(black_box(text), 2).unwrap())
+                        .fold(0i128, i128::wrapping_add)
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, decimal_parsing);
+criterion_main!(benches);
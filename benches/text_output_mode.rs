@@ -0,0 +1,83 @@
+//! Compares fetching a string heavy table into `Utf8`, `LargeUtf8` and `Utf8View` arrays. Runs
+//! against an in-memory SQLite database, so it requires the SQLite ODBC driver to be installed. See
+//! `tests/integration_sqlite.rs`.
+//!
+//! ```shell
+//! cargo bench --bench text_output_mode --features integration-tests
+//! ```
+
+use arrow_odbc::{
+    odbc_api::{Connection, ConnectionOptions, Environment},
+    OdbcReaderBuilder, TextOutputMode,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+/// Each connection to this data source creates a fresh, empty database living in memory.
+const SQLITE: &str = "Driver={SQLite3};Database=:memory:;";
+
+const NUM_ROWS: usize = 1_000_000;
+const NUM_COLUMNS: usize = 10;
+
+/// Creates a table with ten text columns and a million rows. Values are short enough to be inlined
+/// into the views of a `StringViewArray`, every tenth one is too long for that.
+fn setup_table(conn: &Connection<'_>) {
+    let columns: Vec<_> = (0..NUM_COLUMNS)
+        .map(|i| format!("c{i} VARCHAR(40)"))
+        .collect();
+    conn.execute(
+        &format!("CREATE TABLE Strings ({})", columns.join(", ")),
+        (),
+    )
+    .unwrap();
+    let values: Vec<_> = (0..NUM_COLUMNS)
+        .map(|i| {
+            format!(
+                "CASE WHEN n % 10 = 0 THEN printf('a long value of row %d in column {i}', n) \
+                ELSE printf('v%d', n) END"
+            )
+        })
+        .collect();
+    let insert = format!(
+        "WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < {NUM_ROWS}) \
+        INSERT INTO Strings SELECT {} FROM seq",
+        values.join(", ")
+    );
+    conn.execute(&insert, ()).unwrap();
+}
+
+/// Fetches the entire table. Returns the memory occupied by the Arrow arrays, so filling them can
+/// not be optimized away.
+fn fetch_all(conn: &Connection<'_>, text_output_mode: TextOutputMode) -> usize {
+    let cursor = conn.execute("SELECT * FROM Strings", ()).unwrap().unwrap();
+    let reader = OdbcReaderBuilder::new()
+        .with_text_output_mode(text_output_mode)
+        .build(cursor)
+        .unwrap();
+    reader
+        .map(|batch| batch.unwrap().get_array_memory_size())
+        .sum()
+}
+
+fn text_output_mode(c: &mut Criterion) {
+    let env = Environment::new().unwrap();
+    let conn = env
+        .connect_with_connection_string(SQLITE, ConnectionOptions::default())
+        .unwrap();
+    setup_table(&conn);
+
+    let mut group = c.benchmark_group("text_output_mode");
+    // Each iteration fetches a million rows, so fewer samples suffice.
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+    for (name, mode) in [
+        ("utf8", TextOutputMode::Utf8),
+        ("large_utf8", TextOutputMode::LargeUtf8),
+        ("utf8_view", TextOutputMode::Utf8View),
+    ] {
+        group.bench_function(name, |b| b.iter(|| black_box(fetch_all(&conn, mode))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, text_output_mode);
+criterion_main!(benches);
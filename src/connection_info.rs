@@ -0,0 +1,76 @@
+/// Replacement for the values of attributes which must not show up in logs or error messages.
+const MASK: &str = "***";
+
+/// Describes the data source of an ODBC connection string, without giving away credentials. The
+/// values of attributes like `PWD`, `Password`, `UID`, or any attribute whose name contains
+/// `Token`, `Secret` or `Key` are replaced with `***`. Other attributes like `Driver`, `DSN` or
+/// `Server` are kept as they are. Keywords are compared case insensitive. Values may be enclosed in
+/// braces (e.g. `PWD={my;password}`), in which case they may contain semicolons.
+///
+/// ```
+/// use arrow_odbc::sanitize_connection_string;
+///
+/// let sanitized = sanitize_connection_string(
+///     "Driver={ODBC Driver 17 for SQL Server};Server=localhost;UID=SA;PWD={My;Password}",
+/// );
+/// assert_eq!(
+///     "Driver={ODBC Driver 17 for SQL Server};Server=localhost;UID=***;PWD=***",
+///     sanitized
+/// );
+/// ```
+pub fn sanitize_connection_string(connection_string: &str) -> String {
+    let mut attributes = Vec::new();
+    let mut rest = connection_string;
+    while !rest.is_empty() {
+        let (key, after_key) = rest.split_once('=').unwrap_or((rest, ""));
+        let (value, remainder) = split_value(after_key);
+        rest = remainder;
+        let key = key.trim();
+        if key.is_empty() {
+            continue;
+        }
+        if is_secret(key) {
+            attributes.push(format!("{key}={MASK}"));
+        } else {
+            attributes.push(format!("{key}={value}"));
+        }
+    }
+    attributes.join(";")
+}
+
+/// Splits the value of an attribute from the remaining connection string. Braced values end with a
+/// closing brace which is not escaped by doubling it (`}}`), everything else at the next semicolon.
+fn split_value(text: &str) -> (&str, &str) {
+    if text.trim_start().starts_with('{') {
+        let bytes = text.as_bytes();
+        let mut index = text.find('{').unwrap() + 1;
+        while index < bytes.len() {
+            if bytes[index] == b'}' {
+                if bytes.get(index + 1) == Some(&b'}') {
+                    index += 2;
+                    continue;
+                }
+                let (value, remainder) = text.split_at(index + 1);
+                let remainder = remainder.split_once(';').map_or("", |(_, rest)| rest);
+                return (value.trim(), remainder);
+            }
+            index += 1;
+        }
+        // Unterminated braces. Treat the rest as value, rather than leaking it as another keyword.
+        (text.trim(), "")
+    } else {
+        let (value, remainder) = text.split_once(';').unwrap_or((text, ""));
+        (value.trim(), remainder)
+    }
+}
+
+/// `true` for keywords whose values are credentials.
+fn is_secret(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    matches!(
+        key.as_str(),
+        "PWD" | "PASSWORD" | "UID" | "USER" | "USERNAME" | "USER ID"
+    ) || ["PASSWORD", "TOKEN", "SECRET", "KEY"]
+        .iter()
+        .any(|part| key.contains(part))
+}
@@ -6,7 +6,7 @@ use arrow::{
         ArrowPrimitiveType, Time32MillisecondType, Time64MicrosecondType, Time64NanosecondType,
     },
 };
-use chrono::{DateTime, Datelike, NaiveDate, Timelike};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike};
 use odbc_api::{
     buffers::{AnySliceMut, BufferDesc, TextColumnSliceMut},
     sys::{Date, Time, Timestamp},
@@ -23,6 +23,24 @@ pub fn days_since_epoch(date: &Date) -> i32 {
     duration.num_days().try_into().unwrap()
 }
 
+/// Transform date to milliseconds since unix epoch, as used by `Date64`
+pub fn ms_since_epoch_of_date(date: &Date) -> i64 {
+    days_since_epoch(date) as i64 * 24 * 60 * 60 * 1000
+}
+
+/// Timezone naive date and time of an ODBC timestamp, including the full nanosecond fraction.
+pub fn naive_date_time(from: &Timestamp) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(from.year as i32, from.month as u32, from.day as u32)
+        .unwrap()
+        .and_hms_nano_opt(
+            from.hour as u32,
+            from.minute as u32,
+            from.second as u32,
+            from.fraction,
+        )
+        .unwrap()
+}
+
 pub fn seconds_since_epoch(from: &Timestamp) -> i64 {
     let ndt = NaiveDate::from_ymd_opt(from.year as i32, from.month as u32, from.day as u32)
         .unwrap()
@@ -44,6 +62,8 @@ pub fn ms_since_epoch(from: &Timestamp) -> i64 {
     ndt.and_utc().timestamp_millis()
 }
 
+/// ODBC transmits fractions of a second in nanoseconds. Digits finer than a microsecond are
+/// truncated.
 pub fn us_since_epoch(from: &Timestamp) -> i64 {
     let ndt = NaiveDate::from_ymd_opt(from.year as i32, from.month as u32, from.day as u32)
         .unwrap()
@@ -107,6 +127,11 @@ pub fn epoch_to_date(from: i32) -> Date {
     }
 }
 
+/// Elapsed seconds since midnight of an ODBC time
+pub fn seconds_since_midnight(from: &Time) -> i32 {
+    (from.hour as i32 * 60 + from.minute as i32) * 60 + from.second as i32
+}
+
 pub fn sec_since_midnight_to_time(from: i32) -> Time {
     let unit_min = 60;
     let unit_hour = unit_min * 60;
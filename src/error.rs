@@ -1,13 +1,29 @@
+use arrow::{datatypes::DataType as ArrowDataType, error::ArrowError};
+use odbc_api::DataType as OdbcDataType;
 use thiserror::Error;
 
 use crate::reader::ColumnFailure;
 
 /// A variation of things which can go wrong then creating an [`crate::OdbcReader`].
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Failure to retrieve the number of columns from the result set.
     #[error("Unable to retrieve number of columns in result set.\n{0}")]
     UnableToRetrieveNumCols(odbc_api::Error),
+    /// The timezone passed to [`crate::OdbcReaderBuilder::with_timestamp_timezone`] is neither a
+    /// fixed offset (e.g. `+02:00`), nor a timezone name known to Arrow.
+    #[error(
+        "Invalid timestamp timezone. Timezone names (e.g. `Europe/Berlin`) require the `chrono-tz` \
+        feature of Arrow.\n{0}"
+    )]
+    InvalidTimezone(ArrowError),
+    /// The driver reported a negative number of columns for the result set.
+    #[error("The ODBC driver reported an invalid number of columns for the result set: {0}")]
+    InvalidNumCols(i16),
+    /// Failure to retrieve the number of rows affected by the statement.
+    #[error("Unable to retrieve the number of rows affected by the statement.\n{0}")]
+    UnableToRetrieveRowCount(odbc_api::Error),
     /// Indicates that the error is related to a specify column.
     #[error(
         "There is a problem with the SQL type of the column with name: {} and index {}:\n{source}",
@@ -35,6 +51,106 @@ pub enum Error {
         max_bytes_per_batch: usize,
         bytes_per_row: usize,
     },
+    /// The result set has more columns than allowed by
+    /// [`crate::BufferAllocationOptions::max_column_count`].
+    #[error(
+        "The result set has {actual} columns, yet at most {limit} columns are allowed. This limit \
+        protects against allocating huge buffers for queries like `SELECT *` on wide tables. \
+        Please select fewer columns, or raise the limit."
+    )]
+    TooManyColumns { actual: usize, limit: usize },
+    /// Failure to fetch a batch or to write it into an Arrow IPC stream in
+    /// [`crate::OdbcReader::into_ipc_stream`].
+    #[error("Unable to stream record batches in Arrow IPC format.\n{0}")]
+    IpcStream(ArrowError),
+    /// Failure to prepare the statement of an [`crate::OdbcPreparedReader`].
+    #[error("Unable to prepare statement.\n{0}")]
+    UnableToPrepare(odbc_api::Error),
+    /// Failure to query the number or the types of parameters of a prepared statement.
+    #[error("Unable to describe the parameters of the prepared statement.\n{0}")]
+    UnableToDescribeParameters(odbc_api::Error),
+    /// Failure to execute a prepared statement.
+    #[error("Unable to execute the prepared statement.\n{0}")]
+    UnableToExecute(odbc_api::Error),
+    /// [`crate::OdbcPreparedReader::execute_with_params`] binds a single set of parameters.
+    #[error(
+        "Parameters must be passed as a record batch with exactly one row, yet it has {num_rows}."
+    )]
+    ParameterRowCount { num_rows: usize },
+    /// [`crate::OdbcPaginatedReader::new`] has been passed a batch size of zero.
+    #[error("The batch size of a paginated reader must not be zero.")]
+    ZeroPageSize,
+    /// The Arrow type of a parameter can not be bound to a prepared statement.
+    #[error("The parameter at index {index} has Arrow type {data_type}, which is not supported.")]
+    UnsupportedParameterType {
+        // Zero based index of the parameter
+        index: usize,
+        data_type: ArrowDataType,
+    },
+    /// The statement attribute passed to [`crate::OdbcReaderBuilder::with_statement_attribute`]
+    /// controls how buffers are bound to the statement, which is up to the reader.
+    #[error(
+        "Statement attribute {attribute} ({name}) is set by the reader itself, in order to bind \
+        its buffers. Setting it would break fetching."
+    )]
+    ReservedStatementAttribute { attribute: u32, name: &'static str },
+    /// Text values of statement attributes are passed as null terminated strings.
+    #[error("The value of statement attribute {attribute} must not contain NUL characters.")]
+    InvalidStatementAttributeValue { attribute: u32 },
+    /// Failure to set a statement attribute using `SQLSetStmtAttr`.
+    #[error("Unable to set statement attribute {attribute}.\n{source}")]
+    UnableToSetStatementAttribute {
+        attribute: u32,
+        source: odbc_api::Error,
+    },
+    /// Failure to query the attributes of the result set columns, see
+    /// [`crate::OdbcReader::columns_metadata`].
+    #[error("Unable to describe the columns of the result set.\n{0}")]
+    UnableToDescribeColumns(odbc_api::Error),
+    /// Failure to fetch the rows probed in order to infer the schema, see
+    /// [`crate::OdbcReaderBuilder::with_lazy_schema_inference`].
+    #[error("Unable to fetch the rows probed in order to infer the schema.\n{0}")]
+    LazySchemaProbe(ArrowError),
+    /// Failure to fetch a batch in [`crate::OdbcReader::next_with_stats`] or
+    /// [`crate::OdbcReader::into_chunked_arrays`].
+    #[error("Unable to fetch record batch.\n{0}")]
+    FetchBatch(ArrowError),
+    /// Failure to describe the statistics of a batch as Parquet column chunk metadata. Only exists
+    /// with the `parquet` feature. Since [`Error`] is `#[non_exhaustive]`, matches on it compile
+    /// no matter whether some other crate in the dependency graph enables this feature.
+    #[cfg(feature = "parquet")]
+    #[error("Unable to compute Parquet statistics of the record batch.\n{0}")]
+    ParquetStatistics(parquet::errors::ParquetError),
+    /// Failure to bind the row status array requested with
+    /// [`crate::OdbcReaderBuilder::check_row_status`] to the statement.
+    #[error("Unable to bind row status array to the statement.\n{0}")]
+    UnableToBindRowStatus(odbc_api::Error),
+    /// A null pointer has been passed to [`crate::OdbcReader::export_to_c_stream`].
+    #[error("The pointer to export the Arrow C stream into must not be null.")]
+    NullArrowArrayStream,
+    /// Failure to unbind the buffers from the previous cursor, to describe the columns of either
+    /// cursor, or to bind the buffers to the new one in [`crate::OdbcReader::rebind`].
+    #[error("Unable to rebind the buffers of the reader to another cursor.\n{0}")]
+    UnableToRebind(odbc_api::Error),
+    /// The columns of the cursor passed to [`crate::OdbcReader::rebind`] differ from the ones the
+    /// buffers of the reader have been allocated for.
+    #[error(
+        "The relational types of the columns of the new cursor {actual:?} differ from the ones of \
+        the previous cursor {expected:?}. The buffers of the reader can only be reused for result \
+        sets with identical columns. Please build a new reader instead."
+    )]
+    IncompatibleCursor {
+        expected: Vec<OdbcDataType>,
+        actual: Vec<OdbcDataType>,
+    },
+    /// Wraps errors of readers built with [`crate::OdbcReaderBuilder::with_connection_info`], in
+    /// order to tell which data source they originate from.
+    #[error("{source}\nData source: {sanitized_connection_info}")]
+    WithConnectionInfo {
+        /// Connection string with masked credentials, see [`crate::sanitize_connection_string`].
+        sanitized_connection_info: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
     /// We use UTF-16 encoding on windows by default. Since UTF-8 locals on windows system can not
     /// be expected to be the default. Since we use wide methods the ODBC standard demands the
     /// encoding to be UTF-16.
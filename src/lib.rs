@@ -41,12 +41,15 @@
 //!     Ok(())
 //! }
 //! ```
+mod connection_info;
 mod date_time;
 mod decimal;
 mod error;
 mod odbc_writer;
 mod reader;
 mod schema;
+#[cfg(feature = "datafusion")]
+mod table_provider;
 
 // Rexport odbc_api and arrow to make it easier for downstream crates to depend to avoid version
 // mismatches
@@ -54,10 +57,37 @@ pub use arrow;
 pub use odbc_api;
 
 pub use self::{
+    connection_info::sanitize_connection_string,
     error::Error,
-    odbc_writer::{insert_into_table, insert_statement_from_schema, OdbcWriter, WriterError},
+    odbc_writer::{
+        create_table_if_not_exists_statement, insert_into_table, insert_statement_from_schema,
+        merge_statement_from_schema, schema_to_create_table, OdbcWriter, OdbcWriterOptions,
+        SqlDialect, WriterError,
+    },
     reader::{
-        BufferAllocationOptions, ColumnFailure, ConcurrentOdbcReader, OdbcReader, OdbcReaderBuilder,
+        suggestions_for_type, BufferAllocationOptions, ChunkedArray, ColumnFailure,
+        ColumnStatistics, ColumnStrategyRegistry, ColumnWarning, ConcurrentOdbcReader,
+        FloatCoercion, IntegerCoercion, MappingError, MissingColumnBehavior, NullHook,
+        OdbcColumnMetadata, OdbcPaginatedReader, OdbcPreparedReader, OdbcReader, OdbcReaderBuilder,
+        OdbcReaderMetrics, ParameterDescription, PostFetchTransform, ReadStrategy, ScalarValue,
+        SpannerCompatibility, StatementAttributeValue, StrategyFactory, SubPrecisionHandling,
+        TextOutputMode,
     },
     schema::arrow_schema_from,
 };
+
+#[cfg(feature = "async")]
+pub use self::odbc_writer::AsyncOdbcWriter;
+
+#[cfg(feature = "datafusion")]
+pub use self::table_provider::OdbcTableProvider;
+
+#[cfg(feature = "abi")]
+pub use self::reader::free_c_stream;
+
+#[cfg(feature = "parquet")]
+pub use self::reader::OdbcRecordBatchWithStats;
+
+// Not part of the stable API, exported for `benches/decimal_parsing.rs`.
+#[doc(hidden)]
+pub use self::reader::parse_decimal_text;
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, cmp::min};
+use std::{borrow::Cow, cmp::min, fs::File, io::BufReader, path::Path};
 
 use thiserror::Error;
 
@@ -11,7 +11,8 @@ use arrow::{
         TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt8Type,
     },
     error::ArrowError,
-    record_batch::{RecordBatch, RecordBatchReader},
+    ipc::reader::FileReader,
+    record_batch::{RecordBatch, RecordBatchIterator, RecordBatchReader},
 };
 use odbc_api::{
     buffers::{AnyBuffer, AnySliceMut, BufferDesc},
@@ -26,16 +27,29 @@ use crate::{
     decimal::{NullableDecimal128AsText, NullableDecimal256AsText},
 };
 
+pub use self::{
+    create_table::{create_table_if_not_exists_statement, schema_to_create_table, SqlDialect},
+    merge::merge_statement_from_schema,
+};
+
+#[cfg(feature = "async")]
+pub use self::async_writer::AsyncOdbcWriter;
+
 use self::{
-    binary::VariadicBinary,
+    binary::{BinaryViewToBinary, VariadicBinary},
     boolean::boolean_to_bit,
     map_arrow_to_odbc::MapArrowToOdbc,
-    text::{LargeUtf8ToNativeText, Utf8ToNativeText},
+    merge::dialect_from_dbms_name,
+    text::{LargeUtf8ToNativeText, Utf8ToNativeText, Utf8ViewToNativeText},
 };
 
+#[cfg(feature = "async")]
+mod async_writer;
 mod binary;
 mod boolean;
+mod create_table;
 mod map_arrow_to_odbc;
+mod merge;
 mod text;
 
 /// Fastest and most convinient way to stream the contents of arrow record batches into a database
@@ -58,6 +72,46 @@ pub fn insert_into_table(
     inserter.write_all(batches)
 }
 
+/// Options for [`OdbcWriter::insert_with_options`].
+#[derive(Debug, Clone, Copy)]
+pub struct OdbcWriterOptions {
+    /// Delete all rows of the table before inserting the batches, in order to replace its contents
+    /// with a new snapshot. Deleting and inserting happen in the same transaction, so if inserting
+    /// fails the table keeps its original rows. Microsoft SQL Server and PostgreSQL are emptied
+    /// using `TRUNCATE TABLE`, all other databases using `DELETE FROM`, since `TRUNCATE` commits
+    /// implicitly on some of them (e.g. MySQL or Oracle). `false` by default.
+    pub truncate_before_insert: bool,
+    /// Whether the connection is in auto commit mode, which is the default for ODBC connections.
+    /// `odbc_api` offers no way to ask the connection, so the writer relies on this to restore the
+    /// mode after replacing the contents of a table. If `false`, deleting and inserting take part
+    /// in the transaction of the application, which is neither committed nor rolled back by the
+    /// writer. `true` by default.
+    pub autocommit: bool,
+}
+
+impl Default for OdbcWriterOptions {
+    fn default() -> Self {
+        Self {
+            truncate_before_insert: false,
+            autocommit: true,
+        }
+    }
+}
+
+/// Deletes all rows in `table_name`, without committing the transaction.
+fn truncate_table(connection: &Connection, table_name: &str) -> Result<(), WriterError> {
+    let dbms_name = connection
+        .database_management_system_name()
+        .map_err(WriterError::UnknownDbms)?;
+    let sql = match dialect_from_dbms_name(&dbms_name) {
+        SqlDialect::SqlServer | SqlDialect::PostgreSQL => format!("TRUNCATE TABLE {table_name}"),
+        SqlDialect::AnsiSql | SqlDialect::MySQL | SqlDialect::SQLite => {
+            format!("DELETE FROM {table_name}")
+        }
+    };
+    execute_ddl_statement(connection, sql)
+}
+
 /// Generates an insert statement using the table and column names.
 ///
 /// `INSERT INTO <table> (<column_names 0>, <column_names 1>, ...) VALUES (?, ?, ...)`
@@ -80,7 +134,7 @@ fn insert_statement_text(table: &str, column_names: &[&'_ str]) -> String {
 }
 
 /// Wraps column name in quotes, if need be
-fn quote_column_name(column_name: &str) -> Cow<'_, str> {
+pub(crate) fn quote_column_name(column_name: &str) -> Cow<'_, str> {
     if column_name.contains(|c| !valid_in_column_name(c)) {
         Cow::Owned(format!("\"{column_name}\""))
     } else {
@@ -133,6 +187,7 @@ pub fn insert_statement_from_schema(schema: &Schema, table_name: &str) -> String
 
 /// Emitted writing values from arror arrays into a table on the database
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum WriterError {
     #[error("Failure to bind the array parameter buffers to the statement.\n{0}")]
     BindParameterBuffers(#[source] odbc_api::Error),
@@ -150,6 +205,45 @@ pub enum WriterError {
         source: odbc_api::Error,
         sql: String,
     },
+    #[error("At least one key column is required to match existing rows.")]
+    NoKeyColumns,
+    #[error("The key column '{0}' is not a field of the schema.")]
+    UnknownKeyColumn(String),
+    #[error(
+        "Failure to query the name of the database management system, in order to choose the SQL \
+        dialect.\n{0}"
+    )]
+    UnknownDbms(#[source] odbc_api::Error),
+    #[error("Failure to count the parameters of the prepared statement.\n{0}")]
+    CountParameters(#[source] odbc_api::Error),
+    #[error(
+        "The statement has {expected} parameters, yet the schema has {actual} fields. There must be \
+        one field for each placeholder (`?`) in the statement."
+    )]
+    ParameterCountMismatch { expected: usize, actual: usize },
+    #[error(
+        "Creating the writer or inserting a previous batch failed, so no more batches are \
+        accepted. The error is reported by `AsyncOdbcWriter::finish`."
+    )]
+    AsyncWriterFailed,
+    #[error(
+        "The thread of `AsyncOdbcWriter` panicked creating the writer or inserting a batch: \
+        {message}"
+    )]
+    AsyncWriterPanicked { message: String },
+    #[error("An error occurred executing SQL statement. SQL:\n{sql}\n{source}")]
+    ExecuteDdlStatement {
+        #[source]
+        source: odbc_api::Error,
+        sql: String,
+    },
+    #[error(
+        "Failure to control the transaction replacing the contents of the table. Auto commit \
+        could not be changed or the transaction could not be committed.\n{0}"
+    )]
+    Transaction(#[source] odbc_api::Error),
+    #[error("Failure to open the Arrow IPC file.\n{0}")]
+    OpenIpcFile(#[source] std::io::Error),
 }
 
 /// Inserts batches from an [`arrow::record_batch::RecordBatchReader`] into a database.
@@ -183,16 +277,26 @@ where
     /// * `row_capacity`: The amount of rows send to the database in each chunk. With the exception
     ///   of the last chunk, which may be smaller.
     /// * `schema`: Schema needs to have one column for each positional parameter of the statement
-    ///   and match the data which will be supplied to the instance later. Otherwise your code will
-    ///   panic.
+    ///   and match the data which will be supplied to the instance later. A different number of
+    ///   fields and parameters causes a [`WriterError::ParameterCountMismatch`]. Otherwise your code
+    ///   will panic, if the data does not match the schema.
     /// * `statement`: A prepared statement whose SQL text representation contains one placeholder
     ///   for each column. The order of the placeholers must correspond to the orders of the columns
     ///   in the `schema`.
     pub fn new(
         row_capacity: usize,
         schema: &Schema,
-        statement: Prepared<S>,
+        mut statement: Prepared<S>,
     ) -> Result<Self, WriterError> {
+        let num_params = statement
+            .num_params()
+            .map_err(WriterError::CountParameters)?;
+        if usize::from(num_params) != schema.fields().len() {
+            return Err(WriterError::ParameterCountMismatch {
+                expected: num_params.into(),
+                actual: schema.fields().len(),
+            });
+        }
         let strategies: Vec<_> = schema
             .fields()
             .iter()
@@ -314,6 +418,170 @@ impl<'o> OdbcWriter<StatementImpl<'o>> {
             .map_err(|source| WriterError::PreparingInsertStatement { source, sql })?;
         Self::new(row_capacity, schema, statement)
     }
+
+    /// A writer which borrows the connection and upserts the given schema into a table with
+    /// matching column names. Rows whose `key_columns` match an existing row update it, all other
+    /// rows are inserted. The SQL dialect of the statement is chosen based on the name of the
+    /// database management system reported by the driver. See
+    /// [`crate::merge_statement_from_schema`].
+    pub fn new_merge(
+        connection: &'o Connection<'o>,
+        table_name: &str,
+        key_columns: &[&str],
+        schema: &Schema,
+        row_capacity: usize,
+    ) -> Result<Self, WriterError> {
+        let dbms_name = connection
+            .database_management_system_name()
+            .map_err(WriterError::UnknownDbms)?;
+        let dialect = dialect_from_dbms_name(&dbms_name);
+        let sql = merge_statement_from_schema(schema, table_name, key_columns, dialect)?;
+        let statement = connection
+            .prepare(&sql)
+            .map_err(|source| WriterError::PreparingInsertStatement { source, sql })?;
+        Self::new(row_capacity, schema, statement)
+    }
+
+    /// Like [`insert_into_table`], but allows to replace the contents of the table. See
+    /// [`OdbcWriterOptions`].
+    ///
+    /// If `truncate_before_insert` is set and the connection is in auto commit mode, auto commit is
+    /// turned off for the duration of the call and the transaction is either committed or rolled
+    /// back before returning. Auto commit is turned on again afterwards. Without auto commit,
+    /// the application controls the transaction.
+    pub fn insert_with_options(
+        connection: &'o Connection<'o>,
+        batches: &mut impl RecordBatchReader,
+        table_name: &str,
+        batch_size: usize,
+        options: OdbcWriterOptions,
+    ) -> Result<(), WriterError> {
+        let mut insert = || insert_into_table(connection, batches, table_name, batch_size);
+        if !options.truncate_before_insert {
+            return insert();
+        }
+        if !options.autocommit {
+            return truncate_table(connection, table_name).and_then(|()| insert());
+        }
+        connection
+            .set_autocommit(false)
+            .map_err(WriterError::Transaction)?;
+        let result = truncate_table(connection, table_name).and_then(|()| insert());
+        let result = match result {
+            Ok(()) => connection.commit().map_err(WriterError::Transaction),
+            Err(error) => {
+                // Report the error which caused the rollback, rather than a failure to roll back.
+                let _ = connection.rollback();
+                Err(error)
+            }
+        };
+        let restored = connection
+            .set_autocommit(true)
+            .map_err(WriterError::Transaction);
+        result.and(restored)
+    }
+
+    /// Streams the record batches of the Arrow IPC file at `path` into `table_name`, e.g. to load
+    /// files far larger than the available memory. The batches are read one at a time, so besides
+    /// the transit buffer of `batch_size` rows at most one batch of the file is held in memory. The
+    /// schema of the file determines the columns to insert into. `options` are applied the same way
+    /// as by [`Self::insert_with_options`]. Returns the number of rows inserted.
+    ///
+    /// **Note:**
+    ///
+    /// If table or column names are derived from user input, be sure to sanatize the input in order
+    /// to prevent SQL injection attacks.
+    pub fn insert_from_ipc_file(
+        connection: &'o Connection<'o>,
+        table_name: &str,
+        path: impl AsRef<Path>,
+        batch_size: usize,
+        options: OdbcWriterOptions,
+    ) -> Result<u64, WriterError> {
+        let file = File::open(path).map_err(WriterError::OpenIpcFile)?;
+        let reader = FileReader::try_new(BufReader::new(file), None)
+            .map_err(WriterError::ReadingRecordBatch)?;
+        let schema = reader.schema();
+        let mut num_rows = 0;
+        let counted = reader.inspect(|batch| {
+            if let Ok(batch) = batch {
+                num_rows += batch.num_rows() as u64;
+            }
+        });
+        Self::insert_with_options(
+            connection,
+            &mut RecordBatchIterator::new(counted, schema),
+            table_name,
+            batch_size,
+            options,
+        )?;
+        Ok(num_rows)
+    }
+
+    /// Creates a table with a column for each field of `schema` (see
+    /// [`crate::schema_to_create_table`]), unless a table named `table_name` already exists.
+    /// Returns `true` if the table has been created and `false` if it already existed. Useful for
+    /// pipelines which manage the schema of their target tables themselves.
+    ///
+    /// Whether the table existed is derived from the DDL statements themselves: A plain `CREATE
+    /// TABLE` statement is executed first. Should it fail, the statement returned by
+    /// [`crate::create_table_if_not_exists_statement`] is executed. If it succeeds, the table
+    /// existed, otherwise its error is returned. [`SqlDialect::AnsiSql`] has no such statement, so
+    /// the error of the plain statement is returned. On databases which abort the current
+    /// transaction on errors (e.g. PostgreSQL) call this outside of transactions.
+    pub fn create_table_if_not_exists(
+        connection: &'o Connection<'o>,
+        table_name: &str,
+        schema: &Schema,
+        dialect: SqlDialect,
+    ) -> Result<bool, WriterError> {
+        let sql = schema_to_create_table(schema, table_name, dialect)?;
+        let fallback = match dialect {
+            SqlDialect::AnsiSql => None,
+            _ => Some(create_table_if_not_exists_statement(
+                schema, table_name, dialect,
+            )?),
+        };
+        execute_ddl_statement_with_fallback(connection, sql, fallback)
+    }
+
+    /// Drops the table named `table_name`, if it exists. Returns `true` if the table has been
+    /// dropped and `false` if it did not exist. Like in [`Self::create_table_if_not_exists`] a
+    /// plain `DROP TABLE` statement is executed first, and `DROP TABLE IF EXISTS` only if it
+    /// fails.
+    pub fn drop_table_if_exists(
+        connection: &'o Connection<'o>,
+        table_name: &str,
+    ) -> Result<bool, WriterError> {
+        execute_ddl_statement_with_fallback(
+            connection,
+            format!("DROP TABLE {table_name}"),
+            Some(format!("DROP TABLE IF EXISTS {table_name}")),
+        )
+    }
+}
+
+/// Executes `sql` and returns `true` if it succeeds. Otherwise executes `fallback`, which is
+/// expected to be a guarded variant of `sql` doing nothing, and returns `false` if it succeeds.
+/// Telling apart the errors of `sql` would require knowing the SQLSTATEs of each driver. Probing
+/// the table upfront would be racy and mistake any error for a missing table.
+fn execute_ddl_statement_with_fallback(
+    connection: &Connection,
+    sql: String,
+    fallback: Option<String>,
+) -> Result<bool, WriterError> {
+    match (execute_ddl_statement(connection, sql), fallback) {
+        (Ok(()), _) => Ok(true),
+        (Err(_), Some(fallback)) => execute_ddl_statement(connection, fallback).map(|()| false),
+        (Err(error), None) => Err(error),
+    }
+}
+
+fn execute_ddl_statement(connection: &Connection, sql: String) -> Result<(), WriterError> {
+    connection
+        .execute(&sql, ())
+        .map_err(|source| WriterError::ExecuteDdlStatement { source, sql })?;
+    Ok(())
 }
 
 pub trait WriteStrategy {
@@ -340,6 +608,7 @@ fn field_to_write_strategy(field: &Field) -> Result<Box<dyn WriteStrategy>, Writ
         DataType::Utf8 => Box::new(Utf8ToNativeText {}),
         DataType::Boolean => boolean_to_bit(is_nullable),
         DataType::LargeUtf8 => Box::new(LargeUtf8ToNativeText {}),
+        DataType::Utf8View => Box::new(Utf8ViewToNativeText {}),
         DataType::Int8 => Int8Type::identical(is_nullable),
         DataType::Int16 => Int16Type::identical(is_nullable),
         DataType::Int32 => Int32Type::identical(is_nullable),
@@ -380,6 +649,7 @@ fn field_to_write_strategy(field: &Field) -> Result<Box<dyn WriteStrategy>, Writ
             Box::new(NullableTimeAsText::<Time64NanosecondType>::new())
         }
         DataType::Binary => Box::new(VariadicBinary::new(1)),
+        DataType::BinaryView => Box::new(BinaryViewToBinary::new(1)),
         DataType::FixedSizeBinary(length) => {
             Box::new(VariadicBinary::new((*length).try_into().unwrap()))
         }
@@ -0,0 +1,324 @@
+use std::{
+    any::Any,
+    collections::VecDeque,
+    future::Future,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use arrow::record_batch::RecordBatch;
+use odbc_api::handles::AsStatementRef;
+
+use super::{OdbcWriter, WriterError};
+
+/// Inserts record batches into a database, from within an asynchronous application. The blocking
+/// ODBC calls are performed by an [`OdbcWriter`] in a dedicated system thread. Batches are passed
+/// to that thread using a queue holding at most `queue_depth` batches. If the queue is full,
+/// [`Self::write_batch_async`] waits for the database to catch up. This way a fast source can not
+/// cause memory to grow without bound, if the database is the bottleneck.
+///
+/// The [`OdbcWriter`] is created within the writer thread, so neither it nor its statement need to
+/// be `Send`. Only the closure creating it does. Available with the `async` feature.
+pub struct AsyncOdbcWriter {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    /// Notifies the worker about new batches in the queue, or the writer being finished.
+    batch_available: Condvar,
+}
+
+struct State {
+    queue: VecDeque<RecordBatch>,
+    queue_depth: usize,
+    /// Waker of a write waiting for space in the queue.
+    waker: Option<Waker>,
+    /// Set once no more batches are going to be queued.
+    finished: bool,
+    /// Set by the worker if creating the writer or inserting a batch failed. The error itself is
+    /// returned by [`AsyncOdbcWriter::finish`].
+    failed: bool,
+    /// Result of the writer thread, once it is done.
+    outcome: Option<Result<(), WriterError>>,
+    /// Waker of [`AsyncOdbcWriter::finish`] waiting for the writer thread to be done.
+    finish_waker: Option<Waker>,
+}
+
+impl AsyncOdbcWriter {
+    /// Spawns the writer thread, which calls `make_writer` and inserts the queued batches with the
+    /// writer it returns. At most `queue_depth` batches are buffered between the application and
+    /// the database. An error returned by `make_writer` is reported by [`Self::finish`], and so is
+    /// a panic of the writer thread, as [`WriterError::AsyncWriterPanicked`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_odbc::{
+    ///     arrow::record_batch::RecordBatch,
+    ///     odbc_api::{ConnectionOptions, Environment},
+    ///     AsyncOdbcWriter, OdbcWriter,
+    /// };
+    /// use std::{num::NonZeroUsize, sync::OnceLock};
+    ///
+    /// static ENV: OnceLock<Environment> = OnceLock::new();
+    ///
+    /// async fn insert(batches: Vec<RecordBatch>) -> Result<(), anyhow::Error> {
+    ///     let schema = batches[0].schema();
+    ///     let queue_depth = NonZeroUsize::new(2).unwrap();
+    ///     let mut writer = AsyncOdbcWriter::new(queue_depth, move || {
+    ///         let env = ENV.get_or_init(|| Environment::new().unwrap());
+    ///         let connection = env
+    ///             .connect_with_connection_string("DSN=MyDsn;", ConnectionOptions::default())
+    ///             .unwrap();
+    ///         OdbcWriter::from_connection(connection, &schema, "MyTable", 1000)
+    ///     });
+    ///     for batch in batches {
+    ///         writer.write_batch_async(batch).await?;
+    ///     }
+    ///     writer.finish().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new<S, F>(queue_depth: NonZeroUsize, make_writer: F) -> Self
+    where
+        S: AsStatementRef,
+        F: FnOnce() -> Result<OdbcWriter<S>, WriterError> + Send + 'static,
+    {
+        let queue_depth = queue_depth.get();
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                queue: VecDeque::with_capacity(queue_depth),
+                queue_depth,
+                waker: None,
+                finished: false,
+                failed: false,
+                outcome: None,
+                finish_waker: None,
+            }),
+            batch_available: Condvar::new(),
+        });
+        let worker_shared = shared.clone();
+        // Detached. The thread reports its outcome through the shared state.
+        thread::spawn(move || {
+            // A panic must still complete the outcome, or `finish` would wait forever. The writer
+            // is dropped while unwinding and not used afterwards, so asserting unwind safety is
+            // fine.
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                make_writer().and_then(|mut writer| worker_shared.insert_batches(&mut writer))
+            }))
+            .unwrap_or_else(|payload| {
+                Err(WriterError::AsyncWriterPanicked {
+                    message: panic_message(payload.as_ref()),
+                })
+            });
+            worker_shared.done(outcome);
+        });
+        Self { shared }
+    }
+
+    /// Queues `batch` for insertion. The returned future completes as soon as there is room for the
+    /// batch in the queue, i.e. once the writer thread has taken on enough of the previous batches.
+    /// It does not wait for the batch itself to be inserted. Errors creating the writer or inserting
+    /// previous batches are reported as [`WriterError::AsyncWriterFailed`], await [`Self::finish`]
+    /// for the actual error.
+    pub fn write_batch_async(
+        &mut self,
+        batch: RecordBatch,
+    ) -> impl Future<Output = Result<(), WriterError>> + '_ {
+        WriteBatch {
+            shared: &self.shared,
+            batch: Some(batch),
+        }
+    }
+
+    /// Tells the writer thread that no more batches are coming. The returned future completes
+    /// once all queued batches are inserted and the remaining rows have been sent to the database.
+    /// Its output is the first error which occurred creating the writer or inserting batches, if
+    /// any.
+    pub fn finish(self) -> impl Future<Output = Result<(), WriterError>> {
+        let shared = self.shared.clone();
+        // Dropping marks the queue as finished and wakes the writer thread.
+        drop(self);
+        Finish { shared }
+    }
+}
+
+/// Marks the queue as finished without waiting for the writer thread. It inserts the batches still
+/// in the queue in the background. Errors are lost, await [`AsyncOdbcWriter::finish`] to observe
+/// them.
+impl Drop for AsyncOdbcWriter {
+    fn drop(&mut self) {
+        self.shared.lock().finished = true;
+        self.shared.batch_available.notify_one();
+    }
+}
+
+impl Shared {
+    /// The mutex is poisoned, if the writer thread panicked waking a task while holding the lock.
+    /// The state is consistent nevertheless, since wakers are only invoked after changing it.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Executed by the writer thread.
+    fn insert_batches<S>(&self, writer: &mut OdbcWriter<S>) -> Result<(), WriterError>
+    where
+        S: AsStatementRef,
+    {
+        loop {
+            let batch = {
+                let mut state = self.lock();
+                while state.queue.is_empty() && !state.finished {
+                    state = self
+                        .batch_available
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+                let Some(batch) = state.queue.pop_front() else {
+                    break;
+                };
+                // There is room in the queue again
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+                batch
+            };
+            writer.write_batch(&batch)?;
+        }
+        writer.flush()
+    }
+
+    /// Executed by the writer thread once it is done. Rejects further batches, if it failed.
+    fn done(&self, outcome: Result<(), WriterError>) {
+        let mut state = self.lock();
+        if outcome.is_err() {
+            state.failed = true;
+            state.queue.clear();
+        }
+        state.outcome = Some(outcome);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        if let Some(waker) = state.finish_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Text passed to `panic!`, if any.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_owned()
+    }
+}
+
+/// Future returned by [`AsyncOdbcWriter::write_batch_async`].
+struct WriteBatch<'a> {
+    shared: &'a Shared,
+    /// `None` once the batch has been queued.
+    batch: Option<RecordBatch>,
+}
+
+impl Future for WriteBatch<'_> {
+    type Output = Result<(), WriterError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let shared = self.shared;
+        let mut state = shared.lock();
+        if state.failed {
+            return Poll::Ready(Err(WriterError::AsyncWriterFailed));
+        }
+        if state.queue.len() < state.queue_depth {
+            if let Some(batch) = self.batch.take() {
+                state.queue.push_back(batch);
+                drop(state);
+                shared.batch_available.notify_one();
+            }
+            Poll::Ready(Ok(()))
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Future returned by [`AsyncOdbcWriter::finish`].
+struct Finish {
+    shared: Arc<Shared>,
+}
+
+impl Future for Finish {
+    type Output = Result<(), WriterError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.lock();
+        match state.outcome.take() {
+            Some(outcome) => Poll::Ready(outcome),
+            None => {
+                state.finish_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        num::NonZeroUsize,
+        pin::pin,
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+        thread::{self, Thread},
+    };
+
+    use odbc_api::handles::StatementImpl;
+
+    use crate::{OdbcWriter, WriterError};
+
+    use super::AsyncOdbcWriter;
+
+    /// Minimal executor, so the test does not depend on an async runtime.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct ThreadWaker(Thread);
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark()
+            }
+        }
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn panic_of_writer_thread_is_reported_by_finish() {
+        let make_writer = || -> Result<OdbcWriter<StatementImpl<'static>>, WriterError> {
+            panic!("Connection refused")
+        };
+        let writer = AsyncOdbcWriter::new(NonZeroUsize::new(1).unwrap(), make_writer);
+
+        let outcome = block_on(writer.finish());
+
+        assert!(matches!(
+            outcome,
+            Err(WriterError::AsyncWriterPanicked { message }) if message == "Connection refused"
+        ));
+    }
+}
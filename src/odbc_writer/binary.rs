@@ -1,5 +1,5 @@
-use arrow::array::{Array, BinaryArray};
-use odbc_api::buffers::{AnySliceMut, BufferDesc};
+use arrow::array::{Array, BinaryArray, BinaryViewArray};
+use odbc_api::buffers::{AnySliceMut, BinColumnSliceMut, BufferDesc};
 
 use super::{WriteStrategy, WriterError};
 
@@ -27,16 +27,53 @@ impl WriteStrategy for VariadicBinary {
         from: &dyn Array,
     ) -> Result<(), WriterError> {
         let from = from.as_any().downcast_ref::<BinaryArray>().unwrap();
-        let mut to = to.as_bin_view().unwrap();
-        for (row_index, element) in from.iter().enumerate() {
-            if let Some(bytes) = element {
-                to.ensure_max_element_length(bytes.len(), row_index)
-                    .map_err(WriterError::RebindBuffer)?;
-                to.set_cell(param_offset + row_index, Some(bytes))
-            } else {
-                to.set_cell(param_offset + row_index, None);
-            }
+        let to = to.as_bin_view().unwrap();
+        insert_into_bin_slice(from.iter(), to, param_offset)
+    }
+}
+
+pub struct BinaryViewToBinary {
+    buffer_start_size: usize,
+}
+
+impl BinaryViewToBinary {
+    pub fn new(buffer_start_size: usize) -> Self {
+        BinaryViewToBinary { buffer_start_size }
+    }
+}
+
+impl WriteStrategy for BinaryViewToBinary {
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Binary {
+            length: self.buffer_start_size,
+        }
+    }
+
+    fn write_rows(
+        &self,
+        param_offset: usize,
+        to: AnySliceMut<'_>,
+        from: &dyn Array,
+    ) -> Result<(), WriterError> {
+        let from = from.as_any().downcast_ref::<BinaryViewArray>().unwrap();
+        let to = to.as_bin_view().unwrap();
+        insert_into_bin_slice(from.iter(), to, param_offset)
+    }
+}
+
+fn insert_into_bin_slice<'a>(
+    from: impl Iterator<Item = Option<&'a [u8]>>,
+    mut to: BinColumnSliceMut,
+    param_offset: usize,
+) -> Result<(), WriterError> {
+    for (row_index, element) in from.enumerate() {
+        if let Some(bytes) = element {
+            to.ensure_max_element_length(bytes.len(), row_index)
+                .map_err(WriterError::RebindBuffer)?;
+            to.set_cell(param_offset + row_index, Some(bytes))
+        } else {
+            to.set_cell(param_offset + row_index, None);
         }
-        Ok(())
     }
+    Ok(())
 }
@@ -0,0 +1,185 @@
+use std::borrow::Cow;
+
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+
+use super::{quote_column_name, valid_in_column_name, WriterError};
+
+/// SQL dialect used by [`crate::schema_to_create_table`] to pick the names of the column types.
+/// Databases differ in the names they accept for types beyond the very basic ones. E.g. Microsoft
+/// SQL Server calls its 64 Bit floating point type `FLOAT`, while PostgreSQL uses `DOUBLE
+/// PRECISION`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// Type names as specified by the SQL standard. Use this for databases not explicitly listed.
+    AnsiSql,
+    /// Microsoft SQL Server
+    SqlServer,
+    PostgreSQL,
+    MySQL,
+    SQLite,
+}
+
+/// Generates a `CREATE TABLE` statement with a column for each field in the schema. The column
+/// types are chosen so the table can be filled using an [`crate::OdbcWriter`] and read back into
+/// arrays of the same Arrow type using an [`crate::OdbcReader`]. Nullable fields result in `NULL`
+/// columns, non nullable ones in `NOT NULL` columns.
+///
+/// Column names are quoted using the same rules as in [`crate::insert_statement_from_schema`].
+///
+/// # Example
+///
+/// ```
+/// use arrow_odbc::{
+///     schema_to_create_table, SqlDialect,
+///     arrow::datatypes::{Field, DataType, Schema},
+/// };
+///
+/// let field_a = Field::new("a", DataType::Int64, false);
+/// let field_b = Field::new("b", DataType::Float64, true);
+///
+/// let schema = Schema::new(vec![field_a, field_b]);
+/// let sql = schema_to_create_table(&schema, "MyTable", SqlDialect::SqlServer).unwrap();
+///
+/// assert_eq!("CREATE TABLE MyTable (a BIGINT NOT NULL, b FLOAT NULL)", sql)
+/// ```
+///
+/// Fails with [`WriterError::UnsupportedArrowDataType`] if the schema contains a field which can
+/// not be inserted by [`crate::OdbcWriter`].
+pub fn schema_to_create_table(
+    schema: &Schema,
+    table_name: &str,
+    dialect: SqlDialect,
+) -> Result<String, WriterError> {
+    let columns = column_definitions(schema, dialect)?;
+    // Like for insert statements we do not terminate the statement with a semicolon.
+    Ok(format!("CREATE TABLE {table_name} ({columns})"))
+}
+
+/// Like [`schema_to_create_table`], but the statement does nothing if the table already exists.
+/// Microsoft SQL Server does not know `CREATE TABLE IF NOT EXISTS`, so the statement is guarded
+/// with `OBJECT_ID` instead. The SQL standard has no equivalent at all, so for
+/// [`SqlDialect::AnsiSql`] this is a plain `CREATE TABLE` statement.
+pub fn create_table_if_not_exists_statement(
+    schema: &Schema,
+    table_name: &str,
+    dialect: SqlDialect,
+) -> Result<String, WriterError> {
+    let columns = column_definitions(schema, dialect)?;
+    let sql = match dialect {
+        SqlDialect::AnsiSql => format!("CREATE TABLE {table_name} ({columns})"),
+        SqlDialect::SqlServer => format!(
+            "IF OBJECT_ID(N'{}', N'U') IS NULL CREATE TABLE {table_name} ({columns})",
+            table_name.replace('\'', "''")
+        ),
+        SqlDialect::PostgreSQL | SqlDialect::MySQL | SqlDialect::SQLite => {
+            format!("CREATE TABLE IF NOT EXISTS {table_name} ({columns})")
+        }
+    };
+    Ok(sql)
+}
+
+/// Comma separated column definitions for each field of the schema.
+fn column_definitions(schema: &Schema, dialect: SqlDialect) -> Result<String, WriterError> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| column_definition(field, dialect))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(", ");
+    Ok(columns)
+}
+
+fn column_definition(field: &Field, dialect: SqlDialect) -> Result<String, WriterError> {
+    let name = match dialect {
+        // MySQL only accepts double quotes for identifiers in ANSI_QUOTES mode
+        SqlDialect::MySQL if field.name().contains(|c| !valid_in_column_name(c)) => {
+            Cow::Owned(format!("`{}`", field.name()))
+        }
+        _ => quote_column_name(field.name()),
+    };
+    let sql_type = sql_type(field.data_type(), dialect)?;
+    let nullability = if field.is_nullable() {
+        "NULL"
+    } else {
+        "NOT NULL"
+    };
+    Ok(format!("{name} {sql_type} {nullability}"))
+}
+
+/// Name of the SQL type used for a column holding values of the Arrow data type.
+fn sql_type(data_type: &DataType, dialect: SqlDialect) -> Result<Cow<'static, str>, WriterError> {
+    use SqlDialect::*;
+    let sql_type = match (data_type, dialect) {
+        (DataType::Boolean, SqlServer) => "BIT".into(),
+        (DataType::Boolean, _) => "BOOLEAN".into(),
+        // `TINYINT` is unsigned in SQL Server, so it is not an option for signed bytes.
+        (DataType::Int8, MySQL | SQLite) => "TINYINT".into(),
+        (DataType::Int8, _) => "SMALLINT".into(),
+        (DataType::UInt8, SqlServer | SQLite) => "TINYINT".into(),
+        (DataType::UInt8, MySQL) => "TINYINT UNSIGNED".into(),
+        (DataType::UInt8, _) => "SMALLINT".into(),
+        (DataType::Int16, _) => "SMALLINT".into(),
+        (DataType::Int32, _) => "INTEGER".into(),
+        (DataType::Int64, _) => "BIGINT".into(),
+        (DataType::Float16 | DataType::Float32, _) => "REAL".into(),
+        (DataType::Float64, SqlServer) => "FLOAT".into(),
+        (DataType::Float64, _) => "DOUBLE PRECISION".into(),
+        (DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View, SqlServer) => {
+            "NVARCHAR(MAX)".into()
+        }
+        (DataType::Utf8 | DataType::Utf8View, MySQL) => "TEXT".into(),
+        (DataType::LargeUtf8, MySQL) => "LONGTEXT".into(),
+        (DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View, PostgreSQL | SQLite) => {
+            "TEXT".into()
+        }
+        (DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View, AnsiSql) => "CLOB".into(),
+        (DataType::Binary | DataType::BinaryView, SqlServer) => "VARBINARY(MAX)".into(),
+        (DataType::Binary | DataType::BinaryView, PostgreSQL)
+        | (DataType::FixedSizeBinary(_), PostgreSQL) => "BYTEA".into(),
+        (DataType::Binary | DataType::BinaryView, MySQL) => "LONGBLOB".into(),
+        (DataType::Binary | DataType::BinaryView, _) | (DataType::FixedSizeBinary(_), SQLite) => {
+            "BLOB".into()
+        }
+        (DataType::FixedSizeBinary(length), _) => format!("BINARY({length})").into(),
+        (DataType::Date32 | DataType::Date64, _) => "DATE".into(),
+        (DataType::Time32(_) | DataType::Time64(_), SQLite) => "TIME".into(),
+        (DataType::Time32(unit) | DataType::Time64(unit), _) => {
+            format!("TIME({})", fractional_digits(*unit, dialect)).into()
+        }
+        (DataType::Timestamp(_, None), SQLite) => "TIMESTAMP".into(),
+        (DataType::Timestamp(unit, None), SqlServer) => {
+            format!("DATETIME2({})", fractional_digits(*unit, dialect)).into()
+        }
+        (DataType::Timestamp(unit, None), MySQL) => {
+            format!("DATETIME({})", fractional_digits(*unit, dialect)).into()
+        }
+        (DataType::Timestamp(unit, None), _) => {
+            format!("TIMESTAMP({})", fractional_digits(*unit, dialect)).into()
+        }
+        (DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale), _) => {
+            // A negative scale means the values are multiples of a power of ten. We still need a
+            // column able to hold all of the digits.
+            if *scale < 0 {
+                let precision = *precision as i16 - *scale as i16;
+                format!("DECIMAL({precision},0)").into()
+            } else {
+                format!("DECIMAL({precision},{scale})").into()
+            }
+        }
+        (unsupported, _) => return Err(WriterError::UnsupportedArrowDataType(unsupported.clone())),
+    };
+    Ok(sql_type)
+}
+
+/// Number of fractional second digits for time and timestamp columns. Not every database supports
+/// nanoseconds precision, so we use the most precise option available.
+fn fractional_digits(unit: TimeUnit, dialect: SqlDialect) -> u8 {
+    match (unit, dialect) {
+        (TimeUnit::Second, _) => 0,
+        (TimeUnit::Millisecond, _) => 3,
+        (TimeUnit::Microsecond, _) => 6,
+        (TimeUnit::Nanosecond, SqlDialect::SqlServer) => 7,
+        (TimeUnit::Nanosecond, SqlDialect::PostgreSQL | SqlDialect::MySQL) => 6,
+        (TimeUnit::Nanosecond, SqlDialect::AnsiSql | SqlDialect::SQLite) => 9,
+    }
+}
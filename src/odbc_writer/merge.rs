@@ -0,0 +1,262 @@
+use arrow::datatypes::Schema;
+
+use super::{quote_column_name, SqlDialect, WriterError};
+
+/// Generates a statement inserting rows into a table, or updating them in case a row with the same
+/// key already exists (upsert). The resulting statement has one placeholder (`?`) for each field in
+/// the schema, in the same order. `key_columns` must name the fields forming the unique key of the
+/// table.
+///
+/// Depending on the dialect this is a `MERGE` statement, or an `INSERT` with the upsert extension of
+/// the database:
+///
+/// * `SqlServer`, `AnsiSql`: `MERGE INTO ... USING (VALUES (...)) ... WHEN MATCHED ...`
+/// * `PostgreSQL`, `SQLite`: `INSERT INTO ... ON CONFLICT (...) DO UPDATE SET ...`
+/// * `MySQL`: `INSERT INTO ... ON DUPLICATE KEY UPDATE ...`
+///
+/// Column names are quoted using the same rules as in [`crate::insert_statement_from_schema`].
+///
+/// # Example
+///
+/// ```
+/// use arrow_odbc::{
+///     merge_statement_from_schema, SqlDialect,
+///     arrow::datatypes::{Field, DataType, Schema},
+/// };
+///
+/// let field_a = Field::new("a", DataType::Int64, false);
+/// let field_b = Field::new("b", DataType::Utf8, true);
+///
+/// let schema = Schema::new(vec![field_a, field_b]);
+/// let sql = merge_statement_from_schema(&schema, "MyTable", &["a"], SqlDialect::PostgreSQL)
+///     .unwrap();
+///
+/// assert_eq!(
+///     "INSERT INTO MyTable (a, b) VALUES (?, ?) ON CONFLICT (a) DO UPDATE SET b = EXCLUDED.b",
+///     sql
+/// )
+/// ```
+pub fn merge_statement_from_schema(
+    schema: &Schema,
+    table_name: &str,
+    key_columns: &[&str],
+    dialect: SqlDialect,
+) -> Result<String, WriterError> {
+    if key_columns.is_empty() {
+        return Err(WriterError::NoKeyColumns);
+    }
+    if let Some(unknown) = key_columns
+        .iter()
+        .find(|&&key| schema.field_with_name(key).is_err())
+    {
+        return Err(WriterError::UnknownKeyColumn(unknown.to_string()));
+    }
+    let column_names: Vec<_> = schema
+        .fields()
+        .iter()
+        .map(|field| quote_column_name(field.name()))
+        .collect();
+    let keys: Vec<_> = key_columns
+        .iter()
+        .map(|&key| quote_column_name(key))
+        .collect();
+    // Columns updated if a row with the same key already exists
+    let values: Vec<_> = schema
+        .fields()
+        .iter()
+        .filter(|field| !key_columns.contains(&field.name().as_str()))
+        .map(|field| quote_column_name(field.name()))
+        .collect();
+
+    let columns = column_names.join(", ");
+    let placeholders = vec!["?"; column_names.len()].join(", ");
+    let sql = match dialect {
+        SqlDialect::SqlServer | SqlDialect::AnsiSql => {
+            let on = keys
+                .iter()
+                .map(|key| format!("target.{key} = source.{key}"))
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            let when_matched = if values.is_empty() {
+                String::new()
+            } else {
+                let set = values
+                    .iter()
+                    .map(|value| format!("{value} = source.{value}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(" WHEN MATCHED THEN UPDATE SET {set}")
+            };
+            let source_columns = column_names
+                .iter()
+                .map(|name| format!("source.{name}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            // Unlike other statements, SQL Server insists on terminating `MERGE` with a semicolon.
+            // The target alias goes without `AS`, which Oracle does not allow for table aliases.
+            let terminator = if dialect == SqlDialect::SqlServer {
+                ";"
+            } else {
+                ""
+            };
+            format!(
+                "MERGE INTO {table_name} target USING (VALUES ({placeholders})) AS source \
+                ({columns}) ON {on}{when_matched} WHEN NOT MATCHED THEN INSERT ({columns}) VALUES \
+                ({source_columns}){terminator}"
+            )
+        }
+        // SQLite supports this since 3.24. Unlike `INSERT OR REPLACE`, it updates the existing row,
+        // rather than deleting it and inserting a new one, which would fire delete triggers and
+        // reset columns not part of the schema.
+        SqlDialect::PostgreSQL | SqlDialect::SQLite => {
+            let on_conflict = if values.is_empty() {
+                "DO NOTHING".to_owned()
+            } else {
+                let set = values
+                    .iter()
+                    .map(|value| format!("{value} = EXCLUDED.{value}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("DO UPDATE SET {set}")
+            };
+            format!(
+                "INSERT INTO {table_name} ({columns}) VALUES ({placeholders}) ON CONFLICT ({}) \
+                {on_conflict}",
+                keys.join(", ")
+            )
+        }
+        SqlDialect::MySQL => {
+            // Assigning the key to itself turns a duplicate into a no-op, if there is nothing else
+            // to update.
+            let set = if values.is_empty() { &keys } else { &values }
+                .iter()
+                .map(|value| format!("{value} = VALUES({value})"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "INSERT INTO {table_name} ({columns}) VALUES ({placeholders}) ON DUPLICATE KEY \
+                UPDATE {set}"
+            )
+        }
+    };
+    Ok(sql)
+}
+
+/// Guesses the SQL dialect from the name of the database management system, as reported by the
+/// ODBC driver.
+pub fn dialect_from_dbms_name(dbms_name: &str) -> SqlDialect {
+    let dbms_name = dbms_name.to_lowercase();
+    if dbms_name.contains("sql server") {
+        SqlDialect::SqlServer
+    } else if dbms_name.contains("postgres") {
+        SqlDialect::PostgreSQL
+    } else if dbms_name.contains("mysql") || dbms_name.contains("mariadb") {
+        SqlDialect::MySQL
+    } else if dbms_name.contains("sqlite") {
+        SqlDialect::SQLite
+    } else {
+        SqlDialect::AnsiSql
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    use crate::SqlDialect;
+
+    use super::merge_statement_from_schema;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ])
+    }
+
+    #[test]
+    fn merge_statement_for_sqlite() {
+        let sql = merge_statement_from_schema(&schema(), "Scores", &["id"], SqlDialect::SQLite);
+
+        assert_eq!(
+            "INSERT INTO Scores (id, name, score) VALUES (?, ?, ?) ON CONFLICT (id) DO UPDATE SET \
+            name = EXCLUDED.name, score = EXCLUDED.score",
+            sql.unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_statement_for_postgresql() {
+        let sql = merge_statement_from_schema(&schema(), "Scores", &["id"], SqlDialect::PostgreSQL);
+
+        assert_eq!(
+            "INSERT INTO Scores (id, name, score) VALUES (?, ?, ?) ON CONFLICT (id) DO UPDATE SET \
+            name = EXCLUDED.name, score = EXCLUDED.score",
+            sql.unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_statement_for_postgresql_without_value_columns() {
+        let sql = merge_statement_from_schema(
+            &schema(),
+            "Scores",
+            &["id", "name", "score"],
+            SqlDialect::PostgreSQL,
+        );
+
+        assert_eq!(
+            "INSERT INTO Scores (id, name, score) VALUES (?, ?, ?) ON CONFLICT (id, name, score) \
+            DO NOTHING",
+            sql.unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_statement_for_mysql() {
+        let sql = merge_statement_from_schema(&schema(), "Scores", &["id"], SqlDialect::MySQL);
+
+        assert_eq!(
+            "INSERT INTO Scores (id, name, score) VALUES (?, ?, ?) ON DUPLICATE KEY UPDATE \
+            name = VALUES(name), score = VALUES(score)",
+            sql.unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_statement_for_mysql_without_value_columns() {
+        let sql = merge_statement_from_schema(
+            &schema(),
+            "Scores",
+            &["id", "name", "score"],
+            SqlDialect::MySQL,
+        );
+
+        assert_eq!(
+            "INSERT INTO Scores (id, name, score) VALUES (?, ?, ?) ON DUPLICATE KEY UPDATE \
+            id = VALUES(id), name = VALUES(name), score = VALUES(score)",
+            sql.unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_statement_for_ansi_sql_has_no_as_before_target_alias() {
+        let sql = merge_statement_from_schema(&schema(), "Scores", &["id"], SqlDialect::AnsiSql);
+
+        assert_eq!(
+            "MERGE INTO Scores target USING (VALUES (?, ?, ?)) AS source (id, name, score) ON \
+            target.id = source.id WHEN MATCHED THEN UPDATE SET name = source.name, score = \
+            source.score WHEN NOT MATCHED THEN INSERT (id, name, score) VALUES (source.id, \
+            source.name, source.score)",
+            sql.unwrap()
+        );
+    }
+
+    #[test]
+    fn reject_unknown_key_column() {
+        let sql = merge_statement_from_schema(&schema(), "Scores", &["unknown"], SqlDialect::MySQL);
+
+        assert!(sql.is_err());
+    }
+}
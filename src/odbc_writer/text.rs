@@ -1,4 +1,4 @@
-use arrow::array::{Array, LargeStringArray, StringArray};
+use arrow::array::{Array, LargeStringArray, StringArray, StringViewArray};
 use odbc_api::buffers::{AnySliceMut, BufferDesc, TextColumnSliceMut};
 
 use super::{WriteStrategy, WriterError};
@@ -15,6 +15,12 @@ pub type LargeUtf8ToNativeText = LargeUtf8ToNarrow;
 #[cfg(target_os = "windows")]
 pub type LargeUtf8ToNativeText = LargeUtf8ToWide;
 
+#[cfg(not(target_os = "windows"))]
+pub type Utf8ViewToNativeText = Utf8ViewToNarrow;
+
+#[cfg(target_os = "windows")]
+pub type Utf8ViewToNativeText = Utf8ViewToWide;
+
 pub struct Utf8ToNarrow;
 
 impl WriteStrategy for Utf8ToNarrow {
@@ -55,6 +61,26 @@ impl WriteStrategy for LargeUtf8ToNarrow {
     }
 }
 
+pub struct Utf8ViewToNarrow;
+
+impl WriteStrategy for Utf8ViewToNarrow {
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Text { max_str_len: 1 }
+    }
+
+    fn write_rows(
+        &self,
+        param_offset: usize,
+        to: AnySliceMut<'_>,
+        from: &dyn Array,
+    ) -> Result<(), WriterError> {
+        let from = from.as_any().downcast_ref::<StringViewArray>().unwrap();
+        let to = to.as_text_view().unwrap();
+        insert_into_narrow_slice(from.iter(), to, param_offset)?;
+        Ok(())
+    }
+}
+
 fn insert_into_narrow_slice<'a>(
     from: impl Iterator<Item = Option<&'a str>>,
     mut to: TextColumnSliceMut<u8>,
@@ -112,6 +138,26 @@ impl WriteStrategy for LargeUtf8ToWide {
     }
 }
 
+pub struct Utf8ViewToWide;
+
+impl WriteStrategy for Utf8ViewToWide {
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::WText { max_str_len: 1 }
+    }
+
+    fn write_rows(
+        &self,
+        param_offset: usize,
+        to: AnySliceMut<'_>,
+        from: &dyn Array,
+    ) -> Result<(), WriterError> {
+        let from = from.as_any().downcast_ref::<StringViewArray>().unwrap();
+        let to = to.as_w_text_view().unwrap();
+        insert_into_wide_slice(from.iter(), to, param_offset)?;
+        Ok(())
+    }
+}
+
 fn insert_into_wide_slice<'a>(
     from: impl Iterator<Item = Option<&'a str>>,
     mut to: TextColumnSliceMut<u16>,
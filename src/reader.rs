@@ -3,13 +3,13 @@ use std::{convert::TryInto, sync::Arc};
 use arrow::{
     array::{ArrayRef, BooleanBuilder},
     datatypes::{
-        DataType as ArrowDataType, Date32Type, Field, Float32Type, Float64Type, Int16Type,
-        Int32Type, Int64Type, Int8Type, TimeUnit, TimestampMicrosecondType,
-        TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt8Type,
+        ArrowPrimitiveType, DataType as ArrowDataType, Date32Type, Date64Type, Field, Float16Type,
+        Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, Time32SecondType,
+        TimeUnit, TimestampNanosecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
     },
 };
 
-use log::debug;
+use log::{debug, warn};
 use odbc_api::{
     buffers::{AnySlice, BufferDesc, Item},
     Bit, DataType as OdbcDataType, ResultSetMetadata,
@@ -17,38 +17,143 @@ use odbc_api::{
 use thiserror::Error;
 
 mod binary;
+#[cfg(feature = "abi")]
+mod c_stream;
+mod chunked_array;
+mod coercion;
+mod column_metadata;
 mod concurrent_odbc_reader;
 mod decimal;
+mod dictionary;
+mod ffi;
+mod guid;
+mod interval;
+mod json;
+mod lazy_schema;
+mod map_literal;
 mod map_odbc_to_arrow;
+mod money;
+mod null_replacement;
+mod odbc_paginated_reader;
+mod odbc_prepared_reader;
 mod odbc_reader;
+#[cfg(feature = "parquet")]
+mod parquet_statistics;
+mod post_fetch_transform;
+mod postgres_array;
+mod postgres_composite;
+mod row_status;
+mod statement_attribute;
+mod statistics;
+mod strategy_registry;
 mod text;
+mod time;
+mod timestamp;
 mod to_record_batch;
+mod wkb;
+mod xml;
 
-use crate::date_time::{
-    days_since_epoch, ms_since_epoch, ns_since_epoch, seconds_since_epoch, us_since_epoch,
+use crate::{
+    date_time::{days_since_epoch, ms_since_epoch_of_date, ns_since_epoch, seconds_since_midnight},
+    schema::is_wkb_field,
+};
+
+pub(crate) use self::column_metadata::col_type_name;
+
+use self::{
+    dictionary::integer_dictionary_strategy, text::min_limit, timestamp::timestamp_strategy,
 };
 
 pub use self::{
-    binary::{Binary, FixedSizedBinary},
+    binary::{
+        opaque_binary_length, Binary, BinaryView, FixedSizeListFromBinary, FixedSizedBinary,
+        LargeBinary,
+    },
+    chunked_array::ChunkedArray,
+    coercion::{FloatCoercion, IntegerCoercion},
+    column_metadata::OdbcColumnMetadata,
     concurrent_odbc_reader::ConcurrentOdbcReader,
-    decimal::Decimal,
+    decimal::{parse_decimal_text, Decimal, Decimal256, DecimalFromFloat},
+    guid::{is_guid, GuidStrategy},
+    interval::TextIntervalConversion,
+    json::{NormalizeJson, SpannerCompatibility},
+    map_literal::MapLiteral,
     map_odbc_to_arrow::{MapOdbcToArrow, MappingError},
+    money::{Money, MoneyType},
+    null_replacement::{ReplaceNulls, ScalarValue},
+    odbc_paginated_reader::OdbcPaginatedReader,
+    odbc_prepared_reader::{OdbcPreparedReader, ParameterDescription},
     odbc_reader::{OdbcReader, OdbcReaderBuilder},
-    text::choose_text_strategy,
+    post_fetch_transform::{PostFetchTransform, TransformArray},
+    postgres_array::PostgresArray,
+    postgres_composite::PostgresComposite,
+    row_status::ColumnWarning,
+    statement_attribute::StatementAttributeValue,
+    statistics::{ColumnStatistics, OdbcReaderMetrics},
+    strategy_registry::{ColumnStrategyRegistry, StrategyFactory},
+    text::{choose_text_strategy, TextOutputMode},
+    time::TextTimeConversion,
+    timestamp::{
+        is_timestamp_with_offset, LocalTimestamp, SubPrecisionHandling, TextTimestampWithOffset,
+    },
+    to_record_batch::MissingColumnBehavior,
+    wkb::{SpatialEncoding, Wkb, DEFAULT_MAX_SPATIAL_SIZE},
+    xml::{is_xml, Xml, DEFAULT_MAX_XML_SIZE},
 };
 
+pub(crate) use self::xml::{is_large_text, is_xml_type_name};
+
+#[cfg(feature = "abi")]
+pub use self::c_stream::free_c_stream;
+
+#[cfg(feature = "parquet")]
+pub use self::parquet_statistics::OdbcRecordBatchWithStats;
+
+/// Callback invoked with the zero based column index and the row index within the batch, for each
+/// NULL fetched from the data source. See [`crate::OdbcReaderBuilder::with_null_hook`].
+pub type NullHook = Arc<dyn Fn(usize, usize) + Send + Sync>;
+
 /// All decisions needed to copy data from an ODBC buffer to an Arrow Array
 pub trait ReadStrategy {
     /// Describes the buffer which is bound to the ODBC cursor.
     fn buffer_desc(&self) -> BufferDesc;
 
-    /// Create an arrow array from an ODBC buffer described in [`Self::buffer_description`].
+    /// Create an arrow array from an ODBC buffer described in [`Self::buffer_desc`].
+    ///
+    /// Implementations may assume `column_view` to match the buffer description, but must not
+    /// panic for any value the driver puts into the buffer. Values which can not be represented in
+    /// the Arrow array are reported as [`MappingError`] instead.
     fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError>;
+
+    /// Size in bytes of a single element in the buffer bound to the column, if the strategy knows
+    /// it without inspecting [`Self::buffer_desc`]. `None` by default.
+    fn element_size_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Bytes the transit buffer bound to the column occupies for each row, including the
+    /// indicator for NULLs and lengths. Used to choose the number of rows per batch within
+    /// [`crate::OdbcReaderBuilder::with_max_bytes_per_batch`], and for
+    /// [`crate::OdbcReaderMetrics::total_buffer_bytes`]. Derived from [`Self::buffer_desc`] by
+    /// default, e.g. `max_str_len + 1` plus the indicator for text.
+    fn estimated_memory_per_row(&self) -> usize {
+        self.buffer_desc().bytes_per_row()
+    }
+
+    /// Human readable name of the strategy, used to tell in diagnostics and logs how a column is
+    /// fetched. Defaults to the name of the implementing type.
+    fn strategy_type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 pub struct NonNullableBoolean;
 
 impl ReadStrategy for NonNullableBoolean {
+    fn strategy_type_name(&self) -> &'static str {
+        "NonNullableBoolean"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         BufferDesc::Bit { nullable: false }
     }
@@ -66,6 +171,10 @@ impl ReadStrategy for NonNullableBoolean {
 pub struct NullableBoolean;
 
 impl ReadStrategy for NullableBoolean {
+    fn strategy_type_name(&self) -> &'static str {
+        "NullableBoolean"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         BufferDesc::Bit { nullable: true }
     }
@@ -83,7 +192,19 @@ impl ReadStrategy for NullableBoolean {
 /// Allows setting limits for buffers bound to the ODBC data source. Check this out if you find that
 /// you get memory allocation, or zero sized column errors. Used than constructing a reader using
 /// [`crate::OdbcReaderBuilder`].
-#[derive(Default, Debug, Clone, Copy)]
+///
+/// Further options may be added in minor releases, so start from [`Default::default`] and change
+/// the options using the `with_` methods.
+///
+/// ```
+/// use arrow_odbc::BufferAllocationOptions;
+///
+/// let options = BufferAllocationOptions::default()
+///     .with_max_text_size(4096)
+///     .with_fallibale_allocations(true);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct BufferAllocationOptions {
     /// An upper limit for the size of buffers bound to variadic text columns of the data source.
     /// This limit does not (directly) apply to the size of the created arrow buffers, but rather
@@ -98,6 +219,11 @@ pub struct BufferAllocationOptions {
     /// limit is set and the maximum element size, reported by ODBC is used to determine buffer
     /// sizes.
     pub max_text_size: Option<usize>,
+    /// Maximum length in characters of XML documents (e.g. `XML` columns in SQL Server or DB2)
+    /// fetched into `Utf8`, `LargeUtf8` or `Utf8View` fields. Drivers report no maximum length for
+    /// XML, so the buffers are sized by this limit. Documents exceeding it cause an error. `None`
+    /// falls back to `max_text_size`, or [`DEFAULT_MAX_XML_SIZE`] if that is not set either.
+    pub max_xml_size: Option<usize>,
     /// An upper limit for the size of buffers bound to variadic binary columns of the data source.
     /// This limit does not (directly) apply to the size of the created arrow buffers, but rather
     /// applies to the buffers used for the data in transit. Use this option if you have e.g.
@@ -111,12 +237,194 @@ pub struct BufferAllocationOptions {
     /// the buffers can not be allocated due to their size. This might have a performance cost for
     /// constructing the reader. `false` by default.
     pub fallibale_allocations: bool,
+    /// `UInt32` and `UInt64` fields are fetched using signed 32 and 64 Bit integer buffers, since
+    /// ODBC does not offer unsigned ones for all drivers. By default negative values are reported
+    /// as [`MappingError::NegativeUnsigned`]. Set this to `true` in order to reinterpret the bits of
+    /// the signed integer as unsigned instead. This is what you want for drivers (e.g. for MySQL)
+    /// which put unsigned values larger than the signed maximum into the buffer, wrapping them
+    /// around to negative numbers. Values of `INTEGER` and `BIGINT` columns the driver reports as
+    /// unsigned are always reinterpreted. `UInt16` fields are fetched using 32 Bit integer
+    /// buffers, which can hold any unsigned 16 Bit integer, so this does not apply to them. `false`
+    /// by default.
+    pub allow_signed_unsigned_coercion: bool,
+    /// Some drivers do not support binding 8 Bit integer buffers (`SQL_C_STINYINT`) and return
+    /// garbage or fail. Set this to `true` in order to fetch `Int8` fields using 16 Bit integer
+    /// buffers instead. The values are narrowed while filling the Arrow array. Values out of range
+    /// are reported as [`MappingError::IntegerOutOfRange`]. `false` by default.
+    pub fallback_tinyint_to_smallint: bool,
+    /// An upper limit for the number of columns in the result set. Constructing a reader for a
+    /// result set with more columns fails with [`crate::Error::TooManyColumns`]. Useful for
+    /// applications executing user supplied SQL, in order to protect against allocating huge
+    /// buffers for wildcard queries on wide tables. `None` means no limit.
+    pub max_column_count: Option<usize>,
+    /// Set to `true` in order to fetch fields with an otherwise unsupported Arrow type as opaque
+    /// binary, if the relational type of the column has a fixed size representation in ODBC (e.g.
+    /// integers, floats, dates or timestamps). The type of the field is overridden with
+    /// `FixedSizeBinary` matching the size of the C type, so applications knowing the encoding can
+    /// decode the bytes themselves. `false` by default.
+    pub opaque_binary_fallback: bool,
+    /// An upper limit in bytes for a single element of any column buffer. Unlike `max_text_size`
+    /// and `max_binary_size` it applies to text and binary columns alike, in addition to these
+    /// limits. Text and variadic binary elements are clamped to the cap. The size of
+    /// `FixedSizeBinary` elements is dictated by the Arrow type, so exceeding the cap causes a
+    /// [`ColumnFailure::ElementSizeExceedsCap`] instead. `None` means no limit.
+    pub element_size_cap: Option<usize>,
+    /// Databases pad values of fixed sized character columns (e.g. `CHAR(10)`) with trailing
+    /// spaces up to the length of the column. If `true` these are removed, before the text is
+    /// copied into the Arrow array. Variadic character columns (e.g. `VARCHAR`) are not affected.
+    /// `true` by default.
+    pub trim_char_padding: bool,
+    /// `SQL_GUID` columns (e.g. `UNIQUEIDENTIFIER`) fetched into `Utf8` fields are formatted by
+    /// the driver by default. Set this to `true` in order to fetch them as binary and format them
+    /// as lower case hyphenated UUIDs (e.g. `550e8400-e29b-41d4-a716-446655440000`) instead. GUIDs
+    /// fetched into `FixedSizeBinary(16)` fields are always converted into the byte order of a
+    /// UUID. `false` by default.
+    pub format_guid_as_string: bool,
+    /// Set to `true` in order to bind a row status array (`SQL_ATTR_ROW_STATUS_PTR`) and inspect it
+    /// after each fetch. Values of rows the driver reports as `SQL_ROW_ERROR` are replaced with
+    /// nulls, and reported together with rows reported as `SQL_ROW_SUCCESS_WITH_INFO` as
+    /// [`crate::ColumnWarning`]s. `false` by default.
+    pub check_row_status: bool,
+    /// What to do with fractions of ODBC timestamps, which are finer than the unit of a
+    /// `Timestamp(Second | Millisecond | Microsecond, _)` field. Truncated by default.
+    pub sub_precision_handling: SubPrecisionHandling,
+    /// Set to `true` in order to fetch all text columns as `Binary`, holding the bytes as
+    /// transmitted by the driver, rather than validating them to be UTF-8. Use this for data
+    /// sources with other encodings. Equivalent to [`TextOutputMode::RawBytes`] for every column.
+    /// `false` by default.
+    pub text_as_binary_for_non_utf8: bool,
+}
+
+impl Default for BufferAllocationOptions {
+    fn default() -> Self {
+        Self {
+            max_text_size: None,
+            max_xml_size: None,
+            max_binary_size: None,
+            fallibale_allocations: false,
+            allow_signed_unsigned_coercion: false,
+            fallback_tinyint_to_smallint: false,
+            max_column_count: None,
+            opaque_binary_fallback: false,
+            element_size_cap: None,
+            trim_char_padding: true,
+            format_guid_as_string: false,
+            check_row_status: false,
+            sub_precision_handling: SubPrecisionHandling::Truncate,
+            text_as_binary_for_non_utf8: false,
+        }
+    }
+}
+
+impl BufferAllocationOptions {
+    /// Sets [`Self::max_text_size`].
+    pub fn with_max_text_size(mut self, max_text_size: usize) -> Self {
+        self.max_text_size = Some(max_text_size);
+        self
+    }
+
+    /// Sets [`Self::max_xml_size`].
+    pub fn with_max_xml_size(mut self, max_xml_size: usize) -> Self {
+        self.max_xml_size = Some(max_xml_size);
+        self
+    }
+
+    /// Sets [`Self::max_binary_size`].
+    pub fn with_max_binary_size(mut self, max_binary_size: usize) -> Self {
+        self.max_binary_size = Some(max_binary_size);
+        self
+    }
+
+    /// Sets [`Self::fallibale_allocations`].
+    pub fn with_fallibale_allocations(mut self, fallibale_allocations: bool) -> Self {
+        self.fallibale_allocations = fallibale_allocations;
+        self
+    }
+
+    /// Sets [`Self::allow_signed_unsigned_coercion`].
+    pub fn with_allow_signed_unsigned_coercion(
+        mut self,
+        allow_signed_unsigned_coercion: bool,
+    ) -> Self {
+        self.allow_signed_unsigned_coercion = allow_signed_unsigned_coercion;
+        self
+    }
+
+    /// Sets [`Self::fallback_tinyint_to_smallint`].
+    pub fn with_fallback_tinyint_to_smallint(mut self, fallback_tinyint_to_smallint: bool) -> Self {
+        self.fallback_tinyint_to_smallint = fallback_tinyint_to_smallint;
+        self
+    }
+
+    /// Sets [`Self::max_column_count`].
+    pub fn with_max_column_count(mut self, max_column_count: usize) -> Self {
+        self.max_column_count = Some(max_column_count);
+        self
+    }
+
+    /// Sets [`Self::opaque_binary_fallback`].
+    pub fn with_opaque_binary_fallback(mut self, opaque_binary_fallback: bool) -> Self {
+        self.opaque_binary_fallback = opaque_binary_fallback;
+        self
+    }
+
+    /// Sets [`Self::element_size_cap`].
+    pub fn with_element_size_cap(mut self, element_size_cap: usize) -> Self {
+        self.element_size_cap = Some(element_size_cap);
+        self
+    }
+
+    /// Sets [`Self::trim_char_padding`].
+    pub fn with_trim_char_padding(mut self, trim_char_padding: bool) -> Self {
+        self.trim_char_padding = trim_char_padding;
+        self
+    }
+
+    /// Sets [`Self::format_guid_as_string`].
+    pub fn with_format_guid_as_string(mut self, format_guid_as_string: bool) -> Self {
+        self.format_guid_as_string = format_guid_as_string;
+        self
+    }
+
+    /// Sets [`Self::check_row_status`].
+    pub fn with_check_row_status(mut self, check_row_status: bool) -> Self {
+        self.check_row_status = check_row_status;
+        self
+    }
+
+    /// Sets [`Self::sub_precision_handling`].
+    pub fn with_sub_precision_handling(
+        mut self,
+        sub_precision_handling: SubPrecisionHandling,
+    ) -> Self {
+        self.sub_precision_handling = sub_precision_handling;
+        self
+    }
+
+    /// Sets [`Self::text_as_binary_for_non_utf8`].
+    pub fn with_text_as_binary_for_non_utf8(mut self, text_as_binary_for_non_utf8: bool) -> Self {
+        self.text_as_binary_for_non_utf8 = text_as_binary_for_non_utf8;
+        self
+    }
 }
 
+/// Decides how to fetch the values of a single column from the ODBC data source and how to copy
+/// them into an Arrow array of the type of `field`.
+///
+/// Timestamps are fetched the same way, regardless of the timezone annotation of
+/// `ArrowDataType::Timestamp`. ODBC timestamps do not carry timezone information, so the values are
+/// always interpreted as if they were stored in UTC. For `Timestamp(_, None)` this means the
+/// timezone naive value is preserved as is. The exception are columns of type
+/// `SQL_SS_TIMESTAMPOFFSET` (e.g. `DATETIMEOFFSET`) fetched into `Timestamp(_, Some(_))` fields,
+/// whose values are normalized to UTC using their offset.
+///
+/// `sql_type` is the relational type of the column, as described by the driver. It is passed in,
+/// so the column is only described once, no matter how many decisions depend on its type.
 pub fn choose_column_strategy(
     field: &Field,
     query_metadata: &mut impl ResultSetMetadata,
     col_index: u16,
+    sql_type: OdbcDataType,
     buffer_allocation_options: BufferAllocationOptions,
     map_value_errors_to_null: bool,
     trim_fixed_sized_character_strings: bool,
@@ -129,61 +437,265 @@ pub fn choose_column_strategy(
                 Box::new(NonNullableBoolean)
             }
         }
+        ArrowDataType::Int8 if buffer_allocation_options.fallback_tinyint_to_smallint => {
+            Int8Type::map_falliable(
+                field.is_nullable(),
+                map_value_errors_to_null,
+                |&value: &i16| {
+                    i8::try_from(value).map_err(|_| MappingError::IntegerOutOfRange {
+                        value: value.into(),
+                    })
+                },
+            )
+        }
         ArrowDataType::Int8 => Int8Type::identical(field.is_nullable()),
         ArrowDataType::Int16 => Int16Type::identical(field.is_nullable()),
         ArrowDataType::Int32 => Int32Type::identical(field.is_nullable()),
-        ArrowDataType::Int64 => Int64Type::identical(field.is_nullable()),
+        ArrowDataType::Int64 => {
+            if !buffer_allocation_options.allow_signed_unsigned_coercion
+                && is_unsigned_integer(query_metadata, col_index, sql_type, OdbcDataType::BigInt)
+            {
+                return Err(ColumnFailure::SignedUnsignedMismatch);
+            }
+            Int64Type::identical(field.is_nullable())
+        }
         ArrowDataType::UInt8 => UInt8Type::identical(field.is_nullable()),
+        // Every unsigned 16 Bit integer fits into a signed 32 Bit one, so there is nothing to
+        // reinterpret.
+        ArrowDataType::UInt16 => UInt16Type::map_falliable(
+            field.is_nullable(),
+            map_value_errors_to_null,
+            |&value: &i32| {
+                u16::try_from(value).map_err(|_| {
+                    if value < 0 {
+                        MappingError::NegativeUnsigned {
+                            value: value.into(),
+                        }
+                    } else {
+                        MappingError::IntegerOutOfRange {
+                            value: value.into(),
+                        }
+                    }
+                })
+            },
+        ),
+        ArrowDataType::UInt32 => {
+            // Same as for `UInt64` below.
+            if buffer_allocation_options.allow_signed_unsigned_coercion
+                || is_unsigned_integer(query_metadata, col_index, sql_type, OdbcDataType::Integer)
+            {
+                UInt32Type::map_infalliable(field.is_nullable(), |&value: &i32| value as u32)
+            } else {
+                UInt32Type::map_falliable(
+                    field.is_nullable(),
+                    map_value_errors_to_null,
+                    |&value: &i32| {
+                        u32::try_from(value).map_err(|_| MappingError::NegativeUnsigned {
+                            value: value.into(),
+                        })
+                    },
+                )
+            }
+        }
+        ArrowDataType::UInt64 => {
+            // For columns known to be unsigned, a negative value in the signed buffer can only be a
+            // large unsigned value wrapped around.
+            if buffer_allocation_options.allow_signed_unsigned_coercion
+                || is_unsigned_integer(query_metadata, col_index, sql_type, OdbcDataType::BigInt)
+            {
+                UInt64Type::map_infalliable(field.is_nullable(), |&value: &i64| value as u64)
+            } else {
+                UInt64Type::map_falliable(
+                    field.is_nullable(),
+                    map_value_errors_to_null,
+                    |&value: &i64| {
+                        u64::try_from(value).map_err(|_| MappingError::NegativeUnsigned { value })
+                    },
+                )
+            }
+        }
+        // Bound as `REAL` and narrowed, since ODBC has no C type for half precision floats
+        ArrowDataType::Float16 => {
+            Float16Type::map_infalliable(field.is_nullable(), |&value: &f32| {
+                <Float16Type as ArrowPrimitiveType>::Native::from_f32(value)
+            })
+        }
         ArrowDataType::Float32 => Float32Type::identical(field.is_nullable()),
         ArrowDataType::Float64 => Float64Type::identical(field.is_nullable()),
         ArrowDataType::Date32 => Date32Type::map_infalliable(field.is_nullable(), days_since_epoch),
-        ArrowDataType::Utf8 => {
-            let sql_type = query_metadata
-                .col_data_type(col_index)
-                .map_err(ColumnFailure::FailedToDescribeColumn)?;
-            // Use a zero based index here, because we use it everywhere else there we communicate
-            // with users.
-            debug!("Relational type of column {}: {sql_type:?}", col_index - 1);
-            let lazy_display_size = || query_metadata.col_display_size(col_index);
-            // Use the SQL type first to determine buffer length.
-            choose_text_strategy(
+        ArrowDataType::Date64 => {
+            Date64Type::map_infalliable(field.is_nullable(), ms_since_epoch_of_date)
+        }
+        ArrowDataType::Utf8
+            if buffer_allocation_options.format_guid_as_string && is_guid(&sql_type) =>
+        {
+            Box::new(GuidStrategy::new(true))
+        }
+        ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View
+            if is_xml_column(query_metadata, col_index, &sql_type) =>
+        {
+            let output_mode = match field.data_type() {
+                ArrowDataType::Utf8 => TextOutputMode::Utf8,
+                ArrowDataType::LargeUtf8 => TextOutputMode::LargeUtf8,
+                _ => TextOutputMode::Utf8View,
+            };
+            let max_xml_size = buffer_allocation_options
+                .max_xml_size
+                .or(buffer_allocation_options.max_text_size)
+                .unwrap_or(DEFAULT_MAX_XML_SIZE);
+            Box::new(Xml::new(
+                max_xml_size,
+                buffer_allocation_options.element_size_cap,
+                output_mode,
+            ))
+        }
+        ArrowDataType::Utf8 => text_strategy(
+            query_metadata,
+            col_index,
+            sql_type,
+            buffer_allocation_options,
+            trim_fixed_sized_character_strings,
+            TextOutputMode::Utf8,
+        )?,
+        ArrowDataType::LargeUtf8 => text_strategy(
+            query_metadata,
+            col_index,
+            sql_type,
+            buffer_allocation_options,
+            trim_fixed_sized_character_strings,
+            TextOutputMode::LargeUtf8,
+        )?,
+        ArrowDataType::Utf8View => text_strategy(
+            query_metadata,
+            col_index,
+            sql_type,
+            buffer_allocation_options,
+            trim_fixed_sized_character_strings,
+            TextOutputMode::Utf8View,
+        )?,
+        // Databases like PostgreSQL transmit arrays as text literals
+        ArrowDataType::List(element) | ArrowDataType::LargeList(element)
+            if PostgresArray::is_supported_element(element.data_type()) =>
+        {
+            let text = text_strategy(
+                query_metadata,
+                col_index,
                 sql_type,
-                lazy_display_size,
-                buffer_allocation_options.max_text_size,
-                trim_fixed_sized_character_strings,
-            )?
+                buffer_allocation_options,
+                false,
+                TextOutputMode::Utf8,
+            )?;
+            if matches!(field.data_type(), ArrowDataType::LargeList(_)) {
+                Box::new(PostgresArray::new_large(text, element.clone()))
+            } else {
+                Box::new(PostgresArray::new(text, element.clone()))
+            }
+        }
+        // PostgreSQL transmits row types as text literals, too
+        ArrowDataType::Struct(fields)
+            if fields
+                .iter()
+                .all(|field| PostgresArray::is_supported_element(field.data_type())) =>
+        {
+            let text = text_strategy(
+                query_metadata,
+                col_index,
+                sql_type,
+                buffer_allocation_options,
+                false,
+                TextOutputMode::Utf8,
+            )?;
+            Box::new(PostgresComposite::new(text, fields.clone()))
+        }
+        // Sources like ClickHouse transmit maps as text literals
+        ArrowDataType::Map(entries, sorted) if MapLiteral::is_supported_entries(entries) => {
+            let text = text_strategy(
+                query_metadata,
+                col_index,
+                sql_type,
+                buffer_allocation_options,
+                false,
+                TextOutputMode::Utf8,
+            )?;
+            Box::new(MapLiteral::new(text, entries.clone(), *sorted))
         }
         ArrowDataType::Decimal128(precision, scale @ 0..) => {
-            Box::new(Decimal::new(*precision, *scale))
-        }
-        ArrowDataType::Binary => {
-            let sql_type = query_metadata
-                .col_data_type(col_index)
-                .map_err(ColumnFailure::FailedToDescribeColumn)?;
-            let length = sql_type.column_size();
-            let length = match (length, buffer_allocation_options.max_binary_size) {
-                (None, None) => return Err(ColumnFailure::ZeroSizedColumn { sql_type }),
-                (None, Some(limit)) => limit,
-                (Some(len), None) => len.get(),
-                (Some(len), Some(limit)) => {
-                    if len.get() < limit {
-                        len.get()
-                    } else {
-                        limit
-                    }
-                }
+            // Floating point columns are fetched as binary floats and converted by us, rather than
+            // relying on the driver to format them as decimal text. Wildcard numbers may have more
+            // digits than a float can hold though, so these are fetched as text. Currency values
+            // have a fixed scale, so we do not need to rely on the one reported by the driver.
+            // Columns reported with a scale of four already are fetched like any other decimal, so
+            // only columns reported otherwise (e.g. as text) cost asking for the type name.
+            let is_scale_reported = matches!(
+                sql_type,
+                OdbcDataType::Decimal { scale: 4, .. } | OdbcDataType::Numeric { scale: 4, .. }
+            );
+            let money_type = if *scale == 4 && !is_scale_reported {
+                col_type_name(query_metadata, col_index)
+                    .and_then(|type_name| MoneyType::from_type_name(&type_name))
+            } else {
+                None
             };
-            Box::new(Binary::new(length))
+            if let Some(money_type) = money_type {
+                Box::new(Money::new(money_type, *precision))
+            } else if is_wildcard_number(&sql_type) {
+                warn!(
+                    "Column {} is a number without precision and scale (e.g. Oracle `NUMBER`). \
+                    Its values are fetched as text into Decimal128({precision}, {scale}). Values \
+                    with more digits than the field allows for cause an error.",
+                    col_index - 1
+                );
+                Box::new(Decimal::with_exponent(
+                    *precision,
+                    *scale,
+                    WILDCARD_NUMBER_TEXT_LEN,
+                ))
+            } else if matches!(
+                sql_type,
+                OdbcDataType::Float { .. } | OdbcDataType::Real | OdbcDataType::Double
+            ) {
+                Box::new(DecimalFromFloat::new(
+                    field.is_nullable(),
+                    *precision,
+                    *scale,
+                ))
+            } else {
+                Box::new(Decimal::new(*precision, *scale))
+            }
+        }
+        // Precision of Decimal256 exceeds what a 128 Bit integer could hold, so we always fetch the
+        // values as text.
+        ArrowDataType::Decimal256(precision, scale @ 0..) => {
+            Box::new(Decimal256::new(*precision, *scale))
         }
-        ArrowDataType::Timestamp(TimeUnit::Second, _) => {
-            TimestampSecondType::map_infalliable(field.is_nullable(), seconds_since_epoch)
+        ArrowDataType::Binary if is_wkb_field(field) => {
+            match spatial_encoding_of_column(query_metadata, col_index, &sql_type) {
+                Some(encoding) => Box::new(Wkb::new(
+                    encoding,
+                    buffer_allocation_options
+                        .max_binary_size
+                        .unwrap_or(DEFAULT_MAX_SPATIAL_SIZE),
+                    buffer_allocation_options.element_size_cap,
+                )),
+                // The column holds WKB already, e.g. the result of `STAsBinary()`
+                None => binary_strategy(field, sql_type, buffer_allocation_options)?,
+            }
         }
-        ArrowDataType::Timestamp(TimeUnit::Millisecond, _) => {
-            TimestampMillisecondType::map_infalliable(field.is_nullable(), ms_since_epoch)
+        ArrowDataType::Binary | ArrowDataType::LargeBinary | ArrowDataType::BinaryView => {
+            binary_strategy(field, sql_type, buffer_allocation_options)?
         }
-        ArrowDataType::Timestamp(TimeUnit::Microsecond, _) => {
-            TimestampMicrosecondType::map_infalliable(field.is_nullable(), us_since_epoch)
+        ArrowDataType::Timestamp(unit, Some(_)) if is_timestamp_with_offset(&sql_type) => {
+            Box::new(TextTimestampWithOffset::new(*unit))
         }
+        ArrowDataType::Timestamp(
+            unit @ (TimeUnit::Second | TimeUnit::Millisecond | TimeUnit::Microsecond),
+            _,
+        ) => timestamp_strategy(
+            *unit,
+            field.is_nullable(),
+            buffer_allocation_options.sub_precision_handling,
+            map_value_errors_to_null,
+        ),
         ArrowDataType::Timestamp(TimeUnit::Nanosecond, _) => {
             TimestampNanosecondType::map_falliable(
                 field.is_nullable(),
@@ -191,8 +703,58 @@ pub fn choose_column_strategy(
                 ns_since_epoch,
             )
         }
+        ArrowDataType::Time32(TimeUnit::Second) => {
+            Time32SecondType::map_infalliable(field.is_nullable(), seconds_since_midnight)
+        }
+        // `SQL_C_TYPE_TIME` can not hold fractions of a second, so these are fetched as text.
+        ArrowDataType::Time32(unit @ TimeUnit::Millisecond)
+        | ArrowDataType::Time64(unit @ (TimeUnit::Microsecond | TimeUnit::Nanosecond)) => {
+            Box::new(TextTimeConversion::new(*unit))
+        }
+        ArrowDataType::Interval(_) | ArrowDataType::Duration(_) => Box::new(
+            TextIntervalConversion::new(sql_type, field.data_type().clone())?,
+        ),
+        // GUIDs arrive in the mixed-endian layout of the Windows `GUID` struct
+        ArrowDataType::FixedSizeBinary(16) if is_guid(&sql_type) => {
+            Box::new(GuidStrategy::new(false))
+        }
         ArrowDataType::FixedSizeBinary(length) => {
-            Box::new(FixedSizedBinary::new((*length).try_into().unwrap()))
+            let length = (*length).try_into().unwrap();
+            if let Some(cap) = buffer_allocation_options.element_size_cap {
+                if length > cap {
+                    return Err(ColumnFailure::ElementSizeExceedsCap { length, cap });
+                }
+            }
+            Box::new(FixedSizedBinary::new(length))
+        }
+        // Vector databases transmit fixed sized arrays, like embeddings, as binary blobs
+        ArrowDataType::FixedSizeList(element, size)
+            if FixedSizeListFromBinary::element_size(element.data_type()).is_some() =>
+        {
+            Box::new(FixedSizeListFromBinary::new(element.clone(), *size))
+        }
+        ArrowDataType::Dictionary(key_type, value_type)
+            if **key_type == ArrowDataType::Int32 && **value_type == ArrowDataType::Utf8 =>
+        {
+            text_strategy(
+                query_metadata,
+                col_index,
+                sql_type,
+                buffer_allocation_options,
+                trim_fixed_sized_character_strings,
+                TextOutputMode::Dictionary,
+            )?
+        }
+        ArrowDataType::Dictionary(key_type, value_type) => {
+            integer_dictionary_strategy(key_type, value_type, field.is_nullable(), None)?
+        }
+        // ODBC transmits every value of every row, so there are no runs to take advantage of
+        // while fetching. Encoding them would be an extra pass over the data, better performed by
+        // the application, if it actually benefits from run-end encoding.
+        ArrowDataType::RunEndEncoded(_run_ends, values) => {
+            return Err(ColumnFailure::RunEndEncodingUnsupported {
+                values_type: values.data_type().clone(),
+            })
         }
         unsupported_arrow_type => {
             return Err(ColumnFailure::UnsupportedArrowType(
@@ -203,8 +765,148 @@ pub fn choose_column_strategy(
     Ok(strat)
 }
 
+/// Fetches variadic binary columns into `Binary`, `LargeBinary` or `BinaryView` fields.
+fn binary_strategy(
+    field: &Field,
+    sql_type: OdbcDataType,
+    buffer_allocation_options: BufferAllocationOptions,
+) -> Result<Box<dyn ReadStrategy + Send>, ColumnFailure> {
+    let length = sql_type.column_size();
+    let limit = min_limit(
+        buffer_allocation_options.max_binary_size,
+        buffer_allocation_options.element_size_cap,
+    );
+    let length = match (length, limit) {
+        (None, None) => return Err(ColumnFailure::ZeroSizedColumn { sql_type }),
+        (None, Some(limit)) => limit,
+        (Some(len), None) => len.get(),
+        (Some(len), Some(limit)) => {
+            if len.get() < limit {
+                len.get()
+            } else {
+                limit
+            }
+        }
+    };
+    let strategy: Box<dyn ReadStrategy + Send> = match field.data_type() {
+        ArrowDataType::LargeBinary => Box::new(LargeBinary::new(length)),
+        ArrowDataType::BinaryView => Box::new(BinaryView::new(length)),
+        _ => Box::new(Binary::new(length)),
+    };
+    Ok(strategy)
+}
+
+/// `true` if the driver reports the column as `integer_type` without sign, as e.g. MySQL does for
+/// `BIGINT UNSIGNED`. Only columns of `integer_type` are asked for their sign, so other columns do
+/// not cost an extra round trip. Drivers unable to tell the sign are assumed to report signed
+/// columns, the same way schema inference does.
+fn is_unsigned_integer(
+    query_metadata: &mut impl ResultSetMetadata,
+    col_index: u16,
+    sql_type: OdbcDataType,
+    integer_type: OdbcDataType,
+) -> bool {
+    sql_type == integer_type
+        && query_metadata
+            .column_is_unsigned(col_index)
+            .unwrap_or(false)
+}
+
+/// `true` if the column holds XML documents, either reported as an XML type, or as large text with
+/// an XML type name. Only large text columns are asked for their type name, so other columns do
+/// not cost an extra round trip.
+fn is_xml_column(
+    query_metadata: &mut impl ResultSetMetadata,
+    col_index: u16,
+    sql_type: &OdbcDataType,
+) -> bool {
+    is_xml(sql_type)
+        || (is_large_text(sql_type)
+            && matches!(
+                col_type_name(query_metadata, col_index).as_deref(),
+                Some(type_name) if is_xml_type_name(type_name)
+            ))
+}
+
+/// How the values of a spatial column are transmitted, if they need to be converted into WKB.
+fn spatial_encoding_of_column(
+    query_metadata: &mut impl ResultSetMetadata,
+    col_index: u16,
+    sql_type: &OdbcDataType,
+) -> Option<SpatialEncoding> {
+    col_type_name(query_metadata, col_index)
+        .and_then(|type_name| SpatialEncoding::of(sql_type, &type_name))
+}
+
+/// Maximum length of the text representation of a number without precision and scale. Oracle
+/// `NUMBER` holds up to 40 significant digits, in addition there may be a sign, a decimal point and
+/// an exponent.
+const WILDCARD_NUMBER_TEXT_LEN: usize = 48;
+
+/// Oracle drivers report `NUMBER` columns declared without precision and scale as `SQL_FLOAT` with
+/// a precision of zero. Unlike genuine floating point columns, these may hold more significant
+/// digits than a `Float64` could represent, so they must not be fetched as binary floats, if the
+/// application asks for decimals or text. `SQL_DOUBLE` columns are not considered, even if they
+/// report a precision of zero, since these are indistinguishable from genuine doubles.
+fn is_wildcard_number(sql_type: &OdbcDataType) -> bool {
+    matches!(sql_type, OdbcDataType::Float { precision: 0 })
+}
+
+fn text_strategy(
+    query_metadata: &mut impl ResultSetMetadata,
+    col_index: u16,
+    mut sql_type: OdbcDataType,
+    buffer_allocation_options: BufferAllocationOptions,
+    trim_fixed_sized_character_strings: bool,
+    output_mode: TextOutputMode,
+) -> Result<Box<dyn ReadStrategy + Send>, ColumnFailure> {
+    // The display size of floating point types is too small for all the digits of a wildcard
+    // number, so we size the buffer as if it were a numeric with room to spare.
+    if is_wildcard_number(&sql_type) {
+        sql_type = OdbcDataType::Numeric {
+            precision: WILDCARD_NUMBER_TEXT_LEN - 2,
+            scale: 0,
+        };
+    }
+    // Use a zero based index here, because we use it everywhere else there we communicate
+    // with users.
+    debug!("Relational type of column {}: {sql_type:?}", col_index - 1);
+    let lazy_display_size = || query_metadata.col_display_size(col_index);
+    // Use the SQL type first to determine buffer length.
+    choose_text_strategy(
+        sql_type,
+        lazy_display_size,
+        buffer_allocation_options.max_text_size,
+        buffer_allocation_options.element_size_cap,
+        trim_fixed_sized_character_strings,
+        buffer_allocation_options.trim_char_padding,
+        output_mode,
+    )
+}
+
+/// Suggests a supported Arrow type to use instead of `ty`, for types which can not be fetched from
+/// an ODBC data source, but have a supported counterpart. Appended to the message of
+/// [`ColumnFailure::UnsupportedArrowType`]. `None` if there is no obvious alternative.
+pub fn suggestions_for_type(ty: &ArrowDataType) -> Option<&'static str> {
+    let suggestion = match ty {
+        ArrowDataType::Decimal128(_, _) | ArrowDataType::Decimal256(_, _) => {
+            "Decimals with negative scale are not supported. Consider using a scale of zero and \
+            casting the fetched arrays."
+        }
+        ArrowDataType::Dictionary(_, _) => {
+            "Only `Dictionary(Int8, Int32)`, `Dictionary(UInt8, Int32)` and `Dictionary(Int32, Utf8)` \
+            are supported. Otherwise consider using the value type of the dictionary instead and \
+            encoding the fetched arrays."
+        }
+        ArrowDataType::Null => "Consider using a nullable field with the type of the column.",
+        _ => return None,
+    };
+    Some(suggestion)
+}
+
 /// Read error related to a specific column
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ColumnFailure {
     /// We are getting a display or column size from ODBC but it is not larger than 0.
     #[error(
@@ -228,11 +930,28 @@ pub enum ColumnFailure {
         source: odbc_api::Error,
     },
     /// The type specified in the arrow schema is not supported to be fetched from the database.
+    /// For common cases the message suggests a supported type, see [`suggestions_for_type`].
     #[error(
         "Unsupported arrow type: `{0}`. This type can currently not be fetched from an ODBC data \
-        source by an instance of OdbcReader."
+        source by an instance of OdbcReader.{}",
+        suggestions_for_type(.0).map_or(String::new(), |suggestion| format!(" {suggestion}"))
     )]
     UnsupportedArrowType(ArrowDataType),
+    /// The field is run-end encoded. Values must be fetched without encoding.
+    #[error(
+        "Run-end encoded fields can not be fetched from an ODBC data source, since every value is \
+        transmitted individually. Please specify the type of the values (`{values_type}`) for the \
+        field instead, and encode the fetched arrays if your application benefits from it."
+    )]
+    RunEndEncodingUnsupported { values_type: ArrowDataType },
+    /// The field is a signed 64 Bit integer, yet the driver reports the column as unsigned.
+    #[error(
+        "The column is an unsigned BIGINT, yet the Arrow field is a signed 64 Bit integer. Values \
+        larger than the signed maximum would wrap around to negative numbers. Please specify \
+        `UInt64` for the field, or allow signed unsigned coercion if you are certain the values are \
+        within range."
+    )]
+    SignedUnsignedMismatch,
     /// At ODBC api calls gaining information about the columns did fail.
     #[error(
         "An error occurred fetching the column description or data type from the metainformation \
@@ -247,6 +966,64 @@ pub enum ColumnFailure {
         num_elements: usize,
         element_size: usize,
     },
+    /// Elements of the field have a fixed size, which exceeds
+    /// [`BufferAllocationOptions::element_size_cap`].
+    #[error(
+        "Elements of the field are {length} bytes in size, which exceeds the cap of {cap} bytes \
+        for any element of a column buffer. Fixed sized binary elements can not be truncated. \
+        Please specify a variadic binary type for the field or raise the cap."
+    )]
+    ElementSizeExceedsCap { length: usize, cap: usize },
+    /// Columns are matched by name, yet the result set does not contain a column with the name of
+    /// the field.
+    #[error(
+        "The result set does not contain a column with the name of the field. Columns are matched \
+        by name, since `OdbcReaderBuilder::match_columns_by_name` has been set."
+    )]
+    ColumnNotFound,
+    /// The result set does not contain a column for the field, yet it can not be padded with NULLs
+    /// since it is not nullable.
+    #[error(
+        "The result set does not contain a column for the field. It can not be filled with NULLs, \
+        since the field is not nullable."
+    )]
+    MissingColumnNotNullable,
+    /// Columns are matched by name, yet more than one column in the result set has the name of the
+    /// field (compared case insensitive).
+    #[error(
+        "More than one column in the result set has the name of the field. Columns are matched by \
+        name (case insensitive), since `OdbcReaderBuilder::match_columns_by_name` has been set. \
+        Consider using aliases in your query to make the column names unique."
+    )]
+    AmbiguousColumnName,
+    /// The value specified to replace NULLs in this column does not match the Arrow type of the
+    /// field.
+    #[error(
+        "{replacement:?} can not be used to replace NULLs in a column of type {data_type}. Please \
+        specify a replacement matching the type of the field."
+    )]
+    InvalidNullReplacement {
+        data_type: ArrowDataType,
+        replacement: ScalarValue,
+    },
+    /// The dictionary values specified for the column have more entries than the key type of the
+    /// dictionary is able to index.
+    #[error(
+        "The dictionary for the field has {len} values, which can not be indexed by keys of type \
+        {key_type}. Please specify a wider key type or fewer values."
+    )]
+    DictionaryTooLarge { len: usize, key_type: ArrowDataType },
+    /// The field is an interval or a duration, yet the column is not an SQL interval type, or one
+    /// whose fields can not be represented by the type of the field.
+    #[error(
+        "The column of type {sql_type:?} can not be fetched into a field of type {data_type}. \
+        `Interval(YearMonth)` requires a year-month interval column, `Interval(DayTime)` and \
+        `Duration` a day-time interval column and `Interval(MonthDayNano)` any interval column."
+    )]
+    IncompatibleInterval {
+        sql_type: OdbcDataType,
+        data_type: ArrowDataType,
+    },
 }
 
 impl ColumnFailure {
@@ -259,3 +1036,44 @@ impl ColumnFailure {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, BooleanArray};
+    use odbc_api::{
+        buffers::{AnyBuffer, AnySlice},
+        sys::NULL_DATA,
+        Bit,
+    };
+
+    use super::{NullableBoolean, ReadStrategy};
+
+    #[test]
+    fn nullable_boolean_nulls_at_null_data_indicators() {
+        // Given a raw buffer with `SQL_NULL_DATA` indicators at the first, in between and at the
+        // last position. Values behind NULL indicators are deliberately set to `true`.
+        let strategy = NullableBoolean;
+        let mut buffer = AnyBuffer::from_desc(6, strategy.buffer_desc());
+        let AnyBuffer::NullableBit(column) = &mut buffer else {
+            panic!("Expected nullable bit buffer")
+        };
+        {
+            let mut writer = column.writer_n(6);
+            let (values, indicators) = writer.raw_values();
+            values.copy_from_slice(&[Bit(1), Bit(1), Bit(0), Bit(1), Bit(0), Bit(1)]);
+            indicators.copy_from_slice(&[NULL_DATA, 1, 1, NULL_DATA, 1, NULL_DATA]);
+        }
+
+        // When
+        let array = strategy
+            .fill_arrow_array(AnySlice::NullableBit(column.iter(6)))
+            .unwrap();
+
+        // Then
+        let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+        let expected =
+            BooleanArray::from(vec![None, Some(true), Some(false), None, Some(false), None]);
+        assert_eq!(&expected, array);
+        assert_eq!(3, array.null_count());
+    }
+}
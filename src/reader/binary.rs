@@ -1,7 +1,17 @@
-use std::{convert::TryInto, sync::Arc};
+use std::{convert::TryInto, num::NonZeroUsize, sync::Arc};
 
-use arrow::array::{ArrayRef, BinaryBuilder, FixedSizeBinaryBuilder};
-use odbc_api::buffers::{AnySlice, BufferDesc};
+use arrow::{
+    array::{
+        ArrayRef, BinaryViewBuilder, FixedSizeBinaryBuilder, FixedSizeListArray, Float32Array,
+        Float64Array, GenericBinaryBuilder, Int8Array, OffsetSizeTrait,
+    },
+    buffer::NullBuffer,
+    datatypes::{DataType as ArrowDataType, FieldRef},
+};
+use odbc_api::{
+    buffers::{AnySlice, BufferDesc},
+    DataType as OdbcDataType,
+};
 
 use super::{MappingError, ReadStrategy};
 
@@ -14,18 +24,83 @@ impl Binary {
     pub fn new(max_len: usize) -> Self {
         Self { max_len }
     }
+
+    /// Maximum length in bytes of an element in the buffer bound to the column.
+    pub fn element_size(&self) -> usize {
+        self.max_len
+    }
 }
 
 impl ReadStrategy for Binary {
+    fn strategy_type_name(&self) -> &'static str {
+        "Binary"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         BufferDesc::Binary {
             length: self.max_len,
         }
     }
 
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        Ok(variadic_binary::<i32>(column_view))
+    }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        Some(self.element_size())
+    }
+}
+
+/// Same as [`Binary`], but emits `LargeBinaryArray`s, whose 64 Bit offsets allow for more than 2 GiB
+/// of data in a single batch.
+pub struct LargeBinary(Binary);
+
+impl LargeBinary {
+    pub fn new(max_len: usize) -> Self {
+        Self(Binary::new(max_len))
+    }
+}
+
+impl ReadStrategy for LargeBinary {
+    fn strategy_type_name(&self) -> &'static str {
+        "LargeBinary"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.0.buffer_desc()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        Ok(variadic_binary::<i64>(column_view))
+    }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        self.0.element_size_hint()
+    }
+}
+
+/// Same as [`Binary`], but emits `BinaryViewArray`s. Values of up to 12 bytes are inlined into the
+/// views, longer ones are copied into a data buffer without the need for an offset buffer.
+pub struct BinaryView(Binary);
+
+impl BinaryView {
+    pub fn new(max_len: usize) -> Self {
+        Self(Binary::new(max_len))
+    }
+}
+
+impl ReadStrategy for BinaryView {
+    fn strategy_type_name(&self) -> &'static str {
+        "BinaryView"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.0.buffer_desc()
+    }
+
     fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
         let view = column_view.as_bin_view().unwrap();
-        let mut builder = BinaryBuilder::new();
+        let mut builder = BinaryViewBuilder::with_capacity(view.len());
         for value in view.iter() {
             if let Some(bytes) = value {
                 builder.append_value(bytes);
@@ -35,6 +110,24 @@ impl ReadStrategy for Binary {
         }
         Ok(Arc::new(builder.finish()))
     }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        self.0.element_size_hint()
+    }
+}
+
+/// Copies the values of a binary buffer into an array with offsets of type `O`.
+fn variadic_binary<O: OffsetSizeTrait>(column_view: AnySlice) -> ArrayRef {
+    let view = column_view.as_bin_view().unwrap();
+    let mut builder = GenericBinaryBuilder::<O>::new();
+    for value in view.iter() {
+        if let Some(bytes) = value {
+            builder.append_value(bytes);
+        } else {
+            builder.append_null();
+        }
+    }
+    Arc::new(builder.finish())
 }
 
 pub struct FixedSizedBinary {
@@ -46,9 +139,47 @@ impl FixedSizedBinary {
     pub fn new(len: u32) -> Self {
         Self { len }
     }
+
+    /// Length in bytes of an element in the buffer bound to the column.
+    pub fn element_size(&self) -> usize {
+        self.len as usize
+    }
+}
+
+/// Size in bytes of values of a column of `sql_type` if fetched as binary. Used to fetch columns
+/// with otherwise unsupported Arrow types into [`FixedSizedBinary`].
+///
+/// `octet_length` is the size of the column in bytes as reported by the driver
+/// (`SQL_DESC_OCTET_LENGTH`). Fetching as `SQL_C_BINARY` transfers the data source's own
+/// representation of the value, whose size is only known to the driver, so we must not assume the
+/// size of the default C type here. For variable sized types the octet length is merely an upper
+/// bound, so these yield `None`, as does an unknown octet length.
+pub fn opaque_binary_length(
+    sql_type: OdbcDataType,
+    octet_length: Option<NonZeroUsize>,
+) -> Option<u32> {
+    match sql_type {
+        OdbcDataType::Bit
+        | OdbcDataType::TinyInt
+        | OdbcDataType::SmallInt
+        | OdbcDataType::Integer
+        | OdbcDataType::Real
+        | OdbcDataType::BigInt
+        | OdbcDataType::Float { .. }
+        | OdbcDataType::Double
+        | OdbcDataType::Date
+        | OdbcDataType::Time { .. }
+        | OdbcDataType::Timestamp { .. }
+        | OdbcDataType::Binary { .. } => octet_length?.get().try_into().ok(),
+        _ => None,
+    }
 }
 
 impl ReadStrategy for FixedSizedBinary {
+    fn strategy_type_name(&self) -> &'static str {
+        "FixedSizedBinary"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         BufferDesc::Binary {
             length: self.len as usize,
@@ -60,6 +191,12 @@ impl ReadStrategy for FixedSizedBinary {
         let mut builder = FixedSizeBinaryBuilder::new(self.len.try_into().unwrap());
         for value in view.iter() {
             if let Some(bytes) = value {
+                if bytes.len() != self.len as usize {
+                    return Err(MappingError::UnexpectedBinaryLength {
+                        expected: self.len as usize,
+                        actual: bytes.len(),
+                    });
+                }
                 builder.append_value(bytes).unwrap();
             } else {
                 builder.append_null();
@@ -67,4 +204,94 @@ impl ReadStrategy for FixedSizedBinary {
         }
         Ok(Arc::new(builder.finish()))
     }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        Some(self.element_size())
+    }
+}
+
+/// Fetches fixed size lists (e.g. embedding vectors) which are transmitted by the data source as
+/// binary blobs of their elements. Elements are expected to be in little endian byte order.
+pub struct FixedSizeListFromBinary {
+    /// Field describing the elements of the list.
+    element: FieldRef,
+    /// Number of elements in each list
+    size: i32,
+}
+
+impl FixedSizeListFromBinary {
+    pub fn new(element: FieldRef, size: i32) -> Self {
+        Self { element, size }
+    }
+
+    /// Size in bytes of a single element, or `None` if we do not know how to interpret elements of
+    /// this type.
+    pub fn element_size(data_type: &ArrowDataType) -> Option<usize> {
+        match data_type {
+            ArrowDataType::Int8 => Some(1),
+            ArrowDataType::Float32 => Some(4),
+            ArrowDataType::Float64 => Some(8),
+            _ => None,
+        }
+    }
+
+    /// Length in bytes of a single list.
+    fn bytes_per_list(&self) -> usize {
+        Self::element_size(self.element.data_type()).unwrap() * self.size as usize
+    }
+}
+
+impl ReadStrategy for FixedSizeListFromBinary {
+    fn strategy_type_name(&self) -> &'static str {
+        "FixedSizeList(binary)"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Binary {
+            length: self.bytes_per_list(),
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let view = column_view.as_bin_view().unwrap();
+        let len = self.bytes_per_list();
+        // Bytes of all elements. NULL lists are padded with zeroes, so each list occupies the same
+        // number of elements.
+        let mut bytes = Vec::with_capacity(view.len() * len);
+        let mut valid = Vec::with_capacity(view.len());
+        for value in view.iter() {
+            if let Some(value) = value {
+                if value.len() != len {
+                    return Err(MappingError::UnexpectedBinaryLength {
+                        expected: len,
+                        actual: value.len(),
+                    });
+                }
+                bytes.extend_from_slice(value);
+                valid.push(true);
+            } else {
+                bytes.resize(bytes.len() + len, 0);
+                valid.push(false);
+            }
+        }
+        let values: ArrayRef = match self.element.data_type() {
+            ArrowDataType::Int8 => Arc::new(Int8Array::from_iter_values(
+                bytes.iter().map(|&b| i8::from_le_bytes([b])),
+            )),
+            ArrowDataType::Float32 => Arc::new(Float32Array::from_iter_values(
+                bytes
+                    .chunks_exact(4)
+                    .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap())),
+            )),
+            ArrowDataType::Float64 => Arc::new(Float64Array::from_iter_values(
+                bytes
+                    .chunks_exact(8)
+                    .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap())),
+            )),
+            _ => unreachable!("Element type must be checked with `element_size`"),
+        };
+        let nulls = valid.contains(&false).then(|| NullBuffer::from(valid));
+        let array = FixedSizeListArray::new(self.element.clone(), self.size, values, nulls);
+        Ok(Arc::new(array))
+    }
 }
@@ -0,0 +1,59 @@
+use arrow::ffi_stream::FFI_ArrowArrayStream;
+use odbc_api::Cursor;
+
+use crate::{Error, OdbcReader};
+
+impl<C> OdbcReader<C>
+where
+    C: Cursor + Send + 'static,
+{
+    /// Hands the reader over to a consumer of the [Arrow C stream interface][1], e.g. `pyarrow`,
+    /// the R `arrow` package or any other library compiled independently of this one. Batches are
+    /// fetched from the data source as the consumer calls `get_next` and passed on without being
+    /// copied. The consumer owns the stream and is responsible for calling its `release` callback,
+    /// which also closes the cursor. Available with the `abi` feature.
+    ///
+    /// Fails with [`Error::NullArrowArrayStream`] if `out` is null.
+    ///
+    /// # Safety
+    ///
+    /// `out` must be valid for writes and properly aligned. Whatever `out` points to is
+    /// overwritten without being released.
+    ///
+    /// [1]: https://arrow.apache.org/docs/format/CStreamInterface.html
+    pub unsafe fn export_to_c_stream(self, out: *mut FFI_ArrowArrayStream) -> Result<(), Error> {
+        if out.is_null() {
+            return Err(Error::NullArrowArrayStream);
+        }
+        let stream = FFI_ArrowArrayStream::new(Box::new(self));
+        std::ptr::write(out, stream);
+        Ok(())
+    }
+
+    /// Like [`Self::export_to_c_stream`], but allocates the `ArrowArrayStream` struct on the heap
+    /// and hands out a pointer to it. Useful for bindings (e.g. via `cffi`), which would rather
+    /// receive a stream than allocate one. `get_schema` reports the schema of the reader,
+    /// `get_next` fetches one batch and `release` drops the reader, closing the cursor. Available
+    /// with the `abi` feature.
+    ///
+    /// The consumer usually moves the stream out of the struct when importing it (e.g.
+    /// `pyarrow.RecordBatchReader._import_from_c`), leaving the struct itself to be freed with
+    /// [`free_c_stream`]. Freeing a stream which has not been moved out also releases it.
+    pub fn into_c_stream(self) -> *mut FFI_ArrowArrayStream {
+        Box::into_raw(Box::new(FFI_ArrowArrayStream::new(Box::new(self))))
+    }
+}
+
+/// Frees a stream allocated by [`OdbcReader::into_c_stream`]. If the stream has not been released
+/// yet its `release` callback is invoked first, dropping the reader. Does nothing if `stream` is
+/// null. Available with the `abi` feature.
+///
+/// # Safety
+///
+/// `stream` must have been returned by [`OdbcReader::into_c_stream`] and must not be freed more
+/// than once. It must not be used after this call.
+pub unsafe fn free_c_stream(stream: *mut FFI_ArrowArrayStream) {
+    if !stream.is_null() {
+        drop(Box::from_raw(stream));
+    }
+}
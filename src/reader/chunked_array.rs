@@ -0,0 +1,97 @@
+use arrow::{
+    array::{new_empty_array, Array, ArrayRef},
+    compute::concat,
+    datatypes::FieldRef,
+    error::ArrowError,
+};
+
+/// All values of a single column of a result set, as one array for each batch fetched. See
+/// [`crate::OdbcReader::into_chunked_arrays`].
+#[derive(Debug, Clone)]
+pub struct ChunkedArray {
+    field: FieldRef,
+    chunks: Vec<ArrayRef>,
+}
+
+impl ChunkedArray {
+    /// A column without any chunks. Its values are appended batch by batch using [`Self::push`].
+    pub fn new(field: FieldRef) -> Self {
+        Self {
+            field,
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Appends the array of the next batch. Fails, if its type does not match the type of
+    /// [`Self::field`].
+    pub fn push(&mut self, chunk: ArrayRef) -> Result<(), ArrowError> {
+        if self.field.data_type() != chunk.data_type() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Chunk of type {} does not match the type {} of field '{}'.",
+                chunk.data_type(),
+                self.field.data_type(),
+                self.field.name()
+            )));
+        }
+        self.chunks.push(chunk);
+        Ok(())
+    }
+
+    /// Field of the schema of the reader, describing the column.
+    pub fn field(&self) -> &FieldRef {
+        &self.field
+    }
+
+    /// One array for each batch, in the order they have been fetched.
+    pub fn chunks(&self) -> &[ArrayRef] {
+        &self.chunks
+    }
+
+    /// Total number of values in all chunks.
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum()
+    }
+
+    /// `true` if there are no values in any chunk.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total number of NULLs in all chunks.
+    pub fn null_count(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.null_count()).sum()
+    }
+
+    /// Copies all chunks into a single contiguous array. This is the one allocation
+    /// [`crate::OdbcReader::into_chunked_arrays`] defers to the application.
+    pub fn concat(&self) -> Result<ArrayRef, ArrowError> {
+        let chunks: Vec<&dyn Array> = self.chunks.iter().map(|chunk| chunk.as_ref()).collect();
+        if chunks.is_empty() {
+            return Ok(new_empty_array(self.field.data_type()));
+        }
+        concat(&chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{Int32Array, StringArray},
+        datatypes::{DataType, Field},
+    };
+
+    use super::ChunkedArray;
+
+    #[test]
+    fn push_chunk_of_other_type_is_an_error() {
+        let mut column = ChunkedArray::new(Arc::new(Field::new("a", DataType::Int32, true)));
+        column.push(Arc::new(Int32Array::from(vec![1, 2]))).unwrap();
+
+        let result = column.push(Arc::new(StringArray::from(vec!["3"])));
+
+        assert!(result.is_err());
+        assert_eq!(2, column.len());
+    }
+}
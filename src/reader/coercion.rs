@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use arrow::datatypes::{DataType as ArrowDataType, Schema};
+
+use crate::schema::{is_json_field, EXTENSION_NAME_KEY};
+
+use super::TextOutputMode;
+
+/// Controls the Arrow types of integer columns in a schema inferred from the result set. See
+/// [`crate::OdbcReaderBuilder::with_integer_coercion`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntegerCoercion {
+    /// Choose the Arrow type matching the width of the SQL type. E.g. `SMALLINT` becomes `Int16`.
+    #[default]
+    PreserveOriginal,
+    /// Every integer column, no matter its width or signedness, becomes `Int64`. The exception are
+    /// unsigned 64 Bit columns (e.g. `BIGINT UNSIGNED` in MySQL), which stay `UInt64`, since their
+    /// values may exceed the range of `Int64`.
+    AllToInt64,
+}
+
+/// Controls the Arrow types of floating point columns in a schema inferred from the result set. See
+/// [`crate::OdbcReaderBuilder::with_float_coercion`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatCoercion {
+    /// Choose the Arrow type matching the precision of the SQL type. E.g. `REAL` becomes
+    /// `Float32`.
+    #[default]
+    PreserveOriginal,
+    /// Every floating point column becomes `Float64`.
+    AllToFloat64,
+}
+
+/// Changes the data types of the fields in an inferred schema according to the coercion options and
+/// the text output modes. `column_text_output_modes` overrides `text_output_mode` for the fields at
+/// the zero based indices used as keys. If `json_as_large_utf8` is set, JSON fields become
+/// `LargeUtf8`, unless a mode is set for the column explicitly. Since the buffers are chosen based
+/// on the Arrow type, the widening conversion is performed by the ODBC driver, which is required to
+/// support fetching any integer column as a 64 Bit integer and any floating point column as a 64
+/// Bit float.
+pub fn coerce_schema(
+    schema: Schema,
+    integer_coercion: IntegerCoercion,
+    float_coercion: FloatCoercion,
+    text_output_mode: TextOutputMode,
+    column_text_output_modes: &HashMap<usize, TextOutputMode>,
+    json_as_large_utf8: bool,
+) -> Schema {
+    if integer_coercion == IntegerCoercion::PreserveOriginal
+        && float_coercion == FloatCoercion::PreserveOriginal
+        && text_output_mode == TextOutputMode::Utf8
+        && column_text_output_modes.is_empty()
+        && !json_as_large_utf8
+    {
+        return schema;
+    }
+    let fields: Vec<_> = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let coerced = match field.data_type() {
+                ArrowDataType::Int8
+                | ArrowDataType::Int16
+                | ArrowDataType::Int32
+                | ArrowDataType::UInt8
+                | ArrowDataType::UInt16
+                | ArrowDataType::UInt32
+                    if integer_coercion == IntegerCoercion::AllToInt64 =>
+                {
+                    ArrowDataType::Int64
+                }
+                ArrowDataType::Float32 if float_coercion == FloatCoercion::AllToFloat64 => {
+                    ArrowDataType::Float64
+                }
+                ArrowDataType::Utf8
+                    if json_as_large_utf8
+                        && is_json_field(field)
+                        && !column_text_output_modes.contains_key(&index) =>
+                {
+                    ArrowDataType::LargeUtf8
+                }
+                ArrowDataType::Utf8 => match column_text_output_modes
+                    .get(&index)
+                    .unwrap_or(&text_output_mode)
+                {
+                    TextOutputMode::Utf8 => ArrowDataType::Utf8,
+                    TextOutputMode::LargeUtf8 => ArrowDataType::LargeUtf8,
+                    TextOutputMode::Utf8View => ArrowDataType::Utf8View,
+                    TextOutputMode::RawBytes => ArrowDataType::Binary,
+                    TextOutputMode::Dictionary => ArrowDataType::Dictionary(
+                        Box::new(ArrowDataType::Int32),
+                        Box::new(ArrowDataType::Utf8),
+                    ),
+                },
+                other => other.clone(),
+            };
+            let mut field = field.as_ref().clone();
+            // `arrow.json` requires a string storage type. JSON documents fetched as raw bytes or
+            // dictionary encoded text are passed on without the annotation.
+            if is_json_field(&field)
+                && !matches!(
+                    coerced,
+                    ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View
+                )
+            {
+                let mut metadata = field.metadata().clone();
+                metadata.remove(EXTENSION_NAME_KEY);
+                field.set_metadata(metadata);
+            }
+            field.with_data_type(coerced)
+        })
+        .collect();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow::datatypes::{DataType as ArrowDataType, Field, Schema};
+    use odbc_api::DataType as OdbcDataType;
+
+    use crate::{reader::TextOutputMode, schema::arrow_data_type_from};
+
+    use super::{coerce_schema, FloatCoercion, IntegerCoercion};
+
+    #[test]
+    fn coerce_inferred_unsigned_columns_to_int64() {
+        // Given the types inferred for unsigned `TINYINT`, `SMALLINT`, `INTEGER` and `BIGINT`
+        let fields: Vec<_> = [
+            OdbcDataType::TinyInt,
+            OdbcDataType::SmallInt,
+            OdbcDataType::Integer,
+            OdbcDataType::BigInt,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, sql_type)| {
+            let data_type = arrow_data_type_from(sql_type, true).unwrap();
+            Field::new(format!("c{index}"), data_type, true)
+        })
+        .collect();
+        let schema = Schema::new(fields);
+
+        // When
+        let coerced = coerce_schema(
+            schema,
+            IntegerCoercion::AllToInt64,
+            FloatCoercion::PreserveOriginal,
+            TextOutputMode::Utf8,
+            &HashMap::new(),
+            false,
+        );
+
+        // Then all but the 64 Bit column, whose values may not fit, are `Int64`
+        let data_types: Vec<_> = coerced
+            .fields()
+            .iter()
+            .map(|field| field.data_type().clone())
+            .collect();
+        assert_eq!(
+            vec![
+                ArrowDataType::Int64,
+                ArrowDataType::Int64,
+                ArrowDataType::Int64,
+                ArrowDataType::UInt64
+            ],
+            data_types
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    mem::{size_of, ManuallyDrop},
+    num::NonZeroUsize,
+    ptr::null_mut,
+};
+
+use odbc_api::{
+    handles::{
+        AsStatementRef, ColumnDescription, Nullability, Statement, StatementImpl, StatementRef,
+    },
+    sys::{Desc, Pointer, SqlDataType},
+    DataType as OdbcDataType, ResultSetMetadata,
+};
+
+use crate::{schema::num_result_cols, Error};
+
+use super::ffi;
+
+/// Attributes of a result set column, as reported by the ODBC driver. See
+/// [`crate::OdbcReader::columns_metadata`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OdbcColumnMetadata {
+    /// Name of the column. May be empty, e.g. for unnamed expressions.
+    pub name: String,
+    /// Relational type of the column, including its length, precision and scale where applicable.
+    pub data_type: OdbcDataType,
+    /// Concise SQL type code (`SQL_DESC_CONCISE_TYPE`), e.g. `SqlDataType::INTEGER`. Unlike
+    /// `data_type` this also tells driver specific types apart.
+    pub sql_type: SqlDataType,
+    /// Whether the column may hold NULLs.
+    pub nullability: Nullability,
+    /// Maximum number of characters or digits (`SQL_DESC_LENGTH` for character and binary types,
+    /// the number of digits for numeric types). `None` if unknown or unlimited.
+    pub column_size: Option<NonZeroUsize>,
+    /// `SQL_DESC_PRECISION`. For numeric types the number of digits, for time and timestamp types
+    /// the number of digits of the fractional seconds. `None` if the driver fails to report it.
+    pub precision: Option<isize>,
+    /// `SQL_DESC_SCALE`. Number of digits right of the decimal point for numeric types. `None` if
+    /// the driver fails to report it.
+    pub scale: Option<isize>,
+    /// Maximum size of a value in bytes (`SQL_DESC_OCTET_LENGTH`). `None` if unknown, or if the
+    /// driver fails to report it.
+    pub octet_length: Option<NonZeroUsize>,
+    /// Maximum number of characters needed to display a value (`SQL_DESC_DISPLAY_SIZE`). `None`
+    /// if unknown, or if the driver fails to report it.
+    pub display_size: Option<NonZeroUsize>,
+    /// `true` for unsigned numeric types and non-numeric types. `None` if the driver fails to
+    /// report it.
+    pub is_unsigned: Option<bool>,
+    /// Name of the table the column belongs to (`SQL_DESC_BASE_TABLE_NAME`). `None` if the column
+    /// is an expression, or if the driver does not tell.
+    pub base_table_name: Option<String>,
+}
+
+/// Describes every column of the result set, using `SQLDescribeCol` and `SQLColAttribute`. Only
+/// name, type and nullability are required, all other attributes are `None` if the driver does not
+/// support them.
+pub fn columns_metadata(
+    cursor: &mut impl ResultSetMetadata,
+) -> Result<Vec<OdbcColumnMetadata>, Error> {
+    let num_cols = num_result_cols(cursor)?;
+    (1..=num_cols)
+        .map(|column_number| {
+            column_metadata(cursor, column_number).map_err(Error::UnableToDescribeColumns)
+        })
+        .collect()
+}
+
+/// Statement handle of a cursor whose buffers are bound, in order to query the attributes of its
+/// columns on demand. `odbc_api` offers no access to the cursor of a `BlockCursor`, but the
+/// attributes of the columns can be queried while buffers are bound.
+///
+/// Generic over the type `C` of the cursor, so an instance can not outlive the connection the
+/// cursor borrows, and can only be sent to another thread if the cursor can.
+pub struct BoundStatement<C> {
+    /// Never dropped, since the handle is owned and freed by the cursor. `odbc_api` offers no way
+    /// to borrow the handle from a cursor bound to buffers, hence the `'static` lifetime. It is not
+    /// exposed: `as_stmt_ref` only lends out the statement for as long as `self` is borrowed.
+    statement: ManuallyDrop<StatementImpl<'static>>,
+    cursor: PhantomData<C>,
+}
+
+impl<C: AsStatementRef> BoundStatement<C> {
+    /// # Safety
+    ///
+    /// `cursor` must outlive the instance, and must neither close nor free its statement for as
+    /// long as the instance is used. The instance never frees nor closes it.
+    pub unsafe fn new(cursor: &mut C) -> Self {
+        let handle = cursor.as_stmt_ref().as_sys();
+        Self {
+            // SAFETY: `handle` is a valid statement handle, as it is owned by `cursor`. Wrapping it
+            // in `ManuallyDrop` keeps us from freeing it.
+            statement: ManuallyDrop::new(unsafe { StatementImpl::new(handle) }),
+            cursor: PhantomData,
+        }
+    }
+}
+
+// SAFETY: Statement handles may be used from any thread, as long as they are not used from two
+// threads at once. The handle belongs to the cursor of the same reader, which moves between threads
+// together with this instance, and is only used through `&mut self`. So sending it is sound,
+// whenever sending the cursor is.
+unsafe impl<C: Send> Send for BoundStatement<C> {}
+
+impl<C> AsStatementRef for BoundStatement<C> {
+    fn as_stmt_ref(&mut self) -> StatementRef<'_> {
+        self.statement.as_stmt_ref()
+    }
+}
+
+impl<C> ResultSetMetadata for BoundStatement<C> {}
+
+fn column_metadata(
+    cursor: &mut impl ResultSetMetadata,
+    column_number: u16,
+) -> Result<OdbcColumnMetadata, odbc_api::Error> {
+    let mut description = ColumnDescription::default();
+    cursor.describe_col(column_number, &mut description)?;
+    let stmt = cursor.as_stmt_ref();
+    let sql_type = stmt.col_concise_type(column_number).into_result(&stmt)?;
+    Ok(OdbcColumnMetadata {
+        name: cursor.col_name(column_number)?,
+        data_type: description.data_type,
+        sql_type,
+        nullability: description.nullability,
+        column_size: description.data_type.column_size(),
+        // Drivers do not necessarily support every attribute, which must not make the others
+        // unavailable.
+        precision: cursor.col_precision(column_number).ok(),
+        scale: cursor.col_scale(column_number).ok(),
+        octet_length: cursor.col_octet_length(column_number).ok().flatten(),
+        display_size: cursor.col_display_size(column_number).ok().flatten(),
+        is_unsigned: cursor.column_is_unsigned(column_number).ok(),
+        base_table_name: base_table_name(cursor, column_number),
+    })
+}
+
+/// `None` if the driver fails to report the base table name, or reports an empty one. Not every
+/// driver supports this attribute, and we do not want to fail constructing the reader over it.
+fn base_table_name(cursor: &mut impl AsStatementRef, column_number: u16) -> Option<String> {
+    character_attribute(cursor, column_number, Desc::BaseTableName)
+}
+
+/// Data source dependent name of the type of the column (`SQL_DESC_TYPE_NAME`), e.g. `jsonb` in
+/// PostgreSQL. Unlike the SQL type code, this also tells apart types the driver reports as plain
+/// text. `None` if the driver fails to report it, or reports an empty one.
+pub(crate) fn col_type_name(
+    cursor: &mut impl AsStatementRef,
+    column_number: u16,
+) -> Option<String> {
+    character_attribute(cursor, column_number, Desc::TypeName)
+}
+
+/// Field metadata describing where the values of the column stem from, see
+/// [`crate::OdbcReaderBuilder::with_rich_metadata`]. Attributes the driver fails to report, or
+/// reports as empty, are left out.
+pub(crate) fn provenance_metadata(
+    cursor: &mut impl AsStatementRef,
+    column_number: u16,
+) -> HashMap<String, String> {
+    [
+        ("odbc.label", Desc::Label),
+        ("odbc.base_table_name", Desc::BaseTableName),
+        ("odbc.schema_name", Desc::SchemaName),
+        ("odbc.catalog_name", Desc::CatalogName),
+    ]
+    .into_iter()
+    .filter_map(|(key, field_identifier)| {
+        character_attribute(cursor, column_number, field_identifier)
+            .map(|value| (key.to_owned(), value))
+    })
+    .collect()
+}
+
+fn character_attribute(
+    cursor: &mut impl AsStatementRef,
+    column_number: u16,
+    field_identifier: Desc,
+) -> Option<String> {
+    let stmt = cursor.as_stmt_ref();
+    let mut buffer: Vec<Char> = vec![0; 256];
+    loop {
+        let mut string_length: i16 = 0;
+        // The length is passed as a 16 Bit integer. Values requiring a larger buffer are treated
+        // like any other attribute the driver fails to report.
+        let buffer_length: i16 = (buffer.len() * size_of::<Char>()).try_into().ok()?;
+        // SAFETY: Buffer length is passed in bytes, as expected for character attributes.
+        let ret = unsafe {
+            ffi::SQLColAttribute(
+                stmt.as_sys(),
+                column_number,
+                field_identifier,
+                buffer.as_mut_ptr() as Pointer,
+                buffer_length,
+                &mut string_length,
+                null_mut(),
+            )
+        };
+        ffi::sql_result(ret, "SQLColAttribute")
+            .into_result(&stmt)
+            .ok()?;
+        // Negative for `SQL_NO_TOTAL`, in which case we can not tell how much room it would need.
+        let len = usize::try_from(string_length).ok()? / size_of::<Char>();
+        // Truncated. Try again with enough room for the value and its terminating zero.
+        if len >= buffer.len() {
+            buffer.resize(len + 1, 0);
+            continue;
+        }
+        let value = decode(&buffer[..len]);
+        return (!value.is_empty()).then_some(value);
+    }
+}
+
+/// On windows we call the wide function, like `odbc-api` does. See the manifest.
+#[cfg(target_os = "windows")]
+type Char = u16;
+
+#[cfg(not(target_os = "windows"))]
+type Char = u8;
+
+#[cfg(target_os = "windows")]
+fn decode(text: &[u16]) -> String {
+    String::from_utf16_lossy(text)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn decode(text: &[u8]) -> String {
+    String::from_utf8_lossy(text).into_owned()
+}
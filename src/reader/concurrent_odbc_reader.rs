@@ -76,6 +76,8 @@ pub struct ConcurrentOdbcReader<C: Cursor> {
     /// Fetches values from the ODBC datasource using columnar batches. Values are streamed batch
     /// by batch in order to avoid reallocation of the buffers used for tranistion.
     batch_stream: ConcurrentBlockCursor<C>,
+    /// Rows fetched while inferring the schema lazily, which have not been returned yet.
+    probed_batch: Option<RecordBatch>,
 }
 
 impl<C: Cursor + Send + 'static> ConcurrentOdbcReader<C> {
@@ -86,6 +88,7 @@ impl<C: Cursor + Send + 'static> ConcurrentOdbcReader<C> {
         block_cursor: BlockCursor<C, ColumnarAnyBuffer>,
         converter: ToRecordBatch,
         fallibale_allocations: bool,
+        probed_batch: Option<RecordBatch>,
     ) -> Result<Self, Error> {
         let max_batch_size = block_cursor.row_array_size();
         let batch_stream = ConcurrentBlockCursor::from_block_cursor(block_cursor);
@@ -98,6 +101,7 @@ impl<C: Cursor + Send + 'static> ConcurrentOdbcReader<C> {
             buffer,
             converter,
             batch_stream,
+            probed_batch,
         })
     }
 
@@ -121,13 +125,16 @@ where
     type Item = Result<RecordBatch, ArrowError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(batch) = self.probed_batch.take() {
+            return Some(Ok(batch));
+        }
         match self.batch_stream.fetch_into(&mut self.buffer) {
             // We successfully fetched a batch from the database. Try to copy it into a record batch
             // and forward errors if any.
             Ok(true) => {
                 let result_record_batch = self
                     .converter
-                    .buffer_to_record_batch(&self.buffer)
+                    .buffer_to_record_batch(&self.buffer, |_| ())
                     .map_err(|mapping_error| ArrowError::ExternalError(Box::new(mapping_error)));
                 Some(result_record_batch)
             }
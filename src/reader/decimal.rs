@@ -1,10 +1,10 @@
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, Decimal128Builder};
-use odbc_api::{
-    buffers::{AnySlice, BufferDesc},
-    decimal_text_to_i128,
+use arrow::{
+    array::{ArrayRef, Decimal128Builder, Decimal256Builder},
+    datatypes::i256,
 };
+use odbc_api::buffers::{AnySlice, BufferDesc, Item};
 
 use super::{MappingError, ReadStrategy};
 
@@ -12,15 +12,113 @@ pub struct Decimal {
     precision: u8,
     /// We know scale to be non-negative, yet we can save us some conversions storing it as i8.
     scale: i8,
+    /// Maximum length of the text representation of a value, excluding the terminating zero.
+    max_str_len: usize,
+    /// Accept values in scientific notation, like `1.5E+3`.
+    allow_exponent: bool,
 }
 
 impl Decimal {
     pub fn new(precision: u8, scale: i8) -> Self {
-        Self { precision, scale }
+        Self {
+            precision,
+            scale,
+            // Must be able to hold num precision digits a sign and a decimal point
+            max_str_len: precision as usize + 2,
+            allow_exponent: false,
+        }
+    }
+
+    /// For columns the driver may format in scientific notation, like numbers without precision
+    /// and scale. `max_str_len` must account for the exponent.
+    pub fn with_exponent(precision: u8, scale: i8, max_str_len: usize) -> Self {
+        Self {
+            precision,
+            scale,
+            max_str_len,
+            allow_exponent: true,
+        }
     }
 }
 
 impl ReadStrategy for Decimal {
+    fn strategy_type_name(&self) -> &'static str {
+        "Decimal(text)"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Text {
+            max_str_len: self.max_str_len,
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let view = column_view.as_text_view().unwrap();
+        let mut builder = Decimal128Builder::with_capacity(view.len());
+        let scale = self.scale as usize;
+        let upper_bound = upper_bound(self.precision);
+        // Reused to hold values in scientific notation, rewritten as plain decimal text.
+        let mut expanded = Vec::new();
+
+        for opt in view.iter() {
+            if let Some(text) = opt {
+                let text = if self.allow_exponent && expand_exponent(text, &mut expanded) {
+                    &expanded[..]
+                } else {
+                    text
+                };
+                if !is_decimal_text(text, scale) {
+                    return Err(MappingError::DecimalParseError {
+                        raw_value: text.to_owned(),
+                    });
+                }
+                let out_of_precision = || MappingError::DecimalOutOfPrecision {
+                    value: String::from_utf8_lossy(text).into_owned(),
+                    precision: self.precision,
+                };
+                // Parsing only fails if the value has more digits than fit into 128 Bit, in which
+                // case it also exceeds the precision.
+                let num = parse_decimal_text(text, scale).ok_or_else(out_of_precision)?;
+                // Some drivers report a precision smaller than the one of the values they actually
+                // return. Emit an error rather than silently creating an array holding values which
+                // are invalid for its data type.
+                if num.unsigned_abs() >= upper_bound {
+                    return Err(out_of_precision());
+                }
+                builder.append_value(num);
+            } else {
+                builder.append_null();
+            }
+        }
+
+        Ok(Arc::new(
+            builder
+                .finish()
+                .with_precision_and_scale(self.precision, self.scale)
+                .unwrap(),
+        ))
+    }
+}
+
+/// Fills `Decimal256` arrays. Like [`Decimal`] the values are fetched as text, yet they are parsed
+/// into 256 Bit integers, allowing for up to 76 digits.
+pub struct Decimal256 {
+    precision: u8,
+    /// We know scale to be non-negative, yet we can save us some conversions storing it as i8.
+    scale: i8,
+}
+
+impl Decimal256 {
+    pub fn new(precision: u8, scale: i8) -> Self {
+        Self { precision, scale }
+    }
+}
+
+impl ReadStrategy for Decimal256 {
+    fn strategy_type_name(&self) -> &'static str {
+        "Decimal256(text)"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         BufferDesc::Text {
             // Must be able to hold num precision digits a sign and a decimal point
@@ -30,12 +128,48 @@ impl ReadStrategy for Decimal {
 
     fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
         let view = column_view.as_text_view().unwrap();
-        let mut builder = Decimal128Builder::new();
+        let mut builder = Decimal256Builder::with_capacity(view.len());
         let scale = self.scale as usize;
+        // Precision of Decimal256 is at most 76, so this can not overflow.
+        let upper_bound = i256::from_i128(10).wrapping_pow(self.precision.into());
+        // Reused to hold the digits of each value, without the radix character. Large enough for
+        // any value within precision and its sign, so it does not need to grow while filling.
+        let mut digits = String::with_capacity(self.precision as usize + 1);
 
         for opt in view.iter() {
             if let Some(text) = opt {
-                let num = decimal_text_to_i128(text, scale);
+                if !is_decimal_text(text, scale) {
+                    return Err(MappingError::DecimalParseError {
+                        raw_value: text.to_owned(),
+                    });
+                }
+                digits.clear();
+                let mut fraction_len = None;
+                for &c in text {
+                    match c {
+                        b'.' | b',' => fraction_len = Some(0),
+                        _ => {
+                            digits.push(c as char);
+                            if c.is_ascii_digit() {
+                                fraction_len = fraction_len.map(|len| len + 1);
+                            }
+                        }
+                    }
+                }
+                // Scale the value, so the last digit matches the scale of the column.
+                for _ in fraction_len.unwrap_or(0)..scale {
+                    digits.push('0');
+                }
+                let out_of_precision = || MappingError::DecimalOutOfPrecision {
+                    value: String::from_utf8_lossy(text).into_owned(),
+                    precision: self.precision,
+                };
+                // Parsing only fails if the number does not fit into 256 Bit, in which case it
+                // also exceeds the precision.
+                let num = i256::from_string(&digits).ok_or_else(out_of_precision)?;
+                if num.wrapping_abs() >= upper_bound {
+                    return Err(out_of_precision());
+                }
                 builder.append_value(num);
             } else {
                 builder.append_null();
@@ -50,3 +184,426 @@ impl ReadStrategy for Decimal {
         ))
     }
 }
+
+/// Fills a decimal array from a column the database reports as floating point (e.g. `DOUBLE`).
+/// Rather than asking the driver to format the floats as text, we bind a 64 Bit floating point
+/// buffer and scale and round the values ourselves. Not every driver formats floats in a way we
+/// could parse as a decimal, so this is both faster and more reliable.
+pub struct DecimalFromFloat {
+    nullable: bool,
+    precision: u8,
+    /// We know scale to be non-negative, yet we can save us some conversions storing it as i8.
+    scale: i8,
+}
+
+impl DecimalFromFloat {
+    pub fn new(nullable: bool, precision: u8, scale: i8) -> Self {
+        Self {
+            nullable,
+            precision,
+            scale,
+        }
+    }
+
+    fn to_i128(&self, value: f64) -> Result<i128, MappingError> {
+        // Casting NaN to an integer would silently yield zero, and infinity the largest integer.
+        if !value.is_finite() {
+            return Err(MappingError::DecimalParseError {
+                raw_value: value.to_string().into_bytes(),
+            });
+        }
+        let num = (value * 10f64.powi(self.scale.into())).round() as i128;
+        if num.unsigned_abs() >= upper_bound(self.precision) {
+            return Err(MappingError::DecimalOutOfPrecision {
+                value: value.to_string(),
+                precision: self.precision,
+            });
+        }
+        Ok(num)
+    }
+}
+
+impl ReadStrategy for DecimalFromFloat {
+    fn strategy_type_name(&self) -> &'static str {
+        "Decimal(float)"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::F64 {
+            nullable: self.nullable,
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let mut builder;
+        if self.nullable {
+            let values = f64::as_nullable_slice(column_view).unwrap();
+            builder = Decimal128Builder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.map(|&f| self.to_i128(f)).transpose()?);
+            }
+        } else {
+            let values = f64::as_slice(column_view).unwrap();
+            builder = Decimal128Builder::with_capacity(values.len());
+            for &value in values {
+                builder.append_value(self.to_i128(value)?);
+            }
+        }
+
+        Ok(Arc::new(
+            builder
+                .finish()
+                .with_precision_and_scale(self.precision, self.scale)
+                .unwrap(),
+        ))
+    }
+}
+
+/// `true` if `text` is a decimal number with at most `scale` digits after the radix character, which
+/// can be parsed by [`parse_decimal_text`]. An optional sign is allowed. Some databases use `,`
+/// rather than `.` as radix character, depending on their locale, so we accept both.
+fn is_decimal_text(text: &[u8], scale: usize) -> bool {
+    let unsigned = match text.first() {
+        Some(b'-' | b'+') => &text[1..],
+        _ => text,
+    };
+    let mut parts = unsigned.splitn(2, |&c| c == b'.' || c == b',');
+    let integer = parts.next().unwrap_or_default();
+    let fraction = parts.next().unwrap_or_default();
+    let all_digits = |part: &[u8]| part.iter().all(u8::is_ascii_digit);
+    !(integer.is_empty() && fraction.is_empty())
+        && all_digits(integer)
+        && all_digits(fraction)
+        && fraction.len() <= scale
+}
+
+/// Largest absolute exponent we expand. Any value with a larger one either exceeds the precision
+/// or the scale of a decimal, so it is rejected anyway, without allocating a huge string first.
+const MAX_EXPONENT: usize = 128;
+
+/// Rewrites `text` in scientific notation (e.g. `1.5E+3`) into plain decimal text (`1500`) held by
+/// `expanded`. Trailing zeroes of the fraction are removed, since they do not count against the
+/// scale. `false` if `text` has no exponent, or either the exponent or the digits before it are
+/// malformed. In that case `text` is left to [`is_decimal_text`] to be rejected.
+fn expand_exponent(text: &[u8], expanded: &mut Vec<u8>) -> bool {
+    let Some(pos) = text.iter().position(|&c| c == b'e' || c == b'E') else {
+        return false;
+    };
+    let (mantissa, exponent) = (&text[..pos], &text[pos + 1..]);
+    let Some(exponent) = std::str::from_utf8(exponent)
+        .ok()
+        .and_then(|exponent| exponent.parse::<i32>().ok())
+        .filter(|exponent| exponent.unsigned_abs() as usize <= MAX_EXPONENT)
+    else {
+        return false;
+    };
+    if !is_decimal_text(mantissa, usize::MAX) {
+        return false;
+    }
+    let (sign, unsigned) = match mantissa.first() {
+        Some(&c @ (b'-' | b'+')) => (Some(c), &mantissa[1..]),
+        _ => (None, mantissa),
+    };
+    let integer_len = unsigned
+        .iter()
+        .position(|&c| c == b'.' || c == b',')
+        .unwrap_or(unsigned.len());
+    let digits = unsigned.iter().filter(|c| c.is_ascii_digit());
+    let num_digits = digits.clone().count();
+    // Position of the radix character within the digits, after shifting it by the exponent.
+    let point = integer_len as i64 + i64::from(exponent);
+
+    expanded.clear();
+    expanded.extend(sign);
+    if point <= 0 {
+        expanded.push(b'0');
+        expanded.push(b'.');
+        expanded.extend(std::iter::repeat(b'0').take(point.unsigned_abs() as usize));
+        expanded.extend(digits);
+    } else {
+        let point = point as usize;
+        expanded.extend(digits.clone().take(point));
+        expanded.extend(std::iter::repeat(b'0').take(point.saturating_sub(num_digits)));
+        if point < num_digits {
+            expanded.push(b'.');
+            expanded.extend(digits.skip(point));
+        }
+    }
+    if expanded.contains(&b'.') {
+        while expanded.last() == Some(&b'0') {
+            expanded.pop();
+        }
+        if expanded.last() == Some(&b'.') {
+            expanded.pop();
+        }
+    }
+    true
+}
+
+/// Smallest absolute value which no longer fits into `precision` digits. Precision of Decimal128 is
+/// at most 38, so this can not overflow.
+fn upper_bound(precision: u8) -> u128 {
+    10u128.pow(precision.into())
+}
+
+/// Parses `text`, which must satisfy [`is_decimal_text`], into an integer holding the value
+/// multiplied by `10^scale`. Missing trailing zeroes in the fraction are accounted for. `None` if
+/// that integer has more than [`MAX_DIGITS`] digits, since it might not fit into an `i128`.
+///
+/// With the `simd` feature, digits are converted eight at a time (see [`parse_eight_digits`]).
+/// Otherwise, or if leading zeroes make for more than [`MAX_DIGITS`] digits, this is
+/// [`odbc_api::decimal_text_to_i128`]. It would overflow for too many digits, so these are
+/// rejected upfront.
+#[doc(hidden)]
+pub fn parse_decimal_text(text: &[u8], scale: usize) -> Option<i128> {
+    if num_scaled_digits(text, scale) > MAX_DIGITS {
+        return None;
+    }
+    #[cfg(feature = "simd")]
+    if let Some(value) = parse_decimal_text_swar(text, scale) {
+        return Some(value);
+    }
+    Some(odbc_api::decimal_text_to_i128(text, scale))
+}
+
+/// Any integer with up to 38 decimal digits fits into an `i128`.
+const MAX_DIGITS: usize = 38;
+
+/// Number of digits of the integer [`parse_decimal_text`] yields for `text`, not counting leading
+/// zeroes. Trailing zeroes missing from the fraction in order to match `scale` are counted.
+fn num_scaled_digits(text: &[u8], scale: usize) -> usize {
+    let mut significant = 0;
+    let mut fraction_len = None;
+    for &c in text {
+        match c {
+            b'.' | b',' => fraction_len = Some(0),
+            b'0'..=b'9' => {
+                if significant != 0 || c != b'0' {
+                    significant += 1;
+                }
+                fraction_len = fraction_len.map(|len| len + 1);
+            }
+            _ => (),
+        }
+    }
+    if significant == 0 {
+        // Zero stays zero, no matter the scale.
+        return 0;
+    }
+    significant + scale.saturating_sub(fraction_len.unwrap_or(0))
+}
+
+/// `None` if the value has more than [`MAX_DIGITS`] digits, or if `text` is not accepted by
+/// [`is_decimal_text`]. Validating here keeps malformed input from underflowing the digit and
+/// length arithmetic below, rather than relying on the caller.
+///
+/// Digits of a single value are converted eight at a time. Rows are still parsed one after the
+/// other: their text differs in length and position of the radix character, so spreading several
+/// rows across the lanes of a register would require gathering and aligning them first.
+#[cfg(feature = "simd")]
+fn parse_decimal_text_swar(text: &[u8], scale: usize) -> Option<i128> {
+    let (negative, unsigned) = match text.first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    // Digits without the radix character, already padded with the missing trailing zeroes.
+    let mut digits = [b'0'; MAX_DIGITS];
+    let mut len = 0;
+    let mut fraction_len = None;
+    for &c in unsigned {
+        if c == b'.' || c == b',' {
+            // A second radix character is malformed.
+            if fraction_len.replace(0).is_some() {
+                return None;
+            }
+            continue;
+        }
+        if !c.is_ascii_digit() {
+            return None;
+        }
+        *digits.get_mut(len)? = c;
+        len += 1;
+        if let Some(fraction_len) = &mut fraction_len {
+            *fraction_len += 1;
+        }
+    }
+    if len == 0 {
+        return None;
+    }
+    // More digits in the fraction than the scale allows for is malformed, too.
+    len += scale.checked_sub(fraction_len.unwrap_or(0))?;
+    if len > MAX_DIGITS {
+        return None;
+    }
+    // Leading digits which do not fill an entire chunk are converted one by one.
+    let (head, chunks) = digits[..len].split_at(len % 8);
+    let mut value = head
+        .iter()
+        .fold(0u128, |acc, &digit| acc * 10 + u128::from(digit - b'0'));
+    for chunk in chunks.chunks_exact(8) {
+        value = value * 100_000_000 + u128::from(parse_eight_digits(chunk.try_into().unwrap()));
+    }
+    // At most 38 digits, so this can not overflow.
+    let value = value as i128;
+    Some(if negative { -value } else { value })
+}
+
+/// Converts eight ASCII digits into their value, using SIMD within a register (SWAR). Loaded as a
+/// little endian integer the first digit is the least significant byte. Each step combines
+/// neighbouring lanes, which doubles the lane width: `d0 d1` become `10 * d0 + d1`, then pairs of
+/// these become `100 * x0 + x1`, and finally `10000 * y0 + y1`.
+#[cfg(feature = "simd")]
+fn parse_eight_digits(chunk: [u8; 8]) -> u32 {
+    let mut v = u64::from_le_bytes(chunk).wrapping_sub(0x3030_3030_3030_3030);
+    v = (v.wrapping_mul(10).wrapping_add(v >> 8)) & 0x00ff_00ff_00ff_00ff;
+    v = (v.wrapping_mul(100).wrapping_add(v >> 16)) & 0x0000_ffff_0000_ffff;
+    v = v.wrapping_mul(10_000).wrapping_add(v >> 32) & 0x0000_0000_ffff_ffff;
+    v as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{Array, Decimal256Array},
+        datatypes::DataType as ArrowDataType,
+    };
+    use odbc_api::{
+        buffers::{AnyBuffer, ColumnBuffer},
+        DataType as OdbcDataType,
+    };
+
+    use crate::{
+        reader::{MappingError, ReadStrategy},
+        schema::arrow_data_type_from,
+    };
+
+    #[cfg(feature = "simd")]
+    use super::parse_decimal_text_swar;
+    use super::{expand_exponent, parse_decimal_text, Decimal, Decimal256};
+
+    fn expand(text: &str) -> Option<String> {
+        let mut expanded = Vec::new();
+        expand_exponent(text.as_bytes(), &mut expanded)
+            .then(|| String::from_utf8(expanded).unwrap())
+    }
+
+    #[test]
+    fn expand_scientific_notation_into_decimal_text() {
+        assert_eq!(
+            Some("10000000000000000000000000000000000000000"),
+            expand("1E+40").as_deref()
+        );
+        assert_eq!(Some("1500"), expand("1.5E3").as_deref());
+        assert_eq!(Some("-0.0015"), expand("-1.5e-3").as_deref());
+        assert_eq!(Some("12.5"), expand("1,250E1").as_deref());
+        assert_eq!(Some("0.5"), expand(".5E0").as_deref());
+    }
+
+    #[test]
+    fn leave_text_without_valid_exponent_alone() {
+        assert_eq!(None, expand("1500"));
+        assert_eq!(None, expand("1.5E"));
+        assert_eq!(None, expand("1.5E+x"));
+        assert_eq!(None, expand("1a5E3"));
+        assert_eq!(None, expand("1E1000"));
+    }
+
+    /// `NUMERIC(50, 10)` has too many digits for a 128 Bit integer, so it is inferred as
+    /// `Decimal256` and fetched as text.
+    #[test]
+    fn infer_and_fetch_numeric_50_10_as_decimal256() {
+        let sql_type = OdbcDataType::Numeric {
+            precision: 50,
+            scale: 10,
+        };
+        assert_eq!(
+            Some(ArrowDataType::Decimal256(50, 10)),
+            arrow_data_type_from(sql_type, false)
+        );
+
+        // Given a text buffer holding the largest value of the column, a short one and a NULL
+        let strategy = Decimal256::new(50, 10);
+        let mut buffer = AnyBuffer::from_desc(3, strategy.buffer_desc());
+        let AnyBuffer::Text(column) = &mut buffer else {
+            panic!("Expected text buffer")
+        };
+        let max = format!("{}.{}", "9".repeat(40), "9".repeat(10));
+        column.set_value(0, Some(max.as_bytes()));
+        column.set_value(1, Some(b"-1.5"));
+        column.set_value(2, None);
+
+        // When
+        let array = strategy.fill_arrow_array(buffer.view(3)).unwrap();
+
+        // Then
+        let array = array.as_any().downcast_ref::<Decimal256Array>().unwrap();
+        assert_eq!(max, array.value_as_string(0));
+        assert_eq!("-1.5000000000", array.value_as_string(1));
+        assert!(array.is_null(2));
+    }
+
+    #[test]
+    fn parse_decimal_text_with_more_than_38_digits_fails() {
+        let forty_digits = "1234567890".repeat(4);
+
+        assert_eq!(None, parse_decimal_text(forty_digits.as_bytes(), 0));
+        // Digits missing from the fraction count, too
+        assert_eq!(None, parse_decimal_text(b"1.5", 38));
+        // Leading zeroes do not
+        let leading_zeroes = format!("{}1", "0".repeat(40));
+        assert_eq!(Some(1), parse_decimal_text(leading_zeroes.as_bytes(), 0));
+        assert_eq!(Some(0), parse_decimal_text(b"0.0", 40));
+    }
+
+    /// Values in scientific notation may expand into more digits than fit into 128 Bit. These must
+    /// be reported as out of precision, rather than overflow while parsing.
+    #[test]
+    fn report_values_exceeding_128_bit_as_out_of_precision() {
+        // Given a text buffer holding `1E+40` and a value with a mantissa of 40 digits
+        let strategy = Decimal::with_exponent(38, 0, 50);
+        let mut buffer = AnyBuffer::from_desc(1, strategy.buffer_desc());
+        let forty_digits = format!("{}E0", "1234567890".repeat(4));
+        for text in ["1E+40", forty_digits.as_str()] {
+            let AnyBuffer::Text(column) = &mut buffer else {
+                panic!("Expected text buffer")
+            };
+            column.set_value(0, Some(text.as_bytes()));
+
+            // When
+            let result = strategy.fill_arrow_array(buffer.view(1));
+
+            // Then
+            assert!(matches!(
+                result,
+                Err(MappingError::DecimalOutOfPrecision { precision: 38, .. })
+            ));
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn parse_decimal_text_eight_digits_at_a_time() {
+        assert_eq!(
+            Some(1_234_567_890_123),
+            parse_decimal_text_swar(b"12345678901.23", 2)
+        );
+        assert_eq!(Some(-150), parse_decimal_text_swar(b"-1.5", 2));
+        assert_eq!(Some(5), parse_decimal_text_swar(b",05", 2));
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn reject_malformed_decimal_text() {
+        // More digits in the fraction than the scale allows for
+        assert_eq!(None, parse_decimal_text_swar(b"1.234", 2));
+        // Characters other than digits
+        assert_eq!(None, parse_decimal_text_swar(b"12a4", 0));
+        assert_eq!(None, parse_decimal_text_swar(b"1e5", 0));
+        // Several radix characters
+        assert_eq!(None, parse_decimal_text_swar(b"1.2.3", 3));
+        // No digits at all
+        assert_eq!(None, parse_decimal_text_swar(b"-", 0));
+        assert_eq!(None, parse_decimal_text_swar(b"", 2));
+    }
+}
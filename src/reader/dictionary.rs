@@ -0,0 +1,150 @@
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+
+use arrow::{
+    array::{ArrayRef, DictionaryArray, Int32Array, PrimitiveArray, PrimitiveDictionaryBuilder},
+    datatypes::{
+        ArrowDictionaryKeyType, ArrowNativeType, DataType as ArrowDataType, Int32Type, Int8Type,
+        UInt8Type,
+    },
+};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+
+use super::{ColumnFailure, MappingError, ReadStrategy};
+
+/// Chooses the strategy for `Dictionary(Int8, Int32)` and `Dictionary(UInt8, Int32)` fields. Used
+/// for small integer codes (e.g. status or category ids), which are logically enumerations. If
+/// `values` is `None` every distinct value becomes an entry of the dictionary, in the order of
+/// appearance within the batch. Otherwise the keys index into `values`.
+pub fn integer_dictionary_strategy(
+    key_type: &ArrowDataType,
+    value_type: &ArrowDataType,
+    nullable: bool,
+    values: Option<&[i32]>,
+) -> Result<Box<dyn ReadStrategy + Send>, ColumnFailure> {
+    let strategy: Box<dyn ReadStrategy + Send> = match (key_type, value_type) {
+        (ArrowDataType::Int8, ArrowDataType::Int32) => {
+            Box::new(IntegerDictionary::<Int8Type>::new(nullable, values)?)
+        }
+        (ArrowDataType::UInt8, ArrowDataType::Int32) => {
+            Box::new(IntegerDictionary::<UInt8Type>::new(nullable, values)?)
+        }
+        _ => {
+            return Err(ColumnFailure::UnsupportedArrowType(
+                ArrowDataType::Dictionary(Box::new(key_type.clone()), Box::new(value_type.clone())),
+            ))
+        }
+    };
+    Ok(strategy)
+}
+
+/// Fetches 32 Bit integers and encodes them as dictionary with keys of type `K`.
+struct IntegerDictionary<K> {
+    nullable: bool,
+    /// `None` if the dictionary is built from the values of each batch.
+    dictionary: Option<Dictionary>,
+    _key_type: PhantomData<K>,
+}
+
+/// Dictionary provided by the user.
+struct Dictionary {
+    values: ArrayRef,
+    keys_by_value: HashMap<i32, usize>,
+}
+
+impl<K> IntegerDictionary<K>
+where
+    K: ArrowDictionaryKeyType,
+{
+    fn new(nullable: bool, values: Option<&[i32]>) -> Result<Self, ColumnFailure> {
+        let dictionary = values
+            .map(|values| {
+                // The largest key must be representable by the key type.
+                if !values.is_empty() && K::Native::from_usize(values.len() - 1).is_none() {
+                    return Err(ColumnFailure::DictionaryTooLarge {
+                        len: values.len(),
+                        key_type: K::DATA_TYPE,
+                    });
+                }
+                let mut keys_by_value = HashMap::with_capacity(values.len());
+                for (key, &value) in values.iter().enumerate() {
+                    keys_by_value.entry(value).or_insert(key);
+                }
+                let values: ArrayRef = Arc::new(Int32Array::from(values.to_vec()));
+                Ok(Dictionary {
+                    values,
+                    keys_by_value,
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            nullable,
+            dictionary,
+            _key_type: PhantomData,
+        })
+    }
+
+    fn encode(
+        &self,
+        len: usize,
+        values: impl Iterator<Item = Option<i32>>,
+    ) -> Result<ArrayRef, MappingError> {
+        let array: ArrayRef = if let Some(dictionary) = &self.dictionary {
+            let keys = values
+                .map(|value| {
+                    value
+                        .map(|value| {
+                            let key = dictionary
+                                .keys_by_value
+                                .get(&value)
+                                .ok_or(MappingError::ValueNotInDictionary { value })?;
+                            // Validated in the constructor
+                            Ok(K::Native::from_usize(*key).unwrap())
+                        })
+                        .transpose()
+                })
+                .collect::<Result<PrimitiveArray<K>, _>>()?;
+            Arc::new(DictionaryArray::<K>::try_new(keys, dictionary.values.clone()).unwrap())
+        } else {
+            let mut builder = PrimitiveDictionaryBuilder::<K, Int32Type>::with_capacity(len, len);
+            for value in values {
+                match value {
+                    Some(value) => {
+                        builder
+                            .append(value)
+                            .map_err(|_| MappingError::DictionaryKeyOverflow {
+                                key_type: K::DATA_TYPE,
+                            })?;
+                    }
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        };
+        Ok(array)
+    }
+}
+
+impl<K> ReadStrategy for IntegerDictionary<K>
+where
+    K: ArrowDictionaryKeyType + Send,
+{
+    fn strategy_type_name(&self) -> &'static str {
+        "IntegerDictionary"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::I32 {
+            nullable: self.nullable,
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        if self.nullable {
+            let values = column_view.as_nullable_slice::<i32>().unwrap();
+            self.encode(values.len(), values.map(|value| value.copied()))
+        } else {
+            let values = column_view.as_slice::<i32>().unwrap();
+            self.encode(values.len(), values.iter().copied().map(Some))
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! ODBC functions `odbc-api` does not offer safe abstractions for. The bindings are taken from
+//! `odbc-sys`, as reexported by `odbc-api`. On windows we call the wide functions, like `odbc-api`
+//! does. See the manifest.
+
+use odbc_api::{
+    handles::SqlResult,
+    sys::{HStmt, Pointer, SqlReturn},
+};
+
+#[cfg(not(target_os = "windows"))]
+pub use odbc_api::sys::{SQLColAttribute, SQLGetDiagField, SQLSetStmtAttr};
+
+#[cfg(target_os = "windows")]
+pub use odbc_api::sys::{
+    SQLColAttributeW as SQLColAttribute, SQLGetDiagFieldW as SQLGetDiagField,
+    SQLSetStmtAttrW as SQLSetStmtAttr,
+};
+
+extern "system" {
+    /// Same symbol as [`SQLSetStmtAttr`], yet taking the attribute as plain integer. `odbc-sys`
+    /// models attributes as an enum of the ones known to the ODBC standard, and conjuring up a
+    /// variant for a driver specific one (e.g. `SQL_SOPT_SS_*` in SQL Server) would be undefined
+    /// behaviour.
+    #[cfg_attr(target_os = "windows", link_name = "SQLSetStmtAttrW")]
+    #[cfg_attr(not(target_os = "windows"), link_name = "SQLSetStmtAttr")]
+    pub fn SQLSetStmtAttrUnchecked(
+        hstmt: HStmt,
+        attribute: i32,
+        value: Pointer,
+        string_length: i32,
+    ) -> SqlReturn;
+}
+
+/// Interprets the return code of `function`, so diagnostics can be extracted with
+/// [`SqlResult::into_result`].
+pub fn sql_result(ret: SqlReturn, function: &'static str) -> SqlResult<()> {
+    match ret {
+        SqlReturn::SUCCESS => SqlResult::Success(()),
+        SqlReturn::SUCCESS_WITH_INFO => SqlResult::SuccessWithInfo(()),
+        _ => SqlResult::Error { function },
+    }
+}
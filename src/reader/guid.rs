@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, FixedSizeBinaryBuilder, StringBuilder};
+use odbc_api::{
+    buffers::{AnySlice, BufferDesc},
+    sys::SqlDataType,
+    DataType as OdbcDataType,
+};
+
+use super::{MappingError, ReadStrategy};
+
+/// Length of a GUID in bytes.
+const GUID_LEN: usize = 16;
+
+/// Length of a GUID formatted as hyphenated hex string, e.g.
+/// `550e8400-e29b-41d4-a716-446655440000`.
+const HYPHENATED_LEN: usize = 36;
+
+/// `true` if `sql_type` is `SQL_GUID` (`-11`), e.g. `UNIQUEIDENTIFIER` in Microsoft SQL Server.
+pub fn is_guid(sql_type: &OdbcDataType) -> bool {
+    matches!(
+        sql_type,
+        OdbcDataType::Other {
+            data_type: SqlDataType(-11),
+            ..
+        }
+    )
+}
+
+/// Fetches `SQL_GUID` columns as 16 bytes of binary and converts them from the mixed-endian layout
+/// of a Windows `GUID` into the standard (RFC 4122) byte order of a UUID.
+///
+/// Drivers copy the in-memory representation of the `GUID` struct into binary buffers. Its first
+/// three components (`Data1`: 4 bytes, `Data2` and `Data3`: 2 bytes each) are integers, while the
+/// last 8 bytes (`Data4`) are an array and therefore in the order they are displayed. We decode the
+/// integers as little endian explicitly, rather than relying on the byte order of the host, since
+/// this is the layout produced by the drivers on every platform they are shipped for (x86 and ARM
+/// alike). The GUID `550E8400-E29B-41D4-A716-446655440000` arrives as
+/// `00 84 0E 55 9B E2 D4 41 A7 16 44 66 55 44 00 00`. Reversing each of the integer components
+/// yields `55 0E 84 00 E2 9B 41 D4 A7 16 44 66 55 44 00 00`, i.e. the bytes in the order they are
+/// displayed.
+pub struct GuidStrategy {
+    /// `true` in order to produce hyphenated strings instead of `FixedSizeBinary(16)`.
+    as_string: bool,
+}
+
+impl GuidStrategy {
+    pub fn new(as_string: bool) -> Self {
+        Self { as_string }
+    }
+}
+
+impl ReadStrategy for GuidStrategy {
+    fn strategy_type_name(&self) -> &'static str {
+        "Guid"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Binary { length: GUID_LEN }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let view = column_view.as_bin_view().unwrap();
+        let guids = view.iter().map(|value| value.map(uuid_bytes).transpose());
+        let array: ArrayRef = if self.as_string {
+            let mut builder = StringBuilder::with_capacity(view.len(), view.len() * HYPHENATED_LEN);
+            for guid in guids {
+                builder.append_option(guid?.map(|bytes| hyphenated(&bytes)));
+            }
+            Arc::new(builder.finish())
+        } else {
+            let mut builder = FixedSizeBinaryBuilder::with_capacity(view.len(), GUID_LEN as i32);
+            for guid in guids {
+                match guid? {
+                    Some(bytes) => builder.append_value(bytes).unwrap(),
+                    None => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        };
+        Ok(array)
+    }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        Some(GUID_LEN)
+    }
+}
+
+/// Reorders the bytes of a Windows `GUID` into the byte order of a UUID.
+fn uuid_bytes(guid: &[u8]) -> Result<[u8; GUID_LEN], MappingError> {
+    let guid =
+        <[u8; GUID_LEN]>::try_from(guid).map_err(|_| MappingError::UnexpectedBinaryLength {
+            expected: GUID_LEN,
+            actual: guid.len(),
+        })?;
+    let data1 = u32::from_le_bytes([guid[0], guid[1], guid[2], guid[3]]);
+    let data2 = u16::from_le_bytes([guid[4], guid[5]]);
+    let data3 = u16::from_le_bytes([guid[6], guid[7]]);
+    let mut uuid = [0; GUID_LEN];
+    uuid[0..4].copy_from_slice(&data1.to_be_bytes());
+    uuid[4..6].copy_from_slice(&data2.to_be_bytes());
+    uuid[6..8].copy_from_slice(&data3.to_be_bytes());
+    uuid[8..].copy_from_slice(&guid[8..]);
+    Ok(uuid)
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Lower case hex digits, grouped `8-4-4-4-12`.
+fn hyphenated(uuid: &[u8; GUID_LEN]) -> String {
+    let mut text = String::with_capacity(HYPHENATED_LEN);
+    for (index, &byte) in uuid.iter().enumerate() {
+        if matches!(index, 4 | 6 | 8 | 10) {
+            text.push('-');
+        }
+        text.push(HEX_DIGITS[usize::from(byte >> 4)].into());
+        text.push(HEX_DIGITS[usize::from(byte & 0x0f)].into());
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hyphenated, uuid_bytes};
+
+    /// `550E8400-E29B-41D4-A716-446655440000` as copied from a Windows `GUID` struct.
+    const GUID: [u8; 16] = [
+        0x00, 0x84, 0x0E, 0x55, 0x9B, 0xE2, 0xD4, 0x41, 0xA7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00,
+        0x00,
+    ];
+
+    #[test]
+    fn reorder_guid_into_uuid_byte_order() {
+        let uuid = uuid_bytes(&GUID).unwrap();
+
+        assert_eq!(
+            [
+                0x55, 0x0E, 0x84, 0x00, 0xE2, 0x9B, 0x41, 0xD4, 0xA7, 0x16, 0x44, 0x66, 0x55, 0x44,
+                0x00, 0x00
+            ],
+            uuid
+        );
+    }
+
+    #[test]
+    fn format_uuid_hyphenated() {
+        let uuid = uuid_bytes(&GUID).unwrap();
+
+        assert_eq!("550e8400-e29b-41d4-a716-446655440000", hyphenated(&uuid));
+    }
+
+    #[test]
+    fn reject_guid_of_unexpected_length() {
+        assert!(uuid_bytes(&GUID[..15]).is_err());
+    }
+}
@@ -0,0 +1,386 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        ArrayRef, DurationMicrosecondArray, DurationMillisecondArray, DurationNanosecondArray,
+        DurationSecondArray, IntervalDayTimeArray, IntervalMonthDayNanoArray,
+        IntervalYearMonthArray,
+    },
+    datatypes::{
+        DataType as ArrowDataType, IntervalDayTimeType, IntervalMonthDayNanoType, IntervalUnit,
+        TimeUnit,
+    },
+};
+use odbc_api::{
+    buffers::{AnySlice, BufferDesc},
+    sys::SqlDataType,
+    DataType as OdbcDataType,
+};
+
+use super::{ColumnFailure, MappingError, ReadStrategy};
+
+/// Fields of an SQL interval, in the order from the most to the least significant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum IntervalField {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl IntervalField {
+    /// Leading and trailing field of the `SQL_INTERVAL_*` types. `None` for any other type.
+    fn range_of(sql_type: &OdbcDataType) -> Option<(Self, Self)> {
+        use IntervalField::*;
+        let OdbcDataType::Other {
+            data_type: SqlDataType(code),
+            ..
+        } = sql_type
+        else {
+            return None;
+        };
+        let range = match code {
+            101 => (Year, Year),
+            102 => (Month, Month),
+            103 => (Day, Day),
+            104 => (Hour, Hour),
+            105 => (Minute, Minute),
+            106 => (Second, Second),
+            107 => (Year, Month),
+            108 => (Day, Hour),
+            109 => (Day, Minute),
+            110 => (Day, Second),
+            111 => (Hour, Minute),
+            112 => (Hour, Second),
+            113 => (Minute, Second),
+            _ => return None,
+        };
+        Some(range)
+    }
+
+    /// Largest value of the field, if it is not the leading one.
+    fn max(self) -> u64 {
+        match self {
+            IntervalField::Year | IntervalField::Day => u64::MAX,
+            IntervalField::Month => 11,
+            IntervalField::Hour => 23,
+            IntervalField::Minute | IntervalField::Second => 59,
+        }
+    }
+}
+
+/// Fetches SQL intervals into `Interval` or `Duration` arrays. `odbc-api` offers no buffers for
+/// the `SQL_INTERVAL_STRUCT` of ODBC, so the values are fetched as text instead (e.g. `-1-02` for
+/// `INTERVAL YEAR TO MONTH` or `3 04:05:06.789` for `INTERVAL DAY TO SECOND`) and parsed, like
+/// [`super::TextTimeConversion`] does for times.
+pub struct TextIntervalConversion {
+    /// Either `Interval` or `Duration`
+    data_type: ArrowDataType,
+    leading: IntervalField,
+    trailing: IntervalField,
+}
+
+impl TextIntervalConversion {
+    /// Fails if `sql_type` is not an interval type, or its fields can not be represented by
+    /// `data_type`. `Interval(YearMonth)` requires year-month intervals, `Interval(DayTime)` and
+    /// `Duration` day-time intervals, while `Interval(MonthDayNano)` can hold either.
+    pub fn new(sql_type: OdbcDataType, data_type: ArrowDataType) -> Result<Self, ColumnFailure> {
+        let Some((leading, trailing)) =
+            IntervalField::range_of(&sql_type).filter(|&(leading, _)| match data_type {
+                ArrowDataType::Interval(IntervalUnit::YearMonth) => leading <= IntervalField::Month,
+                ArrowDataType::Interval(IntervalUnit::DayTime) | ArrowDataType::Duration(_) => {
+                    leading >= IntervalField::Day
+                }
+                ArrowDataType::Interval(IntervalUnit::MonthDayNano) => true,
+                _ => false,
+            })
+        else {
+            return Err(ColumnFailure::IncompatibleInterval {
+                sql_type,
+                data_type,
+            });
+        };
+        Ok(Self {
+            data_type,
+            leading,
+            trailing,
+        })
+    }
+}
+
+impl ReadStrategy for TextIntervalConversion {
+    fn strategy_type_name(&self) -> &'static str {
+        "Interval(text)"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Text {
+            // Large enough for `-DDDDDDDDD HH:MM:SS.FFFFFFFFF`, with the maximum leading precision
+            // of nine digits.
+            max_str_len: 32,
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let view = column_view.as_text_view().unwrap();
+        let values = view.iter().map(|opt| {
+            opt.map(|text| {
+                parse_interval(text, self.leading, self.trailing).ok_or_else(|| {
+                    MappingError::IntervalParseError {
+                        raw_value: text.to_owned(),
+                    }
+                })
+            })
+            .transpose()
+        });
+        let array: ArrayRef = match self.data_type {
+            ArrowDataType::Interval(IntervalUnit::YearMonth) => Arc::new(
+                values
+                    .map(|value| value.map(|opt| opt.map(|interval| interval.months)))
+                    .collect::<Result<IntervalYearMonthArray, _>>()?,
+            ),
+            ArrowDataType::Interval(IntervalUnit::DayTime) => Arc::new(
+                values
+                    .map(|value| {
+                        value.map(|opt| {
+                            opt.map(|interval| {
+                                IntervalDayTimeType::make_value(
+                                    interval.days,
+                                    // Less than a day, so this always fits into 32 Bit
+                                    (interval.nanoseconds / 1_000_000) as i32,
+                                )
+                            })
+                        })
+                    })
+                    .collect::<Result<IntervalDayTimeArray, _>>()?,
+            ),
+            ArrowDataType::Interval(IntervalUnit::MonthDayNano) => Arc::new(
+                values
+                    .map(|value| {
+                        value.map(|opt| {
+                            opt.map(|interval| {
+                                IntervalMonthDayNanoType::make_value(
+                                    interval.months,
+                                    interval.days,
+                                    interval.nanoseconds,
+                                )
+                            })
+                        })
+                    })
+                    .collect::<Result<IntervalMonthDayNanoArray, _>>()?,
+            ),
+            ArrowDataType::Duration(unit) => {
+                let durations = values.map(|value| {
+                    value.and_then(|opt| {
+                        opt.map(|interval| {
+                            interval
+                                .duration(unit)
+                                .ok_or(MappingError::DurationOverflow { unit })
+                        })
+                        .transpose()
+                    })
+                });
+                match unit {
+                    TimeUnit::Second => {
+                        Arc::new(durations.collect::<Result<DurationSecondArray, _>>()?)
+                    }
+                    TimeUnit::Millisecond => {
+                        Arc::new(durations.collect::<Result<DurationMillisecondArray, _>>()?)
+                    }
+                    TimeUnit::Microsecond => {
+                        Arc::new(durations.collect::<Result<DurationMicrosecondArray, _>>()?)
+                    }
+                    TimeUnit::Nanosecond => {
+                        Arc::new(durations.collect::<Result<DurationNanosecondArray, _>>()?)
+                    }
+                }
+            }
+            _ => unreachable!("Validated in the constructor"),
+        };
+        Ok(array)
+    }
+}
+
+/// An SQL interval, with its time of day part normalized to less than a day. All components have
+/// the same sign.
+#[derive(Debug, PartialEq, Eq)]
+struct ParsedInterval {
+    months: i32,
+    days: i32,
+    nanoseconds: i64,
+}
+
+impl ParsedInterval {
+    /// Elapsed time of a day-time interval in `unit`. Fractions finer than the unit are truncated.
+    /// `None` if the value overflows 64 Bit.
+    fn duration(&self, unit: TimeUnit) -> Option<i64> {
+        let nanos_per_unit = match unit {
+            TimeUnit::Second => 1_000_000_000,
+            TimeUnit::Millisecond => 1_000_000,
+            TimeUnit::Microsecond => 1_000,
+            TimeUnit::Nanosecond => 1,
+        };
+        let units_per_day = SECONDS_PER_DAY as i64 * 1_000_000_000 / nanos_per_unit;
+        i64::from(self.days)
+            .checked_mul(units_per_day)?
+            .checked_add(self.nanoseconds / nanos_per_unit)
+    }
+}
+
+/// Parses the text representation of an interval with the fields `leading` to `trailing`, e.g.
+/// `-1-02` for year to month. The leading field may exceed the range of the field, e.g. `26:30`
+/// for hour to minute. `None` if the text is not a valid interval of this type.
+fn parse_interval(
+    text: &[u8],
+    leading: IntervalField,
+    trailing: IntervalField,
+) -> Option<ParsedInterval> {
+    let text = std::str::from_utf8(text).ok()?.trim();
+    let (is_negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    // Fractional seconds are only allowed for the trailing field, so we split them off first.
+    let (text, fraction) = if trailing == IntervalField::Second {
+        text.split_once('.').unwrap_or((text, ""))
+    } else {
+        (text, "")
+    };
+    if fraction.len() > 9 || !fraction.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let mut parts = text.split(&['-', ' ', ':'][..]);
+    // Values of the fields from year to second
+    let mut values = [0u64; 6];
+    for index in leading as usize..=trailing as usize {
+        let part = parts.next()?;
+        if part.is_empty() || !part.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let value = part.parse::<u64>().ok()?;
+        if index != leading as usize && value > FIELDS[index].max() {
+            return None;
+        }
+        values[index] = value;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+    let [years, months, days, hours, minutes, seconds] = values;
+    let months = years.checked_mul(12)?.checked_add(months)?;
+    let seconds = days
+        .checked_mul(24)?
+        .checked_add(hours)?
+        .checked_mul(60)?
+        .checked_add(minutes)?
+        .checked_mul(60)?
+        .checked_add(seconds)?;
+    let mut sub_seconds = 0;
+    for index in 0..9 {
+        let digit = fraction.as_bytes().get(index).map_or(0, |&c| c - b'0');
+        sub_seconds = sub_seconds * 10 + i64::from(digit);
+    }
+    let sign = if is_negative { -1 } else { 1 };
+    Some(ParsedInterval {
+        months: sign * i32::try_from(months).ok()?,
+        days: sign * i32::try_from(seconds / SECONDS_PER_DAY).ok()?,
+        nanoseconds: sign * ((seconds % SECONDS_PER_DAY) as i64 * 1_000_000_000 + sub_seconds),
+    })
+}
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+const FIELDS: [IntervalField; 6] = [
+    IntervalField::Year,
+    IntervalField::Month,
+    IntervalField::Day,
+    IntervalField::Hour,
+    IntervalField::Minute,
+    IntervalField::Second,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_interval, IntervalField, ParsedInterval};
+
+    fn interval(months: i32, days: i32, nanoseconds: i64) -> Option<ParsedInterval> {
+        Some(ParsedInterval {
+            months,
+            days,
+            nanoseconds,
+        })
+    }
+
+    #[test]
+    fn parse_year_to_month() {
+        let parsed = parse_interval(b"1-02", IntervalField::Year, IntervalField::Month);
+
+        assert_eq!(interval(14, 0, 0), parsed);
+    }
+
+    #[test]
+    fn parse_day_to_second_with_fraction() {
+        let parsed = parse_interval(b"3 04:05:06.5", IntervalField::Day, IntervalField::Second);
+
+        let seconds = 4 * 60 * 60 + 5 * 60 + 6;
+        assert_eq!(
+            interval(0, 3, seconds * 1_000_000_000 + 500_000_000),
+            parsed
+        );
+    }
+
+    #[test]
+    fn parse_negative_intervals() {
+        assert_eq!(
+            interval(-14, 0, 0),
+            parse_interval(b"-1-02", IntervalField::Year, IntervalField::Month)
+        );
+        assert_eq!(
+            interval(0, -1, -1_250_000_000),
+            parse_interval(b"-1 00:00:01.25", IntervalField::Day, IntervalField::Second)
+        );
+    }
+
+    /// Only the leading field may exceed the range of its unit. The time of day part is normalized
+    /// to less than a day.
+    #[test]
+    fn parse_leading_field_exceeding_its_range() {
+        assert_eq!(
+            interval(0, 1, 2 * 60 * 60 * 1_000_000_000 + 30 * 60 * 1_000_000_000),
+            parse_interval(b"26:30", IntervalField::Hour, IntervalField::Minute)
+        );
+        assert_eq!(
+            None,
+            parse_interval(b"1:60", IntervalField::Hour, IntervalField::Minute)
+        );
+        // Too many months for a 32 Bit integer
+        assert_eq!(
+            None,
+            parse_interval(b"3000000000", IntervalField::Month, IntervalField::Month)
+        );
+    }
+
+    #[test]
+    fn reject_malformed_intervals() {
+        let year_to_month =
+            |text: &[u8]| parse_interval(text, IntervalField::Year, IntervalField::Month);
+        assert_eq!(None, year_to_month(b""));
+        assert_eq!(None, year_to_month(b"1-"));
+        assert_eq!(None, year_to_month(b"1-02-03"));
+        assert_eq!(None, year_to_month(b"a-02"));
+        assert_eq!(None, year_to_month(b"+1-02"));
+        // Fractions are only allowed for seconds
+        assert_eq!(None, year_to_month(b"1-02.5"));
+        // Arrow does not resolve more than nanoseconds
+        assert_eq!(
+            None,
+            parse_interval(
+                b"00:00:01.1234567891",
+                IntervalField::Hour,
+                IntervalField::Second
+            )
+        );
+    }
+}
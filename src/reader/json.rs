@@ -0,0 +1,152 @@
+use std::{fmt::Write as _, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, GenericStringArray, OffsetSizeTrait, StringViewArray},
+    datatypes::DataType as ArrowDataType,
+};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+use serde_json::Value;
+
+use super::{MappingError, ReadStrategy};
+
+/// Options for reading from Google Spanner. See
+/// [`crate::OdbcReaderBuilder::with_spanner_compatibility`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpannerCompatibility {
+    /// Parse each JSON document and serialize it again with the keys of objects sorted and
+    /// without insignificant whitespace. Documents with the same content then compare equal as
+    /// text, which is useful for deterministic comparisons. Invalid documents cause a
+    /// [`MappingError::InvalidJson`].
+    pub normalize_json: bool,
+}
+
+/// Fills a string array using another strategy and replaces each JSON document with its canonical
+/// representation. See [`SpannerCompatibility::normalize_json`].
+pub struct NormalizeJson {
+    inner: Box<dyn ReadStrategy + Send>,
+}
+
+impl NormalizeJson {
+    pub fn new(inner: Box<dyn ReadStrategy + Send>) -> Self {
+        Self { inner }
+    }
+}
+
+impl ReadStrategy for NormalizeJson {
+    fn strategy_type_name(&self) -> &'static str {
+        "NormalizeJson"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.inner.buffer_desc()
+    }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        self.inner.element_size_hint()
+    }
+
+    fn estimated_memory_per_row(&self) -> usize {
+        self.inner.estimated_memory_per_row()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let array = self.inner.fill_arrow_array(column_view)?;
+        let normalized: ArrayRef = match array.data_type() {
+            ArrowDataType::Utf8 => Arc::new(normalize_string_array(array.as_string::<i32>())?),
+            ArrowDataType::LargeUtf8 => Arc::new(normalize_string_array(array.as_string::<i64>())?),
+            ArrowDataType::Utf8View => {
+                let documents = array
+                    .as_string_view()
+                    .iter()
+                    .map(|document| document.map(normalize_json).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+                Arc::new(StringViewArray::from(documents))
+            }
+            // `arrow.json` is only annotated on string fields
+            _ => array,
+        };
+        Ok(normalized)
+    }
+}
+
+fn normalize_string_array<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+) -> Result<GenericStringArray<O>, MappingError> {
+    array
+        .iter()
+        .map(|document| document.map(normalize_json).transpose())
+        .collect()
+}
+
+/// Canonical representation of `document`. Keys are sorted explicitly, rather than relying on the
+/// map type of `serde_json`, which preserves insertion order if its `preserve_order` feature is
+/// enabled anywhere in the dependency graph.
+fn normalize_json(document: &str) -> Result<String, MappingError> {
+    let value: Value =
+        serde_json::from_str(document).map_err(|source| MappingError::InvalidJson {
+            value: document.to_owned(),
+            source,
+        })?;
+    let mut canonical = String::with_capacity(document.len());
+    write_canonical(&value, &mut canonical);
+    Ok(canonical)
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(elements) => {
+            out.push('[');
+            for (index, element) in elements.iter().enumerate() {
+                if index != 0 {
+                    out.push(',');
+                }
+                write_canonical(element, out);
+            }
+            out.push(']');
+        }
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+            out.push('{');
+            for (index, (key, element)) in entries.into_iter().enumerate() {
+                if index != 0 {
+                    out.push(',');
+                }
+                write!(out, "{}:", Value::String(key.clone())).unwrap();
+                write_canonical(element, out);
+            }
+            out.push('}');
+        }
+        // Scalars are serialized without whitespace, and strings escaped, by `Display`
+        scalar => write!(out, "{scalar}").unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_json;
+
+    #[test]
+    fn sort_keys_of_nested_objects() {
+        let normalized = normalize_json(r#"{ "b": [1, {"d": null, "c": true}], "a": "x" }"#);
+
+        assert_eq!(
+            r#"{"a":"x","b":[1,{"c":true,"d":null}]}"#,
+            normalized.unwrap()
+        );
+    }
+
+    #[test]
+    fn escape_keys_and_strings() {
+        let normalized = normalize_json(r#"{"quote\"d": "line\nbreak"}"#);
+
+        assert_eq!(r#"{"quote\"d":"line\nbreak"}"#, normalized.unwrap());
+    }
+
+    #[test]
+    fn reject_invalid_document() {
+        let normalized = normalize_json(r#"{"a": "#);
+
+        assert!(normalized.is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, AsArray},
+    compute::{cast, concat_batches},
+    datatypes::{DataType as ArrowDataType, Schema, SchemaRef},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use odbc_api::{sys::SqlDataType, DataType as OdbcDataType};
+
+/// `true` for columns whose relational type tells nothing about their values, e.g. `sql_variant`
+/// in SQL Server. These are inferred as text, unless the schema is inferred lazily. See
+/// [`crate::OdbcReaderBuilder::with_lazy_schema_inference`]. Types `odbc_api` does not have a
+/// variant for, yet which are defined by the ODBC standard or well known drivers, are not
+/// undetermined. Their values are read by dedicated strategies, which probing would bypass.
+pub fn is_undetermined(data_type: &OdbcDataType) -> bool {
+    match data_type {
+        OdbcDataType::Unknown => true,
+        // `SQL_GUID` and the interval types (`SQL_INTERVAL_YEAR` up to
+        // `SQL_INTERVAL_MINUTE_TO_SECOND`) are defined by the ODBC standard. `SQL_SS_XML` and
+        // `SQL_XML` are known to be text, `SQL_SS_TIME2` a time of day and
+        // `SQL_SS_TIMESTAMPOFFSET` a timestamp.
+        OdbcDataType::Other {
+            data_type: SqlDataType(-11 | 101..=113 | -152 | -154 | -155 | -370),
+            ..
+        } => false,
+        OdbcDataType::Other { .. } => true,
+        _ => false,
+    }
+}
+
+/// Concatenates the probed `batches` and replaces the text arrays of their `undetermined` columns
+/// with arrays of the narrowest type able to represent all of their values. Columns holding only
+/// NULLs stay text. The schema of the returned batch is the refined schema.
+pub fn refine_probed_columns(
+    schema: &SchemaRef,
+    batches: &[RecordBatch],
+    undetermined: &[usize],
+) -> Result<RecordBatch, ArrowError> {
+    let batch = concat_batches(schema, batches)?;
+    let mut fields: Vec<_> = schema.fields().iter().cloned().collect();
+    let mut columns = batch.columns().to_vec();
+    for &index in undetermined {
+        let Some(data_type) = infer_from_values(columns[index].as_ref())? else {
+            continue;
+        };
+        columns[index] = cast(&columns[index], &data_type)?;
+        fields[index] = Arc::new(fields[index].as_ref().clone().with_data_type(data_type));
+    }
+    let schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+    RecordBatch::try_new(Arc::new(schema), columns)
+}
+
+/// `Int64` if every non-null value is an integer, `Float64` if every one is a finite number, `Utf8`
+/// otherwise. `None` if there are only NULLs, which do not tell us anything.
+fn infer_from_values(array: &dyn Array) -> Result<Option<ArrowDataType>, ArrowError> {
+    let text = cast(array, &ArrowDataType::Utf8)?;
+    let mut inferred = None;
+    for value in text.as_string::<i32>().iter().flatten() {
+        let data_type = if value.parse::<i64>().is_ok() {
+            ArrowDataType::Int64
+        } else if value.parse::<f64>().map_or(false, f64::is_finite) {
+            // Rust also parses `inf` and `NaN`, which are more likely words than numbers.
+            ArrowDataType::Float64
+        } else {
+            return Ok(Some(ArrowDataType::Utf8));
+        };
+        // Integers can be represented as floats, but not the other way around
+        if inferred != Some(ArrowDataType::Float64) {
+            inferred = Some(data_type);
+        }
+    }
+    Ok(inferred)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{ArrayRef, StringArray},
+        datatypes::{DataType as ArrowDataType, Field, Schema},
+        record_batch::RecordBatch,
+    };
+
+    use super::refine_probed_columns;
+
+    fn probed(values: &[&[Option<&str>]]) -> (Arc<Schema>, Vec<RecordBatch>) {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            ArrowDataType::Utf8,
+            true,
+        )]));
+        let batches = values
+            .iter()
+            .map(|values| {
+                let array: ArrayRef = Arc::new(StringArray::from(values.to_vec()));
+                RecordBatch::try_new(schema.clone(), vec![array]).unwrap()
+            })
+            .collect();
+        (schema, batches)
+    }
+
+    #[test]
+    fn infer_sparse_column_from_later_batch() {
+        let (schema, batches) = probed(&[&[None, None], &[Some("42")]]);
+
+        let refined = refine_probed_columns(&schema, &batches, &[0]).unwrap();
+
+        assert_eq!(3, refined.num_rows());
+        assert_eq!(&ArrowDataType::Int64, refined.schema().field(0).data_type());
+    }
+
+    #[test]
+    fn words_parsed_as_floats_by_rust_stay_text() {
+        let (schema, batches) = probed(&[&[Some("1.5"), Some("inf"), Some("NaN")]]);
+
+        let refined = refine_probed_columns(&schema, &batches, &[0]).unwrap();
+
+        assert_eq!(&ArrowDataType::Utf8, refined.schema().field(0).data_type());
+    }
+
+    #[test]
+    fn column_with_only_nulls_stays_text() {
+        let (schema, batches) = probed(&[&[None], &[None]]);
+
+        let refined = refine_probed_columns(&schema, &batches, &[0]).unwrap();
+
+        assert_eq!(&ArrowDataType::Utf8, refined.schema().field(0).data_type());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use odbc_api::{sys::SqlDataType, DataType as OdbcDataType};
+
+    use super::is_undetermined;
+
+    fn other(code: i16) -> OdbcDataType {
+        OdbcDataType::Other {
+            data_type: SqlDataType(code),
+            column_size: None,
+            decimal_digits: 0,
+        }
+    }
+
+    #[test]
+    fn probe_unknown_and_driver_specific_types() {
+        assert!(is_undetermined(&OdbcDataType::Unknown));
+        // `SQL_SS_VARIANT`
+        assert!(is_undetermined(&other(-150)));
+    }
+
+    #[test]
+    fn do_not_probe_types_defined_by_odbc() {
+        // `SQL_GUID`
+        assert!(!is_undetermined(&other(-11)));
+        // `SQL_INTERVAL_YEAR`, `SQL_INTERVAL_DAY_TO_SECOND` and `SQL_INTERVAL_MINUTE_TO_SECOND`
+        assert!(!is_undetermined(&other(101)));
+        assert!(!is_undetermined(&other(110)));
+        assert!(!is_undetermined(&other(113)));
+        assert!(!is_undetermined(&OdbcDataType::Integer));
+    }
+}
@@ -0,0 +1,223 @@
+use std::{iter::Peekable, str::Chars, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, MapArray, StringArray, StructArray},
+    buffer::{OffsetBuffer, ScalarBuffer},
+    datatypes::{DataType as ArrowDataType, FieldRef},
+};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+
+use super::{postgres_array::parse_elements, MappingError, PostgresArray, ReadStrategy};
+
+/// Fetches key-value columns, like `Map(String, T)` in ClickHouse. The ODBC driver transmits them
+/// as text (e.g. `{'a':1,'b':NULL}`), which we parse into an Arrow `Map`. Keys and values support
+/// the same types as the elements of [`super::PostgresArray`].
+pub struct MapLiteral {
+    /// Strategy fetching the map literals into a `Utf8` array.
+    text: Box<dyn ReadStrategy + Send>,
+    /// Field describing the entries of the map. A struct with a key and a value child.
+    entries: FieldRef,
+    /// Whether the keys of the map are sorted.
+    sorted: bool,
+}
+
+impl MapLiteral {
+    /// `text` must be a strategy emitting `Utf8` arrays. `entries` is the field of a
+    /// `ArrowDataType::Map`.
+    pub fn new(text: Box<dyn ReadStrategy + Send>, entries: FieldRef, sorted: bool) -> Self {
+        Self {
+            text,
+            entries,
+            sorted,
+        }
+    }
+
+    /// `true` if `entries` is a struct with keys and values we know how to parse.
+    pub fn is_supported_entries(entries: &FieldRef) -> bool {
+        match entries.data_type() {
+            ArrowDataType::Struct(fields) => {
+                fields.len() == 2
+                    && fields
+                        .iter()
+                        .all(|field| PostgresArray::is_supported_element(field.data_type()))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ReadStrategy for MapLiteral {
+    fn strategy_type_name(&self) -> &'static str {
+        "MapLiteral"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.text.buffer_desc()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let text = self.text.fill_arrow_array(column_view)?;
+        let text = text.as_any().downcast_ref::<StringArray>().unwrap();
+        let ArrowDataType::Struct(fields) = self.entries.data_type() else {
+            unreachable!("Entries must be checked with `is_supported_entries`")
+        };
+
+        let mut offsets = Vec::with_capacity(text.len() + 1);
+        offsets.push(0i32);
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+        for literal in text.iter() {
+            if let Some(literal) = literal {
+                let invalid = || MappingError::InvalidMapLiteral {
+                    value: literal.to_owned(),
+                };
+                let start = values.len();
+                parse_map_literal(literal, &mut keys, &mut values).ok_or_else(invalid)?;
+                if !fields[1].is_nullable() && values[start..].iter().any(Option::is_none) {
+                    return Err(invalid());
+                }
+            }
+            offsets.push(keys.len().try_into().unwrap());
+        }
+
+        let keys = parse_elements(fields[0].data_type(), &keys)
+            .map_err(|value| MappingError::InvalidMapLiteral { value })?;
+        let values = parse_elements(fields[1].data_type(), &values)
+            .map_err(|value| MappingError::InvalidMapLiteral { value })?;
+        let entries = StructArray::new(fields.clone(), vec![keys, values], None);
+        let array = MapArray::new(
+            self.entries.clone(),
+            OffsetBuffer::new(ScalarBuffer::from(offsets)),
+            entries,
+            text.nulls().cloned(),
+            self.sorted,
+        );
+        Ok(Arc::new(array))
+    }
+}
+
+/// Appends the entries of a map literal like `{'a':1,'b':NULL}` to `keys` and `values`. Keys must
+/// not be NULL. `None` if the literal is malformed.
+fn parse_map_literal(
+    literal: &str,
+    keys: &mut Vec<Option<String>>,
+    values: &mut Vec<Option<String>>,
+) -> Option<()> {
+    let inner = literal.trim().strip_prefix('{')?.strip_suffix('}')?;
+    if inner.trim().is_empty() {
+        return Some(());
+    }
+    let mut chars = inner.chars().peekable();
+    loop {
+        // Keys must not be NULL
+        keys.push(Some(parse_token(&mut chars, ':')??));
+        chars.next_if_eq(&':')?;
+        values.push(parse_token(&mut chars, ',')?);
+        match chars.next() {
+            Some(',') => continue,
+            None => return Some(()),
+            Some(_) => return None,
+        }
+    }
+}
+
+/// Parses a single key or value, up to the `delimiter`. Keys and values may be quoted with single
+/// or double quotes. Backslash escapes the next character within quotes. Only the unquoted `NULL`
+/// is a NULL. The outer `None` indicates a malformed token.
+fn parse_token(chars: &mut Peekable<Chars>, delimiter: char) -> Option<Option<String>> {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    let token = if let Some(quote) = chars.next_if(|&c| c == '\'' || c == '"') {
+        let mut token = String::new();
+        loop {
+            match chars.next()? {
+                c if c == quote => break,
+                '\\' => token.push(chars.next()?),
+                c => token.push(c),
+            }
+        }
+        Some(token)
+    } else {
+        let mut token = String::new();
+        while let Some(c) = chars.next_if(|&c| c != delimiter) {
+            if matches!(c, '{' | '}' | '\'' | '"' | ':' | ',') {
+                return None;
+            }
+            token.push(c);
+        }
+        let token = token.trim_end();
+        if token.is_empty() {
+            return None;
+        }
+        (!token.eq_ignore_ascii_case("NULL")).then(|| token.to_owned())
+    };
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    Some(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_map_literal;
+
+    type Entries = (Vec<Option<String>>, Vec<Option<String>>);
+
+    fn parse(literal: &str) -> Option<Entries> {
+        let mut keys = Vec::new();
+        let mut values = Vec::new();
+        parse_map_literal(literal, &mut keys, &mut values).map(|()| (keys, values))
+    }
+
+    fn text(token: &str) -> Option<String> {
+        Some(token.to_owned())
+    }
+
+    #[test]
+    fn parse_quoted_keys_and_null_value() {
+        assert_eq!(
+            Some((vec![text("a"), text("b")], vec![text("1"), None])),
+            parse("{'a':1, \"b\" : NULL}")
+        );
+    }
+
+    #[test]
+    fn parse_empty_map() {
+        assert_eq!(Some((vec![], vec![])), parse("{ }"));
+    }
+
+    #[test]
+    fn quoted_tokens_keep_delimiters_and_escapes() {
+        assert_eq!(
+            Some((vec![text("a:b,c")], vec![text("it's \"quoted\"")])),
+            parse(r#"{'a:b,c':'it\'s "quoted"'}"#)
+        );
+    }
+
+    #[test]
+    fn quoted_null_is_text() {
+        assert_eq!(
+            Some((vec![text("key")], vec![text("NULL")])),
+            parse("{'key':'NULL'}")
+        );
+    }
+
+    #[test]
+    fn reject_null_key() {
+        assert_eq!(None, parse("{NULL:1}"));
+    }
+
+    #[test]
+    fn reject_nested_maps() {
+        assert_eq!(None, parse("{'a':{'b':1}}"));
+    }
+
+    #[test]
+    fn reject_malformed_literals() {
+        // Missing braces
+        assert_eq!(None, parse("'a':1"));
+        // Missing value
+        assert_eq!(None, parse("{'a':}"));
+        // Missing colon
+        assert_eq!(None, parse("{'a' 1}"));
+        // Unterminated quote
+        assert_eq!(None, parse("{'a:1}"));
+    }
+}
@@ -2,7 +2,8 @@ use std::{marker::PhantomData, sync::Arc};
 
 use arrow::{
     array::{ArrayRef, PrimitiveBuilder},
-    datatypes::ArrowPrimitiveType,
+    datatypes::{ArrowPrimitiveType, DataType as ArrowDataType, TimeUnit},
+    error::ArrowError,
 };
 use chrono::NaiveDateTime;
 use odbc_api::buffers::{AnySlice, BufferDesc, Item};
@@ -141,6 +142,10 @@ where
     T: ArrowPrimitiveType + Send,
     T::Native: Item,
 {
+    fn strategy_type_name(&self) -> &'static str {
+        "NonNullableDirect"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         T::Native::buffer_desc(false)
     }
@@ -170,6 +175,10 @@ where
     T: ArrowPrimitiveType + Send,
     T::Native: Item,
 {
+    fn strategy_type_name(&self) -> &'static str {
+        "NullableDirect"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         T::Native::buffer_desc(true)
     }
@@ -206,6 +215,10 @@ where
     O: Item + Send,
     F: MapElement<O, P::Native>,
 {
+    fn strategy_type_name(&self) -> &'static str {
+        "NonNullableMapped"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         O::buffer_desc(false)
     }
@@ -242,6 +255,10 @@ where
     O: Item + Send,
     F: MapElement<O, P::Native>,
 {
+    fn strategy_type_name(&self) -> &'static str {
+        "NullableMapped"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         O::buffer_desc(true)
     }
@@ -283,6 +300,10 @@ where
     O: Item + Send,
     F: Fn(&O) -> Result<P::Native, MappingError> + Send,
 {
+    fn strategy_type_name(&self) -> &'static str {
+        "ErrorToNull"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         O::buffer_desc(true)
     }
@@ -300,6 +321,7 @@ where
 /// The source value returned from the ODBC datasource is out of range and can not be mapped into
 /// its Arrow target type.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum MappingError {
     #[error(
         "Timestamp is not representable in arrow: {value}\n\
@@ -309,4 +331,171 @@ pub enum MappingError {
         Suggestions to fix this error either reduce the precision or fetch the values as text."
     )]
     OutOfRangeTimestampNs { value: NaiveDateTime },
+    #[error(
+        "The database returned the local time {value}, which does not exist in the timezone \
+        passed to `OdbcReaderBuilder::with_timestamp_timezone`, since it is skipped by the start \
+        of daylight saving time."
+    )]
+    NonexistentLocalTime { value: NaiveDateTime },
+    #[error(
+        "Timestamp is not representable in arrow: {value}\n\
+        Converted to UTC, the value is outside of the range of timestamps a signed 64 Bit integer \
+        can represent in {unit:?}s. Suggestions to fix this error either reduce the precision or \
+        fetch the values as text."
+    )]
+    OutOfRangeTimestamp {
+        value: NaiveDateTime,
+        unit: TimeUnit,
+    },
+    #[error(
+        "The database returned a timestamp with a fraction finer than the {unit:?} precision of \
+        the Arrow field: {value}. Suggestions to fix this error are to specify a schema with a \
+        finer time unit, or to truncate or round such values instead."
+    )]
+    SubPrecisionLoss {
+        value: NaiveDateTime,
+        unit: TimeUnit,
+    },
+    #[error(
+        "Decimal is not representable in arrow: {value}\n\
+        The value returned from the database has more digits than the precision of the Arrow \
+        column ({precision}) allows for. Suggestions to fix this error are to specify a schema \
+        with a larger precision or to fetch the values as text."
+    )]
+    DecimalOutOfPrecision { value: String, precision: u8 },
+    #[error(
+        "The database returned a value for a decimal column which could not be parsed as a \
+        decimal number: '{}' (raw bytes: {raw_value:?}). Some databases return special values \
+        like `NaN` or `Infinity` for numeric columns, which are not representable in an Arrow \
+        decimal. Suggestions to fix this error are to fetch the values as text or floating \
+        point numbers.",
+        String::from_utf8_lossy(raw_value)
+    )]
+    DecimalParseError { raw_value: Vec<u8> },
+    #[error(
+        "Expected the database to return UTF-8, yet what came back was not valid UTF-8: '{value}'. \
+        According to the ODBC standard the encoding of narrow text is specified by your system \
+        locale. So you may want to check your environment and whether it specifies to use an UTF-8 \
+        charset.\n{source}"
+    )]
+    InvalidUtf8 {
+        /// Value returned by the database, invalid sequences replaced with `U+FFFD`.
+        value: String,
+        source: std::str::Utf8Error,
+    },
+    #[error(
+        "Expected the database to return UTF-16, yet what came back was not valid UTF-16. This is \
+        likely a bug in your ODBC driver not supporting wide method calls correctly.\n{source}"
+    )]
+    InvalidUtf16 { source: std::char::DecodeUtf16Error },
+    #[error(
+        "Expected the database to return binary values of {expected} bytes, yet a value of \
+        {actual} bytes came back. Please specify a schema with a variable sized binary type \
+        rather than `FixedSizeBinary` for this column."
+    )]
+    UnexpectedBinaryLength { expected: usize, actual: usize },
+    #[error(
+        "Could not parse array returned by the database: '{value}'. Only one dimensional arrays \
+        with elements matching the element type of the Arrow list are supported. Elements must \
+        not be NULL, unless the element field is nullable."
+    )]
+    InvalidArrayLiteral { value: String },
+    #[error(
+        "Could not parse composite value returned by the database: '{value}'. The number of \
+        attributes must match the number of children of the Arrow struct, and each attribute must \
+        match the type of its child. Attributes must not be NULL, unless the child is nullable."
+    )]
+    InvalidCompositeLiteral { value: String },
+    #[error(
+        "Could not parse map returned by the database: '{value}'. Keys and values must match the \
+        types of the entries of the Arrow map. Keys must not be NULL and values only if the value \
+        field is nullable."
+    )]
+    InvalidMapLiteral { value: String },
+    #[error(
+        "Could not parse JSON document returned by the database: '{value}'. Documents are only \
+        parsed to normalize them. Suggestions to fix this error are to disable the normalization \
+        of JSON documents.\n{source}"
+    )]
+    InvalidJson {
+        value: String,
+        source: serde_json::Error,
+    },
+    #[error(
+        "The database returned a spatial value which could not be converted into WKB. The value \
+        has been expected in the {format}. Curved geometries (e.g. `CIRCULARSTRING`) and the full \
+        globe have no WKB representation. Suggestions to fix this error are to convert the values \
+        in the query, e.g. with `STAsBinary()` in SQL Server or `ST_AsBinary()` in PostGIS."
+    )]
+    InvalidGeometry { format: &'static str },
+    #[error(
+        "The database returned a negative value ({value}) for a column with an unsigned Arrow \
+        type. Unsigned integers are fetched using signed integer buffers. Some drivers put \
+        unsigned values larger than the signed maximum into these buffers, wrapping them around \
+        to negative numbers. If your values are known to be positive, you may want to allow \
+        signed to unsigned coercion in order to reinterpret the bits as an unsigned integer."
+    )]
+    NegativeUnsigned { value: i64 },
+    #[error(
+        "The database returned a value ({value}) which is out of range for the integer type of the \
+        Arrow field. Suggestions to fix this error are to specify a schema with a wider integer \
+        type for this column."
+    )]
+    IntegerOutOfRange { value: i64 },
+    #[error(
+        "The database returned a value ({value}) which is not part of the dictionary specified for \
+        the column. Please add it to the dictionary values or let the dictionary be built from the \
+        fetched values."
+    )]
+    ValueNotInDictionary { value: i32 },
+    #[error(
+        "A batch contains more distinct values than keys of type {key_type} are able to index. \
+        Please specify a wider key type for the dictionary, or fewer rows per batch."
+    )]
+    DictionaryKeyOverflow { key_type: ArrowDataType },
+    #[error(
+        "The text of a single batch exceeds the 2 GiB a `Utf8` array is able to hold. Please \
+        specify `LargeUtf8` for the column (or use `TextOutputMode::LargeUtf8`), or fetch fewer \
+        rows per batch."
+    )]
+    TextOffsetOverflow,
+    #[error(
+        "The database returned a value for a time column which could not be parsed as a time of \
+        day: '{}' (raw bytes: {raw_value:?}). Times with fractional seconds are fetched as text in \
+        the format `HH:MM:SS.fffffff`. Suggestions to fix this error are to fetch the values as \
+        text.",
+        String::from_utf8_lossy(raw_value)
+    )]
+    TimeParseError { raw_value: Vec<u8> },
+    #[error(
+        "The database returned a value for an interval column which could not be parsed as an \
+        interval: '{}' (raw bytes: {raw_value:?}). Intervals are fetched as text, e.g. `-1-02` for \
+        `INTERVAL YEAR TO MONTH` or `3 04:05:06.789` for `INTERVAL DAY TO SECOND`. Suggestions to \
+        fix this error are to fetch the values as text.",
+        String::from_utf8_lossy(raw_value)
+    )]
+    IntervalParseError { raw_value: Vec<u8> },
+    #[error(
+        "The database returned a value for a timestamp with offset which could not be parsed: \
+        '{}' (raw bytes: {raw_value:?}). Timestamps with offset are fetched as text in the format \
+        `YYYY-MM-DD HH:MM:SS.fffffff +HH:MM`. Suggestions to fix this error are to fetch the \
+        values as text.",
+        String::from_utf8_lossy(raw_value)
+    )]
+    TimestampWithOffsetParseError { raw_value: Vec<u8> },
+    #[error(
+        "The database returned an interval which is too large to be represented as \
+        `Duration({unit:?})`. Please specify a coarser unit for the field."
+    )]
+    DurationOverflow { unit: TimeUnit },
+    #[error("A post fetch transform failed.\n{source}")]
+    TransformFailed { source: ArrowError },
+    #[error(
+        "A post fetch transform must return an array of the same type and length as its input. \
+        Expected type: {expected}, actual type: {actual}."
+    )]
+    TransformChangedShape {
+        expected: ArrowDataType,
+        actual: ArrowDataType,
+    },
 }
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Decimal128Builder},
+    datatypes::DataType as ArrowDataType,
+};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+
+use super::{MappingError, ReadStrategy};
+
+/// Currency types of Microsoft SQL Server and Sybase. Both hold fixed point values with exactly
+/// four digits after the radix character, internally represented as integers counting
+/// ten-thousandths of a currency unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoneyType {
+    /// `MONEY`, a 64 Bit integer of ten-thousandths.
+    Money,
+    /// `SMALLMONEY`, a 32 Bit integer of ten-thousandths.
+    SmallMoney,
+}
+
+impl MoneyType {
+    /// Currency type with the name `type_name`, as reported by the driver. `None` if it is not a
+    /// currency type.
+    pub fn from_type_name(type_name: &str) -> Option<Self> {
+        if type_name.eq_ignore_ascii_case("money") {
+            Some(MoneyType::Money)
+        } else if type_name.eq_ignore_ascii_case("smallmoney") {
+            Some(MoneyType::SmallMoney)
+        } else {
+            None
+        }
+    }
+
+    /// `Decimal128(19, 4)` for `MONEY` and `Decimal128(10, 4)` for `SMALLMONEY`. Enough digits to
+    /// represent any value of the type, independent of what the driver reports as precision and
+    /// scale of the column.
+    pub fn arrow_data_type(self) -> ArrowDataType {
+        match self {
+            MoneyType::Money => ArrowDataType::Decimal128(19, MONEY_SCALE),
+            MoneyType::SmallMoney => ArrowDataType::Decimal128(10, MONEY_SCALE),
+        }
+    }
+
+    /// Range of the values in ten-thousandths.
+    fn range(self) -> (i64, i64) {
+        match self {
+            MoneyType::Money => (i64::MIN, i64::MAX),
+            MoneyType::SmallMoney => (i32::MIN.into(), i32::MAX.into()),
+        }
+    }
+
+    /// Length of the longest value formatted as text, e.g. `-922337203685477.5808`.
+    fn max_str_len(self) -> usize {
+        match self {
+            MoneyType::Money => 21,
+            MoneyType::SmallMoney => 12,
+        }
+    }
+}
+
+/// Number of digits after the radix character of currency values.
+const MONEY_SCALE: i8 = 4;
+
+/// Fetches `MONEY` and `SMALLMONEY` columns into `Decimal128(_, 4)` fields. odbc-api does not
+/// offer buffers for `SQL_C_NUMERIC`, so values are transmitted as text. Yet, since the scale of
+/// the currency types is fixed, we know exactly how they are formatted. Each value is converted
+/// into its integer count of ten-thousandths with checked arithmetic, rather than relying on the
+/// precision and scale reported by the driver.
+pub struct Money {
+    money_type: MoneyType,
+    precision: u8,
+}
+
+impl Money {
+    /// `precision` is the one of the Arrow field. Values exceeding it cause an error.
+    pub fn new(money_type: MoneyType, precision: u8) -> Self {
+        Self {
+            money_type,
+            precision,
+        }
+    }
+}
+
+impl ReadStrategy for Money {
+    fn strategy_type_name(&self) -> &'static str {
+        "Money"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Text {
+            max_str_len: self.money_type.max_str_len(),
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let view = column_view.as_text_view().unwrap();
+        let mut builder = Decimal128Builder::with_capacity(view.len());
+        // Precision of Decimal128 is at most 38, so this can not overflow.
+        let upper_bound = 10u128.pow(self.precision.into());
+        let (min, max) = self.money_type.range();
+
+        for opt in view.iter() {
+            if let Some(text) = opt {
+                let num =
+                    parse_money_text(text).ok_or_else(|| MappingError::DecimalParseError {
+                        raw_value: text.to_owned(),
+                    })?;
+                if num < min || num > max || u128::from(num.unsigned_abs()) >= upper_bound {
+                    return Err(MappingError::DecimalOutOfPrecision {
+                        value: String::from_utf8_lossy(text).into_owned(),
+                        precision: self.precision,
+                    });
+                }
+                builder.append_value(num.into());
+            } else {
+                builder.append_null();
+            }
+        }
+
+        Ok(Arc::new(
+            builder
+                .finish()
+                .with_precision_and_scale(self.precision, MONEY_SCALE)
+                .unwrap(),
+        ))
+    }
+}
+
+/// Parses a currency value like `-1234.5678` into ten-thousandths. The integer part may be empty
+/// (e.g. `.5000`), and the fraction may have fewer than four digits. `None` if `text` is malformed
+/// or its value does not fit into 64 Bit.
+fn parse_money_text(text: &[u8]) -> Option<i64> {
+    let (negative, unsigned) = match text.first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let mut parts = unsigned.splitn(2, |&c| c == b'.');
+    let integer = parts.next().unwrap_or_default();
+    let fraction = parts.next().unwrap_or_default();
+    if (integer.is_empty() && fraction.is_empty()) || fraction.len() > MONEY_SCALE as usize {
+        return None;
+    }
+    // Negative values are accumulated as such, so the minimum of `MONEY` does not overflow.
+    let mut value = 0i64;
+    for &c in integer.iter().chain(fraction) {
+        if !c.is_ascii_digit() {
+            return None;
+        }
+        let digit = i64::from(c - b'0');
+        value = value.checked_mul(10)?;
+        value = if negative {
+            value.checked_sub(digit)?
+        } else {
+            value.checked_add(digit)?
+        };
+    }
+    // Account for missing trailing zeroes, so the last digit is a ten-thousandth.
+    let padding = MONEY_SCALE as u32 - fraction.len() as u32;
+    value.checked_mul(10i64.pow(padding))
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, ArrayRef, BooleanArray, PrimitiveArray, StringArray},
+    datatypes::{
+        ArrowPrimitiveType, DataType as ArrowDataType, Float32Type, Float64Type, Int16Type,
+        Int32Type, Int64Type, Int8Type, UInt8Type,
+    },
+};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+
+use super::{ColumnFailure, MappingError, ReadStrategy};
+
+/// A single value, used by [`crate::OdbcReaderBuilder::with_null_replacement`] to fill in for NULLs
+/// fetched from the data source. Also reports minimum and maximum values in
+/// [`crate::ColumnStatistics`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalarValue {
+    /// Replacement for `Boolean` columns.
+    Boolean(bool),
+    /// Replacement for integer columns (`Int8`, `Int16`, `Int32`, `Int64` and `UInt8`). The value
+    /// must be representable by the Arrow type of the column.
+    Int64(i64),
+    /// Replacement for `Float32` and `Float64` columns.
+    Float64(f64),
+    /// Replacement for `Utf8` columns.
+    Utf8(String),
+    /// Days since the UNIX epoch of a `Date32` column. Only used by [`crate::ColumnStatistics`], not
+    /// supported as replacement for NULLs.
+    Date32(i32),
+    /// Values of a `UInt64` column, which may exceed the range of `Int64`. Only used by
+    /// [`crate::ColumnStatistics`], not supported as replacement for NULLs.
+    UInt64(u64),
+}
+
+type Replace = Box<dyn Fn(&dyn Array) -> ArrayRef + Send>;
+
+/// Binds a nullable buffer to the column, yet emits arrays without any NULLs, by replacing them with
+/// a default value. Allows for non nullable fields in the Arrow schema, even though the column in
+/// the data source is nullable.
+pub struct ReplaceNulls {
+    /// Strategy used to fill an array with NULLs from the nullable buffer.
+    nullable: Box<dyn ReadStrategy + Send>,
+    replace: Replace,
+}
+
+impl ReplaceNulls {
+    /// `nullable` must be a strategy which emits arrays of type `data_type`.
+    pub fn new(
+        nullable: Box<dyn ReadStrategy + Send>,
+        data_type: &ArrowDataType,
+        replacement: &ScalarValue,
+    ) -> Result<Self, ColumnFailure> {
+        let replace = match (data_type, replacement) {
+            (ArrowDataType::Boolean, &ScalarValue::Boolean(value)) => {
+                Some(Box::new(move |array: &dyn Array| {
+                    let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+                    let array: BooleanArray =
+                        array.iter().map(|v| Some(v.unwrap_or(value))).collect();
+                    Arc::new(array) as ArrayRef
+                }) as Replace)
+            }
+            (ArrowDataType::Int8, &ScalarValue::Int64(value)) => integer::<Int8Type>(value),
+            (ArrowDataType::Int16, &ScalarValue::Int64(value)) => integer::<Int16Type>(value),
+            (ArrowDataType::Int32, &ScalarValue::Int64(value)) => integer::<Int32Type>(value),
+            (ArrowDataType::Int64, &ScalarValue::Int64(value)) => integer::<Int64Type>(value),
+            (ArrowDataType::UInt8, &ScalarValue::Int64(value)) => integer::<UInt8Type>(value),
+            (ArrowDataType::Float32, &ScalarValue::Float64(value)) => {
+                Some(primitive::<Float32Type>(value as f32))
+            }
+            (ArrowDataType::Float64, &ScalarValue::Float64(value)) => {
+                Some(primitive::<Float64Type>(value))
+            }
+            (ArrowDataType::Utf8, ScalarValue::Utf8(value)) => {
+                let value = value.clone();
+                Some(Box::new(move |array: &dyn Array| {
+                    let array = array.as_any().downcast_ref::<StringArray>().unwrap();
+                    let array: StringArray = array
+                        .iter()
+                        .map(|v| Some(v.unwrap_or(value.as_str())))
+                        .collect();
+                    Arc::new(array) as ArrayRef
+                }) as Replace)
+            }
+            _ => None,
+        };
+        let replace = replace.ok_or_else(|| ColumnFailure::InvalidNullReplacement {
+            data_type: data_type.clone(),
+            replacement: replacement.clone(),
+        })?;
+        Ok(Self { nullable, replace })
+    }
+}
+
+impl ReadStrategy for ReplaceNulls {
+    fn strategy_type_name(&self) -> &'static str {
+        "ReplaceNulls"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.nullable.buffer_desc()
+    }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        self.nullable.element_size_hint()
+    }
+
+    fn estimated_memory_per_row(&self) -> usize {
+        self.nullable.estimated_memory_per_row()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let array = self.nullable.fill_arrow_array(column_view)?;
+        if array.null_count() == 0 {
+            Ok(array)
+        } else {
+            Ok((self.replace)(array.as_ref()))
+        }
+    }
+}
+
+/// `None` if `value` is not representable by the native type of `T`.
+fn integer<T>(value: i64) -> Option<Replace>
+where
+    T: ArrowPrimitiveType,
+    T::Native: TryFrom<i64>,
+{
+    let value = T::Native::try_from(value).ok()?;
+    Some(primitive::<T>(value))
+}
+
+fn primitive<T: ArrowPrimitiveType>(value: T::Native) -> Replace {
+    Box::new(move |array: &dyn Array| {
+        let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+        let array: PrimitiveArray<T> = array.iter().map(|v| Some(v.unwrap_or(value))).collect();
+        Arc::new(array) as ArrayRef
+    })
+}
@@ -0,0 +1,171 @@
+use arrow::{
+    datatypes::SchemaRef,
+    error::ArrowError,
+    record_batch::{RecordBatch, RecordBatchReader},
+};
+use odbc_api::{handles::StatementImpl, Connection, CursorImpl};
+
+use crate::{Error, OdbcReader, OdbcReaderBuilder};
+
+/// Fetches the result of a query page by page, issuing one query per page. The query is extended
+/// with `OFFSET ... ROWS FETCH NEXT ... ROWS ONLY`, so the database only has to process
+/// `batch_size` rows per round trip. In contrast an [`OdbcReader`] executes the query once and
+/// fetches the entire result set through a single cursor.
+///
+/// Each page is a separate query, so the result is only consistent if the base query has a
+/// deterministic order (i.e. ends with an `ORDER BY` clause covering a unique key) and the data
+/// is not modified while reading. SQL Server insists on an `ORDER BY` clause for `OFFSET` anyway.
+/// The syntax is supported by e.g. SQL Server, PostgreSQL and Oracle, but not by MySQL.
+///
+/// # Example
+///
+/// ```no_run
+/// use arrow_odbc::{
+///     arrow::datatypes::{DataType, Field, Schema},
+///     odbc_api::Connection,
+///     OdbcPaginatedReader, OdbcReaderBuilder,
+/// };
+/// use std::sync::Arc;
+///
+/// fn read_pages(connection: &Connection<'_>) -> Result<(), anyhow::Error> {
+///     let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+///     let reader = OdbcPaginatedReader::new(
+///         connection,
+///         "SELECT a FROM MyTable ORDER BY a",
+///         schema,
+///         1000,
+///         &OdbcReaderBuilder::new(),
+///     )?;
+///     for batch in reader {
+///         // ... process batch ...
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct OdbcPaginatedReader<'c> {
+    connection: &'c Connection<'c>,
+    base_sql: String,
+    schema: SchemaRef,
+    batch_size: usize,
+    reader_builder: OdbcReaderBuilder,
+    /// Number of rows skipped by the query fetching the next page.
+    offset: usize,
+    /// Reader of the current page. `None` if the next page has yet to be queried.
+    page: Option<OdbcReader<CursorImpl<StatementImpl<'c>>>>,
+    /// Number of rows read from the current page so far.
+    rows_in_page: usize,
+    /// `true` once a page has been shorter than `batch_size` or an error occurred.
+    exhausted: bool,
+}
+
+impl<'c> OdbcPaginatedReader<'c> {
+    /// # Parameters
+    ///
+    /// * `connection`: Used to execute the query for each page.
+    /// * `base_sql`: Query without any `OFFSET` or `FETCH` clause. Should end with an `ORDER BY`
+    ///   clause, so the pages do not overlap.
+    /// * `schema`: Arrow schema of the result. Passed to each reader, so the query is not
+    ///   described again for every page.
+    /// * `batch_size`: Maximum number of rows per page. Zero is rejected with
+    ///   [`Error::ZeroPageSize`], as no page could ever make progress through the result set.
+    /// * `options`: Configures the reader created for each page. Its schema and maximum number of
+    ///   rows per batch are overwritten by `schema` and `batch_size`.
+    pub fn new(
+        connection: &'c Connection<'c>,
+        base_sql: &str,
+        schema: SchemaRef,
+        batch_size: usize,
+        options: &OdbcReaderBuilder,
+    ) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(Error::ZeroPageSize);
+        }
+        let mut reader_builder = options.clone();
+        reader_builder
+            .with_schema(schema.clone())
+            .with_max_num_rows_per_batch(batch_size);
+        Ok(Self {
+            connection,
+            base_sql: base_sql.trim_end().trim_end_matches(';').to_owned(),
+            schema,
+            batch_size,
+            reader_builder,
+            offset: 0,
+            page: None,
+            rows_in_page: 0,
+            exhausted: false,
+        })
+    }
+
+    /// The query issued to fetch the page starting at `offset`.
+    fn page_sql(&self) -> String {
+        format!(
+            "{} OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+            self.base_sql, self.offset, self.batch_size
+        )
+    }
+
+    fn query_page(&mut self) -> Result<Option<OdbcReader<CursorImpl<StatementImpl<'c>>>>, Error> {
+        let cursor = self
+            .connection
+            .execute(&self.page_sql(), ())
+            .map_err(Error::UnableToExecute)?;
+        cursor
+            .map(|cursor| self.reader_builder.build(cursor))
+            .transpose()
+    }
+}
+
+impl Iterator for OdbcPaginatedReader<'_> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.exhausted {
+                return None;
+            }
+            if self.page.is_none() {
+                match self.query_page() {
+                    Ok(Some(page)) => {
+                        self.page = Some(page);
+                        self.rows_in_page = 0;
+                    }
+                    // The statement did not produce a result set. There is nothing to read.
+                    Ok(None) => {
+                        self.exhausted = true;
+                        return None;
+                    }
+                    Err(error) => {
+                        self.exhausted = true;
+                        return Some(Err(ArrowError::ExternalError(Box::new(error))));
+                    }
+                }
+            }
+            // A memory limit set in the options may split a page into several batches.
+            match self.page.as_mut().unwrap().next() {
+                Some(Ok(batch)) => {
+                    self.rows_in_page += batch.num_rows();
+                    return Some(Ok(batch));
+                }
+                Some(Err(error)) => {
+                    self.exhausted = true;
+                    return Some(Err(error));
+                }
+                None => {
+                    self.page = None;
+                    // A page shorter than the batch size must have been the last one.
+                    if self.rows_in_page < self.batch_size {
+                        self.exhausted = true;
+                    }
+                    self.offset += self.rows_in_page;
+                }
+            }
+        }
+    }
+}
+
+impl RecordBatchReader for OdbcPaginatedReader<'_> {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
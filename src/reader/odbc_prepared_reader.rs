@@ -0,0 +1,203 @@
+use arrow::{
+    array::{Array, AsArray},
+    datatypes::{
+        ArrowPrimitiveType, DataType as ArrowDataType, Float32Type, Float64Type, Int16Type,
+        Int32Type, Int64Type, Int8Type, UInt8Type,
+    },
+    record_batch::RecordBatch,
+};
+use odbc_api::{
+    handles::{AsStatementRef, Nullability, StatementImpl, StatementRef},
+    parameter::InputParameter,
+    Bit, Connection, CursorImpl, DataType as OdbcDataType, IntoParameter, Prepared,
+};
+
+use crate::{schema::arrow_data_type_from, Error, OdbcReader, OdbcReaderBuilder};
+
+use super::statement_attribute::{set_statement_attributes, validate_statement_attributes};
+
+/// Describes a placeholder (`?`) in the SQL text of an [`OdbcPreparedReader`], as reported by the
+/// ODBC driver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterDescription {
+    /// Arrow type corresponding to the relational type of the parameter. `None` if it can not be
+    /// represented in Arrow.
+    pub arrow_type: Option<ArrowDataType>,
+    /// Relational type of the parameter, as reported by the driver.
+    pub odbc_type: OdbcDataType,
+    /// Column size of the relational type. E.g. the number of digits for decimals or the maximum
+    /// length for strings. `0` if not applicable or unknown.
+    pub precision: usize,
+    /// Number of digits to the right of the decimal point. `0` if not applicable.
+    pub scale: i16,
+    /// `false` if and only if the driver is certain the parameter must not be NULL.
+    pub nullable: bool,
+}
+
+/// Prepares a parameterized query once, and creates an [`OdbcReader`] for each execution with
+/// a different set of parameters.
+///
+/// # Example
+///
+/// ```no_run
+/// use arrow_odbc::{
+///     arrow::{array::Int32Array, datatypes::{DataType, Field, Schema}, record_batch::RecordBatch},
+///     odbc_api::Connection,
+///     OdbcPreparedReader,
+/// };
+/// use std::sync::Arc;
+///
+/// fn query_twice(connection: &Connection<'_>) -> Result<(), anyhow::Error> {
+///     let mut prepared = OdbcPreparedReader::new(connection, "SELECT ? + a FROM MyTable")?;
+///     let schema = Arc::new(Schema::new(vec![Field::new("p", DataType::Int32, false)]));
+///     for offset in [1, 2] {
+///         let params = RecordBatch::try_new(
+///             schema.clone(),
+///             vec![Arc::new(Int32Array::from(vec![offset]))],
+///         )?;
+///         let reader = prepared
+///             .execute_with_params(&params)?
+///             .expect("SELECT statement must produce a cursor");
+///         for batch in reader {
+///             // ... process batch ...
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct OdbcPreparedReader<S> {
+    prepared: Prepared<S>,
+    reader_builder: OdbcReaderBuilder,
+}
+
+impl<'c> OdbcPreparedReader<StatementImpl<'c>> {
+    /// Prepares `sql` on `connection`. Use `?` as placeholders for parameters.
+    pub fn new(connection: &'c Connection<'_>, sql: &str) -> Result<Self, Error> {
+        let prepared = connection.prepare(sql).map_err(Error::UnableToPrepare)?;
+        Ok(Self::from_prepared(prepared))
+    }
+}
+
+impl<S> OdbcPreparedReader<S>
+where
+    S: AsStatementRef,
+{
+    /// Wraps an already prepared statement.
+    pub fn from_prepared(prepared: Prepared<S>) -> Self {
+        Self {
+            prepared,
+            reader_builder: OdbcReaderBuilder::new(),
+        }
+    }
+
+    /// Configures the readers created by [`Self::execute_with_params`]. Statement attributes (see
+    /// [`OdbcReaderBuilder::with_statement_attribute`]) are set before each execution.
+    pub fn reader_builder(&mut self) -> &mut OdbcReaderBuilder {
+        &mut self.reader_builder
+    }
+
+    /// Asks the driver to describe each parameter of the prepared statement using
+    /// `SQLDescribeParam`. Not every driver supports this.
+    pub fn describe_parameters(&mut self) -> Result<Vec<ParameterDescription>, Error> {
+        let num_params = self
+            .prepared
+            .num_params()
+            .map_err(Error::UnableToDescribeParameters)?;
+        (1..=num_params)
+            .map(|parameter_number| {
+                let description = self
+                    .prepared
+                    .describe_param(parameter_number)
+                    .map_err(Error::UnableToDescribeParameters)?;
+                let odbc_type = description.data_type;
+                Ok(ParameterDescription {
+                    arrow_type: arrow_data_type_from(odbc_type, false),
+                    odbc_type,
+                    precision: odbc_type.column_size().map(|size| size.get()).unwrap_or(0),
+                    scale: odbc_type.decimal_digits(),
+                    nullable: description.nullability != Nullability::NoNulls,
+                })
+            })
+            .collect()
+    }
+
+    /// Executes the prepared statement, binding each column of `params` to the placeholder with the
+    /// same position. `params` must contain exactly one row. Returns `None` if the statement did
+    /// not produce a result set.
+    ///
+    /// The statement is not prepared again, so the database can reuse its execution plan. The
+    /// returned reader borrows the statement, and closes its cursor once dropped. Consequently the
+    /// borrow checker ensures the reader of the previous execution is gone, before the statement is
+    /// executed again.
+    pub fn execute_with_params(
+        &mut self,
+        params: &RecordBatch,
+    ) -> Result<Option<OdbcReader<CursorImpl<StatementRef<'_>>>>, Error> {
+        if params.num_rows() != 1 {
+            return Err(Error::ParameterRowCount {
+                num_rows: params.num_rows(),
+            });
+        }
+        let params = params
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(index, array)| parameter_from(index, array))
+            .collect::<Result<Vec<_>, _>>()?;
+        let attributes = self.reader_builder.statement_attributes();
+        validate_statement_attributes(attributes)?;
+        set_statement_attributes(&mut self.prepared, attributes)?;
+        let cursor = self
+            .prepared
+            .execute(&params[..])
+            .map_err(Error::UnableToExecute)?;
+        cursor
+            .map(|cursor| self.reader_builder.build_with_attributes_set(cursor))
+            .transpose()
+    }
+}
+
+/// Copies the first value of `array` into a parameter which can be bound to a statement.
+fn parameter_from(index: usize, array: &dyn Array) -> Result<Box<dyn InputParameter>, Error> {
+    fn primitive<T: ArrowPrimitiveType>(array: &dyn Array) -> Option<T::Native> {
+        let array = array.as_primitive::<T>();
+        array.is_valid(0).then(|| array.value(0))
+    }
+
+    let parameter: Box<dyn InputParameter> = match array.data_type() {
+        ArrowDataType::Boolean => {
+            let array = array.as_boolean();
+            let value = array.is_valid(0).then(|| Bit::from_bool(array.value(0)));
+            Box::new(value.into_parameter())
+        }
+        ArrowDataType::Int8 => Box::new(primitive::<Int8Type>(array).into_parameter()),
+        ArrowDataType::Int16 => Box::new(primitive::<Int16Type>(array).into_parameter()),
+        ArrowDataType::Int32 => Box::new(primitive::<Int32Type>(array).into_parameter()),
+        ArrowDataType::Int64 => Box::new(primitive::<Int64Type>(array).into_parameter()),
+        ArrowDataType::UInt8 => Box::new(primitive::<UInt8Type>(array).into_parameter()),
+        ArrowDataType::Float32 => Box::new(primitive::<Float32Type>(array).into_parameter()),
+        ArrowDataType::Float64 => Box::new(primitive::<Float64Type>(array).into_parameter()),
+        ArrowDataType::Utf8 => {
+            let array = array.as_string::<i32>();
+            let value = array.is_valid(0).then(|| array.value(0).to_owned());
+            Box::new(value.into_parameter())
+        }
+        ArrowDataType::LargeUtf8 => {
+            let array = array.as_string::<i64>();
+            let value = array.is_valid(0).then(|| array.value(0).to_owned());
+            Box::new(value.into_parameter())
+        }
+        ArrowDataType::Binary => {
+            let array = array.as_binary::<i32>();
+            let value = array.is_valid(0).then(|| array.value(0).to_vec());
+            Box::new(value.into_parameter())
+        }
+        other => {
+            return Err(Error::UnsupportedParameterType {
+                index,
+                data_type: other.clone(),
+            })
+        }
+    };
+    Ok(parameter)
+}
@@ -1,15 +1,39 @@
-use std::cmp::min;
+use std::{
+    cmp::min,
+    collections::HashMap,
+    fmt::{self, Display},
+    io::Write,
+    sync::Arc,
+};
 
 use arrow::{
     datatypes::SchemaRef,
     error::ArrowError,
+    ipc::writer::StreamWriter,
     record_batch::{RecordBatch, RecordBatchReader},
 };
-use odbc_api::{buffers::ColumnarAnyBuffer, BlockCursor, Cursor};
+use odbc_api::{
+    buffers::ColumnarAnyBuffer,
+    handles::{AsStatementRef, Statement},
+    BlockCursor, Cursor, DataType as OdbcDataType, ResultSetMetadata,
+};
 
-use crate::{BufferAllocationOptions, ConcurrentOdbcReader, Error};
+use crate::{
+    sanitize_connection_string,
+    schema::{extension_type_from, num_result_cols},
+    BufferAllocationOptions, ChunkedArray, ColumnStatistics, ColumnStrategyRegistry, ColumnWarning,
+    ConcurrentOdbcReader, Error, FloatCoercion, IntegerCoercion, MissingColumnBehavior, NullHook,
+    OdbcReaderMetrics, PostFetchTransform, ScalarValue, SpannerCompatibility,
+    StatementAttributeValue, SubPrecisionHandling, TextOutputMode,
+};
 
-use super::to_record_batch::ToRecordBatch;
+use super::{
+    column_metadata::{col_type_name, columns_metadata, BoundStatement, OdbcColumnMetadata},
+    lazy_schema::{is_undetermined, refine_probed_columns},
+    row_status::RowStatusArray,
+    statement_attribute::{set_statement_attributes, validate_statement_attributes},
+    to_record_batch::{ConversionOptions, ToRecordBatch},
+};
 
 /// Arrow ODBC reader. Implements the [`arrow::record_batch::RecordBatchReader`] trait so it can be
 /// used to fill Arrow arrays from an ODBC data source.
@@ -19,6 +43,10 @@ use super::to_record_batch::ToRecordBatch;
 /// statement handles (recommened then using one shot queries, to have an easier life with the
 /// borrow checker).
 ///
+/// It is fine to drop the reader before the result set is consumed. Dropping it unbinds the
+/// buffers and closes the cursor (`SQLCloseCursor`), so a borrowed statement can be executed
+/// again right away. Use [`Self::into_cursor`] instead, if you want to keep the cursor open.
+///
 /// # Example
 ///
 /// ```no_run
@@ -61,14 +89,38 @@ use super::to_record_batch::ToRecordBatch;
 /// }
 /// ```
 pub struct OdbcReader<C: Cursor> {
+    /// Bound to the statement of the cursor, if the user asked us to check the status of each row.
+    /// Declared before `batch_stream`, since it must be dropped before the statement.
+    row_status: Option<RowStatusArray>,
+    /// Warnings for rows flagged in the row status array, which have not been taken by the user
+    /// yet.
+    warnings: Vec<ColumnWarning>,
     /// Converts the content of ODBC buffers into Arrow record batches
     converter: ToRecordBatch,
     /// Fetches values from the ODBC datasource using columnar batches. Values are streamed batch
     /// by batch in order to avoid reallocation of the buffers used for tranistion.
     batch_stream: BlockCursor<C, ColumnarAnyBuffer>,
     /// We remember if the user decided to use fallibale allocations or not in case we need to
-    /// allocate another buffer due to a state transition towards [`ConcurrentOdbcReader`].
-    fallibale_allocations: bool,
+    /// allocate another buffer due to a state transition towards [`ConcurrentOdbcReader`]. The
+    /// other options are kept to describe the reader in log messages.
+    buffer_allocation_options: BufferAllocationOptions,
+    /// Row count reported by the driver, in case the statement did not produce a result set.
+    affected_rows: Option<u64>,
+    /// One entry per column, if the user asked for statistics to be collected.
+    column_statistics: Option<Vec<ColumnStatistics>>,
+    /// Sanitized connection string, errors fetching batches are annotated with.
+    connection_info: Option<String>,
+    /// Size of a row in the transit buffer, in order to account for the bytes received.
+    bytes_per_row: usize,
+    /// Fetches and bytes received from the driver for the current result set.
+    metrics: OdbcReaderMetrics,
+    /// Attributes of each column of the result set, as reported by the driver. Described on first
+    /// request, since most applications never ask for them.
+    columns_metadata: Option<Vec<OdbcColumnMetadata>>,
+    /// Statement of the cursor owned by `batch_stream`, in order to describe its columns.
+    statement: BoundStatement<C>,
+    /// Rows fetched while inferring the schema lazily, which have not been returned yet.
+    probed_batch: Option<RecordBatch>,
 }
 
 impl<C: Cursor> OdbcReader<C> {
@@ -133,10 +185,14 @@ impl<C: Cursor> OdbcReader<C> {
     where
         C: Send + 'static,
     {
+        // The concurrent reader fetches into its own buffers, which do not come with a row status
+        // array.
+        drop(self.row_status);
         ConcurrentOdbcReader::from_block_cursor(
             self.batch_stream,
             self.converter,
-            self.fallibale_allocations,
+            self.buffer_allocation_options.fallibale_allocations,
+            self.probed_batch,
         )
     }
 
@@ -145,16 +201,141 @@ impl<C: Cursor> OdbcReader<C> {
     /// One application of this is to process more than one result set in case you executed a stored
     /// procedure.
     pub fn into_cursor(self) -> Result<C, odbc_api::Error> {
+        drop(self.row_status);
         let (cursor, _buffer) = self.batch_stream.unbind()?;
         Ok(cursor)
     }
 
+    /// Binds the buffers of this reader to `cursor`, instead of allocating new ones by building
+    /// another reader. Useful if the same query is executed over and over again, e.g. polling a
+    /// table for changes. The columns of `cursor` must have the same relational types as the ones
+    /// of the current cursor, otherwise [`Error::IncompatibleCursor`] is returned. The current
+    /// cursor is closed, even if its result set has not been consumed yet. Column statistics and
+    /// metrics start over with the new result set.
+    pub fn rebind(self, mut cursor: C) -> Result<Self, Error> {
+        let num_rows = self.max_rows_per_batch();
+        drop(self.row_status);
+        let (mut previous, buffer) = self.batch_stream.unbind().map_err(Error::UnableToRebind)?;
+        let expected = column_types(&mut previous, Error::UnableToRebind)?;
+        let actual = column_types(&mut cursor, Error::UnableToRebind)?;
+        if expected != actual {
+            return Err(Error::IncompatibleCursor { expected, actual });
+        }
+        let affected_rows = if actual.is_empty() {
+            row_count(&mut cursor).map_err(Error::UnableToRetrieveRowCount)?
+        } else {
+            None
+        };
+        let statement = bound_statement(&mut cursor);
+        let row_status = if self.buffer_allocation_options.check_row_status {
+            Some(RowStatusArray::bind(&mut cursor, num_rows).map_err(Error::UnableToRebind)?)
+        } else {
+            None
+        };
+        let batch_stream = cursor.bind_buffer(buffer).map_err(Error::UnableToRebind)?;
+        let column_statistics = self
+            .column_statistics
+            .map(|statistics| vec![ColumnStatistics::default(); statistics.len()]);
+        Ok(OdbcReader {
+            row_status,
+            warnings: Vec::new(),
+            converter: self.converter,
+            batch_stream,
+            buffer_allocation_options: self.buffer_allocation_options,
+            affected_rows,
+            column_statistics,
+            connection_info: self.connection_info,
+            bytes_per_row: self.bytes_per_row,
+            metrics: OdbcReaderMetrics::default(),
+            columns_metadata: None,
+            statement,
+            probed_batch: None,
+        })
+    }
+
     /// Size of the internal preallocated buffer bound to the cursor and filled by your ODBC driver
     /// in rows. Each record batch will at most have this many rows. Only the last one may have
     /// less.
     pub fn max_rows_per_batch(&self) -> usize {
         self.batch_stream.row_array_size()
     }
+
+    /// Consumes the reader and writes all its record batches to `writer` in the Arrow IPC streaming
+    /// format. The stream starts with the schema, followed by one message for each batch and ends
+    /// with the end of stream marker. This allows for sending the result set over the network or
+    /// into another process, without buffering it in memory. Returns the total number of rows
+    /// written.
+    pub fn into_ipc_stream<W: Write>(self, writer: W) -> Result<u64, Error> {
+        let schema = self.schema();
+        let mut stream = StreamWriter::try_new(writer, &schema).map_err(Error::IpcStream)?;
+        let mut num_rows = 0;
+        for batch in self {
+            let batch = batch.map_err(Error::IpcStream)?;
+            num_rows += batch.num_rows() as u64;
+            stream.write(&batch).map_err(Error::IpcStream)?;
+        }
+        stream.finish().map_err(Error::IpcStream)?;
+        Ok(num_rows)
+    }
+
+    /// Consumes the reader and collects the arrays of all its batches, grouped by column rather
+    /// than by batch. Returns one [`ChunkedArray`] for each field of [`Self::schema`]. Compute
+    /// engines working on whole columns can process the chunks one after another, rather than
+    /// concatenating the batches first. Each chunk is still allocated while fetching its batch.
+    pub fn into_chunked_arrays(self) -> Result<Vec<ChunkedArray>, Error> {
+        let mut columns: Vec<_> = self
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| ChunkedArray::new(field.clone()))
+            .collect();
+        for batch in self {
+            let batch = batch.map_err(Error::FetchBatch)?;
+            for (column, array) in columns.iter_mut().zip(batch.columns()) {
+                column.push(array.clone()).map_err(Error::FetchBatch)?;
+            }
+        }
+        Ok(columns)
+    }
+
+    /// Number of rows affected by the statement which created the cursor, as reported by
+    /// `SQLRowCount`. Only statements without a result set (e.g. `INSERT`, `UPDATE` or `DELETE`
+    /// executed as part of a stored procedure) affect rows, so this is always `None` for queries
+    /// returning columns. Also `None` if the driver does not know the number of affected rows.
+    pub fn affected_rows(&self) -> Option<u64> {
+        self.affected_rows
+    }
+
+    /// Statistics of each column, accumulated over all batches fetched so far. `None` unless
+    /// enabled with [`OdbcReaderBuilder::with_column_statistics`]. Indices match the fields of
+    /// [`Self::schema`].
+    pub fn column_statistics(&self) -> Option<&[ColumnStatistics]> {
+        self.column_statistics.as_deref()
+    }
+
+    /// Warnings for rows the driver flagged in the row status array, since the last call to this
+    /// method. Always empty unless enabled with [`OdbcReaderBuilder::check_row_status`].
+    pub fn take_warnings(&mut self) -> Vec<ColumnWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Attributes of each column of the result set, like precision, scale, nullability or the name
+    /// of the base table, as reported by the driver. The columns are described on the first call,
+    /// which costs several round trips per column, and the result is kept for subsequent calls.
+    /// Indices match the columns of the result set, which are not necessarily the fields of
+    /// [`Self::schema`], if the schema has been specified by the user.
+    pub fn columns_metadata(&mut self) -> Result<&[OdbcColumnMetadata], Error> {
+        if self.columns_metadata.is_none() {
+            self.columns_metadata = Some(columns_metadata(&mut self.statement)?);
+        }
+        Ok(self.columns_metadata.as_deref().unwrap())
+    }
+
+    /// Number of fetches and bytes received from the ODBC driver so far. Useful for tuning the
+    /// batch size, since every fetch is a round trip to the data source.
+    pub fn metrics(&self) -> OdbcReaderMetrics {
+        self.metrics
+    }
 }
 
 impl<C> Iterator for OdbcReader<C>
@@ -164,25 +345,132 @@ where
     type Item = Result<RecordBatch, ArrowError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_batch(false)
+            .map(|result| result.map(|(record_batch, _)| record_batch))
+    }
+}
+
+impl<C> OdbcReader<C>
+where
+    C: Cursor,
+{
+    /// Fetches the next batch. If `batch_statistics` is set, or the user asked for column
+    /// statistics, the statistics of each array are computed right after it has been filled, and
+    /// returned alongside the batch. Otherwise the returned statistics are empty.
+    pub(crate) fn next_batch(
+        &mut self,
+        batch_statistics: bool,
+    ) -> Option<Result<(RecordBatch, Vec<ColumnStatistics>), ArrowError>> {
+        let collect = batch_statistics || self.column_statistics.is_some();
+        let of_arrays = |record_batch: &RecordBatch| {
+            record_batch
+                .columns()
+                .iter()
+                .map(|array| ColumnStatistics::of_array(array.as_ref()))
+                .collect()
+        };
+        if let Some(record_batch) = self.probed_batch.take() {
+            let statistics = if collect {
+                of_arrays(&record_batch)
+            } else {
+                Vec::new()
+            };
+            self.update_column_statistics(&statistics);
+            return Some(Ok((record_batch, statistics)));
+        }
         match self.batch_stream.fetch_with_truncation_check(true) {
             // We successfully fetched a batch from the database. Try to copy it into a record batch
             // and forward errors if any.
             Ok(Some(batch)) => {
+                self.metrics
+                    .record_fetch(batch.num_rows(), self.bytes_per_row);
+                // Checking the row status may replace values of failed rows with NULLs, so in that
+                // case the statistics are computed from the checked arrays.
+                let during_fill = collect && self.row_status.is_none();
+                let mut statistics = Vec::new();
                 let result_record_batch = self
                     .converter
-                    .buffer_to_record_batch(batch)
+                    .buffer_to_record_batch(batch, |array| {
+                        if during_fill {
+                            statistics.push(ColumnStatistics::of_array(array.as_ref()));
+                        }
+                    })
                     .map_err(|mapping_error| ArrowError::ExternalError(Box::new(mapping_error)));
-                Some(result_record_batch)
+                let result_record_batch = match (result_record_batch, &mut self.row_status) {
+                    (Ok(record_batch), Some(row_status)) => {
+                        row_status.check(record_batch, &mut self.warnings)
+                    }
+                    (result, _) => result,
+                };
+                let result = result_record_batch.map(|record_batch| {
+                    if collect && !during_fill {
+                        statistics = of_arrays(&record_batch);
+                    }
+                    (record_batch, statistics)
+                });
+                if let Ok((_, statistics)) = &result {
+                    self.update_column_statistics(statistics);
+                }
+                Some(result)
             }
             // We ran out of batches in the result set. End the iterator.
-            Ok(None) => None,
+            Ok(None) => {
+                self.metrics.record_fetch(0, self.bytes_per_row);
+                None
+            }
             // We had an error fetching the next batch from the database, let's report it as an
             // external error.
-            Err(odbc_error) => Some(Err(odbc_to_arrow_error(odbc_error))),
+            Err(odbc_error) => Some(Err(self.fetch_error(odbc_error))),
+        }
+    }
+
+    /// Accumulates the statistics of a single batch into the column statistics, if enabled.
+    fn update_column_statistics(&mut self, batch_statistics: &[ColumnStatistics]) {
+        if let Some(statistics) = &mut self.column_statistics {
+            for (statistics, batch_statistics) in statistics.iter_mut().zip(batch_statistics) {
+                statistics.combine(batch_statistics);
+            }
+        }
+    }
+
+    fn fetch_error(&self, odbc_error: odbc_api::Error) -> ArrowError {
+        match &self.connection_info {
+            Some(sanitized_connection_info) => {
+                ArrowError::ExternalError(Box::new(Error::WithConnectionInfo {
+                    sanitized_connection_info: sanitized_connection_info.clone(),
+                    source: Box::new(odbc_error),
+                }))
+            }
+            None => odbc_to_arrow_error(odbc_error),
         }
     }
 }
 
+/// Single line summary of the configuration, e.g. for log messages. Contains no information about
+/// the connection.
+impl<C> Display for OdbcReader<C>
+where
+    C: Cursor,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let BufferAllocationOptions {
+            max_text_size,
+            max_binary_size,
+            fallibale_allocations,
+            ..
+        } = self.buffer_allocation_options;
+        write!(
+            f,
+            "OdbcReader(schema={} fields, batch_size={}, options={{max_text_size={max_text_size:?}, \
+            max_binary_size={max_binary_size:?}, fallibale_allocations={fallibale_allocations}, \
+            column_statistics={}}})",
+            self.converter.schema().fields().len(),
+            self.max_rows_per_batch(),
+            self.column_statistics.is_some(),
+        )
+    }
+}
+
 impl<C> RecordBatchReader for OdbcReader<C>
 where
     C: Cursor,
@@ -206,10 +494,44 @@ pub struct OdbcReaderBuilder {
     max_bytes_per_batch: usize,
     schema: Option<SchemaRef>,
     max_text_size: Option<usize>,
+    max_xml_size: Option<usize>,
     max_binary_size: Option<usize>,
     map_value_errors_to_null: bool,
     fallibale_allocations: bool,
+    allow_signed_unsigned_coercion: bool,
+    fallback_tinyint_to_smallint: bool,
+    opaque_binary_fallback: bool,
+    element_size_cap: Option<usize>,
+    trim_char_padding: bool,
+    format_guid_as_string: bool,
+    check_row_status: bool,
+    sub_precision_handling: SubPrecisionHandling,
+    max_column_count: Option<usize>,
     trim_fixed_sized_character_strings: bool,
+    timestamp_timezone: Option<Arc<str>>,
+    match_columns_by_name: bool,
+    /// Maps zero based column indices to the value used to replace NULLs in that column.
+    null_replacements: HashMap<usize, ScalarValue>,
+    /// Maps zero based column indices to the values of a pre-populated dictionary.
+    dictionary_values: HashMap<usize, Vec<i32>>,
+    integer_coercion: IntegerCoercion,
+    float_coercion: FloatCoercion,
+    null_hook: Option<NullHook>,
+    text_output_mode: TextOutputMode,
+    /// Maps zero based column indices to the text output mode overriding `text_output_mode`.
+    column_text_output_modes: HashMap<usize, TextOutputMode>,
+    text_as_binary_for_non_utf8: bool,
+    column_statistics: bool,
+    missing_column_behavior: MissingColumnBehavior,
+    strategy_registry: ColumnStrategyRegistry,
+    post_fetch_transforms: HashMap<usize, PostFetchTransform>,
+    connection_info: Option<String>,
+    statement_attributes: Vec<(u32, StatementAttributeValue)>,
+    /// `Some` if the schema is inferred from the values of the first rows, see
+    /// [`OdbcReaderBuilder::with_lazy_schema_inference`].
+    lazy_schema_probe_rows: Option<usize>,
+    spanner_compatibility: Option<SpannerCompatibility>,
+    rich_metadata: bool,
 }
 
 impl OdbcReaderBuilder {
@@ -228,10 +550,39 @@ impl OdbcReaderBuilder {
             max_bytes_per_batch: DEFAULT_MAX_BYTES_PER_BATCH,
             schema: None,
             max_text_size: None,
+            max_xml_size: None,
             max_binary_size: None,
             fallibale_allocations: false,
+            allow_signed_unsigned_coercion: false,
+            fallback_tinyint_to_smallint: false,
+            opaque_binary_fallback: false,
+            element_size_cap: None,
+            trim_char_padding: true,
+            format_guid_as_string: false,
+            check_row_status: false,
+            sub_precision_handling: SubPrecisionHandling::Truncate,
+            max_column_count: None,
             map_value_errors_to_null: false,
             trim_fixed_sized_character_strings: false,
+            timestamp_timezone: None,
+            match_columns_by_name: false,
+            null_replacements: HashMap::new(),
+            dictionary_values: HashMap::new(),
+            integer_coercion: IntegerCoercion::PreserveOriginal,
+            float_coercion: FloatCoercion::PreserveOriginal,
+            null_hook: None,
+            text_output_mode: TextOutputMode::Utf8,
+            column_text_output_modes: HashMap::new(),
+            text_as_binary_for_non_utf8: false,
+            column_statistics: false,
+            missing_column_behavior: MissingColumnBehavior::Error,
+            strategy_registry: ColumnStrategyRegistry::new(),
+            post_fetch_transforms: HashMap::new(),
+            connection_info: None,
+            statement_attributes: Vec::new(),
+            lazy_schema_probe_rows: None,
+            spanner_compatibility: None,
+            rich_metadata: false,
         }
     }
 
@@ -288,6 +639,16 @@ impl OdbcReaderBuilder {
         self
     }
 
+    /// Maximum length in characters of XML documents, e.g. `XML` columns in SQL Server or DB2.
+    /// Drivers report no maximum length for XML columns, so the buffers bound to them are sized by
+    /// this limit. Documents exceeding it cause an error rather than being truncated. If this
+    /// method is not called the limit set by [`Self::with_max_text_size`] is used, or `65536` if
+    /// that is not set either.
+    pub fn with_max_xml_size(&mut self, max_xml_size: usize) -> &mut Self {
+        self.max_xml_size = Some(max_xml_size);
+        self
+    }
+
     /// An upper limit for the size of buffers bound to variadic binary columns of the data source.
     /// This limit does not (directly) apply to the size of the created arrow buffers, but rather
     /// applies to the buffers used for the data in transit. Use this option if you have e.g.
@@ -303,6 +664,27 @@ impl OdbcReaderBuilder {
         self
     }
 
+    /// An upper limit in bytes for a single element of any column buffer, text and binary alike. In
+    /// contrast to [`Self::with_max_text_size`] and [`Self::with_max_binary_size`] this is a
+    /// single safety valve for all columns. Elements of text and variadic binary columns are
+    /// clamped to the cap. Building the reader fails with
+    /// [`crate::ColumnFailure::ElementSizeExceedsCap`] for `FixedSizeBinary` fields with larger
+    /// elements. If this method is not called, element sizes are not capped.
+    pub fn with_element_size_cap(&mut self, element_size_cap: usize) -> &mut Self {
+        self.element_size_cap = Some(element_size_cap);
+        self
+    }
+
+    /// An upper limit for the number of columns in the result set. Building the reader fails with
+    /// [`crate::Error::TooManyColumns`] if the cursor has more columns than this. Acts as a safety
+    /// brake for applications executing user supplied SQL, which want to protect themselves against
+    /// allocating huge buffers for wildcard queries. If this method is not called the number of
+    /// columns is not limited.
+    pub fn with_max_column_count(&mut self, max_column_count: usize) -> &mut Self {
+        self.max_column_count = Some(max_column_count);
+        self
+    }
+
     /// Set to `true` in order to trigger an [`crate::ColumnFailure::TooLarge`] instead of a panic
     /// in case the buffers can not be allocated due to their size. This might have a performance
     /// cost for constructing the reader. `false` by default.
@@ -311,6 +693,41 @@ impl OdbcReaderBuilder {
         self
     }
 
+    /// `UInt32` and `UInt64` fields are fetched using signed integer buffers. By default negative
+    /// values fetched into such a field cause a [`crate::MappingError::NegativeUnsigned`]. Set to
+    /// `true` in order to reinterpret the bits of the signed value as an unsigned integer instead.
+    /// Use this for drivers which wrap large unsigned values around to negative numbers, if you
+    /// know the values in your database to be positive. Default is `false`.
+    pub fn allow_signed_unsigned_coercion(
+        &mut self,
+        allow_signed_unsigned_coercion: bool,
+    ) -> &mut Self {
+        self.allow_signed_unsigned_coercion = allow_signed_unsigned_coercion;
+        self
+    }
+
+    /// Some ODBC drivers, mostly for older databases, do not support fetching 8 Bit integers and
+    /// return zeroes or fail. Set to `true` in order to fetch `Int8` fields using 16 Bit integer
+    /// buffers instead. The values are narrowed to 8 Bit while filling the Arrow arrays. Default is
+    /// `false`.
+    pub fn fallback_tinyint_to_smallint(
+        &mut self,
+        fallback_tinyint_to_smallint: bool,
+    ) -> &mut Self {
+        self.fallback_tinyint_to_smallint = fallback_tinyint_to_smallint;
+        self
+    }
+
+    /// Escape hatch for exotic types. Set to `true` in order to fetch fields, whose Arrow type is
+    /// not supported, as raw bytes into `FixedSizeBinary` arrays, rather than failing to build the
+    /// reader. This works for columns with a fixed size representation in ODBC, e.g. a 64 Bit
+    /// integer column yields `FixedSizeBinary(8)`. The schema of the reader reflects the overridden
+    /// type. Default is `false`.
+    pub fn opaque_binary_fallback(&mut self, opaque_binary_fallback: bool) -> &mut Self {
+        self.opaque_binary_fallback = opaque_binary_fallback;
+        self
+    }
+
     /// Set to `true` in order to map a value in the database which can not be successfully
     /// converted into its target type to NULL, rather than emitting an external Arrow Error.
     /// E.g. currently mapping errors can happen if a datetime value is not in the rang
@@ -321,7 +738,8 @@ impl OdbcReaderBuilder {
     }
 
     /// If set to `true` text in fixed sized character columns like e.g. CHAR are trimmed of
-    /// whitespaces before converted into Arrow UTF-8 arrays. Default is `false`.
+    /// whitespaces before converted into Arrow UTF-8 arrays. This removes leading whitespace as
+    /// well and takes precedence over [`Self::trim_char_padding`]. Default is `false`.
     pub fn trim_fixed_sized_characters(
         &mut self,
         fixed_sized_character_strings_are_trimmed: bool,
@@ -330,6 +748,311 @@ impl OdbcReaderBuilder {
         self
     }
 
+    /// Databases pad text in fixed sized character columns like e.g. `CHAR(10)` with spaces up to
+    /// the length of the column. By default these trailing spaces are removed. Set to `false` in
+    /// order to keep the padding. Variadic character columns (e.g. `VARCHAR`) are never trimmed.
+    pub fn trim_char_padding(&mut self, trim_char_padding: bool) -> &mut Self {
+        self.trim_char_padding = trim_char_padding;
+        self
+    }
+
+    /// `SQL_GUID` columns, like `UNIQUEIDENTIFIER` in Microsoft SQL Server, are fetched as 16 bytes
+    /// of binary, if the field in the schema is `FixedSizeBinary(16)`. The bytes are reordered from
+    /// the mixed-endian layout of a Windows `GUID` into the standard byte order of a UUID. Set this
+    /// to `true` in order to fetch GUIDs into `Utf8` fields (the type inferred for them) as lower
+    /// case hyphenated UUIDs, e.g. `550e8400-e29b-41d4-a716-446655440000`. Otherwise these are
+    /// formatted by the driver. Default is `false`.
+    pub fn format_guid_as_string(&mut self, format_guid_as_string: bool) -> &mut Self {
+        self.format_guid_as_string = format_guid_as_string;
+        self
+    }
+
+    /// Some data sources fail to fetch individual rows, e.g. due to values which can not be
+    /// converted, while the other rows of the row set are fine. By default such rows end up in the
+    /// record batch as if they had been fetched successfully, with whatever values are in the
+    /// buffers. Set this to `true` in order to bind a row status array and inspect it after each
+    /// fetch. Values of rows the driver reports as failed are replaced with nulls, so the fields of
+    /// the schema should be nullable. Failed rows, as well as rows with additional diagnostics, are
+    /// logged and reported by [`OdbcReader::take_warnings`]. Default is `false`.
+    pub fn check_row_status(&mut self, check_row_status: bool) -> &mut Self {
+        self.check_row_status = check_row_status;
+        self
+    }
+
+    /// ODBC transmits the fraction of a timestamp in nanoseconds. Fields of type
+    /// `Timestamp(Nanosecond, _)` hold it in full. For coarser units the digits finer than the unit
+    /// are truncated by default, e.g. `12:00:00.1239` becomes `12:00:00.123` for
+    /// `Timestamp(Millisecond, _)`. Use this method to round such values instead, to log a warning,
+    /// or to fail with [`crate::MappingError::SubPrecisionLoss`]. Errors are mapped to null if
+    /// [`Self::value_errors_as_null`] is set. Default is [`SubPrecisionHandling::Truncate`].
+    pub fn with_sub_precision_handling(
+        &mut self,
+        sub_precision_handling: SubPrecisionHandling,
+    ) -> &mut Self {
+        self.sub_precision_handling = sub_precision_handling;
+        self
+    }
+
+    /// Timestamps in ODBC do not carry any timezone information. By default all timestamp columns
+    /// in the inferred schema are therefore of type `Timestamp(_, None)`, i.e. timezone naive.
+    /// Many databases (e.g. SQLite or MySQL without explicit configuration) store local time in
+    /// such columns. Use this method to interpret their values as local time of `timezone` (e.g.
+    /// `"+02:00"` or `"Europe/Berlin"`). Every timestamp field of the resulting schema is annotated
+    /// with `timezone`, and the values are converted to UTC, as Arrow demands for timestamps with a
+    /// timezone. This also overrides the timezone of timestamp fields in a schema passed to
+    /// [`Self::with_schema`]. Values of columns carrying their own offset (e.g. `DATETIMEOFFSET`)
+    /// are normalized using that offset instead.
+    ///
+    /// Timezone names require the `chrono-tz` feature of Arrow, otherwise only fixed offsets are
+    /// supported. `None` restores the default.
+    pub fn with_timestamp_timezone(&mut self, timezone: Option<Arc<str>>) -> &mut Self {
+        self.timestamp_timezone = timezone;
+        self
+    }
+
+    /// By default the n-th field of the Arrow schema is filled with the n-th column of the result
+    /// set. If set to `true` each field is instead filled from the result set column with the same
+    /// name (compared case insensitive). This allows the schema passed to [`Self::with_schema`] to
+    /// list its fields in a different order than the query, or to only contain a subset of the
+    /// columns. Building the reader fails if no, or more than one column matches a field. Default
+    /// is `false`.
+    pub fn match_columns_by_name(&mut self, match_columns_by_name: bool) -> &mut Self {
+        self.match_columns_by_name = match_columns_by_name;
+        self
+    }
+
+    /// Replace NULLs fetched for the column at `column_index` (zero based) with `value`. Use this if
+    /// you want a field to be non nullable in the Arrow schema, yet the column in the data source
+    /// may contain NULLs. Without a replacement the ODBC driver would report an error fetching a
+    /// NULL into a non nullable column. The type of `value` must match the Arrow type of the
+    /// field, otherwise building the reader fails with
+    /// [`crate::ColumnFailure::InvalidNullReplacement`]. Supported are `Boolean`, `Utf8`, the
+    /// signed integer types, `UInt8`, `Float32` and `Float64`.
+    pub fn with_null_replacement(&mut self, column_index: usize, value: ScalarValue) -> &mut Self {
+        self.null_replacements.insert(column_index, value);
+        self
+    }
+
+    /// Fields of type `Dictionary(Int8, Int32)` or `Dictionary(UInt8, Int32)` are filled with
+    /// dictionary arrays, which are useful for small integer codes with few distinct values. By
+    /// default each batch gets its own dictionary, holding the distinct values in the order of
+    /// their appearance. Use this method to specify the dictionary for the column at
+    /// `column_index` (zero based) instead, so the keys are the same across batches. Fetching a
+    /// value which is not part of `values` fails with
+    /// [`crate::MappingError::ValueNotInDictionary`]. Ignored for fields of other types.
+    pub fn with_dictionary_values(&mut self, column_index: usize, values: Vec<i32>) -> &mut Self {
+        self.dictionary_values.insert(column_index, values);
+        self
+    }
+
+    /// Controls which Arrow types are inferred for integer columns. With
+    /// [`IntegerCoercion::AllToInt64`] every integer column (e.g. `TINYINT`, `SMALLINT`, `INTEGER`
+    /// or `BIGINT`) is fetched into an `Int64` array, including unsigned ones. Only unsigned 64 Bit
+    /// columns are still fetched into `UInt64` arrays, since their values may not fit into `Int64`.
+    /// This keeps the schema stable if the exact widths of the columns are not important to your
+    /// application. Only applies to inferred
+    /// schemas, a schema specified via [`Self::with_schema`] is always used as is. Default is
+    /// [`IntegerCoercion::PreserveOriginal`].
+    pub fn with_integer_coercion(&mut self, integer_coercion: IntegerCoercion) -> &mut Self {
+        self.integer_coercion = integer_coercion;
+        self
+    }
+
+    /// Controls which Arrow types are inferred for floating point columns. With
+    /// [`FloatCoercion::AllToFloat64`] columns which would otherwise be fetched as `Float32` (e.g.
+    /// `REAL`) are fetched into `Float64` arrays instead. The conversion is widening, so no
+    /// precision is lost. Like integer coercion this only applies to inferred schemas. Default is
+    /// [`FloatCoercion::PreserveOriginal`].
+    pub fn with_float_coercion(&mut self, float_coercion: FloatCoercion) -> &mut Self {
+        self.float_coercion = float_coercion;
+        self
+    }
+
+    /// Controls which Arrow type is inferred for text columns. [`TextOutputMode::Utf8View`] fetches
+    /// text into `StringViewArray`s, which inline short strings and avoid copying them into a
+    /// separate data buffer. [`TextOutputMode::LargeUtf8`] allows for more than 2 GiB of text in a
+    /// single batch. [`TextOutputMode::RawBytes`] fetches text as `Binary`, without validating it
+    /// to be UTF-8. [`TextOutputMode::Dictionary`] fetches text as `Dictionary(Int32, Utf8)`. Only
+    /// applies to inferred schemas, in a schema specified via [`Self::with_schema`] the type of
+    /// each field can be chosen directly. Default is
+    /// [`TextOutputMode::Utf8`].
+    pub fn with_text_output_mode(&mut self, text_output_mode: TextOutputMode) -> &mut Self {
+        self.text_output_mode = text_output_mode;
+        self
+    }
+
+    /// Overrides the mode set with [`Self::with_text_output_mode`] for the column at
+    /// `column_index` (zero based). Use [`TextOutputMode::RawBytes`] to fetch single columns of
+    /// a data source in a legacy encoding as `Binary`. Unlike the other modes, `RawBytes` also
+    /// applies to `Binary` fields of a schema specified with [`Self::with_schema`], if the column is
+    /// not binary itself. Replaces previous modes for the same column.
+    pub fn with_text_output_mode_for_column(
+        &mut self,
+        column_index: usize,
+        text_output_mode: TextOutputMode,
+    ) -> &mut Self {
+        self.column_text_output_modes
+            .insert(column_index, text_output_mode);
+        self
+    }
+
+    /// If `true` all text columns are fetched as `Binary` holding the bytes as transmitted by the
+    /// driver, as if [`TextOutputMode::RawBytes`] has been set for each of them. Arrow requires
+    /// `Utf8` arrays to be valid UTF-8, so this is the escape hatch for data sources using other
+    /// encodings, like Latin-1 or Shift-JIS. Transcoding the bytes is left to the application.
+    /// Modes set with [`Self::with_text_output_mode_for_column`] take precedence. Default is
+    /// `false`.
+    pub fn text_as_binary_for_non_utf8(&mut self, text_as_binary_for_non_utf8: bool) -> &mut Self {
+        self.text_as_binary_for_non_utf8 = text_as_binary_for_non_utf8;
+        self
+    }
+
+    /// Registers a callback which is invoked for every NULL fetched from the data source, with the
+    /// zero based index of the column and the index of the row within the current batch. Useful to
+    /// track data quality metrics without a separate pass over the Arrow arrays. Without a hook
+    /// (the default) no additional work is performed. NULLs replaced due to
+    /// [`Self::with_null_replacement`] are not reported.
+    pub fn with_null_hook(&mut self, hook: NullHook) -> &mut Self {
+        self.null_hook = Some(hook);
+        self
+    }
+
+    /// If `true` the reader collects minimum, maximum and the number of NULLs for each column
+    /// while fetching batches. Query them with [`OdbcReader::column_statistics`]. This saves
+    /// consumers a second pass over the data, yet costs a pass over each batch within the reader.
+    /// Minimum and maximum are only tracked for integer, floating point, date, boolean and text
+    /// columns. Statistics are not available after converting into a [`ConcurrentOdbcReader`].
+    /// Default is `false`.
+    pub fn with_column_statistics(&mut self, column_statistics: bool) -> &mut Self {
+        self.column_statistics = column_statistics;
+        self
+    }
+
+    /// Controls what happens to fields of the schema specified with [`Self::with_schema`], which
+    /// have no matching column in the result set. With [`MissingColumnBehavior::NullPad`] these
+    /// fields are filled with NULLs, which is useful to read from sources with heterogeneous
+    /// schemas (e.g. older tables lacking newer columns) into a unified Arrow schema. Columns are
+    /// missing if they are not found by name (see [`Self::match_columns_by_name`]), or otherwise if
+    /// the field position exceeds the number of columns in the result set. Default is
+    /// [`MissingColumnBehavior::Error`].
+    pub fn with_missing_column_behavior(
+        &mut self,
+        missing_column_behavior: MissingColumnBehavior,
+    ) -> &mut Self {
+        self.missing_column_behavior = missing_column_behavior;
+        self
+    }
+
+    /// Strategies in `registry` take precedence over the built-in ones to fetch columns, if both the
+    /// relational type of the column and the Arrow type of the field match. Use this to support
+    /// driver specific types. By default no strategies are registered.
+    pub fn with_strategy_registry(&mut self, registry: ColumnStrategyRegistry) -> &mut Self {
+        self.strategy_registry = registry;
+        self
+    }
+
+    /// Applies `transform` to every array fetched for the column at `column_index` (zero based),
+    /// before it is part of a record batch emitted by the reader. Useful to run Arrow compute
+    /// kernels (e.g. lowercasing strings or rescaling values) while the array is still hot in the
+    /// cache. The transform must return an array of the same type and length, since the schema of
+    /// the reader does not change. Otherwise reading the batch fails with
+    /// [`crate::MappingError::TransformChangedShape`]. Replaces previous transforms for the same
+    /// column.
+    pub fn with_post_fetch_transform(
+        &mut self,
+        column_index: usize,
+        transform: PostFetchTransform,
+    ) -> &mut Self {
+        self.post_fetch_transforms.insert(column_index, transform);
+        self
+    }
+
+    /// Annotates errors of the reader with a description of the data source, so they can be
+    /// correlated with it in logs, if an application reads from many. `connection_string` is
+    /// sanitized with [`crate::sanitize_connection_string`] first, i.e. credentials are masked.
+    /// Errors constructing the reader, as well as errors fetching batches, are wrapped in
+    /// [`Error::WithConnectionInfo`]. By default errors are not annotated.
+    pub fn with_connection_info(&mut self, connection_string: &str) -> &mut Self {
+        self.connection_info = Some(sanitize_connection_string(connection_string));
+        self
+    }
+
+    /// Sets the statement attribute with the code `attribute` (e.g. `0` for
+    /// `SQL_ATTR_QUERY_TIMEOUT`, or driver specific ones) using `SQLSetStmtAttr`. Attributes are
+    /// applied in the order they have been added, by [`crate::OdbcPreparedReader`] before each
+    /// execution of its statement, and by [`Self::build`] to the cursor before binding the buffers.
+    /// Since the cursor passed to [`Self::build`] has been executed already, only attributes
+    /// affecting fetching take effect there, and drivers may refuse others (e.g.
+    /// `SQL_ATTR_CURSOR_TYPE`) with [`Error::UnableToSetStatementAttribute`]. Attributes used to
+    /// bind the buffers of the reader (e.g. `SQL_ATTR_ROW_ARRAY_SIZE`) are rejected with
+    /// [`Error::ReservedStatementAttribute`].
+    ///
+    /// Only integer and string values are supported, see [`StatementAttributeValue`]. Attributes
+    /// taking pointers to memory which must outlive the call can not be set. There is no
+    /// counterpart for connection attributes, since the connection is owned by the application.
+    /// Set these with `odbc_api` before executing the query, e.g. using
+    /// [`odbc_api::ConnectionOptions`] or [`odbc_api::Connection::set_autocommit`].
+    pub fn with_statement_attribute(
+        &mut self,
+        attribute: u32,
+        value: StatementAttributeValue,
+    ) -> &mut Self {
+        self.statement_attributes.push((attribute, value));
+        self
+    }
+
+    /// Infers the Arrow type of columns, whose relational type is unknown to the driver (e.g.
+    /// `sql_variant` in SQL Server), from their first values, rather than fetching them as text.
+    /// [`Self::build`] fetches up to `max_probe_rows` rows, and chooses `Int64` for such columns if
+    /// all of their non-null values are integers, `Float64` if they are all numbers and text
+    /// otherwise. The probed rows are converted to the final schema and returned as the first batch
+    /// of the reader, so no rows are lost. Values in later batches which can not be represented by
+    /// the inferred type cause errors, so choose `max_probe_rows` large enough to be
+    /// representative. Rows are probed across several fetches, if the memory limit allows for
+    /// fewer rows per batch, so columns which are NULL in the first rows can still be inferred.
+    /// Columns holding only NULLs within the probed rows are fetched as text. Columns of known
+    /// types, including the ones defined by the ODBC standard yet reported as `Other` by
+    /// `odbc_api` (e.g. `SQL_GUID` and intervals), or readers with a schema specified by
+    /// [`Self::with_schema`], are not affected. By
+    /// default, or if `max_probe_rows` is zero, the schema is inferred from the relational types
+    /// alone.
+    pub fn with_lazy_schema_inference(&mut self, max_probe_rows: usize) -> &mut Self {
+        self.lazy_schema_probe_rows = Some(max_probe_rows);
+        self
+    }
+
+    /// Reads `JSON` columns of Google Spanner, whose documents may exceed the size sensible for a
+    /// `Utf8` array, as `LargeUtf8`. The Spanner driver reports them as plain text, so they are
+    /// told apart by the type name (`SQL_DESC_TYPE_NAME`) of the column, which is `JSON`. This
+    /// applies to JSON columns of any other data source as well. Only inferred schemas are
+    /// affected, and modes set with [`Self::with_text_output_mode_for_column`] take precedence.
+    /// See [`SpannerCompatibility`] for further options. By default JSON columns are inferred as
+    /// `Utf8` and fetched as they are.
+    pub fn with_spanner_compatibility(
+        &mut self,
+        spanner_compatibility: SpannerCompatibility,
+    ) -> &mut Self {
+        self.spanner_compatibility = Some(spanner_compatibility);
+        self
+    }
+
+    /// If `true` the metadata of each field is augmented with attributes describing the origin of
+    /// its column, as reported by the driver. The keys are `odbc.label` (`SQL_DESC_LABEL`),
+    /// `odbc.base_table_name` (`SQL_DESC_BASE_TABLE_NAME`), `odbc.schema_name`
+    /// (`SQL_DESC_SCHEMA_NAME`) and `odbc.catalog_name` (`SQL_DESC_CATALOG_NAME`). Attributes the
+    /// driver does not report, or reports as empty (e.g. for expressions), are left out. This
+    /// applies to a schema specified with [`Self::with_schema`] as well, whose metadata is kept.
+    /// Each attribute costs a round trip per column while building the reader. Default is `false`.
+    pub fn with_rich_metadata(&mut self, rich_metadata: bool) -> &mut Self {
+        self.rich_metadata = rich_metadata;
+        self
+    }
+
+    /// Statement attributes added with [`Self::with_statement_attribute`].
+    pub(crate) fn statement_attributes(&self) -> &[(u32, StatementAttributeValue)] {
+        &self.statement_attributes
+    }
+
     /// No matter if the user explicitly specified a limit in row size, a memory limit, both or
     /// neither. In order to construct a reader we need to decide on the buffer size in rows.
     fn buffer_size_in_rows(&self, bytes_per_row: usize) -> Result<usize, Error> {
@@ -362,30 +1085,224 @@ impl OdbcReaderBuilder {
     where
         C: Cursor,
     {
+        let reader = validate_statement_attributes(&self.statement_attributes)
+            .and_then(|()| set_statement_attributes(&mut cursor, &self.statement_attributes))
+            .and_then(|()| self.build_reader(cursor));
+        self.annotate_error(reader)
+    }
+
+    /// Like [`Self::build`], but for cursors whose statement attributes have been set before the
+    /// statement has been executed, like [`crate::OdbcPreparedReader`] does.
+    pub(crate) fn build_with_attributes_set<C>(&self, cursor: C) -> Result<OdbcReader<C>, Error>
+    where
+        C: Cursor,
+    {
+        self.annotate_error(self.build_reader(cursor))
+    }
+
+    /// Wraps errors in [`Error::WithConnectionInfo`], if the user asked for it.
+    fn annotate_error<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        result.map_err(|source| match &self.connection_info {
+            Some(sanitized_connection_info) => Error::WithConnectionInfo {
+                sanitized_connection_info: sanitized_connection_info.clone(),
+                source: Box::new(source),
+            },
+            None => source,
+        })
+    }
+
+    fn build_reader<C>(&self, mut cursor: C) -> Result<OdbcReader<C>, Error>
+    where
+        C: Cursor,
+    {
+        // Probing no rows tells us nothing, so we infer from the relational types alone.
+        if let (Some(max_probe_rows @ 1..), None) = (self.lazy_schema_probe_rows, &self.schema) {
+            return self.build_with_lazy_schema(cursor, max_probe_rows);
+        }
         let buffer_allocation_options = BufferAllocationOptions {
             max_text_size: self.max_text_size,
+            max_xml_size: self.max_xml_size,
             max_binary_size: self.max_binary_size,
             fallibale_allocations: self.fallibale_allocations,
+            allow_signed_unsigned_coercion: self.allow_signed_unsigned_coercion,
+            fallback_tinyint_to_smallint: self.fallback_tinyint_to_smallint,
+            opaque_binary_fallback: self.opaque_binary_fallback,
+            element_size_cap: self.element_size_cap,
+            trim_char_padding: self.trim_char_padding,
+            format_guid_as_string: self.format_guid_as_string,
+            check_row_status: self.check_row_status,
+            sub_precision_handling: self.sub_precision_handling,
+            max_column_count: self.max_column_count,
+            text_as_binary_for_non_utf8: self.text_as_binary_for_non_utf8,
+        };
+        let num_result_cols = num_result_cols(&mut cursor)?;
+        if let Some(limit) = buffer_allocation_options.max_column_count {
+            let actual = usize::from(num_result_cols);
+            if actual > limit {
+                return Err(Error::TooManyColumns { actual, limit });
+            }
+        }
+        // Asking for the row count of a query is not meaningful, and some drivers report garbage.
+        let affected_rows = if num_result_cols == 0 {
+            row_count(&mut cursor).map_err(Error::UnableToRetrieveRowCount)?
+        } else {
+            None
+        };
+        let conversion_options = ConversionOptions {
+            map_value_errors_to_null: self.map_value_errors_to_null,
+            trim_fixed_sized_character_strings: self.trim_fixed_sized_character_strings,
+            timestamp_timezone: self.timestamp_timezone.clone(),
+            match_columns_by_name: self.match_columns_by_name,
+            null_replacements: &self.null_replacements,
+            integer_coercion: self.integer_coercion,
+            float_coercion: self.float_coercion,
+            null_hook: self.null_hook.clone(),
+            text_output_mode: self.text_output_mode,
+            column_text_output_modes: &self.column_text_output_modes,
+            missing_column_behavior: self.missing_column_behavior,
+            strategy_registry: &self.strategy_registry,
+            post_fetch_transforms: &self.post_fetch_transforms,
+            dictionary_values: &self.dictionary_values,
+            spanner_compatibility: self.spanner_compatibility,
+            rich_metadata: self.rich_metadata,
         };
         let converter = ToRecordBatch::new(
             &mut cursor,
             self.schema.clone(),
             buffer_allocation_options,
-            self.map_value_errors_to_null,
-            self.trim_fixed_sized_character_strings
+            conversion_options,
         )?;
         let bytes_per_row = converter.row_size_in_bytes();
         let buffer_size_in_rows = self.buffer_size_in_rows(bytes_per_row)?;
         let row_set_buffer =
             converter.allocate_buffer(buffer_size_in_rows, self.fallibale_allocations)?;
+        let row_status = if self.check_row_status {
+            Some(
+                RowStatusArray::bind(&mut cursor, buffer_size_in_rows)
+                    .map_err(Error::UnableToBindRowStatus)?,
+            )
+        } else {
+            None
+        };
+        let statement = bound_statement(&mut cursor);
         let batch_stream = cursor.bind_buffer(row_set_buffer).unwrap();
+        let column_statistics = self
+            .column_statistics
+            .then(|| vec![ColumnStatistics::default(); converter.schema().fields().len()]);
 
         Ok(OdbcReader {
+            row_status,
+            warnings: Vec::new(),
             converter,
             batch_stream,
-            fallibale_allocations: self.fallibale_allocations,
+            buffer_allocation_options,
+            affected_rows,
+            column_statistics,
+            connection_info: self.connection_info.clone(),
+            bytes_per_row,
+            metrics: OdbcReaderMetrics::default(),
+            columns_metadata: None,
+            statement,
+            probed_batch: None,
         })
     }
+
+    /// Fetches up to `max_probe_rows` rows with the inferred schema, in order to refine the types
+    /// of undetermined columns. The reader for the refined schema binds its buffers to the same
+    /// cursor, and returns the probed rows as its first batch.
+    fn build_with_lazy_schema<C>(
+        &self,
+        mut cursor: C,
+        max_probe_rows: usize,
+    ) -> Result<OdbcReader<C>, Error>
+    where
+        C: Cursor,
+    {
+        let mut builder = self.clone();
+        builder.lazy_schema_probe_rows = None;
+        // Columns with an extension type keep it, e.g. JSON documents stay text, even if `1` or
+        // `true` would fit a narrower type.
+        let undetermined: Vec<usize> = column_types(&mut cursor, Error::UnableToDescribeColumns)?
+            .iter()
+            .enumerate()
+            .filter(|(_, data_type)| is_undetermined(data_type))
+            .filter(|&(index, data_type)| {
+                let column_number = (index + 1).try_into().unwrap();
+                col_type_name(&mut cursor, column_number)
+                    .and_then(|type_name| extension_type_from(&type_name, data_type))
+                    .is_none()
+            })
+            .map(|(index, _)| index)
+            .collect();
+        if undetermined.is_empty() {
+            return builder.build_reader(cursor);
+        }
+
+        let mut probe_builder = builder.clone();
+        probe_builder.max_num_rows_per_batch = max_probe_rows;
+        probe_builder.column_statistics = false;
+        let mut probe = probe_builder.build_reader(cursor)?;
+        let schema = probe.schema();
+        // Batches may hold fewer rows than we asked for, due to the memory limit. Sparse columns
+        // may only reveal their first value in a later one, so we keep fetching.
+        let mut batches = Vec::new();
+        let mut num_probed = 0;
+        while num_probed < max_probe_rows {
+            let Some(batch) = probe.next().transpose().map_err(Error::LazySchemaProbe)? else {
+                break;
+            };
+            num_probed += batch.num_rows();
+            batches.push(batch);
+        }
+        let probed_batch = if batches.is_empty() {
+            None
+        } else {
+            Some(
+                refine_probed_columns(&schema, &batches, &undetermined)
+                    .map_err(Error::LazySchemaProbe)?,
+            )
+        };
+        let metrics = probe.metrics();
+        let warnings = probe.take_warnings();
+        let cursor = probe
+            .into_cursor()
+            .map_err(|odbc_error| Error::LazySchemaProbe(odbc_to_arrow_error(odbc_error)))?;
+
+        builder.schema = Some(probed_batch.as_ref().map_or(schema, |batch| batch.schema()));
+        let mut reader = builder.build_reader(cursor)?;
+        reader.probed_batch = probed_batch;
+        reader.metrics = metrics;
+        reader.warnings = warnings;
+        Ok(reader)
+    }
+}
+
+/// Number of rows affected by the last statement executed on the cursor. `None` if the driver does
+/// not know.
+fn row_count(cursor: &mut impl AsStatementRef) -> Result<Option<u64>, odbc_api::Error> {
+    let stmt = cursor.as_stmt_ref();
+    let count = stmt.row_count().into_result(&stmt)?;
+    // ODBC returns -1 in case a row count is not available
+    Ok(count.try_into().ok())
+}
+
+/// Relational types of all columns in the result set of `cursor`. Failures to describe a column
+/// are reported using `describe_error`.
+fn column_types(
+    cursor: &mut impl ResultSetMetadata,
+    describe_error: fn(odbc_api::Error) -> Error,
+) -> Result<Vec<OdbcDataType>, Error> {
+    let num_cols = num_result_cols(cursor)?;
+    (1..=num_cols)
+        .map(|col_index| cursor.col_data_type(col_index).map_err(describe_error))
+        .collect()
+}
+
+/// Handle to the statement of `cursor`, which stays valid after binding buffers to the cursor.
+fn bound_statement<C: Cursor>(cursor: &mut C) -> BoundStatement<C> {
+    // SAFETY: The cursor is owned by the reader holding the bound statement, and is only closed
+    // after the reader has been consumed (e.g. by `into_cursor`), which also drops the statement.
+    unsafe { BoundStatement::new(cursor) }
 }
 
 pub fn odbc_to_arrow_error(odbc_error: odbc_api::Error) -> ArrowError {
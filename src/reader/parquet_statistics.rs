@@ -0,0 +1,164 @@
+use arrow::record_batch::RecordBatch;
+use odbc_api::Cursor;
+use parquet::{
+    arrow::arrow_to_parquet_schema,
+    basic::Type as PhysicalType,
+    data_type::ByteArray,
+    errors::ParquetError,
+    file::{metadata::ColumnChunkMetaData, statistics::Statistics},
+    schema::types::ColumnDescPtr,
+};
+
+use crate::{ColumnStatistics, Error, OdbcReader};
+
+use super::ScalarValue;
+
+/// A record batch together with the statistics of each of its columns, as they would be written
+/// into the metadata of a Parquet column chunk. See [`OdbcReader::next_with_stats`].
+#[derive(Debug, Clone)]
+pub struct OdbcRecordBatchWithStats {
+    /// Batch fetched from the data source.
+    pub batch: RecordBatch,
+    /// One entry for each leaf column of the Parquet schema derived from the schema of `batch`.
+    /// For flat schemas these are the fields of `batch`.
+    pub column_chunks: Vec<ColumnChunkMetaData>,
+}
+
+impl<C> OdbcReader<C>
+where
+    C: Cursor,
+{
+    /// Like [`Iterator::next`], but also computes minimum, maximum and number of NULLs of each
+    /// column of the batch, in the format of the column chunk metadata of Parquet files. Useful if
+    /// the batches are written into Parquet row groups. The statistics of each array are computed
+    /// right after it has been filled, and shared with [`crate::OdbcReader::column_statistics`], so
+    /// no second pass over the batch is needed. Minimum and maximum are only available for the same
+    /// types as in [`crate::ColumnStatistics`]. Leaves of nested columns carry no statistics.
+    /// Available with the `parquet` feature.
+    pub fn next_with_stats(&mut self) -> Option<Result<OdbcRecordBatchWithStats, Error>> {
+        let (batch, statistics) = match self.next_batch(true)? {
+            Ok(batch_and_statistics) => batch_and_statistics,
+            Err(error) => return Some(Err(Error::FetchBatch(error))),
+        };
+        let result = column_chunks(&batch, &statistics)
+            .map(|column_chunks| OdbcRecordBatchWithStats {
+                batch,
+                column_chunks,
+            })
+            .map_err(Error::ParquetStatistics);
+        Some(result)
+    }
+}
+
+fn column_chunks(
+    batch: &RecordBatch,
+    statistics: &[ColumnStatistics],
+) -> Result<Vec<ColumnChunkMetaData>, ParquetError> {
+    let schema = batch.schema();
+    let descriptor = arrow_to_parquet_schema(&schema)?;
+    descriptor
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(leaf_index, column)| {
+            let root_index = descriptor.get_column_root_idx(leaf_index);
+            let array = batch.column(root_index);
+            let mut builder =
+                ColumnChunkMetaData::builder(column.clone()).set_num_values(array.len() as i64);
+            if !schema.field(root_index).data_type().is_nested() {
+                builder =
+                    builder.set_statistics(parquet_statistics(column, &statistics[root_index]));
+            }
+            builder.build()
+        })
+        .collect()
+}
+
+/// `statistics` of an array in the format of the physical type of `column`.
+fn parquet_statistics(column: &ColumnDescPtr, statistics: &ColumnStatistics) -> Statistics {
+    let nulls = statistics.null_count;
+    let min_value = statistics.min_value.clone();
+    let max_value = statistics.max_value.clone();
+    match column.physical_type() {
+        PhysicalType::BOOLEAN => Statistics::boolean(
+            min_value.and_then(as_bool),
+            max_value.and_then(as_bool),
+            None,
+            nulls,
+            false,
+        ),
+        // Unsigned integers are stored as the bit pattern of their signed counterpart
+        PhysicalType::INT32 => Statistics::int32(
+            min_value.and_then(as_i64).map(|value| value as i32),
+            max_value.and_then(as_i64).map(|value| value as i32),
+            None,
+            nulls,
+            false,
+        ),
+        PhysicalType::INT64 => Statistics::int64(
+            min_value.and_then(as_i64),
+            max_value.and_then(as_i64),
+            None,
+            nulls,
+            false,
+        ),
+        PhysicalType::FLOAT => Statistics::float(
+            min_value.and_then(as_f64).map(|value| value as f32),
+            max_value.and_then(as_f64).map(|value| value as f32),
+            None,
+            nulls,
+            false,
+        ),
+        PhysicalType::DOUBLE => Statistics::double(
+            min_value.and_then(as_f64),
+            max_value.and_then(as_f64),
+            None,
+            nulls,
+            false,
+        ),
+        PhysicalType::BYTE_ARRAY => Statistics::byte_array(
+            min_value.and_then(as_byte_array),
+            max_value.and_then(as_byte_array),
+            None,
+            nulls,
+            false,
+        ),
+        // Minimum and maximum are not tracked for any type mapped to these
+        PhysicalType::INT96 => Statistics::int96(None, None, None, nulls, false),
+        PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            Statistics::fixed_len_byte_array(None, None, None, nulls, false)
+        }
+    }
+}
+
+fn as_bool(value: ScalarValue) -> Option<bool> {
+    match value {
+        ScalarValue::Boolean(value) => Some(value),
+        _ => None,
+    }
+}
+
+fn as_i64(value: ScalarValue) -> Option<i64> {
+    match value {
+        ScalarValue::Int64(value) => Some(value),
+        ScalarValue::Date32(value) => Some(value.into()),
+        // Parquet stores unsigned 64 Bit integers in the physical type `INT64`, reinterpreting
+        // the bits.
+        ScalarValue::UInt64(value) => Some(value as i64),
+        _ => None,
+    }
+}
+
+fn as_f64(value: ScalarValue) -> Option<f64> {
+    match value {
+        ScalarValue::Float64(value) => Some(value),
+        _ => None,
+    }
+}
+
+fn as_byte_array(value: ScalarValue) -> Option<ByteArray> {
+    match value {
+        ScalarValue::Utf8(value) => Some(value.into_bytes().into()),
+        _ => None,
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use arrow::{array::ArrayRef, error::ArrowError};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+
+use super::{MappingError, ReadStrategy};
+
+/// Applied to each array right after it has been filled from the ODBC buffer, e.g. an Arrow compute
+/// kernel. See [`crate::OdbcReaderBuilder::with_post_fetch_transform`].
+pub type PostFetchTransform = Arc<dyn Fn(&ArrayRef) -> Result<ArrayRef, ArrowError> + Send + Sync>;
+
+/// Fills the array using another strategy and passes it through a [`PostFetchTransform`].
+pub struct TransformArray {
+    inner: Box<dyn ReadStrategy + Send>,
+    transform: PostFetchTransform,
+}
+
+impl TransformArray {
+    pub fn new(inner: Box<dyn ReadStrategy + Send>, transform: PostFetchTransform) -> Self {
+        Self { inner, transform }
+    }
+}
+
+impl ReadStrategy for TransformArray {
+    fn strategy_type_name(&self) -> &'static str {
+        "TransformArray"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.inner.buffer_desc()
+    }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        self.inner.element_size_hint()
+    }
+
+    fn estimated_memory_per_row(&self) -> usize {
+        self.inner.estimated_memory_per_row()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let array = self.inner.fill_arrow_array(column_view)?;
+        let transformed =
+            (self.transform)(&array).map_err(|source| MappingError::TransformFailed { source })?;
+        // The schema of the reader is fixed, once it is built.
+        if transformed.data_type() != array.data_type() || transformed.len() != array.len() {
+            return Err(MappingError::TransformChangedShape {
+                expected: array.data_type().clone(),
+                actual: transformed.data_type().clone(),
+            });
+        }
+        Ok(transformed)
+    }
+}
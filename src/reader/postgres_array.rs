@@ -0,0 +1,297 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array,
+        Int64Array, Int8Array, LargeListArray, ListArray, StringArray,
+    },
+    buffer::{OffsetBuffer, ScalarBuffer},
+    datatypes::{DataType as ArrowDataType, FieldRef},
+};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+
+use super::{MappingError, ReadStrategy};
+
+/// Fetches one dimensional arrays from PostgreSQL. The ODBC driver transmits them as text in the
+/// PostgreSQL array literal format (e.g. `{1,2,NULL}`), which we parse into an Arrow `List` or
+/// `LargeList`.
+pub struct PostgresArray {
+    /// Strategy fetching the array literals into a `Utf8` array.
+    text: Box<dyn ReadStrategy + Send>,
+    /// Field describing the elements of the list.
+    element: FieldRef,
+    /// Emit `LargeList` with 64 Bit offsets, rather than `List`.
+    large: bool,
+}
+
+impl PostgresArray {
+    /// `text` must be a strategy emitting `Utf8` arrays.
+    pub fn new(text: Box<dyn ReadStrategy + Send>, element: FieldRef) -> Self {
+        Self {
+            text,
+            element,
+            large: false,
+        }
+    }
+
+    /// Same as [`Self::new`], but emits `LargeList` arrays.
+    pub fn new_large(text: Box<dyn ReadStrategy + Send>, element: FieldRef) -> Self {
+        Self {
+            text,
+            element,
+            large: true,
+        }
+    }
+
+    /// `true` if we know how to parse elements of this type from an array literal.
+    pub fn is_supported_element(data_type: &ArrowDataType) -> bool {
+        matches!(
+            data_type,
+            ArrowDataType::Boolean
+                | ArrowDataType::Int8
+                | ArrowDataType::Int16
+                | ArrowDataType::Int32
+                | ArrowDataType::Int64
+                | ArrowDataType::Float32
+                | ArrowDataType::Float64
+                | ArrowDataType::Utf8
+        )
+    }
+}
+
+impl ReadStrategy for PostgresArray {
+    fn strategy_type_name(&self) -> &'static str {
+        "PostgresArray"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.text.buffer_desc()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let text = self.text.fill_arrow_array(column_view)?;
+        let text = text.as_any().downcast_ref::<StringArray>().unwrap();
+
+        let mut offsets = Vec::with_capacity(text.len() + 1);
+        offsets.push(0usize);
+        let mut elements = Vec::new();
+        for literal in text.iter() {
+            if let Some(literal) = literal {
+                let invalid = || MappingError::InvalidArrayLiteral {
+                    value: literal.to_owned(),
+                };
+                let start = elements.len();
+                parse_array_literal(literal, &mut elements).ok_or_else(invalid)?;
+                if !self.element.is_nullable() && elements[start..].iter().any(Option::is_none) {
+                    return Err(invalid());
+                }
+            }
+            offsets.push(elements.len());
+        }
+
+        let values = parse_elements(self.element.data_type(), &elements)
+            .map_err(|element| MappingError::InvalidArrayLiteral { value: element })?;
+        let nulls = text.nulls().cloned();
+        let array: ArrayRef = if self.large {
+            let offsets: Vec<i64> = offsets.into_iter().map(|offset| offset as i64).collect();
+            Arc::new(LargeListArray::new(
+                self.element.clone(),
+                OffsetBuffer::new(ScalarBuffer::from(offsets)),
+                values,
+                nulls,
+            ))
+        } else {
+            let offsets: Vec<i32> = offsets
+                .into_iter()
+                .map(|offset| offset.try_into().unwrap())
+                .collect();
+            Arc::new(ListArray::new(
+                self.element.clone(),
+                OffsetBuffer::new(ScalarBuffer::from(offsets)),
+                values,
+                nulls,
+            ))
+        };
+        Ok(array)
+    }
+}
+
+/// Appends the elements of a one dimensional array literal like `{1,"two",NULL}` to `elements`.
+/// `None` if the literal is malformed or has more than one dimension.
+fn parse_array_literal(literal: &str, elements: &mut Vec<Option<String>>) -> Option<()> {
+    let inner = literal.trim().strip_prefix('{')?.strip_suffix('}')?;
+    if inner.trim().is_empty() {
+        return Some(());
+    }
+    let mut chars = inner.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let element = if chars.next_if_eq(&'"').is_some() {
+            // Quoted element, may contain delimiters. Backslash escapes the next character.
+            let mut element = String::new();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => element.push(chars.next()?),
+                    c => element.push(c),
+                }
+            }
+            Some(element)
+        } else {
+            let mut element = String::new();
+            while let Some(c) = chars.next_if(|&c| c != ',') {
+                if c == '{' || c == '}' || c == '"' {
+                    return None;
+                }
+                element.push(c);
+            }
+            let element = element.trim_end();
+            if element.is_empty() {
+                return None;
+            }
+            // Only the unquoted `NULL` is a NULL, a quoted "NULL" is the text.
+            (!element.eq_ignore_ascii_case("NULL")).then(|| element.to_owned())
+        };
+        elements.push(element);
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            Some(',') => continue,
+            None => return Some(()),
+            Some(_) => return None,
+        }
+    }
+}
+
+/// Parses the elements into an array of `data_type`. In case of an error the offending element is
+/// returned.
+pub(super) fn parse_elements(
+    data_type: &ArrowDataType,
+    elements: &[Option<String>],
+) -> Result<ArrayRef, String> {
+    fn parse<T: std::str::FromStr>(elements: &[Option<String>]) -> Result<Vec<Option<T>>, String> {
+        elements
+            .iter()
+            .map(|opt| {
+                opt.as_deref()
+                    .map(|text| text.parse().map_err(|_| text.to_owned()))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    let array: ArrayRef = match data_type {
+        ArrowDataType::Boolean => {
+            // PostgreSQL represents booleans as `t` and `f` within array literals
+            let values = elements
+                .iter()
+                .map(|opt| {
+                    opt.as_deref()
+                        .map(|text| match text {
+                            "t" | "true" => Ok(true),
+                            "f" | "false" => Ok(false),
+                            other => Err(other.to_owned()),
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Arc::new(BooleanArray::from(values))
+        }
+        ArrowDataType::Int8 => Arc::new(Int8Array::from(parse::<i8>(elements)?)),
+        ArrowDataType::Int16 => Arc::new(Int16Array::from(parse::<i16>(elements)?)),
+        ArrowDataType::Int32 => Arc::new(Int32Array::from(parse::<i32>(elements)?)),
+        ArrowDataType::Int64 => Arc::new(Int64Array::from(parse::<i64>(elements)?)),
+        ArrowDataType::Float32 => Arc::new(Float32Array::from(parse::<f32>(elements)?)),
+        ArrowDataType::Float64 => Arc::new(Float64Array::from(parse::<f64>(elements)?)),
+        ArrowDataType::Utf8 => Arc::new(
+            elements
+                .iter()
+                .map(Option::as_deref)
+                .collect::<StringArray>(),
+        ),
+        _ => unreachable!("Element type must be checked with `is_supported_element`"),
+    };
+    Ok(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{Array, Int32Array},
+        datatypes::DataType as ArrowDataType,
+    };
+
+    use super::{parse_array_literal, parse_elements};
+
+    fn parse(literal: &str) -> Option<Vec<Option<String>>> {
+        let mut elements = Vec::new();
+        parse_array_literal(literal, &mut elements).map(|()| elements)
+    }
+
+    fn text(element: &str) -> Option<String> {
+        Some(element.to_owned())
+    }
+
+    #[test]
+    fn parse_unquoted_elements_and_null() {
+        assert_eq!(
+            Some(vec![text("1"), None, text("3")]),
+            parse("{1, NULL ,3}")
+        );
+    }
+
+    #[test]
+    fn parse_empty_array() {
+        assert_eq!(Some(vec![]), parse("{}"));
+        assert_eq!(Some(vec![]), parse(" { } "));
+    }
+
+    #[test]
+    fn quoted_null_is_text() {
+        assert_eq!(Some(vec![text("NULL")]), parse(r#"{"NULL"}"#));
+    }
+
+    #[test]
+    fn quoted_elements_keep_delimiters_and_escapes() {
+        assert_eq!(
+            Some(vec![
+                text("a,b"),
+                text(r#"say "hi""#),
+                text(r"back\slash"),
+                text("")
+            ]),
+            parse(r#"{"a,b","say \"hi\"","back\\slash",""}"#)
+        );
+    }
+
+    #[test]
+    fn reject_nested_arrays() {
+        assert_eq!(None, parse("{{1,2},{3,4}}"));
+    }
+
+    #[test]
+    fn reject_malformed_literals() {
+        // Missing braces
+        assert_eq!(None, parse("1,2"));
+        // Unterminated quote
+        assert_eq!(None, parse(r#"{"abc}"#));
+        // Empty element
+        assert_eq!(None, parse("{1,,2}"));
+        // Garbage after a quoted element
+        assert_eq!(None, parse(r#"{"a"b}"#));
+    }
+
+    #[test]
+    fn parse_elements_into_integers() {
+        let array = parse_elements(&ArrowDataType::Int32, &[text("1"), None]).unwrap();
+
+        let expected = Int32Array::from(vec![Some(1), None]);
+        assert_eq!(&expected as &dyn Array, array.as_ref());
+    }
+
+    #[test]
+    fn report_offending_element() {
+        let error = parse_elements(&ArrowDataType::Int32, &[text("1"), text("two")]).unwrap_err();
+
+        assert_eq!("two", error);
+    }
+}
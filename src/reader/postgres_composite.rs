@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, ArrayRef, StringArray, StructArray},
+    datatypes::Fields,
+};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+
+use super::{postgres_array::parse_elements, MappingError, ReadStrategy};
+
+/// Fetches composite values (row types) from PostgreSQL. The ODBC driver transmits them as text in
+/// the PostgreSQL composite literal format (e.g. `(1,"two",,t)`), which we decompose into the
+/// children of an Arrow `Struct`. Children are matched with the attributes of the composite type by
+/// position and support the same types as the elements of [`super::PostgresArray`].
+pub struct PostgresComposite {
+    /// Strategy fetching the composite literals into a `Utf8` array.
+    text: Box<dyn ReadStrategy + Send>,
+    /// Fields describing the children of the struct.
+    fields: Fields,
+}
+
+impl PostgresComposite {
+    /// `text` must be a strategy emitting `Utf8` arrays.
+    pub fn new(text: Box<dyn ReadStrategy + Send>, fields: Fields) -> Self {
+        Self { text, fields }
+    }
+}
+
+impl ReadStrategy for PostgresComposite {
+    fn strategy_type_name(&self) -> &'static str {
+        "PostgresComposite"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.text.buffer_desc()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let text = self.text.fill_arrow_array(column_view)?;
+        let text = text.as_any().downcast_ref::<StringArray>().unwrap();
+
+        // Attribute values of each child, for every row
+        let mut children = vec![Vec::with_capacity(text.len()); self.fields.len()];
+        let mut attributes = Vec::with_capacity(self.fields.len());
+        for literal in text.iter() {
+            if let Some(literal) = literal {
+                let invalid = || MappingError::InvalidCompositeLiteral {
+                    value: literal.to_owned(),
+                };
+                attributes.clear();
+                parse_composite_literal(literal, &mut attributes).ok_or_else(invalid)?;
+                if attributes.len() != self.fields.len() {
+                    return Err(invalid());
+                }
+                for ((values, attribute), field) in children
+                    .iter_mut()
+                    .zip(attributes.drain(..))
+                    .zip(&self.fields)
+                {
+                    if attribute.is_none() && !field.is_nullable() {
+                        return Err(invalid());
+                    }
+                    values.push(attribute);
+                }
+            } else {
+                // Masked by the nulls of the struct
+                for values in &mut children {
+                    values.push(None);
+                }
+            }
+        }
+
+        let arrays = children
+            .iter()
+            .zip(&self.fields)
+            .map(|(values, field)| parse_elements(field.data_type(), values))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|value| MappingError::InvalidCompositeLiteral { value })?;
+        let array = StructArray::new(self.fields.clone(), arrays, text.nulls().cloned());
+        Ok(Arc::new(array))
+    }
+}
+
+/// Appends the attributes of a composite literal like `(1,"two",,t)` to `attributes`. Empty
+/// attributes are NULL. `None` if the literal is malformed.
+fn parse_composite_literal(literal: &str, attributes: &mut Vec<Option<String>>) -> Option<()> {
+    let inner = literal.trim().strip_prefix('(')?.strip_suffix(')')?;
+    let mut chars = inner.chars().peekable();
+    loop {
+        let attribute = if chars.next_if_eq(&'"').is_some() {
+            // Quoted attribute, may contain delimiters. Quotes are escaped by doubling them or
+            // with a backslash, like any other character.
+            let mut attribute = String::new();
+            loop {
+                match chars.next()? {
+                    '"' if chars.next_if_eq(&'"').is_some() => attribute.push('"'),
+                    '"' => break,
+                    '\\' => attribute.push(chars.next()?),
+                    c => attribute.push(c),
+                }
+            }
+            Some(attribute)
+        } else {
+            let mut attribute = String::new();
+            while let Some(c) = chars.next_if(|&c| c != ',') {
+                if c == '(' || c == ')' || c == '"' {
+                    return None;
+                }
+                attribute.push(c);
+            }
+            // Unlike array literals, NULL is represented by omitting the value entirely.
+            (!attribute.is_empty()).then_some(attribute)
+        };
+        attributes.push(attribute);
+        match chars.next() {
+            Some(',') => continue,
+            None => return Some(()),
+            Some(_) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_composite_literal;
+
+    fn parse(literal: &str) -> Option<Vec<Option<String>>> {
+        let mut attributes = Vec::new();
+        parse_composite_literal(literal, &mut attributes).map(|()| attributes)
+    }
+
+    fn text(attribute: &str) -> Option<String> {
+        Some(attribute.to_owned())
+    }
+
+    #[test]
+    fn parse_attributes_with_omitted_null() {
+        assert_eq!(
+            Some(vec![text("1"), text("two"), None, text("t")]),
+            parse("(1,two,,t)")
+        );
+    }
+
+    #[test]
+    fn quoted_empty_attribute_is_empty_text() {
+        assert_eq!(Some(vec![text(""), None]), parse(r#"("",)"#));
+    }
+
+    #[test]
+    fn quoted_attributes_keep_delimiters_and_escapes() {
+        assert_eq!(
+            Some(vec![
+                text("a,(b)"),
+                text(r#"doubled "quote""#),
+                text(r#"escaped "quote""#)
+            ]),
+            parse(r#"("a,(b)","doubled ""quote""","escaped \"quote\"")"#)
+        );
+    }
+
+    #[test]
+    fn nested_composite_is_passed_on_as_text() {
+        assert_eq!(
+            Some(vec![text("1"), text("(2,3)")]),
+            parse(r#"(1,"(2,3)")"#)
+        );
+    }
+
+    #[test]
+    fn reject_malformed_literals() {
+        // Missing parentheses
+        assert_eq!(None, parse("1,2"));
+        // Unquoted nested composite
+        assert_eq!(None, parse("(1,(2,3))"));
+        // Unterminated quote
+        assert_eq!(None, parse(r#"("abc)"#));
+        // Garbage after a quoted attribute
+        assert_eq!(None, parse(r#"("a"b)"#));
+    }
+}
@@ -0,0 +1,242 @@
+use std::{fmt, ptr::null_mut};
+
+use arrow::{
+    array::{ArrayRef, BooleanArray},
+    compute::nullif,
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use log::warn;
+use odbc_api::{
+    handles::{AsHandle, AsStatementRef, Record, Statement},
+    sys::{HStmt, Handle, HandleType, Pointer, StatementAttribute},
+};
+
+use super::ffi;
+
+/// `SQL_DIAG_ROW_NUMBER`
+const DIAG_ROW_NUMBER: i16 = -1248;
+/// `SQL_ROW_SUCCESS`
+const ROW_SUCCESS: u16 = 0;
+/// `SQL_ROW_ERROR`
+const ROW_ERROR: u16 = 5;
+/// `SQL_ROW_SUCCESS_WITH_INFO`
+const ROW_SUCCESS_WITH_INFO: u16 = 6;
+
+/// Reported by [`crate::OdbcReader::take_warnings`] for rows the driver flagged in the row status
+/// array, if enabled with [`crate::OdbcReaderBuilder::check_row_status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnWarning {
+    /// The driver reported `SQL_ROW_ERROR` for the row. All its values have been replaced with
+    /// nulls.
+    RowFetchError {
+        /// Zero based index of the row within the result set.
+        row: u64,
+        /// Diagnostic records the driver reported for the row.
+        diagnostics: Vec<String>,
+    },
+    /// The driver reported `SQL_ROW_SUCCESS_WITH_INFO` for the row, e.g. because a value has been
+    /// rounded. The values of the row are kept.
+    RowFetchedWithInfo {
+        /// Zero based index of the row within the result set.
+        row: u64,
+        /// Diagnostic records the driver reported for the row.
+        diagnostics: Vec<String>,
+    },
+}
+
+impl fmt::Display for ColumnWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (outcome, row, diagnostics) = match self {
+            ColumnWarning::RowFetchError { row, diagnostics } => ("failed", row, diagnostics),
+            ColumnWarning::RowFetchedWithInfo { row, diagnostics } => {
+                ("succeeded with info", row, diagnostics)
+            }
+        };
+        write!(f, "Fetching row {row} {outcome}")?;
+        for diagnostic in diagnostics {
+            write!(f, "\n{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Status of each row in the row set, filled by the driver during `SQLFetch`. Only used by
+/// [`crate::OdbcReader`], which ensures the invariants below, and therefore not part of the public
+/// API.
+///
+/// # Safety invariant
+///
+/// The driver holds on to the address of `statuses` between `SQLSetStmtAttr` and the next call
+/// unbinding it, writing to it during each fetch:
+///
+/// * `statuses` is heap allocated and never reallocated, so its address is stable, even if `Self`
+///   moves.
+/// * [`Drop`] unbinds `SQL_ATTR_ROW_STATUS_PTR` before `statuses` is freed. Should unbinding fail,
+///   `statuses` is leaked rather than freed.
+/// * The owner drops this array before the statement handle is freed, so `statement` is valid
+///   throughout its lifetime. [`crate::OdbcReader`] declares it before its cursor and drops it
+///   explicitly before giving away the cursor.
+/// * The statement is not fetched from concurrently with [`Self::check`] reading `statuses`,
+///   which requires `&mut self`.
+pub(crate) struct RowStatusArray {
+    statement: RawStatement,
+    statuses: Box<[u16]>,
+    /// Number of rows in all previous batches, in order to report row indices within the result
+    /// set.
+    rows_fetched: u64,
+}
+
+// SAFETY: The statement handle is owned by the cursor of the same reader, which moves between
+// threads together with this array. See the safety invariant of `RowStatusArray`.
+unsafe impl Send for RowStatusArray {}
+
+impl RowStatusArray {
+    /// Binds an array with one status for each of `num_rows` rows to the statement of `cursor`.
+    pub fn bind(
+        cursor: &mut impl AsStatementRef,
+        num_rows: usize,
+    ) -> Result<Self, odbc_api::Error> {
+        let stmt = cursor.as_stmt_ref();
+        let mut statuses = vec![ROW_SUCCESS; num_rows].into_boxed_slice();
+        // SAFETY: The boxed slice does not move, even if `Self` does. It is unbound in `drop`
+        // before it is deallocated. See the safety invariant of `RowStatusArray`.
+        let ret = unsafe {
+            ffi::SQLSetStmtAttr(
+                stmt.as_sys(),
+                StatementAttribute::RowStatusPtr,
+                statuses.as_mut_ptr() as Pointer,
+                0,
+            )
+        };
+        ffi::sql_result(ret, "SQLSetStmtAttr").into_result(&stmt)?;
+        Ok(Self {
+            statement: RawStatement(stmt.as_sys()),
+            statuses,
+            rows_fetched: 0,
+        })
+    }
+
+    /// Inspects the status of the rows of `batch`, which must just have been fetched. Values of
+    /// rows which failed to fetch are replaced with nulls. Failed rows and rows with additional
+    /// information are logged and appended to `warnings`.
+    pub fn check(
+        &mut self,
+        batch: RecordBatch,
+        warnings: &mut Vec<ColumnWarning>,
+    ) -> Result<RecordBatch, ArrowError> {
+        let num_rows = batch.num_rows();
+        let offset = self.rows_fetched;
+        self.rows_fetched += num_rows as u64;
+        let statuses = &self.statuses[..num_rows];
+        if !statuses
+            .iter()
+            .any(|&status| matches!(status, ROW_ERROR | ROW_SUCCESS_WITH_INFO))
+        {
+            return Ok(batch);
+        }
+
+        let diagnostics = self.diagnostics();
+        let diagnostics_of_row = |index: usize| {
+            diagnostics
+                .iter()
+                .filter(|(row_number, _)| *row_number == index as isize + 1)
+                .map(|(_, record)| record.clone())
+                .collect()
+        };
+        for (index, &status) in statuses.iter().enumerate() {
+            let row = offset + index as u64;
+            let warning = match status {
+                ROW_ERROR => ColumnWarning::RowFetchError {
+                    row,
+                    diagnostics: diagnostics_of_row(index),
+                },
+                ROW_SUCCESS_WITH_INFO => ColumnWarning::RowFetchedWithInfo {
+                    row,
+                    diagnostics: diagnostics_of_row(index),
+                },
+                _ => continue,
+            };
+            warn!("{warning}");
+            warnings.push(warning);
+        }
+
+        if !statuses.contains(&ROW_ERROR) {
+            return Ok(batch);
+        }
+        let failed: BooleanArray = statuses
+            .iter()
+            .map(|&status| Some(status == ROW_ERROR))
+            .collect();
+        let columns = batch
+            .columns()
+            .iter()
+            .map(|column| nullif(column, &failed))
+            .collect::<Result<Vec<ArrayRef>, _>>()?;
+        RecordBatch::try_new(batch.schema(), columns)
+    }
+
+    /// All diagnostic records of the last fetch, together with the one based number of the row
+    /// within the row set they belong to. Row numbers are negative if unknown.
+    fn diagnostics(&self) -> Vec<(isize, String)> {
+        let mut diagnostics = Vec::new();
+        let mut record = Record::default();
+        let mut record_number = 1;
+        while record.fill_from(&self.statement, record_number) {
+            let mut row_number: isize = -1;
+            // SAFETY: `SQL_DIAG_ROW_NUMBER` is an `SQLLEN`, so the buffer is large enough.
+            unsafe {
+                ffi::SQLGetDiagField(
+                    HandleType::Stmt,
+                    self.statement.as_handle(),
+                    record_number,
+                    DIAG_ROW_NUMBER,
+                    &mut row_number as *mut isize as Pointer,
+                    0,
+                    null_mut(),
+                );
+            }
+            diagnostics.push((row_number, record.to_string()));
+            if record_number == i16::MAX {
+                break;
+            }
+            record_number += 1;
+        }
+        diagnostics
+    }
+}
+
+impl Drop for RowStatusArray {
+    fn drop(&mut self) {
+        // SAFETY: The statement handle is still valid, since the array is dropped before it.
+        // Failing to unbind is not expected, yet if it happens the best we can do is to leak the
+        // array rather than leaving the driver with a dangling pointer.
+        let ret = unsafe {
+            ffi::SQLSetStmtAttr(
+                self.statement.0,
+                StatementAttribute::RowStatusPtr,
+                null_mut(),
+                0,
+            )
+        };
+        if ffi::sql_result(ret, "SQLSetStmtAttr")
+            .into_result(&self.statement)
+            .is_err()
+        {
+            std::mem::forget(std::mem::take(&mut self.statuses));
+        }
+    }
+}
+
+/// Statement handle borrowed from the cursor, in order to extract diagnostics.
+struct RawStatement(HStmt);
+
+unsafe impl AsHandle for RawStatement {
+    fn as_handle(&self) -> Handle {
+        self.0 as Handle
+    }
+
+    fn handle_type(&self) -> HandleType {
+        HandleType::Stmt
+    }
+}
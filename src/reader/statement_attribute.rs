@@ -0,0 +1,118 @@
+use odbc_api::{
+    handles::{AsStatementRef, Statement},
+    sys::Pointer,
+};
+
+use crate::Error;
+
+use super::ffi;
+
+/// Value of an ODBC statement attribute, see
+/// [`crate::OdbcReaderBuilder::with_statement_attribute`]. Attributes taking pointers to memory
+/// which must outlive the call (e.g. `SQL_ATTR_ROW_STATUS_PTR`) can not be set safely, and are
+/// therefore not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatementAttributeValue {
+    /// Integer attributes, like `SQL_ATTR_QUERY_TIMEOUT` or `SQL_ATTR_MAX_ROWS`.
+    Integer(usize),
+    /// Character string attributes. Must not contain NUL characters.
+    Text(String),
+}
+
+/// Attributes controlling how the buffers of the reader are bound to the statement. Setting them
+/// would break fetching, so [`crate::OdbcReaderBuilder::build`] rejects them.
+const RESERVED_ATTRIBUTES: [(u32, &str); 8] = [
+    (5, "SQL_ATTR_ROW_BIND_TYPE"),
+    (17, "SQL_ATTR_PARAM_BIND_TYPE"),
+    (22, "SQL_ATTR_PARAMSET_SIZE"),
+    (23, "SQL_ATTR_ROW_BIND_OFFSET_PTR"),
+    (25, "SQL_ATTR_ROW_STATUS_PTR"),
+    (26, "SQL_ATTR_ROWS_FETCHED_PTR"),
+    (27, "SQL_ATTR_ROW_ARRAY_SIZE"),
+    (10010, "SQL_ATTR_APP_ROW_DESC"),
+];
+
+/// Fails for attributes which would interfere with the buffers of the reader, or text values
+/// which can not be passed as null terminated strings.
+pub fn validate_statement_attributes(
+    attributes: &[(u32, StatementAttributeValue)],
+) -> Result<(), Error> {
+    for (attribute, value) in attributes {
+        if let Some(&(_, name)) = RESERVED_ATTRIBUTES
+            .iter()
+            .find(|(reserved, _)| reserved == attribute)
+        {
+            return Err(Error::ReservedStatementAttribute {
+                attribute: *attribute,
+                name,
+            });
+        }
+        if let StatementAttributeValue::Text(text) = value {
+            if text.contains('\0') {
+                return Err(Error::InvalidStatementAttributeValue {
+                    attribute: *attribute,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sets each attribute using `SQLSetStmtAttr`. Attributes must have been validated with
+/// [`validate_statement_attributes`].
+pub fn set_statement_attributes(
+    statement: &mut impl AsStatementRef,
+    attributes: &[(u32, StatementAttributeValue)],
+) -> Result<(), Error> {
+    let stmt = statement.as_stmt_ref();
+    for (attribute, value) in attributes {
+        let ret = match value {
+            // SAFETY: Integer attributes are passed in place of the pointer and never dereferenced.
+            StatementAttributeValue::Integer(value) => unsafe {
+                ffi::SQLSetStmtAttrUnchecked(
+                    stmt.as_sys(),
+                    *attribute as i32,
+                    *value as Pointer,
+                    IS_UINTEGER,
+                )
+            },
+            StatementAttributeValue::Text(text) => {
+                let mut text = encode(text);
+                // SAFETY: The driver copies null terminated strings, so the buffer only needs to
+                // outlive the call.
+                unsafe {
+                    ffi::SQLSetStmtAttrUnchecked(
+                        stmt.as_sys(),
+                        *attribute as i32,
+                        text.as_mut_ptr() as Pointer,
+                        NTS,
+                    )
+                }
+            }
+        };
+        ffi::sql_result(ret, "SQLSetStmtAttr")
+            .into_result(&stmt)
+            .map_err(|source| Error::UnableToSetStatementAttribute {
+                attribute: *attribute,
+                source,
+            })?;
+    }
+    Ok(())
+}
+
+/// `SQL_IS_UINTEGER`. Tells the driver manager the type of driver specific attributes.
+const IS_UINTEGER: i32 = -5;
+
+/// `SQL_NTS`. The string is null terminated.
+const NTS: i32 = -3;
+
+/// On windows we call the wide function, like `odbc-api` does. See the manifest.
+#[cfg(target_os = "windows")]
+fn encode(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain([0]).collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn encode(text: &str) -> Vec<u8> {
+    text.bytes().chain([0]).collect()
+}
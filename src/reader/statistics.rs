@@ -0,0 +1,214 @@
+use std::cmp::Ordering;
+
+use arrow::{
+    array::{Array, AsArray},
+    compute::{
+        max, max_boolean, max_string, max_string_view, min, min_boolean, min_string,
+        min_string_view,
+    },
+    datatypes::{
+        DataType as ArrowDataType, Date32Type, Float32Type, Float64Type, Int16Type, Int32Type,
+        Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+    },
+};
+
+use super::ScalarValue;
+
+/// Statistics of a single column, collected while fetching record batches with an
+/// [`crate::OdbcReader`]. See [`crate::OdbcReaderBuilder::with_column_statistics`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColumnStatistics {
+    /// Smallest value fetched so far. `None` if only NULLs have been fetched, or if minimum and
+    /// maximum are not collected for the type of the column. These are integers, floats, dates,
+    /// booleans and text.
+    pub min_value: Option<ScalarValue>,
+    /// Largest value fetched so far. `None` under the same conditions as `min_value`.
+    pub max_value: Option<ScalarValue>,
+    /// Number of NULLs fetched so far.
+    pub null_count: u64,
+}
+
+impl ColumnStatistics {
+    /// Accounts for the values in `array`.
+    pub fn update(&mut self, array: &dyn Array) {
+        self.null_count += array.null_count() as u64;
+        if let Some((min_value, max_value)) = min_max(array) {
+            merge(&mut self.min_value, min_value, Ordering::Less);
+            merge(&mut self.max_value, max_value, Ordering::Greater);
+        }
+    }
+
+    /// Statistics of the values in `array` alone.
+    pub(crate) fn of_array(array: &dyn Array) -> Self {
+        let mut statistics = Self::default();
+        statistics.update(array);
+        statistics
+    }
+
+    /// Accounts for the values described by `other`, e.g. the statistics of a single batch.
+    pub(crate) fn combine(&mut self, other: &ColumnStatistics) {
+        self.null_count += other.null_count;
+        if let Some(min_value) = &other.min_value {
+            merge(&mut self.min_value, min_value.clone(), Ordering::Less);
+        }
+        if let Some(max_value) = &other.max_value {
+            merge(&mut self.max_value, max_value.clone(), Ordering::Greater);
+        }
+    }
+}
+
+/// Counters describing the round trips between an [`crate::OdbcReader`] and the ODBC driver. Divide
+/// `total_buffer_bytes` by the elapsed time of the query in order to get the effective throughput.
+/// See [`crate::OdbcReader::metrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OdbcReaderMetrics {
+    /// Number of successful calls to `SQLFetch`, including the last one reporting the end of the
+    /// result set.
+    pub fetch_count: u64,
+    /// Bytes of the transit buffer per row multiplied by the number of rows fetched, summed up over
+    /// all fetches. This is the size of the buffers filled by the driver, which is larger than the
+    /// actual data if variadic values are shorter than their maximum length.
+    pub total_buffer_bytes: u64,
+    /// Number of fetches which returned no rows.
+    pub empty_fetch_count: u64,
+}
+
+impl OdbcReaderMetrics {
+    /// Accounts for a fetch of `num_rows` rows into a buffer with `bytes_per_row` bytes per row.
+    pub fn record_fetch(&mut self, num_rows: usize, bytes_per_row: usize) {
+        self.fetch_count += 1;
+        self.total_buffer_bytes += (num_rows * bytes_per_row) as u64;
+        if num_rows == 0 {
+            self.empty_fetch_count += 1;
+        }
+    }
+}
+
+/// Replaces `current` with `candidate`, if there is no current value yet, or if `candidate` compares
+/// to it as `replace_if`.
+fn merge(current: &mut Option<ScalarValue>, candidate: ScalarValue, replace_if: Ordering) {
+    let replace = match current {
+        None => true,
+        Some(current) => compare(&candidate, current) == Some(replace_if),
+    };
+    if replace {
+        *current = Some(candidate);
+    }
+}
+
+fn compare(left: &ScalarValue, right: &ScalarValue) -> Option<Ordering> {
+    match (left, right) {
+        (ScalarValue::Boolean(l), ScalarValue::Boolean(r)) => l.partial_cmp(r),
+        (ScalarValue::Int64(l), ScalarValue::Int64(r)) => l.partial_cmp(r),
+        (ScalarValue::UInt64(l), ScalarValue::UInt64(r)) => l.partial_cmp(r),
+        (ScalarValue::Float64(l), ScalarValue::Float64(r)) => l.partial_cmp(r),
+        (ScalarValue::Date32(l), ScalarValue::Date32(r)) => l.partial_cmp(r),
+        (ScalarValue::Utf8(l), ScalarValue::Utf8(r)) => l.partial_cmp(r),
+        _ => None,
+    }
+}
+
+/// Minimum and maximum of the array. `None` if the array contains only NULLs or its type is not
+/// supported.
+fn min_max(array: &dyn Array) -> Option<(ScalarValue, ScalarValue)> {
+    fn both<T>(
+        min_value: Option<T>,
+        max_value: Option<T>,
+        to_scalar: impl Fn(T) -> ScalarValue,
+    ) -> Option<(ScalarValue, ScalarValue)> {
+        Some((to_scalar(min_value?), to_scalar(max_value?)))
+    }
+
+    let utf8 = |value: &str| ScalarValue::Utf8(value.to_owned());
+    match array.data_type() {
+        ArrowDataType::Boolean => {
+            let array = array.as_boolean();
+            both(min_boolean(array), max_boolean(array), ScalarValue::Boolean)
+        }
+        ArrowDataType::Int8 => {
+            let array = array.as_primitive::<Int8Type>();
+            both(
+                min(array).map(i64::from),
+                max(array).map(i64::from),
+                ScalarValue::Int64,
+            )
+        }
+        ArrowDataType::Int16 => {
+            let array = array.as_primitive::<Int16Type>();
+            both(
+                min(array).map(i64::from),
+                max(array).map(i64::from),
+                ScalarValue::Int64,
+            )
+        }
+        ArrowDataType::Int32 => {
+            let array = array.as_primitive::<Int32Type>();
+            both(
+                min(array).map(i64::from),
+                max(array).map(i64::from),
+                ScalarValue::Int64,
+            )
+        }
+        ArrowDataType::Int64 => {
+            let array = array.as_primitive::<Int64Type>();
+            both(min(array), max(array), ScalarValue::Int64)
+        }
+        ArrowDataType::UInt8 => {
+            let array = array.as_primitive::<UInt8Type>();
+            both(
+                min(array).map(i64::from),
+                max(array).map(i64::from),
+                ScalarValue::Int64,
+            )
+        }
+        ArrowDataType::UInt16 => {
+            let array = array.as_primitive::<UInt16Type>();
+            both(
+                min(array).map(i64::from),
+                max(array).map(i64::from),
+                ScalarValue::Int64,
+            )
+        }
+        ArrowDataType::UInt32 => {
+            let array = array.as_primitive::<UInt32Type>();
+            both(
+                min(array).map(i64::from),
+                max(array).map(i64::from),
+                ScalarValue::Int64,
+            )
+        }
+        ArrowDataType::UInt64 => {
+            let array = array.as_primitive::<UInt64Type>();
+            both(min(array), max(array), ScalarValue::UInt64)
+        }
+        ArrowDataType::Float32 => {
+            let array = array.as_primitive::<Float32Type>();
+            both(
+                min(array).map(f64::from),
+                max(array).map(f64::from),
+                ScalarValue::Float64,
+            )
+        }
+        ArrowDataType::Float64 => {
+            let array = array.as_primitive::<Float64Type>();
+            both(min(array), max(array), ScalarValue::Float64)
+        }
+        ArrowDataType::Date32 => {
+            let array = array.as_primitive::<Date32Type>();
+            both(min(array), max(array), ScalarValue::Date32)
+        }
+        ArrowDataType::Utf8 => {
+            let array = array.as_string::<i32>();
+            both(min_string(array), max_string(array), utf8)
+        }
+        ArrowDataType::LargeUtf8 => {
+            let array = array.as_string::<i64>();
+            both(min_string(array), max_string(array), utf8)
+        }
+        ArrowDataType::Utf8View => {
+            let array = array.as_string_view();
+            both(min_string_view(array), max_string_view(array), utf8)
+        }
+        _ => None,
+    }
+}
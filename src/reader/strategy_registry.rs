@@ -0,0 +1,59 @@
+use std::{collections::HashMap, sync::Arc};
+
+use arrow::datatypes::{DataType as ArrowDataType, Field};
+use odbc_api::{sys::SqlDataType, DataType as OdbcDataType};
+
+use super::ReadStrategy;
+
+/// Creates the strategy for a single column. Invoked with the field to fill and the relational
+/// type of the column, as reported by the driver.
+pub type StrategyFactory =
+    Arc<dyn Fn(&Field, OdbcDataType) -> Box<dyn ReadStrategy + Send> + Send + Sync>;
+
+/// Driver specific strategies used to fetch columns, which take precedence over the built-in
+/// ones. This allows applications or separate crates to support vendor specific types (e.g.
+/// `DATETIMEOFFSET` in SQL Server) without changes to `arrow-odbc`. See
+/// [`crate::OdbcReaderBuilder::with_strategy_registry`].
+#[derive(Default, Clone)]
+pub struct ColumnStrategyRegistry {
+    /// Keyed by SQL type code and the Arrow type of the field
+    factories: HashMap<(i16, ArrowDataType), StrategyFactory>,
+}
+
+impl ColumnStrategyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `factory` for columns of the relational type `sql_type` fetched into fields of type
+    /// `arrow_type`. Columns are matched by their type code only, so e.g. the length of a `VARCHAR`
+    /// or the precision of a timestamp does not matter. The factory must return a strategy
+    /// emitting arrays of `arrow_type`. Replaces previous registrations for the same pair.
+    pub fn register(
+        &mut self,
+        sql_type: SqlDataType,
+        arrow_type: ArrowDataType,
+        factory: StrategyFactory,
+    ) -> &mut Self {
+        self.factories.insert((sql_type.0, arrow_type), factory);
+        self
+    }
+
+    /// `true` if no strategies have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+
+    /// Creates a strategy using the factory registered for the relational type of the column and
+    /// the type of `field`. `None` if there is none, implying the built-in strategies are used.
+    pub fn strategy_for(
+        &self,
+        field: &Field,
+        sql_type: OdbcDataType,
+    ) -> Option<Box<dyn ReadStrategy + Send>> {
+        let key = (sql_type.data_type().0, field.data_type().clone());
+        self.factories
+            .get(&key)
+            .map(|factory| factory(field, sql_type))
+    }
+}
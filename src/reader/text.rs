@@ -1,66 +1,258 @@
 use std::{char::decode_utf16, cmp::min, num::NonZeroUsize, sync::Arc};
 
-use arrow::array::{ArrayRef, StringBuilder};
+use arrow::{
+    array::{
+        ArrayRef, BinaryBuilder, LargeStringBuilder, StringBuilder, StringDictionaryBuilder,
+        StringViewBuilder,
+    },
+    datatypes::{DataType as ArrowDataType, Int32Type},
+};
 use odbc_api::{
     buffers::{AnySlice, BufferDesc},
     DataType as OdbcDataType,
 };
 
-use super::{ColumnFailure, MappingError, ReadStrategy};
+use super::{is_xml, ColumnFailure, MappingError, ReadStrategy};
+
+/// Arrow array type text columns are fetched into. See
+/// [`crate::OdbcReaderBuilder::with_text_output_mode`]. Further Arrow types may be added in minor
+/// releases.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TextOutputMode {
+    /// `StringArray` with 32 Bit offsets.
+    #[default]
+    Utf8,
+    /// `LargeStringArray` with 64 Bit offsets. Allows for more than 2 GiB of text in a single
+    /// batch.
+    LargeUtf8,
+    /// `StringViewArray`. Short strings are inlined into the views, which saves allocations for
+    /// tables with many small strings.
+    Utf8View,
+    /// `BinaryArray` holding the bytes of the text as transmitted by the driver, without checking
+    /// them to be valid UTF-8. An escape hatch for data sources using other encodings (e.g.
+    /// Latin-1 or Shift-JIS), leaving it to the application to transcode the text. These columns
+    /// are always fetched as narrow text, even if they are reported as wide character types.
+    RawBytes,
+    /// `DictionaryArray` with `Int32` keys and `Utf8` values. Each distinct value is stored only
+    /// once per batch, which saves memory for low cardinality columns, like categories or status
+    /// names.
+    Dictionary,
+}
 
 /// This function decides wether this column will be queried as narrow (assumed to be utf-8) or
 /// wide text (assumed to be utf-16). The reason we do not always use narrow is that the encoding
-/// dependends on the system locals which is usually not UTF-8 on windows systems. Furthermore we
-/// are trying to adapt the buffer size to the maximum string length the column could contain.
+/// dependends on the system locals which is usually not UTF-8 on windows systems. Columns the
+/// driver reports as wide character types (`NCHAR`, `NVARCHAR`) are queried as wide text on every
+/// platform, since not every driver manager transcodes them to the narrow encoding correctly.
+/// Drivers report no maximum length for XML columns, so `max_text_size` must be set in order to
+/// fetch them with this strategy. [`super::Xml`] sizes its buffers with a default instead.
+/// Furthermore we are trying to adapt the buffer size to the maximum string length the column
+/// could contain. If the relational type does not tell it, we ask for the display size of the
+/// column, unless `max_text_size` is set. In that case the limit is used directly, so drivers
+/// which fail to report display sizes can still be used.
 pub fn choose_text_strategy(
     sql_type: OdbcDataType,
     lazy_display_size: impl FnOnce() -> Result<Option<NonZeroUsize>, odbc_api::Error>,
     max_text_size: Option<usize>,
+    element_size_cap: Option<usize>,
     trim_fixed_sized_character_strings: bool,
+    trim_char_padding: bool,
+    output_mode: TextOutputMode,
 ) -> Result<Box<dyn ReadStrategy + Send>, ColumnFailure> {
-    let apply_buffer_limit = |len| match (len, max_text_size) {
+    // The cap also bounds columns without a known length, e.g. `VARCHAR(MAX)`.
+    let apply_buffer_limit = |len, cap: Option<usize>| match (len, min_limit(max_text_size, cap)) {
         (None, None) => Err(ColumnFailure::ZeroSizedColumn { sql_type }),
         (None, Some(limit)) => Ok(limit),
         (Some(len), None) => Ok(len),
         (Some(len), Some(limit)) => Ok(min(len, limit)),
     };
+    // A user provided limit makes the display size obsolete, so we save the roundtrip.
+    let column_len = |len: Option<NonZeroUsize>| match (len, max_text_size) {
+        (Some(len), _) => Ok(Some(len)),
+        (None, Some(_)) => Ok(None),
+        (None, None) => lazy_display_size(),
+    };
     let is_fixed_sized_char = matches!(
         sql_type,
         OdbcDataType::Char { .. } | OdbcDataType::WChar { .. }
     );
-    let trim = trim_fixed_sized_character_strings && is_fixed_sized_char;
-    let strategy: Box<dyn ReadStrategy + Send> = if cfg!(target_os = "windows") {
-        let hex_len = sql_type
-            .utf16_len()
-            .map(Ok)
-            .or_else(|| lazy_display_size().transpose())
-            .transpose()
+    let trim = match (is_fixed_sized_char, trim_fixed_sized_character_strings) {
+        (false, _) => Trim::Nothing,
+        (true, true) => Trim::Whitespace,
+        (true, false) if trim_char_padding => Trim::TrailingSpaces,
+        (true, false) => Trim::Nothing,
+    };
+    // XML is stored as UTF-16, so we fetch it the same way as `NVARCHAR`.
+    let is_wide_char = matches!(
+        sql_type,
+        OdbcDataType::WChar { .. } | OdbcDataType::WVarchar { .. }
+    ) || is_xml(&sql_type);
+    // Raw bytes are passed on as is, so there is no point in asking for UTF-16.
+    let fetch_wide =
+        (cfg!(target_os = "windows") || is_wide_char) && output_mode != TextOutputMode::RawBytes;
+    let strategy: Box<dyn ReadStrategy + Send> = if fetch_wide {
+        let hex_len = column_len(sql_type.utf16_len())
             .map_err(|source| ColumnFailure::UnknownStringLength { sql_type, source })?;
-        let hex_len = apply_buffer_limit(hex_len.map(NonZeroUsize::get))?;
-        wide_text_strategy(hex_len, trim)
+        // The cap is in bytes, yet the length is in UTF-16 code units
+        let hex_len = apply_buffer_limit(
+            hex_len.map(NonZeroUsize::get),
+            element_size_cap.map(|cap| cap / 2),
+        )?;
+        wide_text_strategy(hex_len, trim, output_mode)
     } else {
-        let octet_len = sql_type
-            .utf8_len()
-            .map(Ok)
-            .or_else(|| lazy_display_size().transpose())
-            .transpose()
+        let octet_len = column_len(sql_type.utf8_len())
             .map_err(|source| ColumnFailure::UnknownStringLength { sql_type, source })?;
-        let octet_len = apply_buffer_limit(octet_len.map(NonZeroUsize::get))?;
+        let octet_len = apply_buffer_limit(octet_len.map(NonZeroUsize::get), element_size_cap)?;
         // So far only Linux users seemed to have complained about panics due to garbage indices?
         // Linux usually would use UTF-8, so we only invest work in working around this for narrow
         // strategies
-        narrow_text_strategy(octet_len, trim)
+        narrow_text_strategy(octet_len, trim, output_mode)
     };
 
     Ok(strategy)
 }
 
-fn wide_text_strategy(u16_len: usize, trim: bool) -> Box<dyn ReadStrategy + Send> {
-    Box::new(WideText::new(u16_len, trim))
+/// The stricter of two optional limits.
+pub(super) fn min_limit(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(min(a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Whitespace removed from text fetched from fixed sized character columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trim {
+    /// Keep the text as is
+    Nothing,
+    /// Remove the spaces the database pads `CHAR` columns with
+    TrailingSpaces,
+    /// Remove leading and trailing whitespace
+    Whitespace,
+}
+
+impl Trim {
+    fn apply<'a>(&self, text: &'a str) -> &'a str {
+        match self {
+            Trim::Nothing => text,
+            Trim::TrailingSpaces => text.trim_end_matches(' '),
+            Trim::Whitespace => text.trim(),
+        }
+    }
+
+    /// Like [`Self::apply`], but for text in an unknown encoding. Only ASCII whitespace is removed.
+    fn apply_bytes<'a>(&self, bytes: &'a [u8]) -> &'a [u8] {
+        let end = |is_padding: fn(&u8) -> bool| {
+            bytes
+                .iter()
+                .rposition(|byte| !is_padding(byte))
+                .map_or(0, |last| last + 1)
+        };
+        match self {
+            Trim::Nothing => bytes,
+            Trim::TrailingSpaces => &bytes[..end(|&byte| byte == b' ')],
+            Trim::Whitespace => {
+                let end = end(u8::is_ascii_whitespace);
+                let start = bytes[..end]
+                    .iter()
+                    .position(|byte| !byte.is_ascii_whitespace())
+                    .unwrap_or(end);
+                &bytes[start..end]
+            }
+        }
+    }
+}
+
+fn wide_text_strategy(
+    u16_len: usize,
+    trim: Trim,
+    output_mode: TextOutputMode,
+) -> Box<dyn ReadStrategy + Send> {
+    Box::new(WideText::new(u16_len, trim, output_mode))
 }
 
-fn narrow_text_strategy(octet_len: usize, trim: bool) -> Box<dyn ReadStrategy + Send> {
-    Box::new(NarrowText::new(octet_len, trim))
+fn narrow_text_strategy(
+    octet_len: usize,
+    trim: Trim,
+    output_mode: TextOutputMode,
+) -> Box<dyn ReadStrategy + Send> {
+    Box::new(NarrowText::new(octet_len, trim, output_mode))
+}
+
+/// Builds the Arrow array type chosen by [`TextOutputMode`].
+enum TextBuilder {
+    Utf8(StringBuilder),
+    LargeUtf8(LargeStringBuilder),
+    Utf8View(StringViewBuilder),
+    Dictionary(StringDictionaryBuilder<Int32Type>),
+    /// Holds the UTF-8 representation of text, which has already been decoded. Narrow text skips
+    /// the decoding for [`TextOutputMode::RawBytes`] and appends the bytes directly.
+    Binary(BinaryBuilder),
+}
+
+impl TextBuilder {
+    fn new(output_mode: TextOutputMode, item_capacity: usize, data_capacity: usize) -> Self {
+        match output_mode {
+            TextOutputMode::Utf8 => {
+                Self::Utf8(StringBuilder::with_capacity(item_capacity, data_capacity))
+            }
+            TextOutputMode::LargeUtf8 => Self::LargeUtf8(LargeStringBuilder::with_capacity(
+                item_capacity,
+                data_capacity,
+            )),
+            // Views only need to store strings longer than 12 bytes out of line, so we do not
+            // reserve capacity for the data upfront.
+            TextOutputMode::Utf8View => {
+                Self::Utf8View(StringViewBuilder::with_capacity(item_capacity))
+            }
+            // We do not know the number of distinct values upfront, so we only reserve capacity for
+            // the keys.
+            TextOutputMode::Dictionary => {
+                Self::Dictionary(StringDictionaryBuilder::with_capacity(item_capacity, 0, 0))
+            }
+            TextOutputMode::RawBytes => {
+                Self::Binary(BinaryBuilder::with_capacity(item_capacity, data_capacity))
+            }
+        }
+    }
+
+    /// Fails rather than panics, if the text would overflow the 32 Bit offsets of a `Utf8` array.
+    fn append_option(&mut self, value: Option<&str>) -> Result<(), MappingError> {
+        match self {
+            Self::Utf8(builder) => {
+                let len = builder.values_slice().len() + value.map_or(0, str::len);
+                if len > i32::MAX as usize {
+                    return Err(MappingError::TextOffsetOverflow);
+                }
+                builder.append_option(value)
+            }
+            Self::LargeUtf8(builder) => builder.append_option(value),
+            Self::Utf8View(builder) => builder.append_option(value),
+            Self::Dictionary(builder) => match value {
+                Some(value) => {
+                    builder
+                        .append(value)
+                        .map_err(|_| MappingError::DictionaryKeyOverflow {
+                            key_type: ArrowDataType::Int32,
+                        })?;
+                }
+                None => builder.append_null(),
+            },
+            Self::Binary(builder) => builder.append_option(value),
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            Self::Utf8(builder) => Arc::new(builder.finish()),
+            Self::LargeUtf8(builder) => Arc::new(builder.finish()),
+            Self::Utf8View(builder) => Arc::new(builder.finish()),
+            Self::Dictionary(builder) => Arc::new(builder.finish()),
+            Self::Binary(builder) => Arc::new(builder.finish()),
+        }
+    }
 }
 
 /// Strategy requesting the text from the database as UTF-16 (Wide characters) and emmitting it as
@@ -70,16 +262,26 @@ pub struct WideText {
     /// Maximum string length in u16, excluding terminating zero
     max_str_len: usize,
     /// Wether the string should be trimmed.
-    trim: bool,
+    trim: Trim,
+    /// Type of the Arrow array we emit.
+    output_mode: TextOutputMode,
 }
 
 impl WideText {
-    pub fn new(max_str_len: usize, trim: bool) -> Self {
-        Self { max_str_len, trim }
+    pub fn new(max_str_len: usize, trim: Trim, output_mode: TextOutputMode) -> Self {
+        Self {
+            max_str_len,
+            trim,
+            output_mode,
+        }
     }
 }
 
 impl ReadStrategy for WideText {
+    fn strategy_type_name(&self) -> &'static str {
+        "WideText"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         BufferDesc::WText {
             max_str_len: self.max_str_len,
@@ -93,27 +295,22 @@ impl ReadStrategy for WideText {
         // this is 1 to one, and also not every string is likeyl to use its maximum capacity, we
         // rather accept the reallocation in these scenarios.
         let data_capacity = self.max_str_len * item_capacity;
-        let mut builder = StringBuilder::with_capacity(item_capacity, data_capacity);
+        let mut builder = TextBuilder::new(self.output_mode, item_capacity, data_capacity);
         // Buffer used to convert individual values from utf16 to utf8.
         let mut buf_utf8 = String::new();
         for value in view.iter() {
             buf_utf8.clear();
             let opt = if let Some(utf16) = value {
                 for c in decode_utf16(utf16.as_slice().iter().cloned()) {
-                    buf_utf8.push(c.unwrap());
+                    buf_utf8.push(c.map_err(|source| MappingError::InvalidUtf16 { source })?);
                 }
-                let slice = if self.trim {
-                    buf_utf8.trim()
-                } else {
-                    buf_utf8.as_str()
-                };
-                Some(slice)
+                Some(self.trim.apply(&buf_utf8))
             } else {
                 None
             };
-            builder.append_option(opt);
+            builder.append_option(opt)?;
         }
-        Ok(Arc::new(builder.finish()))
+        Ok(builder.finish())
     }
 }
 
@@ -121,16 +318,26 @@ pub struct NarrowText {
     /// Maximum string length in u8, excluding terminating zero
     max_str_len: usize,
     /// Wether the string should be trimmed.
-    trim: bool,
+    trim: Trim,
+    /// Type of the Arrow array we emit.
+    output_mode: TextOutputMode,
 }
 
 impl NarrowText {
-    pub fn new(max_str_len: usize, trim: bool) -> Self {
-        Self { max_str_len, trim }
+    pub fn new(max_str_len: usize, trim: Trim, output_mode: TextOutputMode) -> Self {
+        Self {
+            max_str_len,
+            trim,
+            output_mode,
+        }
     }
 }
 
 impl ReadStrategy for NarrowText {
+    fn strategy_type_name(&self) -> &'static str {
+        "NarrowText"
+    }
+
     fn buffer_desc(&self) -> BufferDesc {
         BufferDesc::Text {
             max_str_len: self.max_str_len,
@@ -139,18 +346,67 @@ impl ReadStrategy for NarrowText {
 
     fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
         let view = column_view.as_text_view().unwrap();
-        let mut builder = StringBuilder::with_capacity(view.len(), self.max_str_len * view.len());
+        let mut builder =
+            TextBuilder::new(self.output_mode, view.len(), self.max_str_len * view.len());
+        if let TextBuilder::Binary(builder) = &mut builder {
+            for value in view.iter() {
+                builder.append_option(value.map(|bytes| self.trim.apply_bytes(bytes)));
+            }
+            return Ok(Arc::new(builder.finish()));
+        }
         for value in view.iter() {
-            builder.append_option(value.map(|bytes| {
-                let untrimmed = std::str::from_utf8(bytes)
-                    .expect("ODBC driver had been expected to return valid utf8, but did not.");
-                if self.trim {
-                    untrimmed.trim()
-                } else {
-                    untrimmed
-                }
-            }));
+            let opt = value
+                .map(|bytes| {
+                    let untrimmed =
+                        std::str::from_utf8(bytes).map_err(|source| MappingError::InvalidUtf8 {
+                            value: String::from_utf8_lossy(bytes).into_owned(),
+                            source,
+                        })?;
+                    Ok(self.trim.apply(untrimmed))
+                })
+                .transpose()?;
+            builder.append_option(opt)?;
         }
-        Ok(Arc::new(builder.finish()))
+        Ok(builder.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use odbc_api::DataType as OdbcDataType;
+
+    use super::{choose_text_strategy, TextOutputMode};
+
+    /// Drivers which fail to report the display size of a column must not fail choosing a text
+    /// strategy, if the user provided an upper bound anyway.
+    #[test]
+    fn max_text_size_makes_display_size_obsolete() {
+        // Given a column without a known length
+        let sql_type = OdbcDataType::Varchar { length: None };
+        let lazy_display_size = || -> Result<Option<NonZeroUsize>, odbc_api::Error> {
+            panic!("Display size must not be queried")
+        };
+
+        // When
+        let strategy = choose_text_strategy(
+            sql_type,
+            lazy_display_size,
+            Some(10),
+            None,
+            false,
+            true,
+            TextOutputMode::Utf8,
+        )
+        .unwrap();
+
+        // Then
+        let expected = if cfg!(target_os = "windows") {
+            "WideText"
+        } else {
+            "NarrowText"
+        };
+        assert_eq!(expected, strategy.strategy_type_name());
     }
 }
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{ArrayRef, Time32MillisecondArray, Time64MicrosecondArray, Time64NanosecondArray},
+    datatypes::TimeUnit,
+};
+use odbc_api::buffers::{AnySlice, BufferDesc};
+
+use super::{MappingError, ReadStrategy};
+
+/// Fetches times with sub-second precision. The `SQL_C_TYPE_TIME` struct of ODBC only has fields
+/// for hours, minutes and seconds, so the values are fetched as text instead (e.g.
+/// `HH:MM:SS.fffffff`) and parsed into the elapsed time since midnight.
+pub struct TextTimeConversion {
+    unit: TimeUnit,
+}
+
+impl TextTimeConversion {
+    /// `unit` must be `Millisecond` (`Time32`), `Microsecond` or `Nanosecond` (`Time64`).
+    pub fn new(unit: TimeUnit) -> Self {
+        Self { unit }
+    }
+
+    /// Number of fractional digits representable in the unit.
+    fn fraction_digits(&self) -> u32 {
+        match self.unit {
+            TimeUnit::Second => 0,
+            TimeUnit::Millisecond => 3,
+            TimeUnit::Microsecond => 6,
+            TimeUnit::Nanosecond => 9,
+        }
+    }
+}
+
+impl ReadStrategy for TextTimeConversion {
+    fn strategy_type_name(&self) -> &'static str {
+        "Time(text)"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Text {
+            // Large enough for `HH:MM:SS.FFFFFFFFF`, even if the driver appends a terminating space
+            max_str_len: 19,
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let view = column_view.as_text_view().unwrap();
+        let digits = self.fraction_digits();
+        let values = view.iter().map(|opt| {
+            opt.map(|text| {
+                parse_time(text, digits).ok_or_else(|| MappingError::TimeParseError {
+                    raw_value: text.to_owned(),
+                })
+            })
+            .transpose()
+        });
+        let array: ArrayRef = match self.unit {
+            TimeUnit::Millisecond => Arc::new(
+                values
+                    // Milliseconds of a day always fit into 32 Bit
+                    .map(|value| value.map(|opt| opt.map(|ms| ms as i32)))
+                    .collect::<Result<Time32MillisecondArray, _>>()?,
+            ),
+            TimeUnit::Microsecond => {
+                Arc::new(values.collect::<Result<Time64MicrosecondArray, _>>()?)
+            }
+            TimeUnit::Nanosecond => Arc::new(values.collect::<Result<Time64NanosecondArray, _>>()?),
+            TimeUnit::Second => unreachable!("Time32(Second) is not fetched as text"),
+        };
+        Ok(array)
+    }
+}
+
+/// Parses `HH:MM:SS` with an optional fraction of a second into the elapsed time since midnight,
+/// in units of `10^-digits` seconds. Fractional digits exceeding the precision are truncated.
+/// `None` if the text is not a valid time of day.
+fn parse_time(text: &[u8], digits: u32) -> Option<i64> {
+    let text = std::str::from_utf8(text).ok()?.trim();
+    let (hms, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let mut parts = hms.split(':');
+    let mut next_component = |max: i64| {
+        let part = parts.next()?;
+        if part.is_empty() || !part.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        part.parse::<i64>().ok().filter(|&value| value <= max)
+    };
+    let hour = next_component(23)?;
+    let minute = next_component(59)?;
+    let second = next_component(59)?;
+    if parts.next().is_some() || !fraction.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let mut sub_seconds = 0;
+    for index in 0..digits as usize {
+        let digit = fraction.as_bytes().get(index).map_or(0, |&c| c - b'0');
+        sub_seconds = sub_seconds * 10 + i64::from(digit);
+    }
+    let seconds = hour * 3600 + minute * 60 + second;
+    Some(seconds * 10_i64.pow(digits) + sub_seconds)
+}
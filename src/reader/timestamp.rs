@@ -0,0 +1,452 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use arrow::{
+    array::{
+        timezone::Tz, Array, ArrayRef, AsArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+    },
+    datatypes::{
+        ArrowPrimitiveType, ArrowTimestampType, DataType as ArrowDataType, TimeUnit,
+        TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+        TimestampSecondType,
+    },
+    temporal_conversions::as_datetime,
+};
+use chrono::{NaiveDateTime, Offset, TimeDelta, TimeZone};
+use log::warn;
+use odbc_api::{
+    buffers::{AnySlice, BufferDesc},
+    sys::{SqlDataType, Timestamp},
+    DataType as OdbcDataType,
+};
+
+use crate::date_time::{ms_since_epoch, naive_date_time, seconds_since_epoch, us_since_epoch};
+
+use super::{MapOdbcToArrow, MappingError, ReadStrategy};
+
+/// Controls what happens to the fraction of an ODBC timestamp, which is finer than the unit of
+/// the Arrow `Timestamp` field. ODBC transmits fractions of a second in nanoseconds, so
+/// `Timestamp(Nanosecond, _)` fields never lose precision. See
+/// [`crate::OdbcReaderBuilder::with_sub_precision_handling`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubPrecisionHandling {
+    /// Cut off the digits finer than the unit. E.g. `12:00:00.1239` becomes `12:00:00.123` for
+    /// `Timestamp(Millisecond, _)`.
+    #[default]
+    Truncate,
+    /// Round half up to the nearest value representable in the unit. E.g. `12:00:00.1235` becomes
+    /// `12:00:00.124` for `Timestamp(Millisecond, _)`.
+    Round,
+    /// Truncate, but log a warning the first time a value of the column loses precision.
+    Warn,
+    /// Fail with [`MappingError::SubPrecisionLoss`] for values which would lose precision.
+    Error,
+}
+
+/// Chooses the strategy for `Timestamp(Second, _)`, `Timestamp(Millisecond, _)` and
+/// `Timestamp(Microsecond, _)` fields.
+pub fn timestamp_strategy(
+    unit: TimeUnit,
+    nullable: bool,
+    handling: SubPrecisionHandling,
+    map_value_errors_to_null: bool,
+) -> Box<dyn ReadStrategy + Send> {
+    match unit {
+        TimeUnit::Second => with_handling::<TimestampSecondType>(
+            unit,
+            nullable,
+            handling,
+            map_value_errors_to_null,
+            seconds_since_epoch,
+        ),
+        TimeUnit::Millisecond => with_handling::<TimestampMillisecondType>(
+            unit,
+            nullable,
+            handling,
+            map_value_errors_to_null,
+            ms_since_epoch,
+        ),
+        TimeUnit::Microsecond => with_handling::<TimestampMicrosecondType>(
+            unit,
+            nullable,
+            handling,
+            map_value_errors_to_null,
+            us_since_epoch,
+        ),
+        TimeUnit::Nanosecond => unreachable!("Nanosecond timestamps never lose precision"),
+    }
+}
+
+fn with_handling<T>(
+    unit: TimeUnit,
+    nullable: bool,
+    handling: SubPrecisionHandling,
+    map_value_errors_to_null: bool,
+    since_epoch: fn(&Timestamp) -> i64,
+) -> Box<dyn ReadStrategy + Send>
+where
+    T: ArrowPrimitiveType<Native = i64> + Send,
+{
+    let nanos_per_unit = nanos_per_unit(unit);
+    match handling {
+        SubPrecisionHandling::Truncate => T::map_infalliable(nullable, since_epoch),
+        SubPrecisionHandling::Round => T::map_infalliable(nullable, move |value: &Timestamp| {
+            let units_per_second = 1_000_000_000 / nanos_per_unit;
+            let fraction = (value.fraction as i64 + nanos_per_unit / 2) / nanos_per_unit;
+            seconds_since_epoch(value) * units_per_second + fraction
+        }),
+        SubPrecisionHandling::Warn => {
+            // Warn only once per column, rather than flooding the log for every value.
+            let warned = AtomicBool::new(false);
+            T::map_infalliable(nullable, move |value: &Timestamp| {
+                if value.fraction as i64 % nanos_per_unit != 0
+                    && !warned.swap(true, Ordering::Relaxed)
+                {
+                    warn!(
+                        "Timestamp {} has been truncated to {unit:?} precision. Further truncated \
+                        values of this column are not reported.",
+                        naive_date_time(value)
+                    );
+                }
+                since_epoch(value)
+            })
+        }
+        SubPrecisionHandling::Error => T::map_falliable(
+            nullable,
+            map_value_errors_to_null,
+            move |value: &Timestamp| {
+                if value.fraction as i64 % nanos_per_unit != 0 {
+                    Err(MappingError::SubPrecisionLoss {
+                        value: naive_date_time(value),
+                        unit,
+                    })
+                } else {
+                    Ok(since_epoch(value))
+                }
+            },
+        ),
+    }
+}
+
+fn nanos_per_unit(unit: TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Second => 1_000_000_000,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    }
+}
+
+/// Interprets the timestamps filled by another strategy as local time of `timezone` and converts
+/// them to UTC, as Arrow demands for timestamps with a timezone. See
+/// [`crate::OdbcReaderBuilder::with_timestamp_timezone`]. Local times which are ambiguous, since
+/// they occur twice at the end of daylight saving time, are mapped to the earlier instant.
+pub struct LocalTimestamp {
+    inner: Box<dyn ReadStrategy + Send>,
+    unit: TimeUnit,
+    timezone: Tz,
+}
+
+impl LocalTimestamp {
+    /// `inner` must emit timestamps of `unit`.
+    pub fn new(inner: Box<dyn ReadStrategy + Send>, unit: TimeUnit, timezone: Tz) -> Self {
+        Self {
+            inner,
+            unit,
+            timezone,
+        }
+    }
+
+    fn to_utc<T>(&self, local: &dyn Array) -> Result<ArrayRef, MappingError>
+    where
+        T: ArrowTimestampType,
+    {
+        let units_per_second = 1_000_000_000 / nanos_per_unit(self.unit);
+        let ArrowDataType::Timestamp(_, timezone) = local.data_type() else {
+            unreachable!("Inner strategy must emit timestamps")
+        };
+        let utc = local
+            .as_primitive::<T>()
+            .try_unary::<_, T, _>(|value| {
+                // Only fails for values outside the range of chrono, which ODBC can not express
+                let local = as_datetime::<T>(value).unwrap();
+                let offset = self
+                    .timezone
+                    .offset_from_local_datetime(&local)
+                    .earliest()
+                    .ok_or(MappingError::NonexistentLocalTime { value: local })?;
+                let offset = i64::from(offset.fix().local_minus_utc()) * units_per_second;
+                value
+                    .checked_sub(offset)
+                    .ok_or_else(|| out_of_range(local, self.unit))
+            })?
+            .with_timezone_opt(timezone.clone());
+        Ok(Arc::new(utc))
+    }
+}
+
+/// Error for `value`, which does not fit into a timestamp of `unit` after conversion to UTC.
+/// Nanoseconds are reported like any other out of range value with nanoseconds precision.
+fn out_of_range(value: NaiveDateTime, unit: TimeUnit) -> MappingError {
+    match unit {
+        TimeUnit::Nanosecond => MappingError::OutOfRangeTimestampNs { value },
+        unit => MappingError::OutOfRangeTimestamp { value, unit },
+    }
+}
+
+impl ReadStrategy for LocalTimestamp {
+    fn strategy_type_name(&self) -> &'static str {
+        "LocalTimestamp"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.inner.buffer_desc()
+    }
+
+    fn element_size_hint(&self) -> Option<usize> {
+        self.inner.element_size_hint()
+    }
+
+    fn estimated_memory_per_row(&self) -> usize {
+        self.inner.estimated_memory_per_row()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let local = self.inner.fill_arrow_array(column_view)?;
+        match self.unit {
+            TimeUnit::Second => self.to_utc::<TimestampSecondType>(&local),
+            TimeUnit::Millisecond => self.to_utc::<TimestampMillisecondType>(&local),
+            TimeUnit::Microsecond => self.to_utc::<TimestampMicrosecondType>(&local),
+            TimeUnit::Nanosecond => self.to_utc::<TimestampNanosecondType>(&local),
+        }
+    }
+}
+
+/// `true` if `sql_type` is `SQL_SS_TIMESTAMPOFFSET` (`-155`), e.g. `DATETIMEOFFSET` in Microsoft
+/// SQL Server.
+pub fn is_timestamp_with_offset(sql_type: &OdbcDataType) -> bool {
+    matches!(
+        sql_type,
+        OdbcDataType::Other {
+            data_type: SqlDataType(-155),
+            ..
+        }
+    )
+}
+
+/// Fetches timestamps with a UTC offset into `Timestamp(_, Some(_))` fields. The ODBC timestamp
+/// struct has no field for the offset, so the values are fetched as text instead (e.g.
+/// `2021-04-09 18:57:50.1234567 +02:00`), parsed and normalized to UTC. The timezone of the field
+/// is kept as is, so the values are displayed in the timezone the application asked for. Digits
+/// finer than the unit of the field are truncated.
+///
+/// The offset of each value is not preserved. An Arrow timestamp has one timezone for the entire
+/// array, and emitting the offsets as a sibling field would break the correspondence of one field
+/// to one column the reader relies on. Applications in need of the original offset can fetch the
+/// column into a `Utf8` field, or select it in addition, e.g. using `DATEPART(TZOFFSET, ...)`.
+pub struct TextTimestampWithOffset {
+    unit: TimeUnit,
+}
+
+impl TextTimestampWithOffset {
+    pub fn new(unit: TimeUnit) -> Self {
+        Self { unit }
+    }
+}
+
+impl ReadStrategy for TextTimestampWithOffset {
+    fn strategy_type_name(&self) -> &'static str {
+        "TimestampWithOffset(text)"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        BufferDesc::Text {
+            // Large enough for `YYYY-MM-DD HH:MM:SS.FFFFFFFFF +HH:MM`
+            max_str_len: 36,
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let view = column_view.as_text_view().unwrap();
+        let values = view.iter().map(|opt| {
+            opt.map(|text| {
+                let utc = parse_timestamp_with_offset(text).ok_or_else(|| {
+                    MappingError::TimestampWithOffsetParseError {
+                        raw_value: text.to_owned(),
+                    }
+                })?;
+                let utc = utc.and_utc();
+                let value = match self.unit {
+                    TimeUnit::Second => utc.timestamp(),
+                    TimeUnit::Millisecond => utc.timestamp_millis(),
+                    TimeUnit::Microsecond => utc.timestamp_micros(),
+                    TimeUnit::Nanosecond => {
+                        utc.timestamp_nanos_opt()
+                            .ok_or(MappingError::OutOfRangeTimestampNs {
+                                value: utc.naive_utc(),
+                            })?
+                    }
+                };
+                Ok(value)
+            })
+            .transpose()
+        });
+        let array: ArrayRef = match self.unit {
+            TimeUnit::Second => Arc::new(values.collect::<Result<TimestampSecondArray, _>>()?),
+            TimeUnit::Millisecond => {
+                Arc::new(values.collect::<Result<TimestampMillisecondArray, _>>()?)
+            }
+            TimeUnit::Microsecond => {
+                Arc::new(values.collect::<Result<TimestampMicrosecondArray, _>>()?)
+            }
+            TimeUnit::Nanosecond => {
+                Arc::new(values.collect::<Result<TimestampNanosecondArray, _>>()?)
+            }
+        };
+        Ok(array)
+    }
+}
+
+/// Parses `YYYY-MM-DD HH:MM:SS[.fffffffff]` followed by a UTC offset, into the timezone naive
+/// date and time in UTC. The offset may be separated by a space and be formatted as `+HH:MM`,
+/// `+HHMM`, `+HH` or `Z`. `None` if the text is not a valid timestamp with offset.
+fn parse_timestamp_with_offset(text: &[u8]) -> Option<NaiveDateTime> {
+    let text = std::str::from_utf8(text).ok()?.trim();
+    let (local, offset_seconds) = if let Some(local) = text.strip_suffix('Z') {
+        (local, 0)
+    } else {
+        // The date contains dashes, so we only search for the sign of the offset after it.
+        let sign_index = text.get(10..)?.rfind(&['+', '-'][..])? + 10;
+        let (local, offset) = text.split_at(sign_index);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let digits: String = offset[1..].chars().filter(|&c| c != ':').collect();
+        if !(digits.len() == 2 || digits.len() == 4) || !digits.bytes().all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+        let hours: i64 = digits[..2].parse().ok()?;
+        let minutes: i64 = if digits.len() == 4 {
+            digits[2..].parse().ok()?
+        } else {
+            0
+        };
+        if hours > 14 || minutes > 59 {
+            return None;
+        }
+        (local, sign * (hours * 60 + minutes) * 60)
+    };
+    let local = NaiveDateTime::parse_from_str(local.trim_end(), "%Y-%m-%d %H:%M:%S%.f").ok()?;
+    local.checked_sub_signed(TimeDelta::try_seconds(offset_seconds)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{timezone::Tz, TimestampNanosecondArray},
+        datatypes::{TimeUnit, TimestampNanosecondType},
+    };
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    use crate::reader::{decimal::Decimal, MappingError};
+
+    use super::{out_of_range, parse_timestamp_with_offset, LocalTimestamp};
+
+    fn utc(day: u32, hour: u32, min: u32, sec: u32, nano: u32) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(2021, 4, day)
+            .unwrap()
+            .and_hms_nano_opt(hour, min, sec, nano)
+    }
+
+    /// SQL Server reports `DATETIMEOFFSET` with seven fractional digits by default
+    #[test]
+    fn parse_timestamp_with_seven_fractional_digits() {
+        let parsed = parse_timestamp_with_offset(b"2021-04-09 18:57:50.1234567 +02:00");
+
+        assert_eq!(utc(9, 16, 57, 50, 123_456_700), parsed);
+    }
+
+    #[test]
+    fn parse_timestamp_with_negative_offset() {
+        let parsed = parse_timestamp_with_offset(b"2021-04-09 18:57:50 -05:30");
+
+        // Crosses midnight
+        assert_eq!(utc(10, 0, 27, 50, 0), parsed);
+    }
+
+    #[test]
+    fn parse_timestamp_in_utc() {
+        assert_eq!(
+            utc(9, 18, 57, 50, 0),
+            parse_timestamp_with_offset(b"2021-04-09 18:57:50Z")
+        );
+        assert_eq!(
+            utc(9, 18, 57, 50, 0),
+            parse_timestamp_with_offset(b"2021-04-09 18:57:50 Z")
+        );
+    }
+
+    #[test]
+    fn parse_offset_without_colon_or_minutes() {
+        assert_eq!(
+            utc(9, 16, 57, 50, 0),
+            parse_timestamp_with_offset(b"2021-04-09 18:57:50+0200")
+        );
+        assert_eq!(
+            utc(9, 16, 57, 50, 0),
+            parse_timestamp_with_offset(b"2021-04-09 18:57:50 +02")
+        );
+    }
+
+    #[test]
+    fn report_overflow_converting_local_time_to_utc() {
+        // Given the latest timestamp with nanoseconds precision as local time five hours behind UTC
+        let timezone: Tz = "-05:00".parse().unwrap();
+        // `to_utc` does not use the inner strategy
+        let strategy =
+            LocalTimestamp::new(Box::new(Decimal::new(1, 0)), TimeUnit::Nanosecond, timezone);
+        let local = TimestampNanosecondArray::from(vec![i64::MAX]);
+
+        // When
+        let result = strategy.to_utc::<TimestampNanosecondType>(&local);
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(MappingError::OutOfRangeTimestampNs { .. })
+        ));
+    }
+
+    #[test]
+    fn report_out_of_range_timestamps_with_their_unit() {
+        let value = utc(9, 18, 57, 50, 0).unwrap();
+
+        assert!(matches!(
+            out_of_range(value, TimeUnit::Nanosecond),
+            MappingError::OutOfRangeTimestampNs { .. }
+        ));
+        assert!(matches!(
+            out_of_range(value, TimeUnit::Microsecond),
+            MappingError::OutOfRangeTimestamp {
+                unit: TimeUnit::Microsecond,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn reject_timestamp_with_invalid_offset() {
+        assert_eq!(None, parse_timestamp_with_offset(b"2021-04-09 18:57:50"));
+        assert_eq!(
+            None,
+            parse_timestamp_with_offset(b"2021-04-09 18:57:50 +15:00")
+        );
+        assert_eq!(
+            None,
+            parse_timestamp_with_offset(b"2021-04-09 18:57:50 +02:60")
+        );
+        assert_eq!(None, parse_timestamp_with_offset(b"2021-04-09 18:57:50 +2"));
+    }
+}
@@ -1,74 +1,342 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use arrow::{
-    datatypes::{Schema, SchemaRef},
+    array::{new_null_array, timezone::Tz, Array, ArrayRef},
+    datatypes::{DataType as ArrowDataType, Field, Schema, SchemaRef},
     record_batch::RecordBatch,
 };
 use log::info;
-use odbc_api::{buffers::ColumnarAnyBuffer, ResultSetMetadata};
+use odbc_api::{
+    buffers::{AnyBuffer, ColumnarAnyBuffer, ColumnarBuffer},
+    ColumnDescription, DataType as OdbcDataType, ResultSetMetadata,
+};
+
+use crate::{
+    arrow_schema_from,
+    schema::{is_json_field, num_result_cols},
+    BufferAllocationOptions, ColumnFailure, Error,
+};
 
-use crate::{arrow_schema_from, BufferAllocationOptions, ColumnFailure, Error};
+use super::{
+    choose_column_strategy, coercion::coerce_schema, column_metadata::provenance_metadata,
+    integer_dictionary_strategy, is_timestamp_with_offset, opaque_binary_length, text_strategy,
+    ColumnStrategyRegistry, FixedSizedBinary, FloatCoercion, IntegerCoercion, LocalTimestamp,
+    MappingError, NormalizeJson, NullHook, PostFetchTransform, ReadStrategy, ReplaceNulls,
+    ScalarValue, SpannerCompatibility, TextOutputMode, TransformArray,
+};
 
-use super::{choose_column_strategy, MappingError, ReadStrategy};
+/// What to do about fields in the Arrow schema, which have no matching column in the result set.
+/// See [`crate::OdbcReaderBuilder::with_missing_column_behavior`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingColumnBehavior {
+    /// Fail to build the reader.
+    #[default]
+    Error,
+    /// Fill the field with NULLs. Requires the field to be nullable.
+    NullPad,
+}
 
 /// Transforms batches fetched from an ODBC data source in a
 /// [`odbc_api::bufferers::ColumnarAnyBuffer`] into arrow tables of the specified schemas. It also
 /// allocates the buffers to hold the ODBC batches with the matching buffer descriptions.
 pub struct ToRecordBatch {
-    /// Must contain one item for each field in [`Self::schema`]. Encapsulates all the column type
-    /// specific decisions which go into filling an Arrow array from an ODBC data source.
+    /// Contains one item for each field in [`Self::schema`], which is bound to a column of the
+    /// result set. Encapsulates all the column type specific decisions which go into filling an
+    /// Arrow array from an ODBC data source.
     column_strategies: Vec<Box<dyn ReadStrategy + Send>>,
+    /// One based indices of the columns in the ODBC result set, the strategy with the same position
+    /// in [`Self::column_strategies`] is bound to. Usually just `1..=n`, unless the columns are
+    /// matched by name.
+    column_indices: Vec<u16>,
+    /// One item for each field in [`Self::schema`]. Position of the strategy and buffer used to
+    /// fill it, or `None` if the field is missing in the result set and padded with NULLs.
+    buffer_indices: Vec<Option<usize>>,
     /// Arrow schema describing the arrays we want to fill from the Odbc data source.
     schema: SchemaRef,
+    /// Invoked for each NULL in the record batches we emit.
+    null_hook: Option<NullHook>,
+}
+
+/// Options of [`ToRecordBatch::new`] deciding on the schema and the strategies of the columns, in
+/// addition to the [`BufferAllocationOptions`]. See [`crate::OdbcReaderBuilder`] for their meaning.
+pub struct ConversionOptions<'a> {
+    pub map_value_errors_to_null: bool,
+    pub trim_fixed_sized_character_strings: bool,
+    pub timestamp_timezone: Option<Arc<str>>,
+    pub match_columns_by_name: bool,
+    /// Maps zero based column indices to the value used to replace NULLs in that column.
+    pub null_replacements: &'a HashMap<usize, ScalarValue>,
+    pub integer_coercion: IntegerCoercion,
+    pub float_coercion: FloatCoercion,
+    pub null_hook: Option<NullHook>,
+    pub text_output_mode: TextOutputMode,
+    /// Maps zero based column indices to the text output mode overriding `text_output_mode`.
+    pub column_text_output_modes: &'a HashMap<usize, TextOutputMode>,
+    pub missing_column_behavior: MissingColumnBehavior,
+    pub strategy_registry: &'a ColumnStrategyRegistry,
+    pub post_fetch_transforms: &'a HashMap<usize, PostFetchTransform>,
+    /// Maps zero based column indices to the values of a pre-populated dictionary.
+    pub dictionary_values: &'a HashMap<usize, Vec<i32>>,
+    pub spanner_compatibility: Option<SpannerCompatibility>,
+    pub rich_metadata: bool,
 }
 
 impl ToRecordBatch {
     pub fn new(
         cursor: &mut impl ResultSetMetadata,
         schema: Option<SchemaRef>,
-        buffer_allocation_options: BufferAllocationOptions,
-        map_value_errors_to_null: bool,
-        trim_fixed_sized_character_strings: bool,
+        mut buffer_allocation_options: BufferAllocationOptions,
+        options: ConversionOptions<'_>,
     ) -> Result<Self, Error> {
+        let ConversionOptions {
+            map_value_errors_to_null,
+            trim_fixed_sized_character_strings,
+            timestamp_timezone,
+            match_columns_by_name,
+            null_replacements,
+            integer_coercion,
+            float_coercion,
+            null_hook,
+            text_output_mode,
+            column_text_output_modes,
+            missing_column_behavior,
+            strategy_registry,
+            post_fetch_transforms,
+            dictionary_values,
+            spanner_compatibility,
+            rich_metadata,
+        } = options;
+        let text_output_mode = if buffer_allocation_options.text_as_binary_for_non_utf8 {
+            TextOutputMode::RawBytes
+        } else {
+            text_output_mode
+        };
+        let text_output_mode_of = |index: usize| {
+            column_text_output_modes
+                .get(&index)
+                .copied()
+                .unwrap_or(text_output_mode)
+        };
         // Infer schema if not given by the user
         let schema = if let Some(schema) = schema {
             schema
         } else {
-            Arc::new(arrow_schema_from(cursor, map_value_errors_to_null)?)
+            // Inferred fields already carry the sign reported by the driver. Unsigned integer
+            // fields stem from unsigned columns, so their values are reinterpreted without asking
+            // the driver for the sign of each column a second time.
+            buffer_allocation_options.allow_signed_unsigned_coercion = true;
+            let inferred = arrow_schema_from(cursor, map_value_errors_to_null)?;
+            Arc::new(coerce_schema(
+                inferred,
+                integer_coercion,
+                float_coercion,
+                text_output_mode,
+                column_text_output_modes,
+                spanner_compatibility.is_some(),
+            ))
+        };
+        let local_timezone = timestamp_timezone
+            .as_deref()
+            .map(str::parse::<Tz>)
+            .transpose()
+            .map_err(Error::InvalidTimezone)?;
+        let schema = if let Some(timezone) = timestamp_timezone {
+            Arc::new(annotate_timezone(&schema, timezone))
+        } else {
+            schema
+        };
+
+        // `None` for fields without a matching column in the result set
+        let matched_columns: Vec<Option<u16>> = if match_columns_by_name {
+            column_indices_by_name(cursor, &schema)?
+        } else {
+            // Only ask for the number of columns if we intend to do something about missing ones.
+            // Otherwise we leave it to the driver to complain about a column index out of range.
+            let num_cols = match missing_column_behavior {
+                MissingColumnBehavior::Error => usize::MAX,
+                MissingColumnBehavior::NullPad => num_result_cols(cursor)?.into(),
+            };
+            (1..=schema.fields().len())
+                .map(|col_index| (col_index <= num_cols).then(|| col_index.try_into().unwrap()))
+                .collect()
         };
 
-        let column_strategies: Vec<Box<dyn ReadStrategy + Send>> = schema
-            .fields()
-            .iter()
-            .enumerate()
-            .map(|(index, field)| {
-                let col_index = (index + 1).try_into().unwrap();
+        let mut column_strategies: Vec<Box<dyn ReadStrategy + Send>> = Vec::new();
+        let mut column_indices = Vec::new();
+        let mut buffer_indices = Vec::with_capacity(schema.fields().len());
+        // Fields fetched as opaque binary, since their type is not supported otherwise
+        let mut opaque_fields = Vec::new();
+        for (index, (field, col_index)) in schema.fields().iter().zip(matched_columns).enumerate() {
+            let Some(col_index) = col_index else {
+                let failure = match missing_column_behavior {
+                    MissingColumnBehavior::NullPad if field.is_nullable() => {
+                        buffer_indices.push(None);
+                        continue;
+                    }
+                    MissingColumnBehavior::NullPad => ColumnFailure::MissingColumnNotNullable,
+                    MissingColumnBehavior::Error => ColumnFailure::ColumnNotFound,
+                };
+                return Err(failure.into_crate_error(field.name().clone(), index));
+            };
+            // Described once, and shared by everything deciding on the strategy of the column
+            let sql_type = cursor.col_data_type(col_index).map_err(|cause| {
+                ColumnFailure::FailedToDescribeColumn(cause)
+                    .into_crate_error(field.name().clone(), index)
+            })?;
+            let mut choose = |field: &Field| {
+                if let (ArrowDataType::Dictionary(key_type, value_type), Some(values)) =
+                    (field.data_type(), dictionary_values.get(&index))
+                {
+                    return integer_dictionary_strategy(
+                        key_type,
+                        value_type,
+                        field.is_nullable(),
+                        Some(values),
+                    );
+                }
+                // Raw text shares the Arrow type with binary columns
+                if field.data_type() == &ArrowDataType::Binary
+                    && text_output_mode_of(index) == TextOutputMode::RawBytes
+                {
+                    if !matches!(
+                        sql_type,
+                        OdbcDataType::Binary { .. }
+                            | OdbcDataType::Varbinary { .. }
+                            | OdbcDataType::LongVarbinary { .. }
+                    ) {
+                        return text_strategy(
+                            cursor,
+                            col_index,
+                            sql_type,
+                            buffer_allocation_options,
+                            trim_fixed_sized_character_strings,
+                            TextOutputMode::RawBytes,
+                        );
+                    }
+                }
+                // Only ask for the relational type if there is a chance it is of any use
+                if !strategy_registry.is_empty() {
+                    if let Some(strategy) = strategy_registry.strategy_for(field, sql_type) {
+                        return Ok(strategy);
+                    }
+                }
                 choose_column_strategy(
                     field,
                     cursor,
                     col_index,
+                    sql_type,
                     buffer_allocation_options,
                     map_value_errors_to_null,
                     trim_fixed_sized_character_strings,
                 )
-                .map_err(|cause| cause.into_crate_error(field.name().clone(), index))
-            })
-            .collect::<Result<_, _>>()?;
+            };
+            let strategy = if let Some(replacement) = null_replacements.get(&index) {
+                // Bind a nullable buffer, even if the field is not nullable, so the driver is
+                // able to tell us about NULLs in the first place.
+                let nullable = field.as_ref().clone().with_nullable(true);
+                choose(&nullable).and_then(|strategy| {
+                    ReplaceNulls::new(strategy, field.data_type(), replacement)
+                        .map(|strategy| Box::new(strategy) as Box<dyn ReadStrategy + Send>)
+                })
+            } else {
+                choose(field)
+            };
+            let strategy = match strategy {
+                Err(ColumnFailure::UnsupportedArrowType(data_type))
+                    if buffer_allocation_options.opaque_binary_fallback =>
+                {
+                    let octet_length = cursor
+                        .col_octet_length(col_index)
+                        .map_err(ColumnFailure::FailedToDescribeColumn)
+                        .map_err(|cause| cause.into_crate_error(field.name().clone(), index))?;
+                    if let Some(length) = opaque_binary_length(sql_type, octet_length) {
+                        opaque_fields.push((index, length));
+                        Ok(Box::new(FixedSizedBinary::new(length)) as Box<dyn ReadStrategy + Send>)
+                    } else {
+                        Err(ColumnFailure::UnsupportedArrowType(data_type))
+                    }
+                }
+                other => other,
+            };
+            let mut strategy =
+                strategy.map_err(|cause| cause.into_crate_error(field.name().clone(), index))?;
+            if let (Some(timezone), ArrowDataType::Timestamp(unit, _)) =
+                (local_timezone, field.data_type())
+            {
+                // These carry their own offset and are normalized to UTC already
+                if !is_timestamp_with_offset(&sql_type) {
+                    strategy = Box::new(LocalTimestamp::new(strategy, *unit, timezone));
+                }
+            }
+            let normalize_json = matches!(
+                spanner_compatibility,
+                Some(SpannerCompatibility {
+                    normalize_json: true
+                })
+            );
+            if normalize_json && is_json_field(field) {
+                strategy = Box::new(NormalizeJson::new(strategy));
+            }
+            if let Some(transform) = post_fetch_transforms.get(&index) {
+                strategy = Box::new(TransformArray::new(strategy, transform.clone()));
+            }
+            buffer_indices.push(Some(column_strategies.len()));
+            column_strategies.push(strategy);
+            column_indices.push(col_index);
+        }
+        let schema = if opaque_fields.is_empty() {
+            schema
+        } else {
+            let mut fields: Vec<Field> = schema
+                .fields()
+                .iter()
+                .map(|field| field.as_ref().clone())
+                .collect();
+            for (index, length) in opaque_fields {
+                let data_type = ArrowDataType::FixedSizeBinary(length.try_into().unwrap());
+                fields[index] = fields[index].clone().with_data_type(data_type);
+            }
+            Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+        };
+        let schema = if rich_metadata {
+            Arc::new(annotate_provenance(
+                cursor,
+                &schema,
+                &buffer_indices,
+                &column_indices,
+            ))
+        } else {
+            schema
+        };
 
         Ok(ToRecordBatch {
             column_strategies,
+            column_indices,
+            buffer_indices,
             schema,
+            null_hook,
         })
     }
 
     /// Logs buffer description and sizes
     pub fn row_size_in_bytes(&self) -> usize {
         let mut total_bytes = 0;
-        for (read, field) in self.column_strategies.iter().zip(self.schema.fields()) {
+        let bound_fields = self
+            .schema
+            .fields()
+            .iter()
+            .zip(&self.buffer_indices)
+            .filter_map(|(field, buffer_index)| buffer_index.map(|_| field));
+        for (read, field) in self.column_strategies.iter().zip(bound_fields) {
             let name = field.name();
-            let desc = read.buffer_desc();
-            let bytes_per_row = desc.bytes_per_row();
-            info!("Column '{name}'\nBytes used per row: {bytes_per_row}");
+            let bytes_per_row = read.estimated_memory_per_row();
+            info!(
+                "Column '{name}'\nRead strategy: {}\nBytes used per row: {bytes_per_row}",
+                read.strategy_type_name()
+            );
+            if let Some(element_size) = read.element_size_hint() {
+                info!("Column '{name}'\nBytes per element: {element_size}");
+            }
             total_bytes += bytes_per_row;
         }
         info!("Total memory usage per row for single transit buffer: {total_bytes}");
@@ -80,13 +348,27 @@ impl ToRecordBatch {
         max_batch_size: usize,
         fallibale_allocations: bool,
     ) -> Result<ColumnarAnyBuffer, Error> {
-        let descs = self.column_strategies.iter().map(|cs| cs.buffer_desc());
+        let descs = self
+            .column_indices
+            .iter()
+            .zip(&self.column_strategies)
+            .map(|(&col_index, cs)| (col_index, cs.buffer_desc()));
 
         let row_set_buffer = if fallibale_allocations {
-            ColumnarAnyBuffer::try_from_descs(max_batch_size, descs)
-                .map_err(|err| map_allocation_error(err, &self.schema))?
+            let columns = descs
+                .enumerate()
+                .map(|(buffer_index, (col_index, desc))| {
+                    AnyBuffer::try_from_desc(max_batch_size, desc)
+                        .map(|buffer| (col_index, buffer))
+                        .map_err(|source| {
+                            let err = source.add_context(buffer_index.try_into().unwrap());
+                            map_allocation_error(err, &self.schema, &self.buffer_indices)
+                        })
+                })
+                .collect::<Result<_, _>>()?;
+            ColumnarBuffer::new(columns)
         } else {
-            ColumnarAnyBuffer::from_descs(max_batch_size, descs)
+            ColumnarAnyBuffer::from_descs_and_indices(max_batch_size, descs)
         };
         Ok(row_set_buffer)
     }
@@ -95,17 +377,38 @@ impl ToRecordBatch {
         &self.schema
     }
 
+    /// Copies the contents of `odbc_buffer` into a record batch. `inspect` is called with each
+    /// array right after it has been filled.
     pub fn buffer_to_record_batch(
         &self,
         odbc_buffer: &ColumnarAnyBuffer,
+        mut inspect: impl FnMut(&ArrayRef),
     ) -> Result<RecordBatch, MappingError> {
+        let num_rows = odbc_buffer.num_rows();
         let arrow_columns = self
-            .column_strategies
+            .schema
+            .fields()
             .iter()
+            .zip(&self.buffer_indices)
             .enumerate()
-            .map(|(index, strat)| {
-                let column_view = odbc_buffer.column(index);
-                strat.fill_arrow_array(column_view)
+            .map(|(index, (field, &buffer_index))| {
+                let Some(buffer_index) = buffer_index else {
+                    let array = new_null_array(field.data_type(), num_rows);
+                    inspect(&array);
+                    return Ok(array);
+                };
+                let column_view = odbc_buffer.column(buffer_index);
+                let array = self.column_strategies[buffer_index].fill_arrow_array(column_view)?;
+                if let Some(hook) = &self.null_hook {
+                    if array.null_count() != 0 {
+                        (0..array.len())
+                            .filter(|&row_index| array.is_null(row_index))
+                            .for_each(|row_index| hook(index, row_index));
+                    }
+                }
+                // While the array is still in the cache
+                inspect(&array);
+                Ok(array)
             })
             .collect::<Result<Vec<_>, _>>()?;
         let record_batch = RecordBatch::try_new(self.schema.clone(), arrow_columns).unwrap();
@@ -113,20 +416,116 @@ impl ToRecordBatch {
     }
 }
 
-fn map_allocation_error(error: odbc_api::Error, schema: &Schema) -> Error {
+/// Replaces the timezone of every timestamp field in the schema with `timezone`. The values are
+/// converted accordingly by [`LocalTimestamp`].
+fn annotate_timezone(schema: &Schema, timezone: Arc<str>) -> Schema {
+    let fields: Vec<_> = schema
+        .fields()
+        .iter()
+        .map(|field| match field.data_type() {
+            ArrowDataType::Timestamp(unit, _) => field
+                .as_ref()
+                .clone()
+                .with_data_type(ArrowDataType::Timestamp(*unit, Some(timezone.clone()))),
+            _ => field.as_ref().clone(),
+        })
+        .collect();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+/// Adds the `odbc.` prefixed provenance metadata of the bound column to each field, keeping the
+/// metadata the field has already. Fields padded with NULLs have no column to describe.
+fn annotate_provenance(
+    cursor: &mut impl ResultSetMetadata,
+    schema: &Schema,
+    buffer_indices: &[Option<usize>],
+    column_indices: &[u16],
+) -> Schema {
+    let fields: Vec<_> = schema
+        .fields()
+        .iter()
+        .zip(buffer_indices)
+        .map(|(field, buffer_index)| {
+            let field = field.as_ref().clone();
+            let Some(buffer_index) = buffer_index else {
+                return field;
+            };
+            let mut metadata = field.metadata().clone();
+            metadata.extend(provenance_metadata(cursor, column_indices[*buffer_index]));
+            field.with_metadata(metadata)
+        })
+        .collect();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+/// Looks up the one based index of the result set column for each field in `schema` by name. Names
+/// are compared case insensitive, since many databases do not preserve the case of identifiers.
+fn column_indices_by_name(
+    cursor: &mut impl ResultSetMetadata,
+    schema: &Schema,
+) -> Result<Vec<Option<u16>>, Error> {
+    let num_cols = num_result_cols(cursor)?;
+    let mut column_names = Vec::with_capacity(num_cols as usize);
+    let mut column_description = ColumnDescription::default();
+    for col_index in 1..=num_cols {
+        cursor
+            .describe_col(col_index, &mut column_description)
+            .map_err(|cause| Error::ColumnFailure {
+                name: "Unknown".to_owned(),
+                index: (col_index - 1) as usize,
+                source: ColumnFailure::FailedToDescribeColumn(cause),
+            })?;
+        let name = column_description
+            .name_to_string()
+            .map_err(|source| Error::EncodingInvalid { source })?;
+        column_names.push(name.to_lowercase());
+    }
+
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let name = field.name().to_lowercase();
+            let mut matches = column_names
+                .iter()
+                .enumerate()
+                .filter(|(_, column_name)| **column_name == name)
+                .map(|(position, _)| position);
+            match (matches.next(), matches.next()) {
+                (Some(position), None) => Ok(Some((position + 1).try_into().unwrap())),
+                (None, _) => Ok(None),
+                (Some(_), Some(_)) => Err(ColumnFailure::AmbiguousColumnName
+                    .into_crate_error(field.name().clone(), index)),
+            }
+        })
+        .collect()
+}
+
+fn map_allocation_error(
+    error: odbc_api::Error,
+    schema: &Schema,
+    buffer_indices: &[Option<usize>],
+) -> Error {
     match error {
         odbc_api::Error::TooLargeColumnBufferSize {
             buffer_index,
             num_elements,
             element_size,
-        } => Error::ColumnFailure {
-            name: schema.field(buffer_index as usize).name().clone(),
-            index: buffer_index as usize,
-            source: ColumnFailure::TooLarge {
-                num_elements,
-                element_size,
-            },
-        },
+        } => {
+            let index = buffer_indices
+                .iter()
+                .position(|&index| index == Some(buffer_index as usize))
+                .unwrap();
+            Error::ColumnFailure {
+                name: schema.field(index).name().clone(),
+                index,
+                source: ColumnFailure::TooLarge {
+                    num_elements,
+                    element_size,
+                },
+            }
+        }
         _ => {
             panic!("Unexpected error in upstream ODBC api error library")
         }
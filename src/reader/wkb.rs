@@ -0,0 +1,665 @@
+use std::{cmp::min, ops::Range, sync::Arc};
+
+use arrow::array::{ArrayRef, BinaryBuilder};
+use odbc_api::{
+    buffers::{AnySlice, BufferDesc},
+    sys::SqlDataType,
+    DataType as OdbcDataType,
+};
+
+use super::{MappingError, ReadStrategy};
+
+/// Maximum size in bytes of a spatial value, if [`crate::OdbcReaderBuilder::with_max_binary_size`]
+/// is not set. Drivers report no maximum length for spatial columns.
+pub const DEFAULT_MAX_SPATIAL_SIZE: usize = 64 * 1024;
+
+/// `true` for the names of spatial types, i.e. `geometry` and `geography` in SQL Server and
+/// PostGIS.
+fn is_spatial_type_name(type_name: &str) -> bool {
+    type_name.eq_ignore_ascii_case("geometry") || type_name.eq_ignore_ascii_case("geography")
+}
+
+/// How a data source transmits the values of a spatial column, which need to be converted into
+/// WKB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialEncoding {
+    /// Native serialization of the `geometry` and `geography` types of SQL Server, reported as
+    /// `SQL_SS_UDT` (`-151`). Points of `geography` values are stored latitude first.
+    SqlServer { is_geography: bool },
+    /// Extended WKB as hexadecimal text, which is how PostGIS values are transmitted by drivers
+    /// unaware of their type.
+    HexEwkb,
+}
+
+impl SpatialEncoding {
+    /// `None` if the column is not of a spatial type, or is transmitted in an encoding we do not
+    /// know how to convert. Binary columns are assumed to hold WKB already, e.g. the result of
+    /// `STAsBinary()`, and therefore also yield `None`.
+    pub fn of(sql_type: &OdbcDataType, type_name: &str) -> Option<Self> {
+        if !is_spatial_type_name(type_name) {
+            return None;
+        }
+        match sql_type {
+            OdbcDataType::Other {
+                data_type: SqlDataType(-151),
+                ..
+            } => Some(SpatialEncoding::SqlServer {
+                is_geography: type_name.eq_ignore_ascii_case("geography"),
+            }),
+            OdbcDataType::Char { .. }
+            | OdbcDataType::WChar { .. }
+            | OdbcDataType::Varchar { .. }
+            | OdbcDataType::WVarchar { .. }
+            | OdbcDataType::LongVarchar { .. } => Some(SpatialEncoding::HexEwkb),
+            _ => None,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            SpatialEncoding::SqlServer { .. } => "native serialization format of SQL Server",
+            SpatialEncoding::HexEwkb => "hexadecimal representation of extended WKB",
+        }
+    }
+}
+
+/// Fetches spatial values into `Binary` fields annotated with the `geoarrow.wkb` extension type.
+/// Geometries are converted into little endian ISO WKB. WKB has no place for the spatial reference
+/// identifier, so it is dropped. Values exceeding the buffer are reported by the truncation check
+/// of the reader, since a cut off geometry could not be converted anyway.
+pub struct Wkb {
+    encoding: SpatialEncoding,
+    /// Length of the transit buffer in bytes. Hexadecimal text takes twice the size of the
+    /// geometry.
+    buffer_len: usize,
+}
+
+impl Wkb {
+    /// `max_len` is the maximum size of a value in bytes. `element_size_cap` applies to the transit
+    /// buffer in addition to it.
+    pub fn new(encoding: SpatialEncoding, max_len: usize, element_size_cap: Option<usize>) -> Self {
+        let buffer_len = match encoding {
+            SpatialEncoding::SqlServer { .. } => max_len,
+            SpatialEncoding::HexEwkb => max_len * 2,
+        };
+        let buffer_len = element_size_cap.map_or(buffer_len, |cap| min(buffer_len, cap));
+        Self {
+            encoding,
+            buffer_len,
+        }
+    }
+
+    fn convert(&self, value: &[u8], wkb: &mut Vec<u8>) -> Option<()> {
+        match self.encoding {
+            SpatialEncoding::SqlServer { is_geography } => {
+                sql_server_to_wkb(value, is_geography, wkb)
+            }
+            SpatialEncoding::HexEwkb => hex_ewkb_to_wkb(value, wkb),
+        }
+    }
+}
+
+impl ReadStrategy for Wkb {
+    fn strategy_type_name(&self) -> &'static str {
+        "Wkb"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        match self.encoding {
+            SpatialEncoding::SqlServer { .. } => BufferDesc::Binary {
+                length: self.buffer_len,
+            },
+            SpatialEncoding::HexEwkb => BufferDesc::Text {
+                max_str_len: self.buffer_len,
+            },
+        }
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        let values: Box<dyn Iterator<Item = Option<&[u8]>> + '_> = match self.encoding {
+            SpatialEncoding::SqlServer { .. } => {
+                Box::new(column_view.as_bin_view().unwrap().iter())
+            }
+            SpatialEncoding::HexEwkb => Box::new(column_view.as_text_view().unwrap().iter()),
+        };
+        let mut builder = BinaryBuilder::new();
+        // Reused for the WKB of each value
+        let mut wkb = Vec::new();
+        for value in values {
+            if let Some(value) = value {
+                wkb.clear();
+                self.convert(value, &mut wkb)
+                    .ok_or(MappingError::InvalidGeometry {
+                        format: self.encoding.description(),
+                    })?;
+                builder.append_value(&wkb);
+            } else {
+                builder.append_null();
+            }
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+}
+
+/// Reads the values of a serialized geometry front to back. `None` once the bytes are exhausted.
+struct Bytes<'a> {
+    rest: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> Bytes<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            rest: bytes,
+            big_endian: false,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.rest.len() < len {
+            return None;
+        }
+        let (head, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.take(4)?.try_into().unwrap();
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.u32().map(|value| value as i32)
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        let bytes = self.take(8)?.try_into().unwrap();
+        Some(if self.big_endian {
+            f64::from_be_bytes(bytes)
+        } else {
+            f64::from_le_bytes(bytes)
+        })
+    }
+}
+
+/// Byte order mark, geometry type and dimensions of a little endian ISO WKB geometry. `kind` is
+/// the type of the two dimensional geometry, e.g. `1` for a point.
+fn write_header(wkb: &mut Vec<u8>, kind: u32, has_z: bool, has_m: bool) {
+    wkb.push(1);
+    let kind = kind + u32::from(has_z) * 1000 + u32::from(has_m) * 2000;
+    write_u32(wkb, kind);
+}
+
+fn write_u32(wkb: &mut Vec<u8>, value: u32) {
+    wkb.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_f64(wkb: &mut Vec<u8>, value: f64) {
+    wkb.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Collections nested deeper than this are rejected, rather than risking to overflow the stack
+/// converting malformed or malicious values. Real world geometries nest only a few levels deep.
+const MAX_NESTING_DEPTH: u32 = 32;
+
+/// Flags of the extended WKB used by PostGIS, set in the geometry type.
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// Converts extended WKB, as hexadecimal text, into ISO WKB. The spatial reference identifier is
+/// dropped and dimension flags are replaced with ISO geometry types. Plain WKB in either byte
+/// order is accepted as well. `None` if the value is malformed.
+fn hex_ewkb_to_wkb(hex: &[u8], wkb: &mut Vec<u8>) -> Option<()> {
+    let pairs = hex.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return None;
+    }
+    let bytes = pairs
+        .map(|pair| Some((hex_digit(pair[0])? << 4) | hex_digit(pair[1])?))
+        .collect::<Option<Vec<u8>>>()?;
+    let mut input = Bytes::new(&bytes);
+    ewkb_geometry(&mut input, wkb, 0)?;
+    input.rest.is_empty().then_some(())
+}
+
+fn hex_digit(digit: u8) -> Option<u8> {
+    char::from(digit).to_digit(16).map(|value| value as u8)
+}
+
+/// `depth` is the number of collections enclosing the geometry.
+fn ewkb_geometry(input: &mut Bytes, wkb: &mut Vec<u8>, depth: u32) -> Option<()> {
+    if depth > MAX_NESTING_DEPTH {
+        return None;
+    }
+    // Every geometry, including the ones nested in collections, states its own byte order.
+    input.big_endian = match input.u8()? {
+        0 => true,
+        1 => false,
+        _ => return None,
+    };
+    let raw_kind = input.u32()?;
+    if raw_kind & EWKB_SRID != 0 {
+        input.take(4)?;
+    }
+    // Dimensions are either flagged (extended WKB) or encoded in the thousands (ISO WKB)
+    let iso_kind = raw_kind & 0x0fff_ffff;
+    let kind = iso_kind % 1000;
+    let has_z = raw_kind & EWKB_Z != 0 || matches!(iso_kind / 1000, 1 | 3);
+    let has_m = raw_kind & EWKB_M != 0 || matches!(iso_kind / 1000, 2 | 3);
+    let dimensions = 2 + usize::from(has_z) + usize::from(has_m);
+    write_header(wkb, kind, has_z, has_m);
+    match kind {
+        // Point
+        1 => copy_points(input, wkb, 1, dimensions),
+        // LineString
+        2 => {
+            let num_points = input.u32()?;
+            write_u32(wkb, num_points);
+            copy_points(input, wkb, num_points, dimensions)
+        }
+        // Polygon
+        3 => {
+            let num_rings = input.u32()?;
+            write_u32(wkb, num_rings);
+            for _ in 0..num_rings {
+                let num_points = input.u32()?;
+                write_u32(wkb, num_points);
+                copy_points(input, wkb, num_points, dimensions)?;
+            }
+            Some(())
+        }
+        // MultiPoint, MultiLineString, MultiPolygon and GeometryCollection
+        4..=7 => {
+            let num_geometries = input.u32()?;
+            write_u32(wkb, num_geometries);
+            for _ in 0..num_geometries {
+                ewkb_geometry(input, wkb, depth + 1)?;
+            }
+            Some(())
+        }
+        _ => None,
+    }
+}
+
+/// Copies the coordinates of `num_points` points, converting them to little endian.
+fn copy_points(
+    input: &mut Bytes,
+    wkb: &mut Vec<u8>,
+    num_points: u32,
+    dimensions: usize,
+) -> Option<()> {
+    let num_ordinates = usize::try_from(num_points).ok()?.checked_mul(dimensions)?;
+    for _ in 0..num_ordinates {
+        write_f64(wkb, input.f64()?);
+    }
+    Some(())
+}
+
+/// Properties of the native serialization format of SQL Server.
+const SS_HAS_Z: u8 = 0x01;
+const SS_HAS_M: u8 = 0x02;
+const SS_SINGLE_POINT: u8 = 0x08;
+const SS_SINGLE_LINE_SEGMENT: u8 = 0x10;
+
+/// Shape of a geometry in the native serialization format of SQL Server.
+struct Shape {
+    /// Index of the enclosing collection, `-1` for the root.
+    parent: i32,
+    /// Index of the first figure of the shape, `-1` if the shape is empty.
+    figure: i32,
+    /// OpenGIS type, e.g. `1` for a point. Same as in WKB for the types WKB knows.
+    kind: u8,
+}
+
+/// Geometry decoded from the native serialization format of SQL Server. Shapes consist of
+/// figures (e.g. the rings of a polygon), which consist of points.
+struct SqlServerGeometry {
+    /// X and Y of each point. For `geography` longitude and latitude.
+    points: Vec<[f64; 2]>,
+    z: Option<Vec<f64>>,
+    m: Option<Vec<f64>>,
+    /// Index of the first point of each figure
+    figures: Vec<i32>,
+    shapes: Vec<Shape>,
+}
+
+/// Converts the native serialization format of SQL Server `geometry` and `geography` values into
+/// ISO WKB. `None` if the value is malformed or contains curves, which WKB can not represent.
+fn sql_server_to_wkb(bytes: &[u8], is_geography: bool, wkb: &mut Vec<u8>) -> Option<()> {
+    let mut input = Bytes::new(bytes);
+    let _srid = input.u32()?;
+    let version = input.u8()?;
+    if !matches!(version, 1 | 2) {
+        return None;
+    }
+    let properties = input.u8()?;
+    let num_points = if properties & SS_SINGLE_POINT != 0 {
+        1
+    } else if properties & SS_SINGLE_LINE_SEGMENT != 0 {
+        2
+    } else {
+        input.u32()? as usize
+    };
+    let mut points = Vec::new();
+    for _ in 0..num_points {
+        let (first, second) = (input.f64()?, input.f64()?);
+        // `geography` stores latitude first, yet WKB expects the longitude as X
+        points.push(if is_geography {
+            [second, first]
+        } else {
+            [first, second]
+        });
+    }
+    // Z and M values follow the points, if present
+    let mut ordinates = |is_present: bool| -> Option<Option<Vec<f64>>> {
+        if !is_present {
+            return Some(None);
+        }
+        (0..num_points)
+            .map(|_| input.f64())
+            .collect::<Option<_>>()
+            .map(Some)
+    };
+    let z = ordinates(properties & SS_HAS_Z != 0)?;
+    let m = ordinates(properties & SS_HAS_M != 0)?;
+    let (figures, shapes) = if properties & (SS_SINGLE_POINT | SS_SINGLE_LINE_SEGMENT) != 0 {
+        // A single figure and shape are implied
+        let kind = if properties & SS_SINGLE_POINT != 0 {
+            1
+        } else {
+            2
+        };
+        let root = Shape {
+            parent: -1,
+            figure: 0,
+            kind,
+        };
+        (vec![0], vec![root])
+    } else {
+        let num_figures = input.u32()?;
+        let mut figures = Vec::new();
+        for _ in 0..num_figures {
+            let attribute = input.u8()?;
+            // Arcs and composite curves, which are only known to version 2
+            if version == 2 && attribute >= 2 {
+                return None;
+            }
+            figures.push(input.i32()?);
+        }
+        let num_shapes = input.u32()?;
+        let mut shapes = Vec::new();
+        for _ in 0..num_shapes {
+            shapes.push(Shape {
+                parent: input.i32()?,
+                figure: input.i32()?,
+                kind: input.u8()?,
+            });
+        }
+        (figures, shapes)
+    };
+    let geometry = SqlServerGeometry {
+        points,
+        z,
+        m,
+        figures,
+        shapes,
+    };
+    geometry.write_shape(0, 0, wkb)
+}
+
+impl SqlServerGeometry {
+    /// `depth` is the number of collections enclosing the shape.
+    fn write_shape(&self, index: usize, depth: u32, wkb: &mut Vec<u8>) -> Option<()> {
+        if depth > MAX_NESTING_DEPTH {
+            return None;
+        }
+        let kind = self.shapes.get(index)?.kind;
+        write_header(wkb, kind.into(), self.z.is_some(), self.m.is_some());
+        let figures = self.figures_of(index)?;
+        match kind {
+            // Point
+            1 => {
+                if figures.is_empty() {
+                    // WKB represents empty points with NaN coordinates
+                    let dimensions =
+                        2 + usize::from(self.z.is_some()) + usize::from(self.m.is_some());
+                    for _ in 0..dimensions {
+                        write_f64(wkb, f64::NAN);
+                    }
+                    Some(())
+                } else {
+                    let points = self.points_of(figures.start)?;
+                    if points.len() != 1 {
+                        return None;
+                    }
+                    self.write_points(points, wkb);
+                    Some(())
+                }
+            }
+            // LineString
+            2 => {
+                if figures.is_empty() {
+                    write_u32(wkb, 0);
+                } else {
+                    let points = self.points_of(figures.start)?;
+                    write_u32(wkb, points.len().try_into().ok()?);
+                    self.write_points(points, wkb);
+                }
+                Some(())
+            }
+            // Polygon
+            3 => {
+                write_u32(wkb, figures.len().try_into().ok()?);
+                for figure in figures {
+                    let points = self.points_of(figure)?;
+                    write_u32(wkb, points.len().try_into().ok()?);
+                    self.write_points(points, wkb);
+                }
+                Some(())
+            }
+            // MultiPoint, MultiLineString, MultiPolygon and GeometryCollection
+            4..=7 => {
+                let children: Vec<usize> = (index + 1..self.shapes.len())
+                    .filter(|&child| self.shapes[child].parent == index as i32)
+                    .collect();
+                write_u32(wkb, children.len().try_into().ok()?);
+                for child in children {
+                    self.write_shape(child, depth + 1, wkb)?;
+                }
+                Some(())
+            }
+            // Curves and the full globe have no WKB representation
+            _ => None,
+        }
+    }
+
+    /// Figures of a shape without nested shapes. Empty if the shape is empty. They end where the
+    /// next non empty shape starts.
+    fn figures_of(&self, index: usize) -> Option<Range<usize>> {
+        let Ok(start) = usize::try_from(self.shapes[index].figure) else {
+            return Some(0..0);
+        };
+        let end = self.shapes[index + 1..]
+            .iter()
+            .find_map(|shape| usize::try_from(shape.figure).ok())
+            .unwrap_or(self.figures.len());
+        (start <= end && end <= self.figures.len()).then_some(start..end)
+    }
+
+    /// Points of a figure. They end where the next figure starts.
+    fn points_of(&self, figure: usize) -> Option<Range<usize>> {
+        let start = usize::try_from(self.figures[figure]).ok()?;
+        let end = match self.figures.get(figure + 1) {
+            Some(&next) => usize::try_from(next).ok()?,
+            None => self.points.len(),
+        };
+        (start <= end && end <= self.points.len()).then_some(start..end)
+    }
+
+    fn write_points(&self, points: Range<usize>, wkb: &mut Vec<u8>) {
+        for index in points {
+            let [x, y] = self.points[index];
+            write_f64(wkb, x);
+            write_f64(wkb, y);
+            if let Some(z) = &self.z {
+                write_f64(wkb, z[index]);
+            }
+            if let Some(m) = &self.m {
+                write_f64(wkb, m[index]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hex_ewkb_to_wkb, sql_server_to_wkb, MAX_NESTING_DEPTH};
+
+    /// Little endian ISO WKB of a geometry type followed by its ordinates
+    fn wkb_of(kind: u32, counts: &[u32], ordinates: &[f64]) -> Vec<u8> {
+        let mut wkb = vec![1];
+        wkb.extend_from_slice(&kind.to_le_bytes());
+        for count in counts {
+            wkb.extend_from_slice(&count.to_le_bytes());
+        }
+        for ordinate in ordinates {
+            wkb.extend_from_slice(&ordinate.to_le_bytes());
+        }
+        wkb
+    }
+
+    fn convert_hex(hex: &str) -> Option<Vec<u8>> {
+        let mut wkb = Vec::new();
+        hex_ewkb_to_wkb(hex.as_bytes(), &mut wkb).map(|()| wkb)
+    }
+
+    fn convert_sql_server(bytes: &[u8], is_geography: bool) -> Option<Vec<u8>> {
+        let mut wkb = Vec::new();
+        sql_server_to_wkb(bytes, is_geography, &mut wkb).map(|()| wkb)
+    }
+
+    /// `SELECT ST_AsEWKB('SRID=4326;POINT(1 2)')` in PostGIS
+    #[test]
+    fn ewkb_point_with_srid() {
+        let wkb = convert_hex("0101000020E6100000000000000000F03F0000000000000040");
+
+        assert_eq!(Some(wkb_of(1, &[], &[1., 2.])), wkb);
+    }
+
+    /// Plain WKB is accepted in either byte order
+    #[test]
+    fn big_endian_wkb_point() {
+        let wkb = convert_hex("00000000013FF00000000000004000000000000000");
+
+        assert_eq!(Some(wkb_of(1, &[], &[1., 2.])), wkb);
+    }
+
+    /// `SELECT ST_AsEWKB('LINESTRING Z (1 2 3, 4 5 6)')` in PostGIS. The dimension flag is
+    /// replaced by the ISO geometry type.
+    #[test]
+    fn ewkb_line_string_with_z() {
+        let wkb = convert_hex(
+            "010200008002000000000000000000F03F00000000000000400000000000000840000000000000104000\
+            000000000014400000000000001840",
+        );
+
+        assert_eq!(Some(wkb_of(1002, &[2], &[1., 2., 3., 4., 5., 6.])), wkb);
+    }
+
+    /// `SELECT ST_AsEWKB('MULTIPOINT((1 2))')` in PostGIS
+    #[test]
+    fn ewkb_multi_point() {
+        let wkb = convert_hex("0104000000010000000101000000000000000000F03F0000000000000040");
+
+        let mut expected = wkb_of(4, &[1], &[]);
+        expected.extend(wkb_of(1, &[], &[1., 2.]));
+        assert_eq!(Some(expected), wkb);
+    }
+
+    #[test]
+    fn reject_malformed_hex_ewkb() {
+        // Odd number of digits
+        assert_eq!(None, convert_hex("010"));
+        // Not hexadecimal
+        assert_eq!(None, convert_hex("0G"));
+        // Invalid byte order
+        assert_eq!(None, convert_hex("0201000000"));
+        // Truncated coordinates
+        assert_eq!(None, convert_hex("0101000000000000000000F03F"));
+        // Trailing bytes
+        assert_eq!(
+            None,
+            convert_hex("0101000000000000000000F03F000000000000004000")
+        );
+    }
+
+    /// Nesting collections must not be able to overflow the stack
+    #[test]
+    fn reject_ewkb_nested_too_deep() {
+        let collection = "010700000001000000";
+        let point = "0101000000000000000000F03F0000000000000040";
+        let nested = |depth: u32| {
+            let mut hex = collection.repeat(depth as usize);
+            hex.push_str(point);
+            hex
+        };
+
+        assert!(convert_hex(&nested(MAX_NESTING_DEPTH)).is_some());
+        assert_eq!(None, convert_hex(&nested(MAX_NESTING_DEPTH + 1)));
+    }
+
+    /// `geography::Point(47.5, -122.25, 4326)` in SQL Server. Latitude is stored first, yet WKB
+    /// expects the longitude as X.
+    #[test]
+    fn sql_server_geography_point() {
+        let mut bytes = vec![0xE6, 0x10, 0, 0, 1, 0x0C];
+        bytes.extend_from_slice(&47.5f64.to_le_bytes());
+        bytes.extend_from_slice(&(-122.25f64).to_le_bytes());
+
+        let wkb = convert_sql_server(&bytes, true);
+
+        assert_eq!(Some(wkb_of(1, &[], &[-122.25, 47.5])), wkb);
+    }
+
+    /// `geometry::STGeomFromText('POLYGON((0 0, 2 0, 2 2, 0 0))', 0)` in SQL Server
+    #[test]
+    fn sql_server_geometry_polygon() {
+        let mut bytes = vec![0, 0, 0, 0, 1, 0x04, 4, 0, 0, 0];
+        for ordinate in [0., 0., 2., 0., 2., 2., 0., 0.] {
+            bytes.extend_from_slice(&f64::to_le_bytes(ordinate));
+        }
+        // One figure starting at the first point, one polygon shape without parent
+        bytes.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[1, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 3]);
+
+        let wkb = convert_sql_server(&bytes, false);
+
+        assert_eq!(
+            Some(wkb_of(3, &[1, 4], &[0., 0., 2., 0., 2., 2., 0., 0.])),
+            wkb
+        );
+    }
+
+    /// Circular strings have no representation in WKB
+    #[test]
+    fn reject_sql_server_curve() {
+        let mut bytes = vec![0, 0, 0, 0, 2, 0x04, 3, 0, 0, 0];
+        for ordinate in [0., 0., 1., 1., 2., 0.] {
+            bytes.extend_from_slice(&f64::to_le_bytes(ordinate));
+        }
+        bytes.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0, 0]);
+        bytes.extend_from_slice(&[1, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 8]);
+
+        assert_eq!(None, convert_sql_server(&bytes, false));
+    }
+}
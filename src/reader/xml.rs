@@ -0,0 +1,83 @@
+use std::cmp::min;
+
+use arrow::array::ArrayRef;
+use odbc_api::{
+    buffers::{AnySlice, BufferDesc},
+    sys::SqlDataType,
+    DataType as OdbcDataType,
+};
+
+use super::{
+    text::{Trim, WideText},
+    MappingError, ReadStrategy, TextOutputMode,
+};
+
+/// Length in characters of XML documents, if neither
+/// [`crate::OdbcReaderBuilder::with_max_xml_size`] nor
+/// [`crate::OdbcReaderBuilder::with_max_text_size`] is set.
+pub const DEFAULT_MAX_XML_SIZE: usize = 64 * 1024;
+
+/// `true` if `sql_type` is an XML type, i.e. `SQL_SS_XML` (`-152`) in Microsoft SQL Server or
+/// `SQL_XML` (`-370`) in IBM DB2.
+pub fn is_xml(sql_type: &OdbcDataType) -> bool {
+    matches!(
+        sql_type,
+        OdbcDataType::Other {
+            data_type: SqlDataType(-152 | -370),
+            ..
+        }
+    )
+}
+
+/// `true` for text types without an upper bound for their length. Drivers may describe XML columns
+/// as these, e.g. DB2 if `MapXMLDescribe` is configured, in which case only the type name tells them
+/// apart from other text.
+pub(crate) fn is_large_text(sql_type: &OdbcDataType) -> bool {
+    matches!(
+        sql_type,
+        OdbcDataType::LongVarchar { .. } | OdbcDataType::Other { .. }
+    )
+}
+
+/// `true` if the driver reports `type_name` for XML columns, e.g. `XML` in DB2.
+pub(crate) fn is_xml_type_name(type_name: &str) -> bool {
+    type_name.eq_ignore_ascii_case("xml")
+}
+
+/// Fetches XML documents into `LargeUtf8`, `Utf8` or `Utf8View` fields. XML columns are unbounded
+/// in size and drivers report no maximum length for them, so the buffers are sized by a cap
+/// instead. Documents are fetched as UTF-16, which both SQL Server and DB2 store them as. Documents
+/// exceeding the cap are reported by the truncation check of the reader, rather than being cut
+/// off silently.
+pub struct Xml {
+    text: WideText,
+}
+
+impl Xml {
+    /// `max_xml_size` is the maximum length of a document in characters (UTF-16 code units).
+    /// `element_size_cap` is in bytes and applies in addition to it.
+    pub fn new(
+        max_xml_size: usize,
+        element_size_cap: Option<usize>,
+        output_mode: TextOutputMode,
+    ) -> Self {
+        let max_str_len = element_size_cap.map_or(max_xml_size, |cap| min(max_xml_size, cap / 2));
+        Self {
+            text: WideText::new(max_str_len, Trim::Nothing, output_mode),
+        }
+    }
+}
+
+impl ReadStrategy for Xml {
+    fn strategy_type_name(&self) -> &'static str {
+        "Xml"
+    }
+
+    fn buffer_desc(&self) -> BufferDesc {
+        self.text.buffer_desc()
+    }
+
+    fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+        self.text.fill_arrow_array(column_view)
+    }
+}
@@ -1,14 +1,42 @@
 use arrow::datatypes::{DataType as ArrowDataType, Field, Schema, TimeUnit};
 use log::debug;
-use odbc_api::{ColumnDescription, DataType as OdbcDataType, ResultSetMetadata};
-use std::convert::TryInto;
+use odbc_api::{sys::SqlDataType, ColumnDescription, DataType as OdbcDataType, ResultSetMetadata};
+use std::{collections::HashMap, convert::TryInto};
 
-use crate::{ColumnFailure, Error};
+use crate::{
+    reader::{col_type_name, is_large_text, is_xml_type_name, MoneyType, SpatialEncoding},
+    ColumnFailure, Error,
+};
+
+/// Key of the field metadata holding the name of an Arrow extension type.
+pub(crate) const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+
+/// Name of the canonical extension type for JSON documents stored in a string field.
+pub(crate) const JSON_EXTENSION_NAME: &str = "arrow.json";
+
+/// Name of the GeoArrow extension type for geometries stored as WKB in a binary field.
+pub(crate) const WKB_EXTENSION_NAME: &str = "geoarrow.wkb";
+
+/// Key of the field metadata holding the parameters of an Arrow extension type.
+const EXTENSION_METADATA_KEY: &str = "ARROW:extension:metadata";
+
+/// GeoArrow parameters of `geography` columns, whose edges follow great circles rather than
+/// straight lines. SQL Server and PostGIS both default to WGS 84 (SRID 4326) for geographies, and
+/// points are emitted longitude first, which matches `OGC:CRS84`.
+const GEOGRAPHY_EXTENSION_METADATA: &str =
+    r#"{"crs":"OGC:CRS84","crs_type":"authority_code","edges":"spherical"}"#;
 
 /// Query the metadata to create an arrow schema. This method is invoked automatically for you by
 /// [`crate::OdbcReaderBuilder::build`]. You may want to call this method in situtation ther you want
 /// to create an arrow schema without creating the reader yet.
 ///
+/// Columns the data source reports as JSON (e.g. `jsonb` in PostgreSQL) are inferred as `Utf8`
+/// fields annotated with the canonical `arrow.json` extension type. Spatial columns (e.g.
+/// `geography` in SQL Server or `geometry` in PostGIS) are inferred as `Binary` fields holding WKB,
+/// annotated with the `geoarrow.wkb` extension type. Geographies are annotated with spherical edges
+/// and the `OGC:CRS84` coordinate reference system in addition. Currency columns (`MONEY` and `SMALLMONEY` in
+/// SQL Server) are inferred as `Decimal128(19, 4)` and `Decimal128(10, 4)` respectively.
+///
 /// # Example
 ///
 /// ```
@@ -34,11 +62,7 @@ pub fn arrow_schema_from(
     resut_set_metadata: &mut impl ResultSetMetadata,
     map_value_errors_to_null: bool,
 ) -> Result<Schema, Error> {
-    let num_cols: u16 = resut_set_metadata
-        .num_result_cols()
-        .map_err(Error::UnableToRetrieveNumCols)?
-        .try_into()
-        .unwrap();
+    let num_cols = num_result_cols(resut_set_metadata)?;
     let mut fields = Vec::new();
     for index in 0..num_cols {
         let field = arrow_field_from(resut_set_metadata, index, map_value_errors_to_null)?;
@@ -48,6 +72,17 @@ pub fn arrow_schema_from(
     Ok(Schema::new(fields))
 }
 
+/// Number of columns in the result set. A faulty driver may report a negative number, which is
+/// reported as an error rather than causing a panic.
+pub(crate) fn num_result_cols(cursor: &mut impl ResultSetMetadata) -> Result<u16, Error> {
+    let num_cols = cursor
+        .num_result_cols()
+        .map_err(Error::UnableToRetrieveNumCols)?;
+    num_cols
+        .try_into()
+        .map_err(|_| Error::InvalidNumCols(num_cols))
+}
+
 fn arrow_field_from(
     resut_set_metadata: &mut impl ResultSetMetadata,
     index: u16,
@@ -69,7 +104,121 @@ fn arrow_field_from(
             Name: '{name}';",
         column_description.data_type, column_description.nullability
     );
-    let data_type = match column_description.data_type {
+    let is_unsigned = if matches!(
+        column_description.data_type,
+        OdbcDataType::TinyInt
+            | OdbcDataType::SmallInt
+            | OdbcDataType::Integer
+            | OdbcDataType::BigInt
+    ) {
+        // Not every driver supports `SQL_DESC_UNSIGNED`. Integers are signed unless told otherwise.
+        resut_set_metadata
+            .column_is_unsigned(index + 1)
+            .unwrap_or(false)
+    } else {
+        false
+    };
+    let data_type =
+        arrow_data_type_from(column_description.data_type, is_unsigned).ok_or_else(|| {
+            Error::ColumnFailure {
+                name: name.clone(),
+                index: index as usize,
+                source: ColumnFailure::ZeroSizedColumn {
+                    sql_type: column_description.data_type,
+                },
+            }
+        })?;
+    // Only decimal and text columns are told apart by their type name, so only these cost the
+    // additional round trip. The name is fetched once and shared by the decisions below.
+    let type_name = if matches!(
+        data_type,
+        ArrowDataType::Decimal128(..) | ArrowDataType::Utf8
+    ) {
+        col_type_name(resut_set_metadata, index + 1)
+    } else {
+        None
+    };
+    // Drivers report currency types with varying precision and scale, e.g. as `DECIMAL(19, 4)`
+    // or as text. Their values have a fixed scale though, so we map them explicitly.
+    let money_type = type_name.as_deref().and_then(MoneyType::from_type_name);
+    let data_type = money_type.map_or(data_type, MoneyType::arrow_data_type);
+    // Like XML types, XML described as large text is unbounded in size
+    let data_type = match type_name.as_deref() {
+        Some(type_name)
+            if data_type == ArrowDataType::Utf8
+                && is_large_text(&column_description.data_type)
+                && is_xml_type_name(type_name) =>
+        {
+            ArrowDataType::LargeUtf8
+        }
+        _ => data_type,
+    };
+    let extension = match type_name.as_deref() {
+        Some(type_name) if data_type == ArrowDataType::Utf8 => {
+            extension_type_from(type_name, &column_description.data_type)
+        }
+        _ => None,
+    };
+    // Spatial values are converted into WKB while fetching
+    let data_type = if extension == Some(WKB_EXTENSION_NAME) {
+        ArrowDataType::Binary
+    } else {
+        data_type
+    };
+    let is_falliable = matches!(data_type, ArrowDataType::Timestamp(TimeUnit::Nanosecond, _));
+    let nullable =
+        column_description.could_be_nullable() || (is_falliable && map_value_errors_to_null);
+    let mut field = Field::new(name, data_type, nullable);
+    if let Some(extension) = extension {
+        let mut metadata = HashMap::from([(EXTENSION_NAME_KEY.to_owned(), extension.to_owned())]);
+        // Consumers would treat geographies as planar otherwise
+        if matches!(type_name.as_deref(), Some(name) if name.eq_ignore_ascii_case("geography")) {
+            metadata.insert(
+                EXTENSION_METADATA_KEY.to_owned(),
+                GEOGRAPHY_EXTENSION_METADATA.to_owned(),
+            );
+        }
+        field = field.with_metadata(metadata);
+    }
+    Ok(field)
+}
+
+/// Extension type for the values of a column, whose relational type does not tell their meaning.
+/// Drivers report JSON documents as plain text and spatial values as text or driver specific types,
+/// so only the type name tells them apart. `arrow.json` for JSON types, e.g. `json` or `jsonb` in
+/// PostgreSQL or `JSON` in MySQL. `geoarrow.wkb` for spatial types we know how to convert into WKB,
+/// e.g. `geography` in SQL Server. `type_name` is the name reported by the driver for the column.
+pub(crate) fn extension_type_from(
+    type_name: &str,
+    sql_type: &OdbcDataType,
+) -> Option<&'static str> {
+    if type_name.eq_ignore_ascii_case("json") || type_name.eq_ignore_ascii_case("jsonb") {
+        Some(JSON_EXTENSION_NAME)
+    } else if SpatialEncoding::of(sql_type, type_name).is_some() {
+        Some(WKB_EXTENSION_NAME)
+    } else {
+        None
+    }
+}
+
+/// `true` if `field` is annotated with the `arrow.json` extension type.
+pub(crate) fn is_json_field(field: &Field) -> bool {
+    field.metadata().get(EXTENSION_NAME_KEY).map(String::as_str) == Some(JSON_EXTENSION_NAME)
+}
+
+/// `true` if `field` is annotated with the `geoarrow.wkb` extension type.
+pub(crate) fn is_wkb_field(field: &Field) -> bool {
+    field.metadata().get(EXTENSION_NAME_KEY).map(String::as_str) == Some(WKB_EXTENSION_NAME)
+}
+
+/// Arrow type inferred for values of a relational type. `is_unsigned` is only relevant for
+/// integer types. `None` for fixed size binary types without a length, which can not be represented in
+/// Arrow.
+pub(crate) fn arrow_data_type_from(
+    data_type: OdbcDataType,
+    is_unsigned: bool,
+) -> Option<ArrowDataType> {
+    let arrow_data_type = match data_type {
         OdbcDataType::Numeric {
             precision: p @ 0..=38,
             scale,
@@ -78,32 +227,58 @@ fn arrow_field_from(
             precision: p @ 0..=38,
             scale,
         } => ArrowDataType::Decimal128(p as u8, scale.try_into().unwrap()),
-        OdbcDataType::Integer => ArrowDataType::Int32,
-        OdbcDataType::SmallInt => ArrowDataType::Int16,
-        OdbcDataType::Real | OdbcDataType::Float { precision: 0..=24 } => ArrowDataType::Float32,
-        OdbcDataType::Float { precision: _ } | OdbcDataType::Double => ArrowDataType::Float64,
-        OdbcDataType::Date => ArrowDataType::Date32,
-        OdbcDataType::Timestamp { precision: 0 } => {
-            ArrowDataType::Timestamp(TimeUnit::Second, None)
+        // Too many digits for a 128 Bit integer, e.g. `NUMERIC(76, 0)` in Oracle or Snowflake
+        OdbcDataType::Numeric {
+            precision: p @ 39..=76,
+            scale,
+        }
+        | OdbcDataType::Decimal {
+            precision: p @ 39..=76,
+            scale,
+        } => ArrowDataType::Decimal256(p as u8, scale.try_into().unwrap()),
+        OdbcDataType::Integer => {
+            if is_unsigned {
+                ArrowDataType::UInt32
+            } else {
+                ArrowDataType::Int32
+            }
         }
-        OdbcDataType::Timestamp { precision: 1..=3 } => {
-            ArrowDataType::Timestamp(TimeUnit::Millisecond, None)
+        OdbcDataType::SmallInt => {
+            if is_unsigned {
+                ArrowDataType::UInt16
+            } else {
+                ArrowDataType::Int16
+            }
         }
-        OdbcDataType::Timestamp { precision: 4..=6 } => {
-            ArrowDataType::Timestamp(TimeUnit::Microsecond, None)
+        // A precision of zero is reported for Oracle `NUMBER` without precision and scale, which
+        // may hold more digits than even a `Float64` can represent.
+        OdbcDataType::Real | OdbcDataType::Float { precision: 1..=24 } => ArrowDataType::Float32,
+        OdbcDataType::Float { precision: _ } | OdbcDataType::Double => ArrowDataType::Float64,
+        OdbcDataType::Date => ArrowDataType::Date32,
+        OdbcDataType::Timestamp { precision } => {
+            ArrowDataType::Timestamp(timestamp_unit(precision), None)
         }
-        OdbcDataType::Timestamp { precision: _ } => {
-            ArrowDataType::Timestamp(TimeUnit::Nanosecond, None)
+        OdbcDataType::Time { precision }
+        // `SQL_SS_TIME2`. SQL Server reports its `TIME` columns with this driver specific type.
+        | OdbcDataType::Other {
+            data_type: SqlDataType(-154),
+            decimal_digits: precision,
+            ..
+        } => time_type(precision),
+        // `SQL_SS_TIMESTAMPOFFSET`. Values are normalized to UTC while fetching.
+        OdbcDataType::Other {
+            data_type: SqlDataType(-155),
+            decimal_digits,
+            ..
+        } => ArrowDataType::Timestamp(timestamp_unit(decimal_digits), Some("+00:00".into())),
+        OdbcDataType::BigInt => {
+            if is_unsigned {
+                ArrowDataType::UInt64
+            } else {
+                ArrowDataType::Int64
+            }
         }
-        OdbcDataType::BigInt => ArrowDataType::Int64,
         OdbcDataType::TinyInt => {
-            let is_unsigned = resut_set_metadata
-                .column_is_unsigned(index + 1)
-                .map_err(|e| Error::ColumnFailure {
-                    name: name.clone(),
-                    index: index as usize,
-                    source: ColumnFailure::FailedToDescribeColumn(e),
-                })?;
             if is_unsigned {
                 ArrowDataType::UInt8
             } else {
@@ -112,24 +287,18 @@ fn arrow_field_from(
         }
         OdbcDataType::Bit => ArrowDataType::Boolean,
         OdbcDataType::Binary { length } => {
-            let length = length
-                .ok_or_else(|| Error::ColumnFailure {
-                    name: name.clone(),
-                    index: index as usize,
-                    source: ColumnFailure::ZeroSizedColumn {
-                        sql_type: OdbcDataType::Binary { length },
-                    },
-                })?
-                .get()
-                .try_into()
-                .unwrap();
-            ArrowDataType::FixedSizeBinary(length)
+            ArrowDataType::FixedSizeBinary(length?.get().try_into().unwrap())
         }
         OdbcDataType::LongVarbinary { length: _ } | OdbcDataType::Varbinary { length: _ } => {
             ArrowDataType::Binary
         }
+        // `SQL_SS_XML` in SQL Server and `SQL_XML` in DB2. XML documents are unbounded in size, so
+        // they may exceed the 2 GiB of text a `Utf8` array can hold.
+        OdbcDataType::Other {
+            data_type: SqlDataType(-152 | -370),
+            ..
+        } => ArrowDataType::LargeUtf8,
         OdbcDataType::Unknown
-        | OdbcDataType::Time { precision: _ }
         | OdbcDataType::Numeric { .. }
         | OdbcDataType::Decimal { .. }
         | OdbcDataType::Other {
@@ -143,9 +312,25 @@ fn arrow_field_from(
         | OdbcDataType::LongVarchar { length: _ }
         | OdbcDataType::Varchar { length: _ } => ArrowDataType::Utf8,
     };
-    let is_falliable = matches!(data_type, ArrowDataType::Timestamp(TimeUnit::Nanosecond, _));
-    let nullable =
-        column_description.could_be_nullable() || (is_falliable && map_value_errors_to_null);
-    let field = Field::new(name, data_type, nullable);
-    Ok(field)
+    Some(arrow_data_type)
+}
+
+/// Coarsest unit able to represent a timestamp with `precision` fractional digits of a second.
+fn timestamp_unit(precision: i16) -> TimeUnit {
+    match precision {
+        0 => TimeUnit::Second,
+        1..=3 => TimeUnit::Millisecond,
+        4..=6 => TimeUnit::Microsecond,
+        _ => TimeUnit::Nanosecond,
+    }
+}
+
+/// Narrowest time of day type able to hold `precision` fractional digits of a second.
+fn time_type(precision: i16) -> ArrowDataType {
+    match precision {
+        0 => ArrowDataType::Time32(TimeUnit::Second),
+        1..=3 => ArrowDataType::Time32(TimeUnit::Millisecond),
+        4..=6 => ArrowDataType::Time64(TimeUnit::Microsecond),
+        _ => ArrowDataType::Time64(TimeUnit::Nanosecond),
+    }
 }
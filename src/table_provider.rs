@@ -0,0 +1,374 @@
+use std::{
+    any::Any,
+    fmt::{self, Write},
+    iter,
+    sync::{Arc, Mutex},
+};
+
+use arrow::datatypes::{Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion::{
+    catalog::Session,
+    datasource::TableProvider,
+    error::{DataFusionError, Result},
+    execution::TaskContext,
+    logical_expr::{BinaryExpr, Expr, Operator, TableProviderFilterPushDown, TableType},
+    physical_plan::{
+        stream::RecordBatchReceiverStream,
+        streaming::{PartitionStream, StreamingTableExec},
+        ExecutionPlan, SendableRecordBatchStream,
+    },
+    scalar::ScalarValue,
+};
+use odbc_api::{parameter::InputParameter, Connection, IntoParameter};
+
+use crate::{arrow_schema_from, odbc_writer::quote_column_name, OdbcReaderBuilder};
+
+/// Registers a table of an ODBC data source with Apache DataFusion, so it can be queried using SQL
+/// or data frames. Scans are executed by sending a `SELECT` statement to the database and fetching
+/// the result set with an [`crate::OdbcReader`]. Only the columns required by the query are
+/// selected. Filters comparing a column with a literal (`=`, `<>`, `<`, `<=`, `>`, `>=`) and
+/// `IS NULL` are appended as `WHERE` clause, with the literals bound as parameters, so fewer rows
+/// are transmitted. Strings are only compared for (in)equality in the database, since the order of
+/// strings depends on its collation. DataFusion still applies these filters itself, since the
+/// database may compare e.g. strings differently, for instance ignoring case or trailing spaces. Column names are quoted the same way as by
+/// [`crate::insert_statement_from_schema`], while `table_name` is used verbatim, so it may be
+/// qualified with a schema.
+///
+/// Scans share the connection of the provider, so each scan fetches its entire result set before
+/// releasing the connection to the next one and passing the batches on. Otherwise a plan reading
+/// from two scans at once, like a self join, could deadlock. The blocking ODBC calls are performed
+/// on a thread dedicated to blocking tasks, so they do not stall the asynchronous runtime. A limit
+/// is honoured by not fetching more rows than requested. Available with the `datafusion` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::{Arc, OnceLock};
+/// use arrow_odbc::{odbc_api::{Environment, ConnectionOptions}, OdbcTableProvider};
+/// use datafusion::prelude::SessionContext;
+///
+/// static ENV: OnceLock<Environment> = OnceLock::new();
+///
+/// async fn run(connection_string: &str) -> Result<(), anyhow::Error> {
+///     let env = ENV.get_or_init(|| Environment::new().unwrap());
+///     let connection =
+///         env.connect_with_connection_string(connection_string, ConnectionOptions::default())?;
+///     let provider = OdbcTableProvider::new(connection, "MyTable")?;
+///
+///     let ctx = SessionContext::new();
+///     ctx.register_table("my_table", Arc::new(provider))?;
+///     let _batches = ctx.sql("SELECT a FROM my_table WHERE b > 42").await?.collect().await?;
+///     Ok(())
+/// }
+/// ```
+pub struct OdbcTableProvider {
+    /// ODBC connections can be sent to other threads, but not be shared between them. Shared with
+    /// the streams of running scans.
+    connection: Arc<Mutex<Connection<'static>>>,
+    table_name: String,
+    schema: SchemaRef,
+    reader_builder: OdbcReaderBuilder,
+}
+
+impl OdbcTableProvider {
+    /// Infers the schema of the table from the columns of `SELECT * FROM <table_name>`.
+    pub fn new(connection: Connection<'static>, table_name: &str) -> Result<Self> {
+        Self::with_reader_builder(connection, table_name, OdbcReaderBuilder::new())
+    }
+
+    /// Like [`Self::new`], but scans use `reader_builder` to create the readers, e.g. in order to
+    /// limit the buffer size for large text columns. The schema of the builder is ignored, since
+    /// each scan specifies the schema of the columns it needs.
+    pub fn with_reader_builder(
+        connection: Connection<'static>,
+        table_name: &str,
+        reader_builder: OdbcReaderBuilder,
+    ) -> Result<Self> {
+        // Only the metadata is needed, so we do not want any rows.
+        let query = format!("SELECT * FROM {table_name} WHERE 1=0");
+        let mut cursor = connection
+            .execute(&query, ())
+            .map_err(external)?
+            .ok_or_else(|| no_result_set(&query))?;
+        let schema = arrow_schema_from(&mut cursor, false).map_err(external)?;
+        drop(cursor);
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            table_name: table_name.to_owned(),
+            schema: Arc::new(schema),
+            reader_builder,
+        })
+    }
+
+    /// `SELECT` statement fetching the fields of `schema` for all rows matching `filters`, and the
+    /// literals to bind to its placeholders.
+    fn select_statement(&self, schema: &Schema, filters: &[Expr]) -> (String, Vec<ScalarValue>) {
+        let columns: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|field| quote_column_name(field.name()))
+            .collect();
+        let mut query = format!("SELECT {} FROM {}", columns.join(", "), self.table_name);
+        let (conditions, literals): (Vec<_>, Vec<_>) =
+            filters.iter().filter_map(filter_to_sql).unzip();
+        if !conditions.is_empty() {
+            write!(query, " WHERE {}", conditions.join(" AND ")).unwrap();
+        }
+        (query, literals.into_iter().flatten().cloned().collect())
+    }
+}
+
+#[async_trait]
+impl TableProvider for OdbcTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // Queries like `SELECT COUNT(*)` project no columns at all, yet SQL requires us to select
+        // at least one. In that case we fetch all of them and leave the projection to the plan.
+        let (schema, remaining_projection) = match projection {
+            Some(indices) if !indices.is_empty() => (Arc::new(self.schema.project(indices)?), None),
+            other => (self.schema.clone(), other.cloned()),
+        };
+        let (query, literals) = self.select_statement(&schema, filters);
+        let partition = OdbcPartition {
+            connection: self.connection.clone(),
+            query,
+            literals,
+            schema: schema.clone(),
+            reader_builder: self.reader_builder.clone(),
+            limit,
+        };
+        let exec = StreamingTableExec::try_new(
+            schema,
+            vec![Arc::new(partition)],
+            remaining_projection.as_ref(),
+            iter::empty(),
+            false,
+            limit,
+        )?;
+        Ok(Arc::new(exec))
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        let pushdown = filters
+            .iter()
+            .map(|filter| {
+                if filter_to_sql(filter).is_some() {
+                    TableProviderFilterPushDown::Inexact
+                } else {
+                    TableProviderFilterPushDown::Unsupported
+                }
+            })
+            .collect();
+        Ok(pushdown)
+    }
+}
+
+/// Executes the `SELECT` statement of a scan and streams its result set.
+struct OdbcPartition {
+    connection: Arc<Mutex<Connection<'static>>>,
+    query: String,
+    /// Bound to the placeholders of `query`, in order.
+    literals: Vec<ScalarValue>,
+    schema: SchemaRef,
+    reader_builder: OdbcReaderBuilder,
+    /// Maximum number of rows to fetch. `None` for all of them.
+    limit: Option<usize>,
+}
+
+impl fmt::Debug for OdbcPartition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OdbcPartition")
+            .field("query", &self.query)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl PartitionStream for OdbcPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        // Room for one batch in flight, while the next one is fetched.
+        let mut builder = RecordBatchReceiverStream::builder(self.schema.clone(), 1);
+        let tx = builder.tx();
+        let connection = self.connection.clone();
+        let query = self.query.clone();
+        let literals = self.literals.clone();
+        let mut reader_builder = self.reader_builder.clone();
+        reader_builder.with_schema(self.schema.clone());
+        let mut remaining = self.limit.unwrap_or(usize::MAX);
+        builder.spawn_blocking(move || {
+            // Another scan panicked while using the connection. Its state is unknown, so we rather
+            // report an error than keep using it.
+            let connection = connection.lock().map_err(|_| {
+                DataFusionError::Execution(
+                    "The ODBC connection is unusable, since a previous scan panicked while using \
+                    it."
+                    .to_owned(),
+                )
+            })?;
+            // Only literals with a parameter representation are pushed down, see `filter_to_sql`.
+            let parameters: Vec<_> = literals
+                .iter()
+                .map(|literal| literal_to_parameter(literal).unwrap())
+                .collect();
+            let cursor = connection
+                .execute(&query, parameters.as_slice())
+                .map_err(external)?
+                .ok_or_else(|| no_result_set(&query))?;
+            let reader = reader_builder.build(cursor).map_err(external)?;
+            // Fetch all batches before releasing the connection, so other scans of this provider
+            // can make progress, even if this stream is not polled until they are done.
+            let mut batches = Vec::new();
+            for batch in reader {
+                if remaining == 0 {
+                    break;
+                }
+                let batch = batch?;
+                let batch = batch.slice(0, batch.num_rows().min(remaining));
+                remaining -= batch.num_rows();
+                batches.push(batch);
+            }
+            drop(connection);
+            for batch in batches {
+                // The receiving end is gone, if the query has been cancelled.
+                if tx.blocking_send(Ok(batch)).is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+        builder.build()
+    }
+}
+
+/// SQL condition equivalent to `filter`, if it compares a column with a literal or checks a column
+/// for NULL. A literal is represented by a placeholder (`?`) and returned alongside the condition.
+fn filter_to_sql(filter: &Expr) -> Option<(String, Option<&ScalarValue>)> {
+    let (left, op, right) = match filter {
+        Expr::IsNull(operand) => {
+            let Expr::Column(column) = operand.as_ref() else {
+                return None;
+            };
+            let column = quote_column_name(&column.name);
+            return Some((format!("{column} IS NULL"), None));
+        }
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => (left, op, right),
+        _ => return None,
+    };
+    let (column, value, literal_first) = match (left.as_ref(), right.as_ref()) {
+        (Expr::Column(column), Expr::Literal(value)) => (column, value, false),
+        (Expr::Literal(value), Expr::Column(column)) => (column, value, true),
+        _ => return None,
+    };
+    let is_string = matches!(value, ScalarValue::Utf8(_) | ScalarValue::LargeUtf8(_));
+    let op = match op {
+        Operator::Eq => "=",
+        Operator::NotEq => "<>",
+        // The order of strings depends on the collation of the database, which likely differs
+        // from the byte wise order DataFusion uses. Rows the database considers out of range would
+        // be missing from the result, so we do not push these comparisons down.
+        Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq if is_string => return None,
+        Operator::Lt => "<",
+        Operator::LtEq => "<=",
+        Operator::Gt => ">",
+        Operator::GtEq => ">=",
+        _ => return None,
+    };
+    literal_to_parameter(value)?;
+    let column = quote_column_name(&column.name);
+    let condition = if literal_first {
+        format!("? {op} {column}")
+    } else {
+        format!("{column} {op} ?")
+    };
+    Some((condition, Some(value)))
+}
+
+/// Parameter to bind for `value`. `None` for NULL, since comparisons with NULL are never true, and
+/// for types without an ODBC parameter representation.
+fn literal_to_parameter(value: &ScalarValue) -> Option<Box<dyn InputParameter>> {
+    let parameter: Box<dyn InputParameter> = match value {
+        ScalarValue::Int8(Some(v)) => Box::new(*v),
+        ScalarValue::Int16(Some(v)) => Box::new(*v),
+        ScalarValue::Int32(Some(v)) => Box::new(*v),
+        ScalarValue::Int64(Some(v)) => Box::new(*v),
+        ScalarValue::UInt8(Some(v)) => Box::new(i16::from(*v)),
+        ScalarValue::UInt16(Some(v)) => Box::new(i32::from(*v)),
+        ScalarValue::UInt32(Some(v)) => Box::new(i64::from(*v)),
+        ScalarValue::UInt64(Some(v)) => Box::new(i64::try_from(*v).ok()?),
+        ScalarValue::Float32(Some(v)) if v.is_finite() => Box::new(*v),
+        ScalarValue::Float64(Some(v)) if v.is_finite() => Box::new(*v),
+        ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => {
+            Box::new(v.clone().into_parameter())
+        }
+        _ => return None,
+    };
+    Some(parameter)
+}
+
+fn external(error: impl std::error::Error + Send + Sync + 'static) -> DataFusionError {
+    DataFusionError::External(Box::new(error))
+}
+
+fn no_result_set(query: &str) -> DataFusionError {
+    DataFusionError::Execution(format!("Query did not produce a result set: {query}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::prelude::{col, lit};
+
+    use super::filter_to_sql;
+
+    #[test]
+    fn push_down_comparison_of_integers() {
+        let condition = filter_to_sql(&col("a").gt_eq(lit(42i32)));
+
+        assert_eq!("a >= ?", condition.unwrap().0);
+    }
+
+    #[test]
+    fn push_down_equality_of_strings() {
+        let condition = filter_to_sql(&lit("x").not_eq(col("a")));
+
+        assert_eq!("? <> a", condition.unwrap().0);
+    }
+
+    #[test]
+    fn do_not_push_down_string_ranges() {
+        assert!(filter_to_sql(&col("a").lt(lit("x"))).is_none());
+        assert!(filter_to_sql(&col("a").gt_eq(lit("x"))).is_none());
+    }
+
+    #[test]
+    fn push_down_is_null() {
+        let (condition, literal) = filter_to_sql(&col("a").is_null()).unwrap();
+
+        assert_eq!("a IS NULL", condition);
+        assert!(literal.is_none());
+    }
+}
@@ -1,19 +1,26 @@
-use std::{sync::Arc, thread};
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    thread,
+};
 
 use arrow::{
     array::{
-        Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Date64Array, Decimal128Array,
-        Decimal256Builder, FixedSizeBinaryArray, Float16Array, Float32Array, Int16Array,
-        Int32Array, Int64Array, Int8Array, LargeStringArray, StringArray, Time32MillisecondArray,
-        Time32SecondArray, Time64MicrosecondArray, Time64NanosecondArray,
-        TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
-        TimestampSecondArray, UInt8Array,
+        Array, ArrayRef, BinaryArray, BinaryViewArray, BooleanArray, Date32Array, Date64Array,
+        Decimal128Array, Decimal256Array, Decimal256Builder, DictionaryArray, FixedSizeBinaryArray,
+        FixedSizeListArray, Float16Array, Float32Array, Int16Array, Int32Array, Int64Array,
+        Int8Array, LargeBinaryArray, LargeListArray, LargeStringArray, ListArray, MapArray,
+        StringArray, StringViewArray, StructArray, Time32MillisecondArray, Time32SecondArray,
+        Time64MicrosecondArray, Time64NanosecondArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt16Array,
+        UInt32Array, UInt64Array, UInt8Array,
     },
     datatypes::{
-        ArrowPrimitiveType, DataType, Decimal256Type, Field, Float16Type, Schema, SchemaRef,
-        TimeUnit,
+        ArrowPrimitiveType, DataType, Decimal256Type, Field, Fields, Float16Type, Int32Type,
+        Int8Type, IntervalUnit, Schema, SchemaRef, TimeUnit, UInt8Type,
     },
     error::ArrowError,
+    ipc::reader::StreamReader,
     record_batch::{RecordBatch, RecordBatchReader},
 };
 use chrono::NaiveDate;
@@ -28,12 +35,16 @@ use arrow_odbc::{
     arrow::array::Float64Array,
     arrow_schema_from, insert_into_table,
     odbc_api::{
-        buffers::TextRowSet,
-        sys::{AttrConnectionPooling, AttrCpMatch},
-        Connection, ConnectionOptions, Cursor, CursorImpl, Environment, IntoParameter,
-        StatementConnection,
+        buffers::{AnySlice, BufferDesc, TextRowSet},
+        sys::{AttrConnectionPooling, AttrCpMatch, SqlDataType},
+        Connection, ConnectionOptions, Cursor, CursorImpl, DataType as OdbcDataType, Environment,
+        IntoParameter, Nullability, StatementConnection,
     },
-    ColumnFailure, Error, OdbcReaderBuilder, OdbcWriter, WriterError,
+    parse_decimal_text, schema_to_create_table, suggestions_for_type, ColumnFailure,
+    ColumnStatistics, ColumnStrategyRegistry, Error, FloatCoercion, IntegerCoercion, MappingError,
+    MissingColumnBehavior, OdbcPaginatedReader, OdbcPreparedReader, OdbcReaderBuilder,
+    OdbcReaderMetrics, OdbcWriter, OdbcWriterOptions, ReadStrategy, ScalarValue, SqlDialect,
+    StatementAttributeValue, SubPrecisionHandling, TextOutputMode, WriterError,
 };
 
 use stdext::function_name;
@@ -94,6 +105,27 @@ fn fetch_16bit_integer() {
     assert_eq!([1, 2, 3], *array_vals.values());
 }
 
+/// Integer columns of any width are fetched as `Int64` if requested
+#[test]
+fn coerce_integers_to_int64() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "SMALLINT NOT NULL", "(1),(2),(3)");
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_integer_coercion(IntegerCoercion::AllToInt64)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    assert_eq!(&DataType::Int64, reader.schema().field(0).data_type());
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!([1, 2, 3], *array_vals.values());
+}
+
 /// Fill a record batch with non nullable Integer 8 Bit directly from the datasource. Remark:
 /// Tinyint is unsigned for MSSQL
 #[test]
@@ -183,1359 +215,4409 @@ fn fetch_decimal128_negative_scale_unsupported() {
     ))
 }
 
-/// Observe that an explicitly specified Uint16 triggers an unsupported error
+/// Fields of the schema are listed in a different order than the columns in the query. Matching
+/// columns by name must still fill each field with the right column.
 #[test]
-fn unsupported_16bit_unsigned_integer() {
+fn match_columns_by_name() {
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-
-    // Setup a table on the database with some floats (so we can fetch them)
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["SMALLINT NOT NULL"]).unwrap();
-    let sql = format!("INSERT INTO {table_name} (a) VALUES (1),(2),(3)");
+    setup_empty_table(
+        &conn,
+        table_name,
+        &["INTEGER NOT NULL", "VARCHAR(10) NOT NULL"],
+    )
+    .unwrap();
+    let sql = format!("INSERT INTO {table_name} (a, b) VALUES (42, 'Hello')");
     conn.execute(&sql, ()).unwrap();
+    let sql = format!("SELECT b, A FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
 
-    // Query column with values to get a cursor
-    let sql = format!("SELECT a FROM {table_name}");
+    // When
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, false),
+        Field::new("b", DataType::Utf8, false),
+    ]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .match_columns_by_name(true)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then
+    let a = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    let b = record_batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(42, a.value(0));
+    assert_eq!("Hello", b.value(0));
+}
+
+/// If the result set contains two columns whose names only differ in case, we can not tell which
+/// one to use for a field with that name.
+#[test]
+fn match_columns_by_name_ambiguous() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER", "INTEGER"]).unwrap();
+    let sql = format!("SELECT a, b AS A FROM {table_name}");
     let cursor = conn.execute(&sql, ()).unwrap().unwrap();
 
-    // Specify Uint16 manually, since inference of the arrow type from the sql type would yield a
-    // signed 16 bit integer.
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::UInt16, false)]));
-    let result = OdbcReaderBuilder::new().with_schema(schema).build(cursor);
+    // When
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+    let result = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .match_columns_by_name(true)
+        .build(cursor);
 
+    // Then
     assert!(matches!(
         result,
         Err(Error::ColumnFailure {
-            source: ColumnFailure::UnsupportedArrowType(DataType::UInt16),
+            source: ColumnFailure::AmbiguousColumnName,
             index: 0,
             name: _
         })
     ))
 }
 
-/// Fill a record batch with non nullable Boolean from Bits
+/// NULLs in the data source are replaced, so they can be fetched into a non nullable field.
 #[test]
-fn fetch_boolean() {
+fn replace_nulls_in_non_nullable_field() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(NULL),(3)");
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
 
-    let array_any = fetch_arrow_data(table_name, "BIT NOT NULL", "(1),(0),(1)").unwrap();
-
-    let array_vals = array_any.as_any().downcast_ref::<BooleanArray>().unwrap();
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .with_null_replacement(0, ScalarValue::Int64(-1))
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
 
-    // Assert that the correct values are found within the arrow batch
-    assert!(array_vals.value(0));
-    assert!(!array_vals.value(1));
-    assert!(array_vals.value(2));
+    // Then
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(0, array_vals.null_count());
+    assert_eq!([1, -1, 3], array_vals.values()[..]);
 }
 
-/// Fill a record batch with nullable Booleans from Bits
+/// A replacement which does not fit the type of the field is reported then building the reader
 #[test]
-fn fetch_nullable_boolean() {
+fn null_replacement_with_wrong_type() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = empty_cursor(table_name, "INTEGER");
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
 
-    let array_any = fetch_arrow_data(table_name, "BIT", "(1),(NULL),(0)").unwrap();
-
-    let array_vals = array_any.as_any().downcast_ref::<BooleanArray>().unwrap();
+    // When
+    let result = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .with_null_replacement(0, ScalarValue::Utf8("n/a".to_owned()))
+        .build(cursor);
 
-    // Assert that the correct values are found within the arrow batch
-    assert!(array_vals.is_valid(0));
-    assert!(array_vals.value(0));
-    assert!(array_vals.is_null(1));
-    assert!(array_vals.is_valid(2));
-    assert!(!array_vals.value(2));
+    // Then
+    assert!(matches!(
+        result,
+        Err(Error::ColumnFailure {
+            source: ColumnFailure::InvalidNullReplacement { .. },
+            index: 0,
+            name: _
+        })
+    ))
 }
 
-/// Fill a record batch with non nullable `f32` directly from the datasource
+/// Hook registered with `with_null_hook` is called for each NULL with column and row index
 #[test]
-fn fetch_32bit_floating_point() {
+fn null_hook_reports_nulls() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(NULL),(3),(NULL)");
+    let nulls = Arc::new(Mutex::new(Vec::new()));
+    let hook = {
+        let nulls = nulls.clone();
+        Arc::new(move |column_index: usize, row_index: usize| {
+            nulls.lock().unwrap().push((column_index, row_index))
+        })
+    };
 
-    let array_any = fetch_arrow_data(table_name, "REAL NOT NULL", "(1),(2),(3)").unwrap();
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_null_hook(hook)
+        .build(cursor)
+        .unwrap();
+    reader.next().unwrap().unwrap();
 
-    let array_vals = array_any.as_any().downcast_ref::<Float32Array>().unwrap();
-    assert_float_eq!([1., 2., 3.][..], array_vals.values(), abs_all <= 000.1);
+    // Then
+    assert_eq!(vec![(0, 1), (0, 3)], *nulls.lock().unwrap());
 }
 
-/// Fill a record batch with non nullable `f64` directly from the datasource
+/// Minimum, maximum and NULL count are accumulated over all batches fetched
 #[test]
-fn fetch_64bit_floating_point() {
+fn collect_column_statistics() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(3),(NULL),(-2),(7),(NULL)");
 
-    let array_any =
-        fetch_arrow_data(table_name, "DOUBLE PRECISION NOT NULL", "(1),(2),(3)").unwrap();
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(2)
+        .with_column_statistics(true)
+        .build(cursor)
+        .unwrap();
+    for batch in reader.by_ref() {
+        batch.unwrap();
+    }
 
-    let array_vals = array_any.as_any().downcast_ref::<Float64Array>().unwrap();
+    // Then
+    let expected = ColumnStatistics {
+        min_value: Some(ScalarValue::Int64(-2)),
+        max_value: Some(ScalarValue::Int64(7)),
+        null_count: 2,
+    };
+    assert_eq!(Some(&[expected][..]), reader.column_statistics());
+}
 
-    // Assert that the correct values are found within the arrow batch
-    assert_float_eq!([1., 2., 3.][..], array_vals.values(), abs_all <= 000.1);
+/// Statistics of unsigned columns. Neither MSSQL nor the ODBC types we can create in it yield them,
+/// so the arrays are passed directly.
+#[test]
+fn column_statistics_of_unsigned_integers() {
+    // Given two batches of a UInt16 and a UInt64 column each
+    let uint16 = [
+        UInt16Array::from(vec![Some(7), None, Some(u16::MAX)]),
+        UInt16Array::from(vec![Some(3), None]),
+    ];
+    let uint64 = [
+        UInt64Array::from(vec![Some(u64::MAX), Some(42)]),
+        UInt64Array::from(vec![None, Some(5)]),
+    ];
+
+    // When accounting for them
+    let mut uint16_statistics = ColumnStatistics::default();
+    for array in &uint16 {
+        uint16_statistics.update(array);
+    }
+    let mut uint64_statistics = ColumnStatistics::default();
+    for array in &uint64 {
+        uint64_statistics.update(array);
+    }
+
+    // Then values beyond the range of Int64 are reported without loss
+    let expected = ColumnStatistics {
+        min_value: Some(ScalarValue::Int64(3)),
+        max_value: Some(ScalarValue::Int64(u16::MAX.into())),
+        null_count: 2,
+    };
+    assert_eq!(expected, uint16_statistics);
+    let expected = ColumnStatistics {
+        min_value: Some(ScalarValue::UInt64(5)),
+        max_value: Some(ScalarValue::UInt64(u64::MAX)),
+        null_count: 1,
+    };
+    assert_eq!(expected, uint64_statistics);
 }
 
-/// Fill a record batch with non nullable `i64` directly from the datasource
+/// Attributes of the result set columns are reported, alongside the Arrow schema
 #[test]
-fn fetch_64bit_integer() {
+fn columns_metadata() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "DECIMAL(10,2) NOT NULL", "(12.34)");
 
-    let array_any = fetch_arrow_data(table_name, "BIGINT NOT NULL", "(1),(2),(3)").unwrap();
-
-    let array_vals = array_any.as_any().downcast_ref::<Int64Array>().unwrap();
+    // When
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!([1, 2, 3], *array_vals.values());
+    // Then
+    let metadata = &reader.columns_metadata().unwrap()[0];
+    assert_eq!("a", metadata.name);
+    assert_eq!(
+        OdbcDataType::Decimal {
+            precision: 10,
+            scale: 2
+        },
+        metadata.data_type
+    );
+    assert_eq!(SqlDataType::DECIMAL, metadata.sql_type);
+    assert_eq!(Nullability::NoNulls, metadata.nullability);
+    assert_eq!(Some(10), metadata.precision);
+    assert_eq!(Some(2), metadata.scale);
 }
 
-/// Fill a record batch of Strings from a varchar source column
+/// Columns are described on demand, even after buffers have been bound and batches fetched
 #[test]
-fn fetch_varchar() {
+fn columns_metadata_after_fetching_batch() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "VARCHAR(10)", "('one')");
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    reader.next().unwrap().unwrap();
 
-    let array_any =
-        fetch_arrow_data(table_name, "VARCHAR(50)", "('Hello'),('Bonjour'),(NULL)").unwrap();
-
-    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+    // When
+    let metadata = reader.columns_metadata().unwrap();
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!("Hello", array_vals.value(0));
-    assert_eq!("Bonjour", array_vals.value(1));
-    assert!(array_vals.is_null(2));
+    // Then
+    assert_eq!(1, metadata.len());
+    assert_eq!(
+        OdbcDataType::Varchar {
+            length: NonZeroUsize::new(10)
+        },
+        metadata[0].data_type
+    );
 }
 
-/// Fill a record batch of Strings from a varchar source column
+/// Each batch contributes one chunk to the array of each column
 #[test]
-fn trim_fixed_sized_character_data() {
+fn read_into_chunked_arrays() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(2),(NULL)");
 
-    let cursor = cursor_over(table_name, "CHAR(4)", "('1234'),(' 123'),('123 ')");
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(4)
-        .trim_fixed_sized_characters(true)
+    // When
+    let columns = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(2)
         .build(cursor)
         .unwrap()
-        .into_concurrent()
+        .into_chunked_arrays()
         .unwrap();
-    // Batch for batch copy values from ODBC buffer into arrow batches
-    let record_batch = reader.next().unwrap().unwrap();
-    let array_any = record_batch.column(0).clone();
 
-    // Assert that the correct values are found within the arrow batch
-    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
-    assert_eq!("1234", array_vals.value(0));
-    assert_eq!("123", array_vals.value(1));
-    assert_eq!("123", array_vals.value(2));
+    // Then
+    assert_eq!(1, columns.len());
+    let column = &columns[0];
+    assert_eq!(2, column.chunks().len());
+    assert_eq!(3, column.len());
+    assert_eq!(1, column.null_count());
+    let values = column.concat().unwrap();
+    let values = values.as_any().downcast_ref::<Int32Array>().unwrap();
+    assert_eq!(
+        [Some(1), Some(2), None],
+        values.iter().collect::<Vec<_>>()[..]
+    );
 }
 
-/// Fill a record batch of Strings from a nvarchar source column
+/// Text is passed on as raw bytes, without being validated as UTF-8
 #[test]
-fn fetch_nvarchar() {
+fn fetch_text_as_raw_bytes() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "CHAR(7)", "('Hello'),(NULL)");
 
-    let array_any =
-        fetch_arrow_data(table_name, "NVARCHAR(50)", "('Hello'),('Bonjour'),(NULL)").unwrap();
-
-    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .text_as_binary_for_non_utf8(true)
+        .build(cursor)
+        .unwrap();
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!("Hello", array_vals.value(0));
-    assert_eq!("Bonjour", array_vals.value(1));
-    assert!(array_vals.is_null(2));
+    // Then
+    assert_eq!(&DataType::Binary, reader.schema().field(0).data_type());
+    let batch = reader.next().unwrap().unwrap();
+    let array = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<BinaryArray>()
+        .unwrap();
+    // Padding of the fixed sized column is trimmed
+    assert_eq!(Some(&b"Hello"[..]), array.iter().next().unwrap());
+    assert!(array.is_null(1));
 }
 
-/// Fill a record batch of Dates
+/// Only the column with the raw bytes mode is fetched as binary
 #[test]
-fn fetch_dates() {
+fn fetch_single_column_as_raw_bytes() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, ConnectionOptions::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARCHAR(10)", "VARCHAR(10)"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a, b) VALUES ('raw', 'text')");
+    conn.execute(&sql, ()).unwrap();
+    let sql = format!("SELECT a, b FROM {table_name}");
+    let cursor = conn.into_cursor(&sql, ()).unwrap().unwrap();
 
-    let array_any =
-        fetch_arrow_data(table_name, "DATE", "('2021-04-09'),(NULL),('2002-09-30')").unwrap();
-
-    let array_vals = array_any.as_any().downcast_ref::<Date32Array>().unwrap();
+    // When
+    let reader = OdbcReaderBuilder::new()
+        .with_text_output_mode_for_column(0, TextOutputMode::RawBytes)
+        .build(cursor)
+        .unwrap();
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!(
-        Some(NaiveDate::from_ymd_opt(2021, 4, 9).unwrap()),
-        array_vals.value_as_date(0)
-    );
-    assert!(array_vals.is_null(1));
-    assert_eq!(
-        Some(NaiveDate::from_ymd_opt(2002, 9, 30).unwrap()),
-        array_vals.value_as_date(2)
-    );
+    // Then
+    let schema = reader.schema();
+    assert_eq!(&DataType::Binary, schema.field(0).data_type());
+    assert_eq!(&DataType::Utf8, schema.field(1).data_type());
 }
 
-/// Fill a record batch of non nullable Dates
+/// Low cardinality text columns are encoded as dictionary while filling the array, if requested
+/// in the schema
 #[test]
-fn fetch_non_null_dates() {
+fn fetch_text_as_dictionary() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "VARCHAR(10)",
+        "('open'),('closed'),(NULL),('open')",
+    );
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+        true,
+    )]));
 
-    let array_any =
-        fetch_arrow_data(table_name, "DATE NOT NULL", "('2021-04-09'),('2002-09-30')").unwrap();
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
 
-    let array_vals = array_any.as_any().downcast_ref::<Date32Array>().unwrap();
+    // Then
+    let array = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<DictionaryArray<Int32Type>>()
+        .unwrap();
+    let values = array
+        .values()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(2, values.len());
+    assert_eq!("open", values.value(0));
+    assert_eq!("closed", values.value(1));
+    let keys: Vec<_> = array.keys().iter().collect();
+    assert_eq!([Some(0), Some(1), None, Some(0)], keys[..]);
+}
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!(
-        Some(NaiveDate::from_ymd_opt(2021, 4, 9).unwrap()),
-        array_vals.value_as_date(0)
-    );
-    assert_eq!(
-        Some(NaiveDate::from_ymd_opt(2002, 9, 30).unwrap()),
-        array_vals.value_as_date(1)
-    );
-}
-
-/// Fill a record batch of non nullable timestamps with milliseconds precision
+/// The type of a `sql_variant` column is inferred from its first values. The probed rows are
+/// returned as the first batch.
 #[test]
-fn fetch_non_null_date_time() {
+fn lazy_schema_inference_of_variant_column() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "SQL_VARIANT", "(NULL),(42),(7)");
 
-    let array_any = fetch_arrow_data(
-        table_name,
-        "DATETIME NOT NULL",
-        "('2021-04-09 18:57:50.12'),('2002-09-30 12:43:17.45')",
-    )
-    .unwrap();
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_lazy_schema_inference(2)
+        .with_max_num_rows_per_batch(10)
+        .build(cursor)
+        .unwrap();
 
-    let array_vals = array_any
+    // Then
+    assert_eq!(&DataType::Int64, reader.schema().field(0).data_type());
+    let probed = reader.next().unwrap().unwrap();
+    let probed = probed
+        .column(0)
         .as_any()
-        .downcast_ref::<TimestampMillisecondArray>()
+        .downcast_ref::<Int64Array>()
         .unwrap();
-
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2021, 4, 9)
-                .unwrap()
-                .and_hms_milli_opt(18, 57, 50, 120)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(0)
-    );
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2002, 9, 30)
-                .unwrap()
-                .and_hms_milli_opt(12, 43, 17, 450)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(1)
-    );
+    assert_eq!([None, Some(42)], probed.iter().collect::<Vec<_>>()[..]);
+    let rest = reader.next().unwrap().unwrap();
+    let rest = rest
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!([Some(7)], rest.iter().collect::<Vec<_>>()[..]);
+    assert!(reader.next().is_none());
 }
 
-/// Fill a record batch of nullable timestamps with milliseconds precision
+/// Every round trip to the driver is counted, including the last one finding no more rows.
 #[test]
-fn fetch_date_time_us() {
+fn collect_reader_metrics() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(2),(3),(4),(5)");
 
-    let array_any = fetch_arrow_data(
-        table_name,
-        "DATETIME2(6)",
-        "('2021-04-09 18:57:50'),(NULL),('2002-09-30 12:43:17')",
-    )
-    .unwrap();
-
-    let array_vals = array_any
-        .as_any()
-        .downcast_ref::<TimestampMicrosecondArray>()
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(2)
+        .build(cursor)
         .unwrap();
+    for batch in reader.by_ref() {
+        batch.unwrap();
+    }
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2021, 4, 9)
-                .unwrap()
-                .and_hms_milli_opt(18, 57, 50, 0)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(0)
-    );
-    assert!(array_vals.is_null(1));
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2002, 9, 30)
-                .unwrap()
-                .and_hms_milli_opt(12, 43, 17, 00)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(2)
-    );
+    // Then
+    let expected = OdbcReaderMetrics {
+        fetch_count: 4,
+        // 4 bytes for the integer and 8 bytes for the indicator, per row
+        total_buffer_bytes: 5 * 12,
+        empty_fetch_count: 1,
+    };
+    assert_eq!(expected, reader.metrics());
 }
 
-/// Fill a record batch of nullable timestamps with milliseconds precision
+/// Dropping a reader before the result set is consumed must close the cursor, so the statement can
+/// be executed again.
 #[test]
-fn fetch_date_time_ms() {
+fn drop_reader_before_result_set_is_consumed() {
+    // Given a prepared query returning three rows
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-
-    let array_any = fetch_arrow_data(
-        table_name,
-        "DATETIME",
-        "('2021-04-09 18:57:50'),(NULL),('2002-09-30 12:43:17')",
-    )
-    .unwrap();
-
-    let array_vals = array_any
-        .as_any()
-        .downcast_ref::<TimestampMillisecondArray>()
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES (1),(2),(3)");
+    conn.execute(&sql, ()).unwrap();
+    let mut prepared = conn
+        .prepare(&format!("SELECT a FROM {table_name} ORDER BY id"))
+        .unwrap();
+    let mut builder = OdbcReaderBuilder::new();
+    builder.with_max_num_rows_per_batch(2);
+
+    // When dropping readers before the first batch, and after the first batch with one row still
+    // left in the result set
+    for num_batches in [0, 1] {
+        let cursor = prepared.execute(()).unwrap().unwrap();
+        let mut reader = builder.build(cursor).unwrap();
+        for _ in 0..num_batches {
+            reader.next().unwrap().unwrap();
+        }
+        drop(reader);
+    }
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2021, 4, 9)
-                .unwrap()
-                .and_hms_milli_opt(18, 57, 50, 0)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(0)
-    );
-    assert!(array_vals.is_null(1));
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2002, 9, 30)
-                .unwrap()
-                .and_hms_milli_opt(12, 43, 17, 00)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(2)
-    );
+    // Then the statement can still be executed and yields the entire result set
+    let cursor = prepared.execute(()).unwrap().unwrap();
+    let reader = builder.build(cursor).unwrap();
+    let num_rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+    assert_eq!(3, num_rows);
 }
 
-/// Fill a record batch of non nullable timestamps with nanoseconds precision
+/// Reuse the buffers of a reader for a second execution of the same query.
 #[test]
-fn fetch_non_null_date_time_ns() {
+fn rebind_reader_to_new_cursor() {
+    // Given a table with two rows and a reader which consumed a result set over it
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    conn.execute(&format!("INSERT INTO {table_name} (a) VALUES (1),(2)"), ())
+        .unwrap();
+    let query = format!("SELECT a FROM {table_name} ORDER BY id");
+    let cursor = conn.execute(&query, ()).unwrap().unwrap();
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    assert_eq!(2, reader.next().unwrap().unwrap().num_rows());
+    assert!(reader.next().is_none());
 
-    let array_any = fetch_arrow_data(
-        table_name,
-        "DATETIME2 NOT NULL",
-        "('2021-04-09 18:57:50.1234567'),('2002-09-30 12:43:17.456')",
-    )
-    .unwrap();
+    // When polling the table again, after another row has been inserted
+    conn.execute(&format!("INSERT INTO {table_name} (a) VALUES (3)"), ())
+        .unwrap();
+    let cursor = conn.execute(&query, ()).unwrap().unwrap();
+    let mut reader = reader.rebind(cursor).unwrap();
 
-    let array_vals = array_any
+    // Then the reader yields the new result set
+    let batch = reader.next().unwrap().unwrap();
+    let array_vals = batch
+        .column(0)
         .as_any()
-        .downcast_ref::<TimestampNanosecondArray>()
+        .downcast_ref::<Int32Array>()
         .unwrap();
-
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2021, 4, 9)
-                .unwrap()
-                .and_hms_nano_opt(18, 57, 50, 123_456_700)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(0)
-    );
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2002, 9, 30)
-                .unwrap()
-                .and_hms_nano_opt(12, 43, 17, 456_000_000)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(1)
-    );
+    assert_eq!([1, 2, 3], *array_vals.values());
+    assert!(reader.next().is_none());
 }
 
-/// Precision 7 timestamps need to be mapped to nanoseconds. Nanoseconds timestamps have a valid
-/// range in arrow between 1677-09-21 00:12:44 and 2262-04-11 23:47:16.854775807 due to be
-/// represented as a signed 64Bit Integer
+/// Rows fetched without errors are not affected by checking the row status array, and the array is
+/// unbound from the statement once the reader is dropped.
 #[test]
-fn fetch_out_of_range_date_time_ns() {
+fn check_row_status_of_successful_rows() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    conn.execute(&format!("INSERT INTO {table_name} (a) VALUES (1),(2)"), ())
+        .unwrap();
+    let mut prepared = conn
+        .prepare(&format!("SELECT a FROM {table_name} ORDER BY id"))
+        .unwrap();
 
-    let error = fetch_arrow_data(
-        table_name,
-        "DATETIME2 NOT NULL",
-        "('2300-01-01 00:00:00.1234567')",
-    )
-    .unwrap_err();
+    // When
+    let cursor = prepared.execute(()).unwrap().unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .check_row_status(true)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+    let warnings = reader.take_warnings();
+    drop(reader);
+    // Executing the statement again, without a row status array bound to it
+    let mut cursor = prepared.execute(()).unwrap().unwrap();
+    let buffer = TextRowSet::for_cursor(10, &mut cursor, None).unwrap();
+    let mut block_cursor = cursor.bind_buffer(buffer).unwrap();
+    let num_rows = block_cursor.fetch().unwrap().unwrap().num_rows();
 
-    assert_eq!(
-        "External error: Timestamp is not representable in arrow: 2300-01-01 00:00:00.123456700\n\
-        Timestamps with nanoseconds precision are represented using a signed 64 Bit integer. This \
-        limits their range to values between 1677-09-21 00:12:44 and \
-        2262-04-11 23:47:16.854775807. The value returned from the database is outside of this \
-        range. Suggestions to fix this error either reduce the precision or fetch the values as \
-        text.",
-        error.to_string()
-    )
+    // Then
+    let array_vals = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!([1, 2], *array_vals.values());
+    assert!(warnings.is_empty());
+    assert_eq!(2, num_rows);
 }
 
-/// Precision 7 timestamps need to be mapped to nanoseconds. Nanoseconds timestamps have a valid
-/// range in arrow between 1677-09-21 00:12:44 and 2262-04-11 23:47:16.854775807 due to be
-/// represented as a signed 64Bit Integer. Default behaviour is to emit an error. In this case we
-/// want to map such values to NULL though.
+/// Buffers of a reader can not be reused for a result set with different columns.
 #[test]
-fn map_out_of_range_date_time_to_null() {
+fn rebind_reader_to_incompatible_cursor() {
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let cursor = cursor_over(
-        table_name,
-        "DATETIME2 NOT NULL",
-        "('2300-01-01 00:00:00.1234567'),('2002-09-30 12:43:17.456')",
-    );
-
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(100)
-        .value_errors_as_null(true)
-        .build(cursor)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER", "VARCHAR(10)"]).unwrap();
+    let cursor = conn
+        .execute(&format!("SELECT a FROM {table_name}"), ())
         .unwrap()
-        .into_concurrent()
         .unwrap();
-    // Batch for batch copy values from ODBC buffer into arrow batches
-    let record_batch = reader.next().unwrap().unwrap();
-    let array = record_batch.column(0).clone();
+    let reader = OdbcReaderBuilder::new().build(cursor).unwrap();
 
-    // Assert that the correct values are found within the arrow batch
-    let array_vals = array
-        .as_any()
-        .downcast_ref::<TimestampNanosecondArray>()
+    // Use another connection, since the result set of the first cursor is still pending
+    let other_conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    assert!(array_vals.is_null(0));
-    assert_eq!(
-        Some(
-            NaiveDate::from_ymd_opt(2002, 9, 30)
-                .unwrap()
-                .and_hms_nano_opt(12, 43, 17, 456_000_000)
-                .unwrap()
-        ),
-        array_vals.value_as_datetime(1)
-    );
+    let cursor = other_conn
+        .execute(&format!("SELECT b FROM {table_name}"), ())
+        .unwrap()
+        .unwrap();
+    let result = reader.rebind(cursor);
+
+    assert!(matches!(result, Err(Error::IncompatibleCursor { .. })));
 }
 
-/// Fill a record batch of Decimals
+/// Prepare a query once, describe its parameter and execute it twice with different arguments.
 #[test]
-fn fetch_decimals() {
-    // Given a cursor over a table with one decimal column
+fn prepared_reader_with_parameters() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let cursor = cursor_over(table_name, "DECIMAL(5,2) NOT NULL", "(123.45),(678.90)");
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES (1),(2),(3)");
+    conn.execute(&sql, ()).unwrap();
+    let sql = format!("SELECT a FROM {table_name} WHERE a > ? ORDER BY id");
+    let params_schema = Arc::new(Schema::new(vec![Field::new("p", DataType::Int32, false)]));
+    let params = |value: i32| {
+        RecordBatch::try_new(
+            params_schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![value]))],
+        )
+        .unwrap()
+    };
 
-    // When fetching it in batches of five
+    // When
+    let mut prepared = OdbcPreparedReader::new(&conn, &sql).unwrap();
+    let descriptions = prepared.describe_parameters().unwrap();
+    let mut first = Vec::new();
+    let reader = prepared.execute_with_params(&params(1)).unwrap().unwrap();
+    for batch in reader {
+        let batch = batch.unwrap();
+        let array = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        first.extend(array.values().iter().copied());
+    }
+    let mut second = Vec::new();
+    let reader = prepared.execute_with_params(&params(2)).unwrap().unwrap();
+    for batch in reader {
+        let batch = batch.unwrap();
+        let array = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        second.extend(array.values().iter().copied());
+    }
+
+    // Then
+    assert_eq!(1, descriptions.len());
+    assert_eq!(Some(DataType::Int32), descriptions[0].arrow_type);
+    assert_eq!([2, 3], first[..]);
+    assert_eq!([3], second[..]);
+}
+
+/// Statement attributes are set before each execution of a prepared reader.
+#[test]
+fn prepared_reader_with_statement_attribute() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES (1),(2),(3)");
+    conn.execute(&sql, ()).unwrap();
+    let sql = format!("SELECT a FROM {table_name} WHERE a > ? ORDER BY id");
+    let params_schema = Arc::new(Schema::new(vec![Field::new("p", DataType::Int32, false)]));
+    let params =
+        RecordBatch::try_new(params_schema, vec![Arc::new(Int32Array::from(vec![0]))]).unwrap();
+
+    // When
+    let mut prepared = OdbcPreparedReader::new(&conn, &sql).unwrap();
+    // SQL_ATTR_MAX_ROWS
+    prepared
+        .reader_builder()
+        .with_statement_attribute(1, StatementAttributeValue::Integer(2));
+    let reader = prepared.execute_with_params(&params).unwrap().unwrap();
+    let mut values = Vec::new();
+    for batch in reader {
+        let batch = batch.unwrap();
+        let array = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        values.extend(array.values().iter().copied());
+    }
+
+    // Then
+    assert_eq!([1, 2], values[..]);
+}
+
+/// Attributes used to bind the buffers of the reader must not be overwritten by the user.
+#[test]
+fn reject_reserved_statement_attribute() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = empty_cursor(table_name, "INTEGER");
+
+    // When
+    // SQL_ATTR_ROW_ARRAY_SIZE
+    let result = OdbcReaderBuilder::new()
+        .with_statement_attribute(27, StatementAttributeValue::Integer(1))
+        .build(cursor);
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(Error::ReservedStatementAttribute { attribute: 27, .. })
+    ));
+}
+
+/// Attributes are set on the cursor passed to `build`, so drivers refusing them cause an error
+/// rather than the attribute being silently ignored.
+#[test]
+fn build_sets_statement_attributes_on_cursor() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = empty_cursor(table_name, "INTEGER");
+
+    // When
+    // Not an attribute known to the driver
+    let result = OdbcReaderBuilder::new()
+        .with_statement_attribute(123_456, StatementAttributeValue::Integer(1))
+        .build(cursor);
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(Error::UnableToSetStatementAttribute {
+            attribute: 123_456,
+            ..
+        })
+    ));
+}
+
+/// Negative values can not be fetched into unsigned Arrow fields, unless the user explicitly opts
+/// into reinterpreting them.
+#[test]
+fn fetch_negative_value_as_unsigned_32bit_integer() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::UInt32, true)]));
+
+    // When
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(-1)");
     let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(5)
+        .with_schema(schema.clone())
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+    // Close the cursor, before setting up the table again
+    drop(reader);
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(-1)");
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .allow_signed_unsigned_coercion(true)
         .build(cursor)
         .unwrap();
     let record_batch = reader.next().unwrap().unwrap();
 
-    // Then the elements in the first column of the first batch must match the decimals in the
-    // database.
-    let column = record_batch.column(0).clone();
-    let array_vals = column.as_any().downcast_ref::<Decimal128Array>().unwrap();
+    // Then
+    assert!(result.is_err());
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<UInt32Array>()
+        .unwrap();
+    assert_eq!([1, u32::MAX], *array_vals.values());
+}
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!("123.45", array_vals.value_as_string(0));
-    assert_eq!("678.90", array_vals.value_as_string(1));
+/// Values out of range of an unsigned 16 Bit integer are reported as errors.
+#[test]
+fn fetch_unsigned_16bit_integer() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::UInt16, true)]));
+
+    // When
+    let cursor = cursor_over(table_name, "INTEGER", "(0),(65535),(NULL)");
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema.clone())
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+    drop(reader);
+    let cursor = cursor_over(table_name, "INTEGER", "(65536)");
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+
+    // Then
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<UInt16Array>()
+        .unwrap();
+    assert_eq!(
+        [Some(0), Some(u16::MAX), None],
+        array_vals.iter().collect::<Vec<_>>()[..]
+    );
+    assert!(result.is_err());
 }
 
-/// Ensure we do not drop sign in Decimal parsing
+/// Drivers which do not support 8 Bit integer buffers can fill `Int8` fields using 16 Bit ones
 #[test]
-fn fetch_negative_decimal() {
-    // Given a cursor over a table with one decimal column
+fn fetch_int8_using_smallint_buffer() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let cursor = cursor_over(table_name, "DECIMAL(5,2) NOT NULL", "(-123.45)");
+    let cursor = cursor_over(table_name, "SMALLINT", "(1),(-2),(NULL)");
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int8, true)]));
 
-    // When fetching it in batches of five
+    // When
     let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(5)
+        .with_schema(schema)
+        .fallback_tinyint_to_smallint(true)
         .build(cursor)
         .unwrap();
     let record_batch = reader.next().unwrap().unwrap();
 
-    // Then the elements in the first column of the first batch must match the decimals in the
-    // database.
-    let column = record_batch.column(0).clone();
-    let array_vals = column.as_any().downcast_ref::<Decimal128Array>().unwrap();
+    // Then
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int8Array>()
+        .unwrap();
+    assert_eq!(
+        vec![Some(1), Some(-2), None],
+        array_vals.iter().collect::<Vec<_>>()
+    );
+}
 
-    // Assert that the correct values are found within the arrow batch
-    assert_eq!("-123.45", array_vals.value_as_string(0));
+/// The message of the error is a single paragraph, without any whitespace of the source code
+/// indentation leaking into it.
+#[test]
+fn run_end_encoding_unsupported_message() {
+    let failure = ColumnFailure::RunEndEncodingUnsupported {
+        values_type: DataType::Int32,
+    };
+
+    assert_eq!(
+        "Run-end encoded fields can not be fetched from an ODBC data source, since every value is \
+        transmitted individually. Please specify the type of the values (`Int32`) for the field \
+        instead, and encode the fetched arrays if your application benefits from it.",
+        failure.to_string()
+    );
 }
 
-/// Fetch variable sized binary data binary data
+/// Run-end encoded fields are rejected, pointing users towards the type of the values.
 #[test]
-fn fetch_varbinary_data() {
+fn unsupported_run_end_encoded_field() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(1),(2)");
+    let data_type = DataType::RunEndEncoded(
+        Arc::new(Field::new("run_ends", DataType::Int32, false)),
+        Arc::new(Field::new("values", DataType::Int32, true)),
+    );
+    let schema = Arc::new(Schema::new(vec![Field::new("a", data_type, true)]));
 
-    // Setup a table on the database with some values (so we can fetch them)
-    let conn = ENV
-        .connect_with_connection_string(MSSQL, Default::default())
-        .unwrap();
-    setup_empty_table(&conn, table_name, &["VARBINARY(30) NOT NULL"]).unwrap();
-    let sql = format!("INSERT INTO {table_name} (a) VALUES (?)");
-    // Use prepared query and arguments for insertion, since literal representation depends a lot
-    // on the DB under test.
-    let mut insert = conn.prepare(&sql).unwrap();
-    insert.execute(&b"Hello".into_parameter()).unwrap();
-    insert.execute(&b"World".into_parameter()).unwrap();
+    // When
+    let result = OdbcReaderBuilder::new().with_schema(schema).build(cursor);
 
-    // Query column with values to get a cursor
-    let sql = format!("SELECT a FROM {table_name} ORDER BY id");
-    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    // Then
+    assert!(matches!(
+        result,
+        Err(Error::ColumnFailure {
+            source: ColumnFailure::RunEndEncodingUnsupported {
+                values_type: DataType::Int32
+            },
+            index: 0,
+            name: _
+        })
+    ))
+}
 
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+/// Transforms registered for a column are applied to each array fetched for it.
+#[test]
+fn post_fetch_transform() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER NOT NULL", "(1),(2),(3)");
+
+    // When scaling the values of the first column by ten, after fetching them
     let mut reader = OdbcReaderBuilder::new()
-        // Batches will contain at most 100 entries.
-        .with_max_num_rows_per_batch(100)
-        // Instantiate reader with Arrow schema and ODBC cursor
+        .with_post_fetch_transform(
+            0,
+            Arc::new(|array: &ArrayRef| {
+                let array = array.as_any().downcast_ref::<Int32Array>().unwrap();
+                let scaled: Int32Array = array.iter().map(|v| v.map(|v| v * 10)).collect();
+                Ok(Arc::new(scaled) as ArrayRef)
+            }),
+        )
         .build(cursor)
         .unwrap();
+    let batch = reader.next().unwrap().unwrap();
 
-    // Batch for batch copy values from ODBC buffer into arrow batches
-    let arrow_batch = reader.next().unwrap().unwrap();
+    // Then
+    let array = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!([10, 20, 30], *array.values());
+}
 
-    // Assert that the correct values are found within the arrow batch
-    let array_vals = arrow_batch
+/// Strategies from a registry take precedence over the built-in ones.
+#[test]
+fn custom_strategy_from_registry() {
+    // Given
+    struct Doubled;
+
+    impl ReadStrategy for Doubled {
+        fn buffer_desc(&self) -> BufferDesc {
+            BufferDesc::I32 { nullable: false }
+        }
+
+        fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+            let values = column_view.as_slice::<i32>().unwrap();
+            let array: Int32Array = values.iter().map(|value| value * 2).collect();
+            Ok(Arc::new(array))
+        }
+    }
+
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER NOT NULL", "(1),(2)");
+    let mut registry = ColumnStrategyRegistry::new();
+    registry.register(
+        SqlDataType::INTEGER,
+        DataType::Int32,
+        Arc::new(|_field: &Field, _sql_type: odbc_api::DataType| {
+            Box::new(Doubled) as Box<dyn ReadStrategy + Send>
+        }),
+    );
+
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_strategy_registry(registry)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then
+    let array = batch
         .column(0)
         .as_any()
-        .downcast_ref::<BinaryArray>()
+        .downcast_ref::<Int32Array>()
         .unwrap();
-    assert_eq!(b"Hello", array_vals.value(0));
-    assert_eq!(b"World", array_vals.value(1));
+    assert_eq!([2, 4], array.values()[..]);
+}
+
+/// The number of rows per batch is derived from the memory the strategies estimate for each row.
+#[test]
+fn batch_size_from_estimated_memory_per_row() {
+    // Given
+    struct Generous;
+
+    impl ReadStrategy for Generous {
+        fn buffer_desc(&self) -> BufferDesc {
+            BufferDesc::I32 { nullable: false }
+        }
+
+        fn fill_arrow_array(&self, column_view: AnySlice) -> Result<ArrayRef, MappingError> {
+            let values = column_view.as_slice::<i32>().unwrap();
+            Ok(Arc::new(Int32Array::from(values.to_vec())))
+        }
+
+        fn estimated_memory_per_row(&self) -> usize {
+            1000
+        }
+    }
+
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER NOT NULL", "(1),(2),(3)");
+    let mut registry = ColumnStrategyRegistry::new();
+    registry.register(
+        SqlDataType::INTEGER,
+        DataType::Int32,
+        Arc::new(|_field: &Field, _sql_type: odbc_api::DataType| {
+            Box::new(Generous) as Box<dyn ReadStrategy + Send>
+        }),
+    );
+
+    // When
+    let reader = OdbcReaderBuilder::new()
+        .with_strategy_registry(registry)
+        .with_max_bytes_per_batch(2500)
+        .build(cursor)
+        .unwrap();
+
+    // Then
+    assert_eq!(2, reader.max_rows_per_batch());
+}
+
+/// The reader describes its configuration in a single line, for use in log messages.
+#[test]
+fn display_reader_configuration() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1)");
+
+    // When
+    let reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(10)
+        .with_max_text_size(4096)
+        .build(cursor)
+        .unwrap();
+
+    // Then
+    assert_eq!(
+        "OdbcReader(schema=1 fields, batch_size=10, options={max_text_size=Some(4096), \
+        max_binary_size=None, fallibale_allocations=false, column_statistics=false})",
+        reader.to_string()
+    );
+}
+
+/// With the opaque binary fallback an otherwise unsupported type is fetched as raw bytes.
+#[test]
+fn opaque_binary_fallback_for_unsupported_type() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "SMALLINT", "(1)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Dictionary(Box::new(DataType::Int16), Box::new(DataType::Utf8)),
+        true,
+    )]));
+
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .opaque_binary_fallback(true)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then
+    assert_eq!(
+        &DataType::FixedSizeBinary(2),
+        reader.schema().field(0).data_type()
+    );
+    let array = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<FixedSizeBinaryArray>()
+        .unwrap();
+    assert_eq!(1i16.to_ne_bytes(), array.value(0));
+}
+
+/// Fields without a matching column in the result set are filled with NULLs, if the user opts in.
+#[test]
+fn null_pad_missing_column() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(2)");
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ]));
+
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .with_missing_column_behavior(MissingColumnBehavior::NullPad)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then
+    let a = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!([1, 2], a.values()[..]);
+    assert_eq!(2, batch.column(1).null_count());
+    assert_eq!(&DataType::Utf8, batch.column(1).data_type());
+}
+
+/// Observe that an explicitly specified dictionary with unsupported key and value types triggers
+/// an unsupported error
+#[test]
+fn unsupported_dictionary() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    // Setup a table on the database with some floats (so we can fetch them)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["SMALLINT NOT NULL"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES (1),(2),(3)");
+    conn.execute(&sql, ()).unwrap();
+
+    // Query column with values to get a cursor
+    let sql = format!("SELECT a FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Dictionary(Box::new(DataType::Int16), Box::new(DataType::Utf8)),
+        false,
+    )]));
+    let result = OdbcReaderBuilder::new().with_schema(schema).build(cursor);
+
+    assert!(matches!(
+        result,
+        Err(Error::ColumnFailure {
+            source: ColumnFailure::UnsupportedArrowType(DataType::Dictionary(_, _)),
+            index: 0,
+            name: _
+        })
+    ))
+}
+
+/// Interval fields can only be fetched from SQL interval columns
+#[test]
+fn interval_field_for_non_interval_column() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Interval(IntervalUnit::DayTime),
+        true,
+    )]));
+
+    let result = OdbcReaderBuilder::new().with_schema(schema).build(cursor);
+
+    assert!(matches!(
+        result,
+        Err(Error::ColumnFailure {
+            source: ColumnFailure::IncompatibleInterval {
+                data_type: DataType::Interval(IntervalUnit::DayTime),
+                ..
+            },
+            index: 0,
+            name: _
+        })
+    ))
+}
+
+/// Duration fields can only be fetched from day-time interval columns
+#[test]
+fn duration_field_for_non_interval_column() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "BIGINT", "(1)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Duration(TimeUnit::Millisecond),
+        true,
+    )]));
+
+    let result = OdbcReaderBuilder::new().with_schema(schema).build(cursor);
+
+    assert!(matches!(
+        result,
+        Err(Error::ColumnFailure {
+            source: ColumnFailure::IncompatibleInterval {
+                data_type: DataType::Duration(TimeUnit::Millisecond),
+                ..
+            },
+            index: 0,
+            name: _
+        })
+    ))
+}
+
+/// The error message for unsupported types suggests a supported alternative, if there is one.
+#[test]
+fn unsupported_arrow_type_suggests_alternative() {
+    let error = ColumnFailure::UnsupportedArrowType(DataType::Dictionary(
+        Box::new(DataType::Int16),
+        Box::new(DataType::Utf8),
+    ));
+
+    assert_eq!(
+        "Unsupported arrow type: `Dictionary(Int16, Utf8)`. This type can currently not be fetched \
+        from an ODBC data source by an instance of OdbcReader. Only `Dictionary(Int8, Int32)`, \
+        `Dictionary(UInt8, Int32)` and `Dictionary(Int32, Utf8)` are supported. Otherwise consider \
+        using the value type of the dictionary instead and encoding the fetched arrays.",
+        error.to_string()
+    );
+    assert!(suggestions_for_type(&DataType::Int32).is_none());
+}
+
+/// Errors are annotated with the data source, without revealing the credentials.
+#[test]
+fn annotate_errors_with_connection_info() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(table_name, "SMALLINT", "(1)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Dictionary(Box::new(DataType::Int16), Box::new(DataType::Utf8)),
+        true,
+    )]));
+    let result = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .with_connection_info(MSSQL)
+        .build(cursor);
+
+    let Err(error) = result else {
+        panic!("Building the reader must fail");
+    };
+    assert!(matches!(error, Error::WithConnectionInfo { .. }));
+    let message = error.to_string();
+    assert!(message.ends_with(
+        "Data source: Driver={ODBC Driver 17 for SQL Server};Server=localhost;UID=***;PWD=***"
+    ));
+    assert!(!message.contains("My@Test@Password1"));
+}
+
+/// Fill a record batch with non nullable Boolean from Bits
+#[test]
+fn fetch_boolean() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(table_name, "BIT NOT NULL", "(1),(0),(1)").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<BooleanArray>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert!(array_vals.value(0));
+    assert!(!array_vals.value(1));
+    assert!(array_vals.value(2));
+}
+
+/// Fill a record batch with nullable Booleans from Bits
+#[test]
+fn fetch_nullable_boolean() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(table_name, "BIT", "(1),(NULL),(0)").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<BooleanArray>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert!(array_vals.is_valid(0));
+    assert!(array_vals.value(0));
+    assert!(array_vals.is_null(1));
+    assert!(array_vals.is_valid(2));
+    assert!(!array_vals.value(2));
+}
+
+/// Times with fractional seconds are fetched as text and parsed, since the ODBC time struct has no
+/// field for fractions of a second.
+#[test]
+fn fetch_time_with_fractional_seconds() {
+    // Given a table with times at midnight, noon and the end of the day
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["TIME(7)"]).unwrap();
+    let sql = format!(
+        "INSERT INTO {table_name} (a) VALUES ('00:00:00'), ('12:00:00'), ('23:59:59.9999999'), \
+        (NULL)"
+    );
+    conn.execute(&sql, ()).unwrap();
+
+    // When fetching the column with millisecond, microsecond and nanosecond precision
+    let sql = format!("SELECT a, a, a FROM {table_name} ORDER BY id");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("ms", DataType::Time32(TimeUnit::Millisecond), true),
+        Field::new("us", DataType::Time64(TimeUnit::Microsecond), true),
+        Field::new("ns", DataType::Time64(TimeUnit::Nanosecond), true),
+    ]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then fractional seconds are truncated to the precision of each field
+    let expected: Time32MillisecondArray = [Some(0), Some(43_200_000), Some(86_399_999), None]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        &expected,
+        batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Time32MillisecondArray>()
+            .unwrap()
+    );
+    let expected: Time64MicrosecondArray =
+        [Some(0), Some(43_200_000_000), Some(86_399_999_999), None]
+            .into_iter()
+            .collect();
+    assert_eq!(
+        &expected,
+        batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Time64MicrosecondArray>()
+            .unwrap()
+    );
+    let expected: Time64NanosecondArray = [
+        Some(0),
+        Some(43_200_000_000_000),
+        Some(86_399_999_999_900),
+        None,
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        &expected,
+        batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Time64NanosecondArray>()
+            .unwrap()
+    );
+}
+
+/// Times without fractional seconds are bound as ODBC time structs
+#[test]
+fn fetch_time_as_seconds_since_midnight() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "TIME(0)", "('00:00:00'),('12:34:56'),(NULL)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Time32(TimeUnit::Second),
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    let expected: Time32SecondArray = [Some(0), Some(45_296), None].into_iter().collect();
+    assert_eq!(
+        &expected,
+        batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Time32SecondArray>()
+            .unwrap()
+    );
+}
+
+/// The unit of the inferred time type depends on the fractional precision of the column
+#[test]
+fn infer_time_unit_from_precision() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(
+        &conn,
+        table_name,
+        &["TIME(0)", "TIME(3)", "TIME(6)", "TIME(7)"],
+    )
+    .unwrap();
+    let sql = format!("SELECT a, b, c, d FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    let reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+
+    let schema = reader.schema();
+    assert_eq!(
+        &DataType::Time32(TimeUnit::Second),
+        schema.field(0).data_type()
+    );
+    assert_eq!(
+        &DataType::Time32(TimeUnit::Millisecond),
+        schema.field(1).data_type()
+    );
+    assert_eq!(
+        &DataType::Time64(TimeUnit::Microsecond),
+        schema.field(2).data_type()
+    );
+    assert_eq!(
+        &DataType::Time64(TimeUnit::Nanosecond),
+        schema.field(3).data_type()
+    );
+}
+
+/// Half precision floats are fetched as `REAL` and narrowed
+#[test]
+fn fetch_16bit_floating_point() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "REAL", "(1.5),(NULL),(-2)");
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float16, true)]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    let expected: Float16Array = [Some(F16::from_f32(1.5)), None, Some(F16::from_f32(-2.0))]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        &expected,
+        batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Float16Array>()
+            .unwrap()
+    );
+}
+
+/// Fill a record batch with non nullable `f32` directly from the datasource
+#[test]
+fn fetch_32bit_floating_point() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(table_name, "REAL NOT NULL", "(1),(2),(3)").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<Float32Array>().unwrap();
+    assert_float_eq!([1., 2., 3.][..], array_vals.values(), abs_all <= 000.1);
+}
+
+/// `REAL` columns are fetched as `Float64` if requested
+#[test]
+fn coerce_floats_to_float64() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "REAL NOT NULL", "(1.5),(2),(3)");
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_float_coercion(FloatCoercion::AllToFloat64)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    assert_eq!(&DataType::Float64, reader.schema().field(0).data_type());
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .unwrap();
+    assert_eq!([1.5, 2., 3.], *array_vals.values());
+}
+
+/// Fill a record batch with non nullable `f64` directly from the datasource
+#[test]
+fn fetch_64bit_floating_point() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any =
+        fetch_arrow_data(table_name, "DOUBLE PRECISION NOT NULL", "(1),(2),(3)").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<Float64Array>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_float_eq!([1., 2., 3.][..], array_vals.values(), abs_all <= 000.1);
+}
+
+/// Fill a record batch with non nullable `i64` directly from the datasource
+#[test]
+fn fetch_64bit_integer() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(table_name, "BIGINT NOT NULL", "(1),(2),(3)").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<Int64Array>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!([1, 2, 3], *array_vals.values());
+}
+
+/// XML columns can be fetched without specifying a size limit, since a default cap is used for
+/// their buffers.
+#[test]
+fn fetch_xml_with_default_size_cap() {
+    // Given an XML column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "XML", "('<a>Hello</a>'),(NULL)");
+
+    // When fetching it without any size limit
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then the documents are fetched as `LargeUtf8`
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!("<a>Hello</a>", array_vals.value(0));
+    assert!(array_vals.is_null(1));
+}
+
+/// XML documents exceeding the cap must cause an error, rather than being truncated.
+#[test]
+fn xml_exceeding_max_xml_size() {
+    // Given an XML document with 12 characters
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "XML", "('<a>Hello</a>')");
+
+    // When fetching it with a cap of 5 characters
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_xml_size(5)
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+
+    // Then we get an error
+    assert!(result.is_err())
+}
+
+/// SQL Server `GEOGRAPHY` columns are inferred as `Binary` with the `geoarrow.wkb` extension type.
+/// Their values are converted from the native serialization format into WKB, longitude first.
+#[test]
+fn fetch_geography_as_wkb() {
+    // Given a geography column with a point
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "GEOGRAPHY",
+        "(geography::Point(47.5, -122.25, 4326)),(NULL)",
+    );
+
+    // When fetching it with an inferred schema
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then the point is fetched as WKB
+    let field = reader.schema().field(0).clone();
+    assert_eq!(&DataType::Binary, field.data_type());
+    assert_eq!(
+        Some("geoarrow.wkb"),
+        field
+            .metadata()
+            .get("ARROW:extension:name")
+            .map(String::as_str)
+    );
+    assert_eq!(
+        Some(r#"{"crs":"OGC:CRS84","crs_type":"authority_code","edges":"spherical"}"#),
+        field
+            .metadata()
+            .get("ARROW:extension:metadata")
+            .map(String::as_str)
+    );
+    let mut expected = vec![1, 1, 0, 0, 0];
+    expected.extend_from_slice(&(-122.25f64).to_le_bytes());
+    expected.extend_from_slice(&47.5f64.to_le_bytes());
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<BinaryArray>()
+        .unwrap();
+    assert_eq!(expected.as_slice(), array_vals.value(0));
+    assert!(array_vals.is_null(1));
+}
+
+/// Polygons are serialized by SQL Server with figures and shapes, rather than as a single point.
+#[test]
+fn fetch_geometry_polygon_as_wkb() {
+    // Given a geometry column with a triangle
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "GEOMETRY",
+        "(geometry::STGeomFromText('POLYGON((0 0, 2 0, 2 2, 0 0))', 0))",
+    );
+
+    // When fetching it
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then it is fetched as a WKB polygon with one ring of four points
+    let mut expected = vec![1, 3, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0];
+    for coordinate in [0., 0., 2., 0., 2., 2., 0., 0.] {
+        expected.extend_from_slice(&f64::to_le_bytes(coordinate));
+    }
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<BinaryArray>()
+        .unwrap();
+    assert_eq!(expected.as_slice(), array_vals.value(0));
+}
+
+/// SQL Server stores JSON documents in `NVARCHAR` columns, rather than in a type of its own. Only
+/// the type name tells JSON columns apart, so these must not be annotated with `arrow.json`.
+#[test]
+fn json_in_nvarchar_column_is_not_annotated() {
+    // Given a text column holding a JSON document
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "NVARCHAR(50) CHECK (ISJSON(a) = 1)",
+        "('{\"answer\":42}')",
+    );
+
+    // When fetching it with an inferred schema
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then the field is plain text
+    let field = reader.schema().field(0).clone();
+    assert_eq!(&DataType::Utf8, field.data_type());
+    assert!(field.metadata().is_empty());
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!("{\"answer\":42}", array_vals.value(0));
+}
+
+/// Rich metadata tells consumers which table, schema and catalog a column stems from.
+#[test]
+fn annotate_fields_with_provenance() {
+    // Given a cursor over a table column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(42)");
+
+    // When building a reader with rich metadata
+    let reader = OdbcReaderBuilder::new()
+        .with_rich_metadata(true)
+        .build(cursor)
+        .unwrap();
+
+    // Then the field is annotated with the origin of the column
+    let schema = reader.schema();
+    let metadata = schema.field(0).metadata();
+    assert_eq!(Some("a"), metadata.get("odbc.label").map(String::as_str));
+    assert_eq!(
+        Some(table_name),
+        metadata.get("odbc.base_table_name").map(String::as_str)
+    );
+    assert_eq!(
+        Some("dbo"),
+        metadata.get("odbc.schema_name").map(String::as_str)
+    );
+    assert_eq!(
+        Some("master"),
+        metadata.get("odbc.catalog_name").map(String::as_str)
+    );
+}
+
+/// Fill a record batch of Strings from a varchar source column
+#[test]
+fn fetch_varchar() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any =
+        fetch_arrow_data(table_name, "VARCHAR(50)", "('Hello'),('Bonjour'),(NULL)").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!("Hello", array_vals.value(0));
+    assert_eq!("Bonjour", array_vals.value(1));
+    assert!(array_vals.is_null(2));
+}
+
+/// Fill a `StringViewArray` from a varchar source column
+#[test]
+fn fetch_varchar_as_utf8_view() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "VARCHAR(50)",
+        "('Hello'),('A string too long to be inlined'),(NULL)",
+    );
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_text_output_mode(TextOutputMode::Utf8View)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    assert_eq!(&DataType::Utf8View, reader.schema().field(0).data_type());
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringViewArray>()
+        .unwrap();
+    assert_eq!("Hello", array_vals.value(0));
+    assert_eq!("A string too long to be inlined", array_vals.value(1));
+    assert!(array_vals.is_null(2));
+}
+
+/// Fill a `LargeStringArray` from a varchar source column
+#[test]
+fn fetch_varchar_as_large_utf8() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "VARCHAR(50)", "('Hello'),(NULL)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::LargeUtf8,
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!("Hello", array_vals.value(0));
+    assert!(array_vals.is_null(1));
+}
+
+/// SQL Server reports XML columns with a driver specific type. They are fetched as `LargeUtf8`.
+#[test]
+fn fetch_xml() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "XML", "('<a>Hello</a>'),(NULL)");
+
+    // XML columns report no maximum length, so we need to specify an upper bound
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_text_size(100)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    assert_eq!(&DataType::LargeUtf8, reader.schema().field(0).data_type());
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!("<a>Hello</a>", array_vals.value(0));
+    assert!(array_vals.is_null(1));
+}
+
+/// Fill a record batch of Strings from a varchar source column
+#[test]
+fn trim_fixed_sized_character_data() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(table_name, "CHAR(4)", "('1234'),(' 123'),('123 ')");
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(4)
+        .trim_fixed_sized_characters(true)
+        .build(cursor)
+        .unwrap()
+        .into_concurrent()
+        .unwrap();
+    // Batch for batch copy values from ODBC buffer into arrow batches
+    let record_batch = reader.next().unwrap().unwrap();
+    let array_any = record_batch.column(0).clone();
+
+    // Assert that the correct values are found within the arrow batch
+    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!("1234", array_vals.value(0));
+    assert_eq!("123", array_vals.value(1));
+    assert_eq!("123", array_vals.value(2));
+}
+
+/// Padding of fixed sized character columns is removed by default, leading spaces are kept.
+#[test]
+fn trim_char_padding_by_default() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(table_name, "CHAR(5)", "('12'),(' 12')").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!("12", array_vals.value(0));
+    assert_eq!(" 12", array_vals.value(1));
+}
+
+/// Users may opt out of removing the padding of fixed sized character columns.
+#[test]
+fn keep_char_padding() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(table_name, "CHAR(5)", "('12')");
+    let mut reader = OdbcReaderBuilder::new()
+        .trim_char_padding(false)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!("12   ", array_vals.value(0));
+}
+
+/// GUIDs are reordered from the mixed-endian layout of the Windows `GUID` struct into the byte
+/// order of a UUID.
+#[test]
+fn fetch_guid_as_fixed_size_binary() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(
+        table_name,
+        "UNIQUEIDENTIFIER",
+        "('550E8400-E29B-41D4-A716-446655440000'),(NULL)",
+    );
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::FixedSizeBinary(16),
+        true,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<FixedSizeBinaryArray>()
+        .unwrap();
+    assert_eq!(
+        [
+            0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4, 0xa7, 0x16, 0x44, 0x66, 0x55, 0x44,
+            0x00, 0x00
+        ],
+        array_vals.value(0)
+    );
+    assert!(array_vals.is_null(1));
+}
+
+/// GUIDs are formatted as lower case hyphenated UUIDs, if requested.
+#[test]
+fn format_guid_as_string() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(
+        table_name,
+        "UNIQUEIDENTIFIER",
+        "('550E8400-E29B-41D4-A716-446655440000'),('00000000-0000-0000-0000-000000000001'),(NULL)",
+    );
+    let mut reader = OdbcReaderBuilder::new()
+        .format_guid_as_string(true)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!("550e8400-e29b-41d4-a716-446655440000", array_vals.value(0));
+    assert_eq!("00000000-0000-0000-0000-000000000001", array_vals.value(1));
+    assert!(array_vals.is_null(2));
+}
+
+/// Small integer codes are fetched into a dictionary, built from the distinct values of the batch.
+#[test]
+fn fetch_integer_codes_as_dictionary() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(table_name, "TINYINT", "(1),(2),(1),(NULL)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Dictionary(Box::new(DataType::UInt8), Box::new(DataType::Int32)),
+        true,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<DictionaryArray<UInt8Type>>()
+        .unwrap();
+    let keys: Vec<_> = array_vals.keys().iter().collect();
+    assert_eq!(vec![Some(0), Some(1), Some(0), None], keys);
+    let values = array_vals
+        .values()
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(&[1, 2], values.values());
+}
+
+/// If the dictionary is provided upfront, the keys index into it, and all batches share it.
+#[test]
+fn fetch_integer_codes_with_predefined_dictionary() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(table_name, "INTEGER NOT NULL", "(30),(10)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Int32)),
+        false,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .with_dictionary_values(0, vec![10, 20, 30])
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<DictionaryArray<Int8Type>>()
+        .unwrap();
+    assert_eq!(&[2, 0], array_vals.keys().values());
+    let values = array_vals
+        .values()
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(&[10, 20, 30], values.values());
+}
+
+/// Fill a record batch of Strings from a nvarchar source column
+#[test]
+fn fetch_nvarchar() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any =
+        fetch_arrow_data(table_name, "NVARCHAR(50)", "('Hello'),('Bonjour'),(NULL)").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!("Hello", array_vals.value(0));
+    assert_eq!("Bonjour", array_vals.value(1));
+    assert!(array_vals.is_null(2));
+}
+
+/// Wide character columns are fetched as UTF-16 and transcoded, so characters outside of the
+/// system locale (including ones requiring surrogate pairs) survive.
+#[test]
+fn fetch_non_ascii_nvarchar() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(table_name, "NVARCHAR(10)", "(N'Ünïcödé'),(N'€😀')").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!("Ünïcödé", array_vals.value(0));
+    assert_eq!("€😀", array_vals.value(1));
+}
+
+/// PostgreSQL transmits arrays as text literals. We emulate this with a VARCHAR column.
+#[test]
+fn fetch_list_from_array_literals() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "VARCHAR(20)",
+        "('{1,2,NULL}'),(NULL),('{}'),('{ 3 , \"4\" }')",
+    );
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::List(Arc::new(Field::new("item", DataType::Int32, true))),
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .unwrap();
+    let expected = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+        Some(vec![Some(1), Some(2), None]),
+        None,
+        Some(vec![]),
+        Some(vec![Some(3), Some(4)]),
+    ]);
+    assert_eq!(&expected, array_vals);
+}
+
+/// Array literals can also be fetched into lists with 64 Bit offsets
+#[test]
+fn fetch_large_list_from_array_literals() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "VARCHAR(20)", "('{a,\"b,c\"}'),(NULL)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::LargeList(Arc::new(Field::new("item", DataType::Utf8, true))),
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeListArray>()
+        .unwrap();
+    assert_eq!(&[0, 2, 2], array_vals.value_offsets());
+    assert!(array_vals.is_null(1));
+    let elements = array_vals
+        .values()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!("a", elements.value(0));
+    assert_eq!("b,c", elements.value(1));
+}
+
+/// PostgreSQL transmits row types as composite literals. We emulate this with a VARCHAR column.
+#[test]
+fn fetch_struct_from_composite_literals() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "VARCHAR(30)",
+        "('(1,\"two, \"\"2\"\"\",t)'),(NULL),('(3,,f)')",
+    );
+    let fields = Fields::from(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("active", DataType::Boolean, false),
+    ]);
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Struct(fields),
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .unwrap();
+    assert!(array_vals.is_null(1));
+    let ids = array_vals
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(1, ids.value(0));
+    assert_eq!(3, ids.value(2));
+    let names = array_vals
+        .column(1)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!("two, \"2\"", names.value(0));
+    assert!(names.is_null(2));
+    let active = array_vals
+        .column(2)
+        .as_any()
+        .downcast_ref::<BooleanArray>()
+        .unwrap();
+    assert!(active.value(0));
+    assert!(!active.value(2));
+}
+
+/// ClickHouse transmits maps as text literals. We emulate this with a VARCHAR column.
+#[test]
+fn fetch_map_from_map_literals() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "VARCHAR(30)",
+        "('{''a'':1,''b'':NULL}'),(NULL),('{}')",
+    );
+    let entries = Arc::new(Field::new(
+        "entries",
+        DataType::Struct(Fields::from(vec![
+            Field::new("keys", DataType::Utf8, false),
+            Field::new("values", DataType::Int32, true),
+        ])),
+        false,
+    ));
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Map(entries, false),
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<MapArray>()
+        .unwrap();
+    assert_eq!(&[0, 2, 2, 2], array_vals.value_offsets());
+    assert!(array_vals.is_null(1));
+    let keys = array_vals
+        .keys()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!("a", keys.value(0));
+    assert_eq!("b", keys.value(1));
+    let values = array_vals
+        .values()
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(1, values.value(0));
+    assert!(values.is_null(1));
+}
+
+/// Fixed size lists of floats, transmitted as binary blobs in little endian byte order. Like
+/// embeddings stored in vector databases.
+#[test]
+fn fetch_fixed_size_list_from_binary() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    // 1.0 and 2.0 as little endian f32
+    let cursor = cursor_over(table_name, "BINARY(8)", "(0x0000803F00000040),(NULL)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::FixedSizeList(Arc::new(Field::new("item", DataType::Float32, true)), 2),
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<FixedSizeListArray>()
+        .unwrap();
+    assert!(array_vals.is_valid(0));
+    assert!(array_vals.is_null(1));
+    let first = array_vals.value(0);
+    let first = first.as_any().downcast_ref::<Float32Array>().unwrap();
+    assert_eq!([1.0, 2.0], first.values()[..]);
+}
+
+/// Fill a record batch of Dates
+#[test]
+fn fetch_dates() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any =
+        fetch_arrow_data(table_name, "DATE", "('2021-04-09'),(NULL),('2002-09-30')").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<Date32Array>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!(
+        Some(NaiveDate::from_ymd_opt(2021, 4, 9).unwrap()),
+        array_vals.value_as_date(0)
+    );
+    assert!(array_vals.is_null(1));
+    assert_eq!(
+        Some(NaiveDate::from_ymd_opt(2002, 9, 30).unwrap()),
+        array_vals.value_as_date(2)
+    );
+}
+
+/// Fill a record batch of non nullable Dates
+#[test]
+fn fetch_non_null_dates() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any =
+        fetch_arrow_data(table_name, "DATE NOT NULL", "('2021-04-09'),('2002-09-30')").unwrap();
+
+    let array_vals = array_any.as_any().downcast_ref::<Date32Array>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!(
+        Some(NaiveDate::from_ymd_opt(2021, 4, 9).unwrap()),
+        array_vals.value_as_date(0)
+    );
+    assert_eq!(
+        Some(NaiveDate::from_ymd_opt(2002, 9, 30).unwrap()),
+        array_vals.value_as_date(1)
+    );
+}
+
+/// Fill a `Date64Array` from a date column, for consumers requiring milliseconds since epoch
+#[test]
+fn fetch_dates_as_date64() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "DATE", "('2021-04-09'),(NULL)");
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Date64, true)]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    let array_vals = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Date64Array>()
+        .unwrap();
+    assert_eq!(
+        Some(NaiveDate::from_ymd_opt(2021, 4, 9).unwrap()),
+        array_vals.value_as_date(0)
+    );
+    assert!(array_vals.is_null(1));
+}
+
+/// Fill a record batch of non nullable timestamps with milliseconds precision
+#[test]
+fn fetch_non_null_date_time() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(
+        table_name,
+        "DATETIME NOT NULL",
+        "('2021-04-09 18:57:50.12'),('2002-09-30 12:43:17.45')",
+    )
+    .unwrap();
+
+    let array_vals = array_any
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2021, 4, 9)
+                .unwrap()
+                .and_hms_milli_opt(18, 57, 50, 120)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(0)
+    );
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2002, 9, 30)
+                .unwrap()
+                .and_hms_milli_opt(12, 43, 17, 450)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(1)
+    );
+}
+
+/// Fill a record batch of nullable timestamps with milliseconds precision
+#[test]
+fn fetch_date_time_us() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(
+        table_name,
+        "DATETIME2(6)",
+        "('2021-04-09 18:57:50'),(NULL),('2002-09-30 12:43:17')",
+    )
+    .unwrap();
+
+    let array_vals = array_any
+        .as_any()
+        .downcast_ref::<TimestampMicrosecondArray>()
+        .unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2021, 4, 9)
+                .unwrap()
+                .and_hms_milli_opt(18, 57, 50, 0)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(0)
+    );
+    assert!(array_vals.is_null(1));
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2002, 9, 30)
+                .unwrap()
+                .and_hms_milli_opt(12, 43, 17, 00)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(2)
+    );
+}
+
+/// Fill a record batch of nullable timestamps with milliseconds precision
+#[test]
+fn fetch_date_time_ms() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(
+        table_name,
+        "DATETIME",
+        "('2021-04-09 18:57:50'),(NULL),('2002-09-30 12:43:17')",
+    )
+    .unwrap();
+
+    let array_vals = array_any
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2021, 4, 9)
+                .unwrap()
+                .and_hms_milli_opt(18, 57, 50, 0)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(0)
+    );
+    assert!(array_vals.is_null(1));
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2002, 9, 30)
+                .unwrap()
+                .and_hms_milli_opt(12, 43, 17, 00)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(2)
+    );
+}
+
+/// Fractions finer than the unit of the field can be rounded rather than truncated.
+#[test]
+fn round_timestamp_to_milliseconds() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(
+        table_name,
+        "DATETIME2(7)",
+        "('2021-04-09 18:57:50.1234567'),('2021-04-09 18:57:59.9996')",
+    );
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Timestamp(TimeUnit::Millisecond, None),
+        true,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .with_sub_precision_handling(SubPrecisionHandling::Round)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .unwrap();
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2021, 4, 9)
+                .unwrap()
+                .and_hms_milli_opt(18, 57, 50, 123)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(0)
+    );
+    // Rounding carries over into the next second
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2021, 4, 9)
+                .unwrap()
+                .and_hms_milli_opt(18, 58, 0, 0)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(1)
+    );
+}
+
+/// Fetching a timestamp which would lose precision fails, if requested.
+#[test]
+fn sub_precision_loss_is_an_error() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let cursor = cursor_over(
+        table_name,
+        "DATETIME2(7)",
+        "('2021-04-09 18:57:50.1234567')",
+    );
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Timestamp(TimeUnit::Microsecond, None),
+        true,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .with_sub_precision_handling(SubPrecisionHandling::Error)
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+
+    assert!(result.is_err());
+}
+
+/// Interpret timestamps as local time of a timezone, for databases storing local time
+#[test]
+fn fetch_date_time_with_timezone_annotation() {
+    // Given a cursor over a table with a timestamp column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "DATETIME2(0)", "('2021-04-09 18:57:50')");
+
+    // When fetching it with a timezone annotation
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(5)
+        .with_timestamp_timezone(Some("+02:00".into()))
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then the schema carries the timezone, and the values are converted to UTC
+    assert_eq!(
+        &DataType::Timestamp(TimeUnit::Second, Some("+02:00".into())),
+        reader.schema().field(0).data_type()
+    );
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<TimestampSecondArray>()
+        .unwrap();
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2021, 4, 9)
+                .unwrap()
+                .and_hms_opt(16, 57, 50)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(0)
+    );
+}
+
+/// Timestamps with offset are normalized to UTC, if fetched into a field with a timezone
+#[test]
+fn fetch_date_time_offset_normalized_to_utc() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "DATETIMEOFFSET(3)",
+        "('2021-04-09 18:57:50.123 +02:00'),(NULL)",
+    );
+
+    // When
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then
+    assert_eq!(
+        &DataType::Timestamp(TimeUnit::Millisecond, Some("+00:00".into())),
+        reader.schema().field(0).data_type()
+    );
+    let array_vals = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .unwrap();
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2021, 4, 9)
+                .unwrap()
+                .and_hms_milli_opt(16, 57, 50, 123)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(0)
+    );
+    assert!(array_vals.is_null(1));
+}
+
+/// Timezones are validated while building the reader
+#[test]
+fn invalid_timestamp_timezone() {
+    // Given a cursor over a table with a timestamp column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "DATETIME2(0)", "('2021-04-09 18:57:50')");
+
+    // When building a reader with a timezone which is neither an offset nor a name
+    let result = OdbcReaderBuilder::new()
+        .with_timestamp_timezone(Some("not a timezone".into()))
+        .build(cursor);
+
+    // Then building the reader fails
+    assert!(matches!(result, Err(Error::InvalidTimezone(_))));
+}
+
+/// Fill a record batch of non nullable timestamps with nanoseconds precision
+#[test]
+fn fetch_non_null_date_time_ns() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let array_any = fetch_arrow_data(
+        table_name,
+        "DATETIME2 NOT NULL",
+        "('2021-04-09 18:57:50.1234567'),('2002-09-30 12:43:17.456')",
+    )
+    .unwrap();
+
+    let array_vals = array_any
+        .as_any()
+        .downcast_ref::<TimestampNanosecondArray>()
+        .unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2021, 4, 9)
+                .unwrap()
+                .and_hms_nano_opt(18, 57, 50, 123_456_700)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(0)
+    );
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2002, 9, 30)
+                .unwrap()
+                .and_hms_nano_opt(12, 43, 17, 456_000_000)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(1)
+    );
+}
+
+/// Precision 7 timestamps need to be mapped to nanoseconds. Nanoseconds timestamps have a valid
+/// range in arrow between 1677-09-21 00:12:44 and 2262-04-11 23:47:16.854775807 due to be
+/// represented as a signed 64Bit Integer
+#[test]
+fn fetch_out_of_range_date_time_ns() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    let error = fetch_arrow_data(
+        table_name,
+        "DATETIME2 NOT NULL",
+        "('2300-01-01 00:00:00.1234567')",
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        "External error: Timestamp is not representable in arrow: 2300-01-01 00:00:00.123456700\n\
+        Timestamps with nanoseconds precision are represented using a signed 64 Bit integer. This \
+        limits their range to values between 1677-09-21 00:12:44 and \
+        2262-04-11 23:47:16.854775807. The value returned from the database is outside of this \
+        range. Suggestions to fix this error either reduce the precision or fetch the values as \
+        text.",
+        error.to_string()
+    )
+}
+
+/// Precision 7 timestamps need to be mapped to nanoseconds. Nanoseconds timestamps have a valid
+/// range in arrow between 1677-09-21 00:12:44 and 2262-04-11 23:47:16.854775807 due to be
+/// represented as a signed 64Bit Integer. Default behaviour is to emit an error. In this case we
+/// want to map such values to NULL though.
+#[test]
+fn map_out_of_range_date_time_to_null() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "DATETIME2 NOT NULL",
+        "('2300-01-01 00:00:00.1234567'),('2002-09-30 12:43:17.456')",
+    );
+
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(100)
+        .value_errors_as_null(true)
+        .build(cursor)
+        .unwrap()
+        .into_concurrent()
+        .unwrap();
+    // Batch for batch copy values from ODBC buffer into arrow batches
+    let record_batch = reader.next().unwrap().unwrap();
+    let array = record_batch.column(0).clone();
+
+    // Assert that the correct values are found within the arrow batch
+    let array_vals = array
+        .as_any()
+        .downcast_ref::<TimestampNanosecondArray>()
+        .unwrap();
+    assert!(array_vals.is_null(0));
+    assert_eq!(
+        Some(
+            NaiveDate::from_ymd_opt(2002, 9, 30)
+                .unwrap()
+                .and_hms_nano_opt(12, 43, 17, 456_000_000)
+                .unwrap()
+        ),
+        array_vals.value_as_datetime(1)
+    );
+}
+
+/// Parsing decimal text yields the same values as `odbc_api`, with or without the `simd` feature.
+#[test]
+fn parse_decimal_text_like_odbc_api() {
+    let cases: [(&[u8], usize); 9] = [
+        (b"0", 0),
+        (b"-0.5", 1),
+        (b"+12.3", 3),
+        (b"123456789", 0),
+        (b"1234567890123456.78901234", 10),
+        (b".5", 2),
+        (b"5.", 2),
+        (b"-1,25", 2),
+        (b"-99999999999999999999999999999999999.999", 3),
+    ];
+    for (text, scale) in cases {
+        assert_eq!(
+            Some(arrow_odbc::odbc_api::decimal_text_to_i128(text, scale)),
+            parse_decimal_text(text, scale)
+        );
+    }
+}
+
+/// Fill a record batch of Decimals
+#[test]
+fn fetch_decimals() {
+    // Given a cursor over a table with one decimal column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "DECIMAL(5,2) NOT NULL", "(123.45),(678.90)");
+
+    // When fetching it in batches of five
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(5)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then the elements in the first column of the first batch must match the decimals in the
+    // database.
+    let column = record_batch.column(0).clone();
+    let array_vals = column.as_any().downcast_ref::<Decimal128Array>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!("123.45", array_vals.value_as_string(0));
+    assert_eq!("678.90", array_vals.value_as_string(1));
+}
+
+/// Currency columns have a fixed scale of four digits, independent of what the driver reports.
+#[test]
+fn fetch_money_as_decimal() {
+    // Given a table with a MONEY and a SMALLMONEY column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, ConnectionOptions::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["MONEY", "SMALLMONEY"]).unwrap();
+    let sql = format!(
+        "INSERT INTO {table_name} (a, b) VALUES \
+        (-922337203685477.5808, -214748.3648), (123.45, 0.5), (NULL, NULL)"
+    );
+    conn.execute(&sql, ()).unwrap();
+    let sql = format!("SELECT a, b FROM {table_name} ORDER BY id");
+    let cursor = conn.into_cursor(&sql, ()).unwrap().unwrap();
+
+    // When fetching them with an inferred schema
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then they are fetched into decimals with a scale of four
+    let schema = reader.schema();
+    assert_eq!(&DataType::Decimal128(19, 4), schema.field(0).data_type());
+    assert_eq!(&DataType::Decimal128(10, 4), schema.field(1).data_type());
+    let money = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Decimal128Array>()
+        .unwrap();
+    let small_money = record_batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<Decimal128Array>()
+        .unwrap();
+    assert_eq!("-922337203685477.5808", money.value_as_string(0));
+    assert_eq!("123.4500", money.value_as_string(1));
+    assert!(money.is_null(2));
+    assert_eq!("-214748.3648", small_money.value_as_string(0));
+    assert_eq!("0.5000", small_money.value_as_string(1));
+    assert!(small_money.is_null(2));
+}
+
+/// Ensure we do not drop sign in Decimal parsing
+#[test]
+fn fetch_negative_decimal() {
+    // Given a cursor over a table with one decimal column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "DECIMAL(5,2) NOT NULL", "(-123.45)");
+
+    // When fetching it in batches of five
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(5)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then the elements in the first column of the first batch must match the decimals in the
+    // database.
+    let column = record_batch.column(0).clone();
+    let array_vals = column.as_any().downcast_ref::<Decimal128Array>().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    assert_eq!("-123.45", array_vals.value_as_string(0));
+}
+
+/// Decimals can be fetched into `Decimal256` arrays, with a different scale than the column
+#[test]
+fn fetch_decimal_256() {
+    // Given a cursor over a table with a decimal column of maximum precision
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "DECIMAL(38,2)",
+        "(123456789012345678901234567890123456.78),(-1.5),(NULL)",
+    );
+
+    // When fetching it as a decimal with 256 Bit
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Decimal256(40, 3),
+        true,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then
+    let array_vals = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Decimal256Array>()
+        .unwrap();
+    assert_eq!(
+        "123456789012345678901234567890123456.780",
+        array_vals.value_as_string(0)
+    );
+    assert_eq!("-1.500", array_vals.value_as_string(1));
+    assert!(array_vals.is_null(2));
+}
+
+/// Values which have more digits than the precision of the decimal column in the schema allows for
+/// must not silently end up in the Arrow array.
+#[test]
+fn fetch_decimal_exceeding_precision_of_schema() {
+    // Given a cursor over a table with one decimal column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "DECIMAL(5,0) NOT NULL", "(12345)");
+
+    // When fetching it with a schema specifying a precision of only three digits
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Decimal128(3, 0),
+        false,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(5)
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+
+    // Then an error is emitted
+    assert_eq!(
+        "External error: Decimal is not representable in arrow: 12345\n\
+        The value returned from the database has more digits than the precision of the Arrow \
+        column (3) allows for. Suggestions to fix this error are to specify a schema with a larger \
+        precision or to fetch the values as text.",
+        result.unwrap_err().to_string()
+    )
+}
+
+/// Text which is not a decimal number (e.g. `NaN`) must not silently become zero
+#[test]
+fn fetch_decimal_from_text_which_is_not_a_number() {
+    // Given a cursor over a table with a text column holding `NaN`
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "VARCHAR(10) NOT NULL", "('NaN')");
+
+    // When fetching it as a decimal
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Decimal128(5, 2),
+        false,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(5)
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+
+    // Then an error is emitted, containing the offending value
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("'NaN' (raw bytes: [78, 97, 78])"), "{error}");
+}
+
+/// Fetch decimals from a column the database reports as floating point
+#[test]
+fn fetch_decimals_from_float_column() {
+    // Given a cursor over a table with one float column
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "FLOAT", "(123.456),(NULL),(-0.005)");
+
+    // When fetching it with a schema requesting decimals with a scale of two
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Decimal128(10, 2),
+        true,
+    )]));
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(5)
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+
+    // Then the values are rounded to the scale of the decimal
+    let column = record_batch.column(0).clone();
+    let array_vals = column.as_any().downcast_ref::<Decimal128Array>().unwrap();
+    assert_eq!("123.46", array_vals.value_as_string(0));
+    assert!(array_vals.is_null(1));
+    assert_eq!("-0.01", array_vals.value_as_string(2));
+}
+
+/// Fetch variable sized binary data binary data
+#[test]
+fn fetch_varbinary_data() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    // Setup a table on the database with some values (so we can fetch them)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARBINARY(30) NOT NULL"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES (?)");
+    // Use prepared query and arguments for insertion, since literal representation depends a lot
+    // on the DB under test.
+    let mut insert = conn.prepare(&sql).unwrap();
+    insert.execute(&b"Hello".into_parameter()).unwrap();
+    insert.execute(&b"World".into_parameter()).unwrap();
+
+    // Query column with values to get a cursor
+    let sql = format!("SELECT a FROM {table_name} ORDER BY id");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        // Batches will contain at most 100 entries.
+        .with_max_num_rows_per_batch(100)
+        // Instantiate reader with Arrow schema and ODBC cursor
+        .build(cursor)
+        .unwrap();
+
+    // Batch for batch copy values from ODBC buffer into arrow batches
+    let arrow_batch = reader.next().unwrap().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    let array_vals = arrow_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<BinaryArray>()
+        .unwrap();
+    assert_eq!(b"Hello", array_vals.value(0));
+    assert_eq!(b"World", array_vals.value(1));
+}
+
+/// Fill a `LargeBinaryArray` from a varbinary source column
+#[test]
+fn fetch_varbinary_as_large_binary() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "VARBINARY(10)", "(0x0102),(NULL)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::LargeBinary,
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    let array_vals = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeBinaryArray>()
+        .unwrap();
+    assert_eq!([1, 2], array_vals.value(0));
+    assert!(array_vals.is_null(1));
+}
+
+/// Fill a `BinaryViewArray` from a varbinary source column
+#[test]
+fn fetch_varbinary_as_binary_view() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(
+        table_name,
+        "VARBINARY(20)",
+        "(0x0102),(NULL),(0x0102030405060708090A0B0C0D0E0F10)",
+    );
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::BinaryView,
+        true,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    let array_vals = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<BinaryViewArray>()
+        .unwrap();
+    assert_eq!([1, 2], array_vals.value(0));
+    assert!(array_vals.is_null(1));
+    assert_eq!((1..=16).collect::<Vec<u8>>(), array_vals.value(2));
+}
+
+/// A variable sized binary value of the wrong length is reported, rather than causing a panic
+#[test]
+fn fetch_fixed_sized_binary_of_wrong_length() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "VARBINARY(5) NOT NULL", "(0x0102)");
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::FixedSizeBinary(5),
+        false,
+    )]));
+
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+
+    assert_eq!(
+        "External error: Expected the database to return binary values of 5 bytes, yet a value of \
+        2 bytes came back. Please specify a schema with a variable sized binary type rather than \
+        `FixedSizeBinary` for this column.",
+        result.unwrap_err().to_string()
+    );
+}
+
+/// Fetch fixed sized binary data binary data
+#[test]
+fn fetch_fixed_sized_binary_data() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    // Setup a table on the database with some values (so we can fetch them)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["BINARY(5) NOT NULL"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES (?)");
+    // Use prepared query and arguments for insertion, since literal representation depends a lot
+    // on the DB under test.
+    let mut insert = conn.prepare(&sql).unwrap();
+    insert.execute(&b"Hello".into_parameter()).unwrap();
+    insert.execute(&b"World".into_parameter()).unwrap();
+
+    // Query column with values to get a cursor
+    let sql = format!("SELECT a FROM {table_name} ORDER BY id");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        // Batches will contain at most 100 entries.
+        .with_max_num_rows_per_batch(100)
+        // Instantiate reader with Arrow schema and ODBC cursor
+        .build(cursor)
+        .unwrap();
+
+    // Batch for batch copy values from ODBC buffer into arrow batches
+    let arrow_batch = reader.next().unwrap().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    let array_vals = arrow_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<FixedSizeBinaryArray>()
+        .unwrap();
+    assert_eq!(b"Hello", array_vals.value(0));
+    assert_eq!(b"World", array_vals.value(1));
+}
+
+/// Like [`fetch_32bit_floating_point`], but utilizing a prepared query instead of a one shot.
+#[test]
+fn prepared_query() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    // Setup a table on the database with some floats (so we can fetch them)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["REAL NOT NULL"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES (1),(2),(3)");
+    conn.execute(&sql, ()).unwrap();
+
+    // Query column with values to get a cursor
+    let sql = format!("SELECT a FROM {table_name}");
+    let mut prepared = conn.prepare(&sql).unwrap();
+    let cursor = prepared.execute(()).unwrap().unwrap();
+
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        // Batches will contain at most 100 entries.
+        .with_max_num_rows_per_batch(100)
+        // Instantiate reader with Arrow schema and ODBC cursor
+        .build(cursor)
+        .unwrap();
+
+    // Batch for batch copy values from ODBC buffer into arrow batches
+    let arrow_batch = reader.next().unwrap().unwrap();
+
+    // Assert that the correct values are found within the arrow batch
+    let array_vals = arrow_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Float32Array>()
+        .unwrap();
+    assert_float_eq!([1., 2., 3.][..], array_vals.values(), abs_all <= 000.1);
+}
+
+#[test]
+fn infer_schema() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    // Setup a table on the database with some floats (so we can fetch them)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["REAL NOT NULL"]).unwrap();
+
+    // Prepare query to get metadata
+    let sql = format!("SELECT a FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        // Instantiate reader with Arrow schema and ODBC cursor
+        .build(cursor)
+        .unwrap();
+
+    let actual = reader.schema();
+    let expected = Arc::new(Schema::new(vec![Field::new("a", DataType::Float32, false)]));
+
+    assert_eq!(expected, actual)
+}
+
+#[test]
+fn fetch_schema_for_table() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+
+    // Setup a table on the database with some floats (so we can fetch them)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["REAL NOT NULL"]).unwrap();
+
+    // Prepare query to get metadata
+    let sql = format!("SELECT a FROM {table_name}");
+    let mut prepared = conn.prepare(&sql).unwrap();
+
+    // Now that we have prepared statement, we want to use it to query metadata.
+    let schema = arrow_schema_from(&mut prepared, false).unwrap();
+
+    assert_eq!(
+        "Field { \
+            name: \"a\", \
+            data_type: Float32, \
+            nullable: false, \
+            dict_id: 0, \
+            dict_is_ordered: false, \
+            metadata: {} \
+        }",
+        schema.to_string()
+    )
+}
+
+/// Allocating octet length bytes is not enough if the column on the database is encoded in UTF-16
+/// since all codepoints in range from U+0800 to U+FFFF take three bytes in UTF-8 but only two bytes
+/// in UTF-16. We test this with the 'Trade Mark Sign' (`™`) (U+2122).
+///
+/// For this test to be meaningful it must run on a Linux platform, since only then we do query the
+/// wide column with a narrow buffer and therfore convert UTF-16 to UTF-8 within the dbms.
+#[test]
+fn should_allocate_enough_memory_for_wchar_column_bound_to_u8() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = query_single_value(table_name, "NCHAR(1) NOT NULL", "™");
+
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        // Instantiate reader with Arrow schema and ODBC cursor
+        .build(cursor)
+        .unwrap();
+    // Batch for batch copy values from ODBC buffer into arrow batches
+    let record_batch = reader.next().unwrap().unwrap();
+    let array_any = record_batch.column(0).clone();
+
+    // Assert that the correct values are found within the arrow batch
+    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!("™", array_vals.value(0));
+}
+
+/// Allocating octet length bytes is not enough if the column on the database is encoded in UTF-8
+/// since all codepoints in range from U+0000 to U+007F take two bytes in UTF-16 but only one byte
+/// in UTF-8. We test this with the letter a (U+0061).
+///
+/// For this test to be meaningful it must run on a windows platform, since only then we do query the
+/// wide column with a narrow buffer and therfore convert UTF-8 to UTF-16 within the dbms.
+#[test]
+fn should_allocate_enough_memory_for_varchar_column_bound_to_u16() {
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = query_single_value(table_name, "CHAR(1) NOT NULL", "Ü");
+
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        // Instantiate reader with Arrow schema and ODBC cursor
+        .build(cursor)
+        .unwrap();
+    // Batch for batch copy values from ODBC buffer into arrow batches
+    let record_batch = reader.next().unwrap().unwrap();
+    let array_any = record_batch.column(0).clone();
+
+    // Assert that the correct values are found within the arrow batch
+    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!("Ü", array_vals.value(0));
+}
+
+/// Often than VARCHAR(MAX) is used the actual values in these columns are in the range of 100kb and
+/// not several kb. Sadly if we allocate the buffers, we have to assume the largest possible element
+/// this test verifies that users can specify sensible upper limits using their domain knowledge
+/// about the table in order to prevent out of memory issues.
+#[test]
+fn should_allow_to_fetch_from_varchar_max() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARCHAR(MAX)"]).unwrap();
+    let sql = format!("SELECT a FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    // When
+    let result = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(100)
+        .with_max_text_size(1024)
+        .build(cursor);
+
+    // Then
+    // In particular we do **not** get either a zero sized column or out of memory error.
+    assert!(result.is_ok())
+}
+
+/// The element size cap bounds text buffers, even if no other limit is specified.
+#[test]
+fn element_size_cap_bounds_varchar_max() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARCHAR(MAX)"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES ('Hello')");
+    conn.execute(&sql, ()).unwrap();
+    let sql = format!("SELECT a FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    // When
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(100)
+        .with_element_size_cap(1024)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then
+    let array = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!("Hello", array.value(0));
+}
+
+/// Fixed sized binary elements can not be clamped, so exceeding the cap is an error.
+#[test]
+fn element_size_cap_exceeded_by_fixed_size_binary() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["BINARY(16)"]).unwrap();
+    let sql = format!("SELECT a FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::FixedSizeBinary(16),
+        true,
+    )]));
+
+    // When
+    let result = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .with_element_size_cap(8)
+        .build(cursor);
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(Error::ColumnFailure {
+            source: ColumnFailure::ElementSizeExceedsCap { length: 16, cap: 8 },
+            ..
+        })
+    ));
+}
+
+/// If column limits are too small and truncation occurs, we expect an error to be raised.
+#[test]
+fn should_error_for_truncation() {
+    // Given a column with one value of length 9
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARCHAR(MAX)"]).unwrap();
+    let sql = format!("INSERT INTO {table_name} (a) VALUES ('123456789')");
+    conn.execute(&sql, ()).unwrap();
+    let sql = format!("SELECT a FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    // When fetching that value with a text limit of 5
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        .with_max_text_size(5)
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+
+    // Then we get an error, rather than the truncation only occurring as a warning.
+    assert!(result.is_err())
+}
+
+#[test]
+fn should_allow_to_fetch_from_varbinary_max() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARBINARY(MAX)"]).unwrap();
+    let sql = format!("SELECT a FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    // When
+    let result = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(100)
+        .with_max_binary_size(1024)
+        .build(cursor);
+
+    // Then
+    // In particular we do **not** get either a zero sized column or out of memory error.
+    assert!(result.is_ok())
+}
+
+#[test]
+fn fallibale_allocations() {
+    // Given
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARBINARY(4096)"]).unwrap();
+    let sql = format!("SELECT a FROM {table_name}");
+    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+
+    // When
+    let result = OdbcReaderBuilder::new()
+        .with_max_bytes_per_batch(usize::MAX)
+        .with_max_num_rows_per_batch(100_000_000)
+        .with_fallibale_allocations(true)
+        .build(cursor);
+
+    // Then
+    // In particular we do **not** get either a zero sized column or out of memory error.
+    assert!(result.is_err());
+    assert!(matches!(
+        result.err().unwrap(),
+        Error::ColumnFailure {
+            name: _,
+            index: 0,
+            source: ColumnFailure::TooLarge {
+                num_elements: 100_000_000,
+                element_size: 4096
+            }
+        }
+    ));
+}
+
+#[test]
+fn too_many_columns() {
+    // Given a cursor over a result set with two columns
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    let cursor = conn.execute("SELECT 1 AS A, 2 AS B", ()).unwrap().unwrap();
+
+    // When building a reader which only allows for one column
+    let result = OdbcReaderBuilder::new()
+        .with_max_column_count(1)
+        .build(cursor);
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(Error::TooManyColumns {
+            actual: 2,
+            limit: 1
+        })
+    ));
+}
+
+#[test]
+fn stream_result_set_in_ipc_format() {
+    // Given a cursor over a table with three rows
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(2),(NULL)");
+    let reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(2)
+        .build(cursor)
+        .unwrap();
+
+    // When
+    let mut bytes = Vec::new();
+    let num_rows = reader.into_ipc_stream(&mut bytes).unwrap();
+
+    // Then
+    assert_eq!(3, num_rows);
+    let batches = StreamReader::try_new(bytes.as_slice(), None)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(2, batches.len());
+    let first = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!([1, 2], *first.values());
+    assert!(batches[1].column(0).is_null(0));
+}
+
+#[test]
+fn read_multiple_result_sets() {
+    // Given a cursor returning two result sets
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    let cursor = conn
+        .execute("SELECT 1 AS A; SELECT 2 AS B;", ())
+        .unwrap()
+        .unwrap();
+
+    // When
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        .build(cursor)
+        .unwrap();
+    let first = reader.next().unwrap().unwrap();
+    let cursor = reader.into_cursor().unwrap();
+    let cursor = cursor.more_results().unwrap().unwrap();
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        .build(cursor)
+        .unwrap();
+    let second = reader.next().unwrap().unwrap();
+
+    // Then
+    let first_vals = first
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(1, first_vals.value(0));
+    let second_vals = second
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(2, second_vals.value(0));
+}
+
+/// A result set produced by a statement without columns, reports the number of affected rows
+#[test]
+fn affected_rows_of_result_free_statement() {
+    // Given a batch of two SQL statements, the second one affecting two rows
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    let cursor = conn
+        .execute(
+            "SELECT 1 AS A; SELECT A INTO #local_temp_table FROM (VALUES (1), (2)) AS T(A);",
+            (),
+        )
+        .unwrap()
+        .unwrap();
+
+    // When
+    let reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let first = reader.affected_rows();
+    let cursor = reader.into_cursor().unwrap();
+    let cursor = cursor.more_results().unwrap().unwrap();
+    let reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let second = reader.affected_rows();
+
+    // Then
+    assert_eq!(None, first);
+    assert_eq!(Some(2), second);
+}
+
+#[test]
+fn read_multiple_result_sets_with_second_no_schema() {
+    // Given a batch of three SQL statements, the second being result-free
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    let cursor = conn
+        .execute(
+            "SELECT 1 AS A; SELECT 1 AS A INTO #local_temp_table; SELECT A FROM #local_temp_table;",
+            (),
+        )
+        .unwrap()
+        .unwrap();
+
+    // When
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        .build(cursor)
+        .unwrap();
+    let first = reader.next().unwrap().unwrap();
+    let cursor = reader.into_cursor().unwrap();
+    let cursor = cursor.more_results().unwrap().unwrap();
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        .build(cursor)
+        .unwrap();
+    let second_schema = reader.schema();
+
+    let cursor = reader.into_cursor().unwrap();
+    let cursor = cursor.more_results().unwrap().unwrap();
+    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(1)
+        .build(cursor)
+        .unwrap();
+    let third = reader.next().unwrap().unwrap();
+
+    // Then
+    let first_vals = first
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(1, first_vals.value(0));
+    let second_column_count = second_schema.fields().len();
+    assert_eq!(0, second_column_count);
+    let third_vals = third
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(1, third_vals.value(0));
+}
+
+#[test]
+fn applies_row_limit_for_default_constructed_readers() {
+    // Given a cursor over a datascheme with a small per row memory footprint
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "INTEGER", "(42)");
+
+    // When constructing a reader from that cursor without specifying an explicity memory or row
+    // limit
+    let reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+
+    // Then the row limit is set to u16::MAX
+    assert_eq!(reader.max_rows_per_batch(), 65535)
+}
+
+#[test]
+fn applies_memory_size_limit() {
+    // Given a cursor over a datascheme with a small per row memory footprint
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "VARCHAR(512)", "('Hello')");
+
+    // When constructing a reader from that cursor without specifying an explicity memory or row
+    // limit
+    let reader = OdbcReaderBuilder::new()
+        // Limit buffer to 10 MiB
+        .with_max_bytes_per_batch(10 * 1024 * 1024)
+        .build(cursor)
+        .unwrap();
+
+    // Buffer now holds less than 65535 rows due to size limit
+    assert!(reader.max_rows_per_batch() < 65535)
+}
+
+#[test]
+fn memory_size_limit_can_not_hold_a_single_row() {
+    // Given a cursor over a datascheme with a small per row memory footprint
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let cursor = cursor_over(table_name, "VARCHAR(512)", "('Hello')");
+
+    // When constructing a reader from that cursor without specifying an explicity memory or row
+    // limit
+    let result = OdbcReaderBuilder::new()
+        // Limit buffer to 1 Byte
+        .with_max_bytes_per_batch(1)
+        .build(cursor);
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(Error::OdbcBufferTooSmall {
+            max_bytes_per_batch: 1,
+            bytes_per_row: _
+        })
+    ))
+}
+
+#[test]
+fn insert_does_not_support_list_type() {
+    // Given a table and a db connection.
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
+
+    // When we try to create an OdbcWriter inserting an Arrow List
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::List(Arc::new(Field::new("b", DataType::Utf8, true))),
+        true,
+    )]));
+
+    let insert = format!("INSERT INTO {table_name} (a) VALUES (?)");
+    let prepared = conn.prepare(&insert).unwrap();
+    let result = OdbcWriter::new(10, schema.as_ref(), prepared);
+
+    // Then we recive an unsupported error
+    assert!(matches!(
+        result,
+        Err(WriterError::UnsupportedArrowDataType(_))
+    ))
 }
 
-/// Fetch fixed sized binary data binary data
 #[test]
-fn fetch_fixed_sized_binary_data() {
+fn insert_with_more_fields_than_parameters() {
+    // Given a table and a db connection.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-
-    // Setup a table on the database with some values (so we can fetch them)
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["BINARY(5) NOT NULL"]).unwrap();
-    let sql = format!("INSERT INTO {table_name} (a) VALUES (?)");
-    // Use prepared query and arguments for insertion, since literal representation depends a lot
-    // on the DB under test.
-    let mut insert = conn.prepare(&sql).unwrap();
-    insert.execute(&b"Hello".into_parameter()).unwrap();
-    insert.execute(&b"World".into_parameter()).unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER", "INTEGER"]).unwrap();
+
+    // When we try to create an OdbcWriter with three fields for a statement with two parameters
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Int32, true),
+        Field::new("c", DataType::Int32, true),
+    ]));
+    let insert = format!("INSERT INTO {table_name} (a, b) VALUES (?, ?)");
+    let prepared = conn.prepare(&insert).unwrap();
+    let result = OdbcWriter::new(10, schema.as_ref(), prepared);
 
-    // Query column with values to get a cursor
-    let sql = format!("SELECT a FROM {table_name} ORDER BY id");
-    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    // Then we receive an error, before anything is bound to the statement
+    assert!(matches!(
+        result,
+        Err(WriterError::ParameterCountMismatch {
+            expected: 2,
+            actual: 3
+        })
+    ))
+}
 
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        // Batches will contain at most 100 entries.
-        .with_max_num_rows_per_batch(100)
-        // Instantiate reader with Arrow schema and ODBC cursor
-        .build(cursor)
+#[test]
+fn insert_text() {
+    // Given a table and a record batch reader returning a batch with a text column.
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
+    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
+    let array = StringArray::from(vec![Some("Hello"), None, Some("World")]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Utf8, true)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
-    // Batch for batch copy values from ODBC buffer into arrow batches
-    let arrow_batch = reader.next().unwrap().unwrap();
+    // When
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
-    // Assert that the correct values are found within the arrow batch
-    let array_vals = arrow_batch
-        .column(0)
-        .as_any()
-        .downcast_ref::<FixedSizeBinaryArray>()
-        .unwrap();
-    assert_eq!(b"Hello", array_vals.value(0));
-    assert_eq!(b"World", array_vals.value(1));
+    // Then
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "Hello\nNULL\nWorld";
+    assert_eq!(expected, actual);
 }
 
-/// Like [`fetch_32bit_floating_point`], but utilizing a prepared query instead of a one shot.
+/// Replace the rows of a table with a new snapshot
 #[test]
-fn prepared_query() {
+fn truncate_table_before_insert() {
+    // Given a table with two rows
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-
-    // Setup a table on the database with some floats (so we can fetch them)
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["REAL NOT NULL"]).unwrap();
-    let sql = format!("INSERT INTO {table_name} (a) VALUES (1),(2),(3)");
-    conn.execute(&sql, ()).unwrap();
-
-    // Query column with values to get a cursor
-    let sql = format!("SELECT a FROM {table_name}");
-    let mut prepared = conn.prepare(&sql).unwrap();
-    let cursor = prepared.execute(()).unwrap().unwrap();
-
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        // Batches will contain at most 100 entries.
-        .with_max_num_rows_per_batch(100)
-        // Instantiate reader with Arrow schema and ODBC cursor
-        .build(cursor)
+    setup_empty_table(&conn, table_name, &["INTEGER NOT NULL"]).unwrap();
+    conn.execute(&format!("INSERT INTO {table_name} (a) VALUES (1), (2)"), ())
         .unwrap();
+    let array = Int32Array::from(vec![3]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
-    // Batch for batch copy values from ODBC buffer into arrow batches
-    let arrow_batch = reader.next().unwrap().unwrap();
+    // When
+    let options = OdbcWriterOptions {
+        truncate_before_insert: true,
+        ..Default::default()
+    };
+    OdbcWriter::insert_with_options(&conn, &mut reader, table_name, 5, options).unwrap();
 
-    // Assert that the correct values are found within the arrow batch
-    let array_vals = arrow_batch
-        .column(0)
-        .as_any()
-        .downcast_ref::<Float32Array>()
-        .unwrap();
-    assert_float_eq!([1., 2., 3.][..], array_vals.values(), abs_all <= 000.1);
+    // Then
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    assert_eq!("3", actual);
 }
 
+/// Batches of an Arrow IPC file are streamed into the table
 #[test]
-fn infer_schema() {
-    let table_name = function_name!().rsplit_once(':').unwrap().1;
+fn insert_from_arrow_ipc_file() {
+    use arrow::ipc::writer::FileWriter;
 
-    // Setup a table on the database with some floats (so we can fetch them)
+    // Given an IPC file with two batches
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["REAL NOT NULL"]).unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+    let path = std::env::temp_dir().join(format!("{table_name}.arrow"));
+    let mut writer = FileWriter::try_new(std::fs::File::create(&path).unwrap(), &schema).unwrap();
+    for values in [vec![Some(1), None], vec![Some(3)]] {
+        let array = Int32Array::from(values);
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+        writer.write(&batch).unwrap();
+    }
+    writer.finish().unwrap();
 
-    // Prepare query to get metadata
-    let sql = format!("SELECT a FROM {table_name}");
-    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    // When
+    let num_rows =
+        OdbcWriter::insert_from_ipc_file(&conn, table_name, &path, 2, OdbcWriterOptions::default())
+            .unwrap();
 
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        // Instantiate reader with Arrow schema and ODBC cursor
-        .build(cursor)
+    // Then
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(3, num_rows);
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    assert_eq!("1\nNULL\n3", actual);
+}
+
+/// Truncating and inserting happens in one transaction, so a failed insert keeps the original rows
+#[test]
+fn failed_insert_rolls_back_truncation() {
+    // Given a table with two rows and a batch with a NULL, violating the NOT NULL constraint
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER NOT NULL"]).unwrap();
+    conn.execute(&format!("INSERT INTO {table_name} (a) VALUES (1), (2)"), ())
         .unwrap();
+    let array = Int32Array::from(vec![Some(3), None]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
-    let actual = reader.schema();
-    let expected = Arc::new(Schema::new(vec![Field::new("a", DataType::Float32, false)]));
+    // When
+    let options = OdbcWriterOptions {
+        truncate_before_insert: true,
+        ..Default::default()
+    };
+    let result = OdbcWriter::insert_with_options(&conn, &mut reader, table_name, 5, options);
 
-    assert_eq!(expected, actual)
+    // Then
+    assert!(result.is_err());
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    assert_eq!("1\n2", actual);
 }
 
+/// Without auto commit, replacing the contents takes part in the transaction of the application
 #[test]
-fn fetch_schema_for_table() {
+fn truncate_table_within_transaction_of_application() {
+    // Given a table with two rows and a connection without auto commit
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-
-    // Setup a table on the database with some floats (so we can fetch them)
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["REAL NOT NULL"]).unwrap();
-
-    // Prepare query to get metadata
-    let sql = format!("SELECT a FROM {table_name}");
-    let mut prepared = conn.prepare(&sql).unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER NOT NULL"]).unwrap();
+    conn.execute(&format!("INSERT INTO {table_name} (a) VALUES (1), (2)"), ())
+        .unwrap();
+    conn.set_autocommit(false).unwrap();
+    let array = Int32Array::from(vec![3]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
-    // Now that we have prepared statement, we want to use it to query metadata.
-    let schema = arrow_schema_from(&mut prepared, false).unwrap();
+    // When
+    let options = OdbcWriterOptions {
+        truncate_before_insert: true,
+        autocommit: false,
+    };
+    OdbcWriter::insert_with_options(&conn, &mut reader, table_name, 5, options).unwrap();
+    conn.rollback().unwrap();
 
-    assert_eq!(
-        "Field { \
-            name: \"a\", \
-            data_type: Float32, \
-            nullable: false, \
-            dict_id: 0, \
-            dict_is_ordered: false, \
-            metadata: {} \
-        }",
-        schema.to_string()
-    )
+    // Then neither has the writer committed, nor turned on auto commit
+    conn.set_autocommit(true).unwrap();
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    assert_eq!("1\n2", actual);
 }
 
-/// Allocating octet length bytes is not enough if the column on the database is encoded in UTF-16
-/// since all codepoints in range from U+0800 to U+FFFF take three bytes in UTF-8 but only two bytes
-/// in UTF-16. We test this with the 'Trade Mark Sign' (`™`) (U+2122).
-///
-/// For this test to be meaningful it must run on a Linux platform, since only then we do query the
-/// wide column with a narrow buffer and therfore convert UTF-16 to UTF-8 within the dbms.
+/// This test is most relevant on windows platforms, the UTF-8 is not the default encoding and text
+/// should be encoded as UTF-16
 #[test]
-fn should_allocate_enough_memory_for_wchar_column_bound_to_u8() {
+fn insert_non_ascii_text() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let cursor = query_single_value(table_name, "NCHAR(1) NOT NULL", "™");
-
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        // Instantiate reader with Arrow schema and ODBC cursor
-        .build(cursor)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    // Batch for batch copy values from ODBC buffer into arrow batches
-    let record_batch = reader.next().unwrap().unwrap();
-    let array_any = record_batch.column(0).clone();
+    setup_empty_table(&conn, table_name, &["VARCHAR(50)"]).unwrap();
+    let array = StringArray::from(vec![Some("Frühstück µ")]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Utf8, true)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
-    // Assert that the correct values are found within the arrow batch
-    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
-    assert_eq!("™", array_vals.value(0));
+    // When
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+
+    // Then
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "Frühstück µ";
+    assert_eq!(expected, actual);
 }
 
-/// Allocating octet length bytes is not enough if the column on the database is encoded in UTF-8
-/// since all codepoints in range from U+0000 to U+007F take two bytes in UTF-16 but only one byte
-/// in UTF-8. We test this with the letter a (U+0061).
-///
-/// For this test to be meaningful it must run on a windows platform, since only then we do query the
-/// wide column with a narrow buffer and therfore convert UTF-8 to UTF-16 within the dbms.
 #[test]
-fn should_allocate_enough_memory_for_varchar_column_bound_to_u16() {
+fn insert_nullable_booleans() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let cursor = query_single_value(table_name, "CHAR(1) NOT NULL", "Ü");
-
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        // Instantiate reader with Arrow schema and ODBC cursor
-        .build(cursor)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    // Batch for batch copy values from ODBC buffer into arrow batches
-    let record_batch = reader.next().unwrap().unwrap();
-    let array_any = record_batch.column(0).clone();
+    setup_empty_table(&conn, table_name, &["BIT"]).unwrap();
+    let array = BooleanArray::from(vec![Some(true), None, Some(false)]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Boolean, true)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch.clone(), batch]);
 
-    // Assert that the correct values are found within the arrow batch
-    let array_vals = array_any.as_any().downcast_ref::<StringArray>().unwrap();
-    assert_eq!("Ü", array_vals.value(0));
+    // When
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+
+    // Then
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1\nNULL\n0\n1\nNULL\n0";
+    assert_eq!(expected, actual);
 }
 
-/// Often than VARCHAR(MAX) is used the actual values in these columns are in the range of 100kb and
-/// not several kb. Sadly if we allocate the buffers, we have to assume the largest possible element
-/// this test verifies that users can specify sensible upper limits using their domain knowledge
-/// about the table in order to prevent out of memory issues.
 #[test]
-fn should_allow_to_fetch_from_varchar_max() {
-    // Given
+fn insert_non_nullable_booleans() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARCHAR(MAX)"]).unwrap();
-    let sql = format!("SELECT a FROM {table_name}");
-    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    setup_empty_table(&conn, table_name, &["BIT"]).unwrap();
+    let array = BooleanArray::from(vec![Some(true), Some(false), Some(false)]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Boolean, false)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch.clone(), batch]);
 
     // When
-    let result = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(100)
-        .with_max_text_size(1024)
-        .build(cursor);
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
-    // In particular we do **not** get either a zero sized column or out of memory error.
-    assert!(result.is_ok())
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1\n0\n0\n1\n0\n0";
+    assert_eq!(expected, actual);
 }
 
-/// If column limits are too small and truncation occurs, we expect an error to be raised.
 #[test]
-fn should_error_for_truncation() {
-    // Given a column with one value of length 9
+fn insert_nullable_int8() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARCHAR(MAX)"]).unwrap();
-    let sql = format!("INSERT INTO {table_name} (a) VALUES ('123456789')");
-    conn.execute(&sql, ()).unwrap();
-    let sql = format!("SELECT a FROM {table_name}");
-    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    setup_empty_table(&conn, table_name, &["TINYINT"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int8, true)]));
+    let array1 = Int8Array::from(vec![Some(1), None, Some(3)]);
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let array2 = Int8Array::from(vec![Some(4), None, Some(6)]);
+    let batch2 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array2)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1, batch2]);
 
-    // When fetching that value with a text limit of 5
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        .with_max_text_size(5)
-        .build(cursor)
-        .unwrap();
-    let result = reader.next().unwrap();
+    // When
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
-    // Then we get an error, rather than the truncation only occurring as a warning.
-    assert!(result.is_err())
+    // Then
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1\nNULL\n3\n4\nNULL\n6";
+    assert_eq!(expected, actual);
 }
 
 #[test]
-fn should_allow_to_fetch_from_varbinary_max() {
-    // Given
+fn insert_non_nullable_int8() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARBINARY(MAX)"]).unwrap();
-    let sql = format!("SELECT a FROM {table_name}");
-    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    setup_empty_table(&conn, table_name, &["TINYINT"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int8, false)]));
+    let array1 = Int8Array::from(vec![Some(1), Some(2), Some(3)]);
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let array2 = Int8Array::from(vec![Some(4), Some(5), Some(6)]);
+    let batch2 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array2)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1, batch2]);
 
     // When
-    let result = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(100)
-        .with_max_binary_size(1024)
-        .build(cursor);
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
-    // In particular we do **not** get either a zero sized column or out of memory error.
-    assert!(result.is_ok())
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1\n2\n3\n4\n5\n6";
+    assert_eq!(expected, actual);
 }
 
 #[test]
-fn fallibale_allocations() {
-    // Given
+fn insert_nullable_int16() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARBINARY(4096)"]).unwrap();
-    let sql = format!("SELECT a FROM {table_name}");
-    let cursor = conn.execute(&sql, ()).unwrap().unwrap();
+    setup_empty_table(&conn, table_name, &["SMALLINT"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int16, true)]));
+    let array1 = Int16Array::from(vec![Some(1), None, Some(3)]);
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1]);
 
     // When
-    let result = OdbcReaderBuilder::new()
-        .with_max_bytes_per_batch(usize::MAX)
-        .with_max_num_rows_per_batch(100_000_000)
-        .with_fallibale_allocations(true)
-        .build(cursor);
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
-    // In particular we do **not** get either a zero sized column or out of memory error.
-    assert!(result.is_err());
-    assert!(matches!(
-        result.err().unwrap(),
-        Error::ColumnFailure {
-            name: _,
-            index: 0,
-            source: ColumnFailure::TooLarge {
-                num_elements: 100_000_000,
-                element_size: 4096
-            }
-        }
-    ));
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1\nNULL\n3";
+    assert_eq!(expected, actual);
 }
 
 #[test]
-fn read_multiple_result_sets() {
-    // Given a cursor returning two result sets
+fn insert_nullable_int32() {
+    // Given a table and a record batch reader returning a batch with a text column.
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    let cursor = conn
-        .execute("SELECT 1 AS A; SELECT 2 AS B;", ())
-        .unwrap()
-        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+    let array1 = Int32Array::from(vec![Some(1), None, Some(3)]);
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1]);
 
     // When
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        .build(cursor)
-        .unwrap();
-    let first = reader.next().unwrap().unwrap();
-    let cursor = reader.into_cursor().unwrap();
-    let cursor = cursor.more_results().unwrap().unwrap();
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        .build(cursor)
-        .unwrap();
-    let second = reader.next().unwrap().unwrap();
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
-    let first_vals = first
-        .column(0)
-        .as_any()
-        .downcast_ref::<Int32Array>()
-        .unwrap();
-    assert_eq!(1, first_vals.value(0));
-    let second_vals = second
-        .column(0)
-        .as_any()
-        .downcast_ref::<Int32Array>()
-        .unwrap();
-    assert_eq!(2, second_vals.value(0));
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1\nNULL\n3";
+    assert_eq!(expected, actual);
 }
 
 #[test]
-fn read_multiple_result_sets_with_second_no_schema() {
-    // Given a batch of three SQL statements, the second being result-free
+fn insert_nullable_int64() {
+    // Given a table and a record batch reader returning a batch with a text column.
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    let cursor = conn
-        .execute(
-            "SELECT 1 AS A; SELECT 1 AS A INTO #local_temp_table; SELECT A FROM #local_temp_table;",
-            (),
-        )
-        .unwrap()
-        .unwrap();
+    setup_empty_table(&conn, table_name, &["BIGINT"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    let array1 = Int64Array::from(vec![Some(1), None, Some(3)]);
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1]);
 
     // When
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        .build(cursor)
-        .unwrap();
-    let first = reader.next().unwrap().unwrap();
-    let cursor = reader.into_cursor().unwrap();
-    let cursor = cursor.more_results().unwrap().unwrap();
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        .build(cursor)
-        .unwrap();
-    let second_schema = reader.schema();
-
-    let cursor = reader.into_cursor().unwrap();
-    let cursor = cursor.more_results().unwrap().unwrap();
-    // Now that we have a cursor, we want to iterate over its rows and fill an arrow batch with it.
-    let mut reader = OdbcReaderBuilder::new()
-        .with_max_num_rows_per_batch(1)
-        .build(cursor)
-        .unwrap();
-    let third = reader.next().unwrap().unwrap();
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
-    let first_vals = first
-        .column(0)
-        .as_any()
-        .downcast_ref::<Int32Array>()
-        .unwrap();
-    assert_eq!(1, first_vals.value(0));
-    let second_column_count = second_schema.fields().len();
-    assert_eq!(0, second_column_count);
-    let third_vals = third
-        .column(0)
-        .as_any()
-        .downcast_ref::<Int32Array>()
-        .unwrap();
-    assert_eq!(1, third_vals.value(0));
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1\nNULL\n3";
+    assert_eq!(expected, actual);
 }
 
 #[test]
-fn applies_row_limit_for_default_constructed_readers() {
-    // Given a cursor over a datascheme with a small per row memory footprint
+fn insert_non_nullable_unsigned_int8() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let cursor = cursor_over(table_name, "INTEGER", "(42)");
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["SMALLINT"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::UInt8, false)]));
+    let array1 = UInt8Array::from(vec![Some(1), Some(2), Some(3)]);
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1]);
 
-    // When constructing a reader from that cursor without specifying an explicity memory or row
-    // limit
-    let reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    // When
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
-    // Then the row limit is set to u16::MAX
-    assert_eq!(reader.max_rows_per_batch(), 65535)
+    // Then
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1\n2\n3";
+    assert_eq!(expected, actual);
 }
 
 #[test]
-fn applies_memory_size_limit() {
-    // Given a cursor over a datascheme with a small per row memory footprint
+fn insert_nullable_f32() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let cursor = cursor_over(table_name, "VARCHAR(512)", "('Hello')");
-
-    // When constructing a reader from that cursor without specifying an explicity memory or row
-    // limit
-    let reader = OdbcReaderBuilder::new()
-        // Limit buffer to 10 MiB
-        .with_max_bytes_per_batch(10 * 1024 * 1024)
-        .build(cursor)
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-
-    // Buffer now holds less than 65535 rows due to size limit
-    assert!(reader.max_rows_per_batch() < 65535)
-}
-
-#[test]
-fn memory_size_limit_can_not_hold_a_single_row() {
-    // Given a cursor over a datascheme with a small per row memory footprint
-    let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let cursor = cursor_over(table_name, "VARCHAR(512)", "('Hello')");
-
-    // When constructing a reader from that cursor without specifying an explicity memory or row
-    // limit
-    let result = OdbcReaderBuilder::new()
-        // Limit buffer to 1 Byte
-        .with_max_bytes_per_batch(1)
-        .build(cursor);
+    setup_empty_table(&conn, table_name, &["REAL"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float32, true)]));
+    let array1 = Float32Array::from(vec![Some(1.), None, Some(3.)]);
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+
+    // When
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
-    assert!(matches!(
-        result,
-        Err(Error::OdbcBufferTooSmall {
-            max_bytes_per_batch: 1,
-            bytes_per_row: _
-        })
-    ))
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1.0\nNULL\n3.0";
+    assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_does_not_support_list_type() {
-    // Given a table and a db connection.
+fn insert_nullable_f64() {
+    // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
-
-    // When we try to create an OdbcWriter inserting an Arrow List
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "a",
-        DataType::List(Arc::new(Field::new("b", DataType::Utf8, true))),
-        true,
-    )]));
+    setup_empty_table(&conn, table_name, &["FLOAT(25)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float64, true)]));
+    let array1 = Float64Array::from(vec![Some(1.), None, Some(3.)]);
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1]);
 
-    let insert = format!("INSERT INTO {table_name} (a) VALUES (?)");
-    let prepared = conn.prepare(&insert).unwrap();
-    let result = OdbcWriter::new(10, schema.as_ref(), prepared);
+    // When
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
-    // Then we recive an unsupported error
-    assert!(matches!(
-        result,
-        Err(WriterError::UnsupportedArrowDataType(_))
-    ))
+    // Then
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    let expected = "1.0\nNULL\n3.0";
+    assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_text() {
+fn insert_nullable_f16() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
-    let array = StringArray::from(vec![Some("Hello"), None, Some("World")]);
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Utf8, true)]));
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    setup_empty_table(&conn, table_name, &["REAL"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float16, true)]));
+    let array1: Float16Array = [Some(F16::from_f32(1.0)), None, Some(F16::from_f32(3.0))]
+        .into_iter()
+        .collect();
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "Hello\nNULL\nWorld";
+    let expected = "1.0\nNULL\n3.0";
     assert_eq!(expected, actual);
 }
 
-/// This test is most relevant on windows platforms, the UTF-8 is not the default encoding and text
-/// should be encoded as UTF-16
 #[test]
-fn insert_non_ascii_text() {
+fn insert_non_nullable_f16() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARCHAR(50)"]).unwrap();
-    let array = StringArray::from(vec![Some("Frühstück µ")]);
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Utf8, true)]));
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    setup_empty_table(&conn, table_name, &["REAL"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float16, false)]));
+    let array1: Float16Array = [
+        Some(F16::from_f32(1.0)),
+        Some(F16::from_f32(2.0)),
+        Some(F16::from_f32(3.0)),
+    ]
+    .into_iter()
+    .collect();
+    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch1]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "Frühstück µ";
+    let expected = "1.0\n2.0\n3.0";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_nullable_booleans() {
+fn insert_timestamp_with_seconds_precisions() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["BIT"]).unwrap();
-    let array = BooleanArray::from(vec![Some(true), None, Some(false)]);
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Boolean, true)]));
+    setup_empty_table(&conn, table_name, &["DATETIME2(0)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Timestamp(TimeUnit::Second, None),
+        false,
+    )]));
+    // Corresponds to single element array with entry 1970-05-09T14:25:11+0:00
+    let array = TimestampSecondArray::from(vec![11111111]);
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch.clone(), batch]);
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1\nNULL\n0\n1\nNULL\n0";
+    let expected = "1970-05-09 14:25:11";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_non_nullable_booleans() {
+fn insert_timestamp_with_milliseconds_precisions() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["BIT"]).unwrap();
-    let array = BooleanArray::from(vec![Some(true), Some(false), Some(false)]);
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Boolean, false)]));
+    setup_empty_table(&conn, table_name, &["DATETIME2(3)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Timestamp(TimeUnit::Millisecond, None),
+        false,
+    )]));
+    // Corresponds to single element array with entry 1970-05-09T14:25:11.111
+    let array = TimestampMillisecondArray::from(vec![11111111111]);
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch.clone(), batch]);
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1\n0\n0\n1\n0\n0";
+    let expected = "1970-05-09 14:25:11.111";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_nullable_int8() {
+fn insert_timestamp_with_microseconds_precisions() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["TINYINT"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int8, true)]));
-    let array1 = Int8Array::from(vec![Some(1), None, Some(3)]);
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let array2 = Int8Array::from(vec![Some(4), None, Some(6)]);
-    let batch2 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array2)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1, batch2]);
+    setup_empty_table(&conn, table_name, &["DATETIME2(6)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Timestamp(TimeUnit::Microsecond, None),
+        false,
+    )]));
+    // Corresponds to single element array with entry 1970-05-09T14:25:11.111111
+    let array = TimestampMicrosecondArray::from(vec![11111111111111]);
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1\nNULL\n3\n4\nNULL\n6";
+    let expected = "1970-05-09 14:25:11.111111";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_non_nullable_int8() {
+fn insert_timestamp_with_nanoseconds_precisions() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["TINYINT"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int8, false)]));
-    let array1 = Int8Array::from(vec![Some(1), Some(2), Some(3)]);
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let array2 = Int8Array::from(vec![Some(4), Some(5), Some(6)]);
-    let batch2 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array2)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1, batch2]);
+    setup_empty_table(&conn, table_name, &["DATETIME2(7)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Timestamp(TimeUnit::Nanosecond, None),
+        false,
+    )]));
+    // Corresponds to single element array with entry 1970-05-09T14:25:11.111111111
+    let array = TimestampNanosecondArray::from(vec![11111111111111111]);
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1\n2\n3\n4\n5\n6";
+    let expected = "1970-05-09 14:25:11.1111111";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_nullable_int16() {
+fn insert_date32_array() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["SMALLINT"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int16, true)]));
-    let array1 = Int16Array::from(vec![Some(1), None, Some(3)]);
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+    setup_empty_table(&conn, table_name, &["DATE"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Date32, false)]));
+    // Corresponds to single element array with entry 1970-01-01
+    let array: Date32Array = [Some(0)].into_iter().collect();
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1\nNULL\n3";
+    let expected = "1970-01-01";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_nullable_int32() {
+fn insert_date64_array() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
-    let array1 = Int32Array::from(vec![Some(1), None, Some(3)]);
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+    setup_empty_table(&conn, table_name, &["DATE"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Date64, false)]));
+    // Corresponds to single element array with entry 1970-01-01
+    let array: Date64Array = [Some(0)].into_iter().collect();
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1\nNULL\n3";
+    let expected = "1970-01-01";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_nullable_int64() {
+fn insert_time32_second_array() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["BIGINT"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
-    let array1 = Int64Array::from(vec![Some(1), None, Some(3)]);
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+    setup_empty_table(&conn, table_name, &["TIME(0)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Time32(TimeUnit::Second),
+        false,
+    )]));
+    // Corresponds to single element array with entry 03:05:11
+    let array: Time32SecondArray = [Some(11_111)].into_iter().collect();
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1\nNULL\n3";
+    let expected = "03:05:11";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_non_nullable_unsigned_int8() {
+fn insert_time32_ms_array() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["SMALLINT"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::UInt8, false)]));
-    let array1 = UInt8Array::from(vec![Some(1), Some(2), Some(3)]);
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+    setup_empty_table(&conn, table_name, &["TIME(3)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Time32(TimeUnit::Millisecond),
+        false,
+    )]));
+    // Corresponds to single element array with entry 03:05:11.111
+    let array: Time32MillisecondArray = [Some(11_111_111)].into_iter().collect();
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1\n2\n3";
+    let expected = "03:05:11.111";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_nullable_f32() {
+fn insert_time64_us_array() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["REAL"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float32, true)]));
-    let array1 = Float32Array::from(vec![Some(1.), None, Some(3.)]);
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+    setup_empty_table(&conn, table_name, &["TIME(6)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Time64(TimeUnit::Microsecond),
+        false,
+    )]));
+    // Corresponds to single element array with entry 03:05:11.111111
+    let array: Time64MicrosecondArray = [Some(11_111_111_111)].into_iter().collect();
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1.0\nNULL\n3.0";
+    let expected = "03:05:11.111111";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_nullable_f64() {
+fn insert_time64_ns_array() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["FLOAT(25)"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float64, true)]));
-    let array1 = Float64Array::from(vec![Some(1.), None, Some(3.)]);
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+    setup_empty_table(&conn, table_name, &["TIME(7)"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Time64(TimeUnit::Nanosecond),
+        false,
+    )]));
+    // Corresponds to single element array with entry 03:05:11.111111111
+    let array: Time64NanosecondArray = [Some(11_111_111_111_111)].into_iter().collect();
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1.0\nNULL\n3.0";
+    // We currently insert nanoseconds with precision 7
+    let expected = "03:05:11.1111111";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_nullable_f16() {
+fn insert_binary() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["REAL"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float16, true)]));
-    let array1: Float16Array = [Some(F16::from_f32(1.0)), None, Some(F16::from_f32(3.0))]
-        .into_iter()
-        .collect();
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+    setup_empty_table(&conn, table_name, &["VARBINARY(4096)"]).unwrap();
+    let array = BinaryArray::from(vec![
+        Some([1, 2].as_slice()),
+        None,
+        Some([3, 4, 5, 6, 7].as_slice()),
+    ]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Binary, true)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1.0\nNULL\n3.0";
+    let expected = "0102\nNULL\n0304050607";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_non_nullable_f16() {
+fn insert_fixed_binary() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["REAL"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Float16, false)]));
-    let array1: Float16Array = [
-        Some(F16::from_f32(1.0)),
-        Some(F16::from_f32(2.0)),
-        Some(F16::from_f32(3.0)),
-    ]
-    .into_iter()
-    .collect();
-    let batch1 = RecordBatch::try_new(schema.clone(), vec![Arc::new(array1)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch1]);
+    setup_empty_table(&conn, table_name, &["VARBINARY(4096)"]).unwrap();
+    let array = BinaryArray::from(vec![
+        Some([1, 2].as_slice()),
+        None,
+        Some([3, 4, 5, 6, 7].as_slice()),
+    ]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Binary, true)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+    let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1.0\n2.0\n3.0";
+    let expected = "0102\nNULL\n0304050607";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_timestamp_with_seconds_precisions() {
+fn insert_decimal_128() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["DATETIME2(0)"]).unwrap();
+    setup_empty_table(&conn, table_name, &["NUMERIC(5,3)"]).unwrap();
+    let array: Decimal128Array = [Some(12345), None, Some(67891), Some(1), Some(1000)]
+        .into_iter()
+        .collect();
+    let array = array.with_precision_and_scale(5, 3).unwrap();
     let schema = Arc::new(Schema::new(vec![Field::new(
         "a",
-        DataType::Timestamp(TimeUnit::Second, None),
-        false,
+        DataType::Decimal128(5, 3),
+        true,
     )]));
-    // Corresponds to single element array with entry 1970-05-09T14:25:11+0:00
-    let array = TimestampSecondArray::from(vec![11111111]);
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
     let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    insert_into_table(&conn, &mut reader, table_name, 2).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1970-05-09 14:25:11";
+    let expected = "12.345\nNULL\n67.891\n.001\n1.000";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_timestamp_with_milliseconds_precisions() {
+fn insert_decimal_256() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["DATETIME2(3)"]).unwrap();
+    setup_empty_table(&conn, table_name, &["NUMERIC(5,3)"]).unwrap();
+    let mut builder = Decimal256Builder::new();
+    let mut bytes = [0u8; 32];
+    type I256 = <Decimal256Type as ArrowPrimitiveType>::Native;
+    bytes[0..4].copy_from_slice(12345i32.to_le_bytes().as_slice());
+    builder.append_value(I256::from_le_bytes(bytes));
+    builder.append_null();
+    let array = builder.finish().with_precision_and_scale(5, 3).unwrap();
     let schema = Arc::new(Schema::new(vec![Field::new(
         "a",
-        DataType::Timestamp(TimeUnit::Millisecond, None),
-        false,
+        DataType::Decimal256(5, 3),
+        true,
     )]));
-    // Corresponds to single element array with entry 1970-05-09T14:25:11.111
-    let array = TimestampMillisecondArray::from(vec![11111111111]);
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
     let mut reader = StubBatchReader::new(schema, vec![batch]);
 
@@ -1544,52 +4626,59 @@ fn insert_timestamp_with_milliseconds_precisions() {
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1970-05-09 14:25:11.111";
+    let expected = "12.345\nNULL";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_timestamp_with_microseconds_precisions() {
+fn insert_decimal_128_with_negative_scale() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["DATETIME2(6)"]).unwrap();
+    setup_empty_table(&conn, table_name, &["NUMERIC(5,0)"]).unwrap();
+    let array: Decimal128Array = [Some(123), None, Some(456), Some(1), Some(10)]
+        .into_iter()
+        .collect();
+    let array = array.with_precision_and_scale(3, -2).unwrap();
     let schema = Arc::new(Schema::new(vec![Field::new(
         "a",
-        DataType::Timestamp(TimeUnit::Microsecond, None),
-        false,
+        DataType::Decimal128(3, -2),
+        true,
     )]));
-    // Corresponds to single element array with entry 1970-05-09T14:25:11.111111
-    let array = TimestampMicrosecondArray::from(vec![11111111111111]);
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
     let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    insert_into_table(&conn, &mut reader, table_name, 2).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1970-05-09 14:25:11.111111";
+    let expected = "12300\nNULL\n45600\n100\n1000";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_timestamp_with_nanoseconds_precisions() {
+fn insert_decimal_256_with_negative_scale() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["DATETIME2(7)"]).unwrap();
+    setup_empty_table(&conn, table_name, &["NUMERIC(5,0)"]).unwrap();
+    let mut builder = Decimal256Builder::new();
+    let mut bytes = [0u8; 32];
+    type I256 = <Decimal256Type as ArrowPrimitiveType>::Native;
+    bytes[0..4].copy_from_slice(123i32.to_le_bytes().as_slice());
+    builder.append_value(I256::from_le_bytes(bytes));
+    builder.append_null();
+    let array = builder.finish().with_precision_and_scale(3, -2).unwrap();
     let schema = Arc::new(Schema::new(vec![Field::new(
         "a",
-        DataType::Timestamp(TimeUnit::Nanosecond, None),
-        false,
+        DataType::Decimal256(3, -2),
+        true,
     )]));
-    // Corresponds to single element array with entry 1970-05-09T14:25:11.111111111
-    let array = TimestampNanosecondArray::from(vec![11111111111111111]);
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
     let mut reader = StubBatchReader::new(schema, vec![batch]);
 
@@ -1598,380 +4687,604 @@ fn insert_timestamp_with_nanoseconds_precisions() {
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1970-05-09 14:25:11.1111111";
+    let expected = "12300\nNULL";
     assert_eq!(expected, actual);
 }
 
+/// Create a table from a schema and insert a batch into it
 #[test]
-fn insert_date32_array() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn create_table_from_schema_and_insert() {
+    // Given a schema and a batch with a value for each column
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["DATE"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Date32, false)]));
-    // Corresponds to single element array with entry 1970-01-01
-    let array: Date32Array = [Some(0)].into_iter().collect();
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    conn.execute(&format!("DROP TABLE IF EXISTS {table_name}"), ())
+        .unwrap();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, false),
+        Field::new("b", DataType::Utf8, true),
+        Field::new("c", DataType::Decimal128(5, 2), true),
+        Field::new("d", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int32Array::from(vec![42])),
+        Arc::new(StringArray::from(vec![Some("Hello")])),
+        Arc::new(
+            Decimal128Array::from(vec![Some(12345)])
+                .with_precision_and_scale(5, 2)
+                .unwrap(),
+        ),
+        Arc::new(TimestampMillisecondArray::from(vec![Some(
+            1_617_994_670_123,
+        )])),
+    ];
+    let batch = RecordBatch::try_new(schema.clone(), columns).unwrap();
+    let mut reader = StubBatchReader::new(schema.clone(), vec![batch]);
 
     // When
+    let create_table = schema_to_create_table(&schema, table_name, SqlDialect::SqlServer).unwrap();
+    conn.execute(&create_table, ()).unwrap();
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1970-01-01";
+    let actual = table_to_string(&conn, table_name, &["a", "b", "c", "d"]);
+    let expected = "42,Hello,123.45,2021-04-09 18:57:50.123";
     assert_eq!(expected, actual);
 }
 
+/// Column types and nullability in the create table statement depend on the SQL dialect
 #[test]
-fn insert_date64_array() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn create_table_statement_for_each_dialect() {
+    let schema = Schema::new(vec![
+        Field::new("a", DataType::Boolean, false),
+        Field::new("b", DataType::Float64, true),
+        Field::new("c", DataType::Timestamp(TimeUnit::Nanosecond, None), true),
+        Field::new("my col", DataType::Utf8, true),
+    ]);
+
+    let sql = |dialect| schema_to_create_table(&schema, "MyTable", dialect).unwrap();
+
+    assert_eq!(
+        "CREATE TABLE MyTable (a BOOLEAN NOT NULL, b DOUBLE PRECISION NULL, c TIMESTAMP(9) NULL, \
+        \"my col\" CLOB NULL)",
+        sql(SqlDialect::AnsiSql)
+    );
+    assert_eq!(
+        "CREATE TABLE MyTable (a BIT NOT NULL, b FLOAT NULL, c DATETIME2(7) NULL, \
+        \"my col\" NVARCHAR(MAX) NULL)",
+        sql(SqlDialect::SqlServer)
+    );
+    assert_eq!(
+        "CREATE TABLE MyTable (a BOOLEAN NOT NULL, b DOUBLE PRECISION NULL, c TIMESTAMP(6) NULL, \
+        \"my col\" TEXT NULL)",
+        sql(SqlDialect::PostgreSQL)
+    );
+    assert_eq!(
+        "CREATE TABLE MyTable (a BOOLEAN NOT NULL, b DOUBLE PRECISION NULL, c DATETIME(6) NULL, \
+        `my col` TEXT NULL)",
+        sql(SqlDialect::MySQL)
+    );
+    assert_eq!(
+        "CREATE TABLE MyTable (a BOOLEAN NOT NULL, b DOUBLE PRECISION NULL, c TIMESTAMP NULL, \
+        \"my col\" TEXT NULL)",
+        sql(SqlDialect::SQLite)
+    );
+}
+
+/// Creating and dropping tables reports whether anything has been done.
+#[test]
+fn create_and_drop_table_idempotently() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["DATE"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Date64, false)]));
-    // Corresponds to single element array with entry 1970-01-01
-    let array: Date64Array = [Some(0)].into_iter().collect();
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    conn.execute(&format!("DROP TABLE IF EXISTS {table_name}"), ())
+        .unwrap();
+    let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    let created =
+        OdbcWriter::create_table_if_not_exists(&conn, table_name, &schema, SqlDialect::SqlServer)
+            .unwrap();
+    let created_again =
+        OdbcWriter::create_table_if_not_exists(&conn, table_name, &schema, SqlDialect::SqlServer)
+            .unwrap();
+    let dropped = OdbcWriter::drop_table_if_exists(&conn, table_name).unwrap();
+    let dropped_again = OdbcWriter::drop_table_if_exists(&conn, table_name).unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "1970-01-01";
-    assert_eq!(expected, actual);
+    assert!(created);
+    assert!(!created_again);
+    assert!(dropped);
+    assert!(!dropped_again);
 }
 
+/// Without a guarded statement to fall back to, the error of `CREATE TABLE` is reported instead of
+/// guessing whether the table exists.
 #[test]
-fn insert_time32_second_array() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn create_existing_table_with_ansi_sql_is_an_error() {
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["TIME(0)"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new(
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+
+    // When
+    let result =
+        OdbcWriter::create_table_if_not_exists(&conn, table_name, &schema, SqlDialect::AnsiSql);
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(WriterError::ExecuteDdlStatement { .. })
+    ));
+}
+
+/// Types which can not be inserted must not be part of the create table statement either
+#[test]
+fn create_table_does_not_support_list_type() {
+    let schema = Schema::new(vec![Field::new(
         "a",
-        DataType::Time32(TimeUnit::Second),
-        false,
-    )]));
-    // Corresponds to single element array with entry 03:05:11
-    let array: Time32SecondArray = [Some(11_111)].into_iter().collect();
+        DataType::List(Arc::new(Field::new("b", DataType::Utf8, true))),
+        true,
+    )]);
+
+    let result = schema_to_create_table(&schema, "MyTable", SqlDialect::AnsiSql);
+
+    assert!(matches!(
+        result,
+        Err(WriterError::UnsupportedArrowDataType(_))
+    ))
+}
+
+#[test]
+fn insert_taking_ownership_of_connection() {
+    // Given a table and a record batch reader returning a batch with a text column.
+    let table_name = function_name!().rsplit_once(':').unwrap().1;
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
+    let array = StringArray::from(vec![Some("Hello"), None, Some("World")]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Utf8, true)]));
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    let reader = StubBatchReader::new(schema.clone(), vec![batch]);
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    let mut inserter = {
+        let conn = ENV
+            .connect_with_connection_string(MSSQL, Default::default())
+            .unwrap();
+        let row_capacity = 50;
+        OdbcWriter::from_connection(conn, &schema, table_name, row_capacity).unwrap()
+    };
+    inserter.write_all(reader).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "03:05:11";
+    let expected = "Hello\nNULL\nWorld";
     assert_eq!(expected, actual);
 }
 
+#[cfg(feature = "async")]
 #[test]
-fn insert_time32_ms_array() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn insert_from_async_writer() {
+    use arrow_odbc::AsyncOdbcWriter;
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Wake, Waker},
+        thread::Thread,
+    };
+
+    /// Minimal executor, so the test does not depend on an async runtime.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct ThreadWaker(Thread);
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark()
+            }
+        }
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    // Given a table and three batches with one row each
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["TIME(3)"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "a",
-        DataType::Time32(TimeUnit::Millisecond),
-        false,
-    )]));
-    // Corresponds to single element array with entry 03:05:11.111
-    let array: Time32MillisecondArray = [Some(11_111_111)].into_iter().collect();
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    setup_empty_table(&conn, table_name, &["INTEGER"]).unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+    let batches = [1, 2, 3].map(|value| {
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![value]))],
+        )
+        .unwrap()
+    });
+
+    // When writing them through a queue which holds only one batch at a time
+    let writer_schema = schema.clone();
+    let mut writer = AsyncOdbcWriter::new(NonZeroUsize::new(1).unwrap(), move || {
+        let conn = ENV
+            .connect_with_connection_string(MSSQL, Default::default())
+            .unwrap();
+        OdbcWriter::from_connection(conn, &writer_schema, table_name, 2)
+    });
+    for batch in batches {
+        block_on(writer.write_batch_async(batch)).unwrap();
+    }
+    block_on(writer.finish()).unwrap();
+
+    // Then
+    let actual = table_to_string(&conn, table_name, &["a"]);
+    assert_eq!("1\n2\n3", actual);
+}
+
+/// Query a table with DataFusion, pushing down the projection and a filter.
+#[cfg(feature = "datafusion")]
+#[tokio::test]
+async fn query_table_with_datafusion() {
+    use arrow_odbc::OdbcTableProvider;
+    use datafusion::prelude::SessionContext;
+
+    // Given. `function_name!` would name the future of an async function.
+    let table_name = "query_table_with_datafusion";
+    let conn = ENV
+        .connect_with_connection_string(MSSQL, Default::default())
+        .unwrap();
+    setup_empty_table(&conn, table_name, &["INTEGER", "VARCHAR(10)"]).unwrap();
+    let sql =
+        format!("INSERT INTO {table_name} (a, b) VALUES (1, 'one'), (2, 'two'), (3, 'three')");
+    conn.execute(&sql, ()).unwrap();
+    let provider = OdbcTableProvider::new(conn, table_name).unwrap();
+    let ctx = SessionContext::new();
+    ctx.register_table("t", Arc::new(provider)).unwrap();
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    let batches = ctx
+        .sql("SELECT b FROM t WHERE a >= 2 ORDER BY a")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "03:05:11.111";
-    assert_eq!(expected, actual);
+    let actual: Vec<_> = batches
+        .iter()
+        .flat_map(|batch| {
+            let array = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap();
+            array
+                .iter()
+                .map(|value| value.unwrap().to_owned())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(["two", "three"], *actual);
 }
 
-#[test]
-fn insert_time64_us_array() {
-    // Given a table and a record batch reader returning a batch with a text column.
-    let table_name = function_name!().rsplit_once(':').unwrap().1;
+/// Literals of pushed down filters are bound as parameters, and scans stop fetching once the limit
+/// is reached.
+#[cfg(feature = "datafusion")]
+#[tokio::test]
+async fn query_table_with_datafusion_limit() {
+    use arrow_odbc::OdbcTableProvider;
+    use datafusion::prelude::SessionContext;
+
+    // Given. `function_name!` would name the future of an async function.
+    let table_name = "query_table_with_datafusion_limit";
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["TIME(6)"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "a",
-        DataType::Time64(TimeUnit::Microsecond),
-        false,
-    )]));
-    // Corresponds to single element array with entry 03:05:11.111111
-    let array: Time64MicrosecondArray = [Some(11_111_111_111)].into_iter().collect();
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    setup_empty_table(&conn, table_name, &["INTEGER", "VARCHAR(10)"]).unwrap();
+    let sql = format!(
+        "INSERT INTO {table_name} (a, b) VALUES (1, 'it''s'), (2, 'two'), (3, 'three'), (4, 'four')"
+    );
+    conn.execute(&sql, ()).unwrap();
+    let provider = OdbcTableProvider::new(conn, table_name).unwrap();
+    let ctx = SessionContext::new();
+    ctx.register_table("t", Arc::new(provider)).unwrap();
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    let batches = ctx
+        .sql("SELECT a FROM t WHERE b <> 'it''s' LIMIT 2")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "03:05:11.111111";
-    assert_eq!(expected, actual);
+    let num_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+    assert_eq!(2, num_rows);
+    for batch in &batches {
+        let array = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert!(array.values().iter().all(|&a| a != 1));
+    }
 }
 
+/// Hand batches to a consumer of the Arrow C stream interface.
+#[cfg(feature = "abi")]
 #[test]
-fn insert_time64_ns_array() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn export_reader_to_c_stream() {
+    use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let conn = ENV
-        .connect_with_connection_string(MSSQL, Default::default())
-        .unwrap();
-    setup_empty_table(&conn, table_name, &["TIME(7)"]).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "a",
-        DataType::Time64(TimeUnit::Nanosecond),
-        false,
-    )]));
-    // Corresponds to single element array with entry 03:05:11.111111111
-    let array: Time64NanosecondArray = [Some(11_111_111_111_111)].into_iter().collect();
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(2),(3)");
+    let reader = OdbcReaderBuilder::new().build(cursor).unwrap();
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    let mut stream = FFI_ArrowArrayStream::empty();
+    unsafe { reader.export_to_c_stream(&mut stream) }.unwrap();
+    let imported = ArrowArrayStreamReader::try_new(stream).unwrap();
+    let batches = imported.collect::<Result<Vec<_>, _>>().unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    // We currently insert nanoseconds with precision 7
-    let expected = "03:05:11.1111111";
-    assert_eq!(expected, actual);
+    assert_eq!(1, batches.len());
+    let array_vals = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!([1, 2, 3], *array_vals.values());
 }
 
+#[cfg(feature = "parquet")]
 #[test]
-fn insert_binary() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn parquet_statistics_of_batch() {
+    use parquet::file::statistics::Statistics;
+
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let conn = ENV
-        .connect_with_connection_string(MSSQL, Default::default())
-        .unwrap();
-    setup_empty_table(&conn, table_name, &["VARBINARY(4096)"]).unwrap();
-    let array = BinaryArray::from(vec![
-        Some([1, 2].as_slice()),
-        None,
-        Some([3, 4, 5, 6, 7].as_slice()),
-    ]);
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Binary, true)]));
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    let cursor = cursor_over(table_name, "INTEGER", "(3),(NULL),(1),(2)");
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    let with_stats = reader.next_with_stats().unwrap().unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "0102\nNULL\n0304050607";
-    assert_eq!(expected, actual);
+    assert_eq!(4, with_stats.batch.num_rows());
+    assert_eq!(1, with_stats.column_chunks.len());
+    let chunk = &with_stats.column_chunks[0];
+    assert_eq!(4, chunk.num_values());
+    let Some(Statistics::Int32(statistics)) = chunk.statistics() else {
+        panic!("Expected Int32 statistics")
+    };
+    assert_eq!(1, *statistics.min());
+    assert_eq!(3, *statistics.max());
+    assert_eq!(1, statistics.null_count());
+    assert!(reader.next_with_stats().is_none());
 }
 
+#[cfg(feature = "abi")]
 #[test]
-fn insert_fixed_binary() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn reader_into_heap_allocated_c_stream() {
+    use arrow::ffi_stream::ArrowArrayStreamReader;
+    use arrow_odbc::free_c_stream;
+
+    // Given
     let table_name = function_name!().rsplit_once(':').unwrap().1;
-    let conn = ENV
-        .connect_with_connection_string(MSSQL, Default::default())
-        .unwrap();
-    setup_empty_table(&conn, table_name, &["VARBINARY(4096)"]).unwrap();
-    let array = BinaryArray::from(vec![
-        Some([1, 2].as_slice()),
-        None,
-        Some([3, 4, 5, 6, 7].as_slice()),
-    ]);
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Binary, true)]));
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    let cursor = cursor_over(table_name, "INTEGER", "(1),(2),(3)");
+    let reader = OdbcReaderBuilder::new().build(cursor).unwrap();
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    let stream = reader.into_c_stream();
+    let imported = unsafe { ArrowArrayStreamReader::from_raw(stream) }.unwrap();
+    unsafe { free_c_stream(stream) };
+    let batches = imported.collect::<Result<Vec<_>, _>>().unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "0102\nNULL\n0304050607";
-    assert_eq!(expected, actual);
+    assert_eq!(1, batches.len());
+    let array_vals = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!([1, 2, 3], *array_vals.values());
 }
 
 #[test]
-fn insert_decimal_128() {
+fn insert_large_text() {
     // Given a table and a record batch reader returning a batch with a text column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["NUMERIC(5,3)"]).unwrap();
-    let array: Decimal128Array = [Some(12345), None, Some(67891), Some(1), Some(1000)]
-        .into_iter()
-        .collect();
-    let array = array.with_precision_and_scale(5, 3).unwrap();
+    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
+    let array = LargeStringArray::from(vec![Some("Hello"), None, Some("World")]);
     let schema = Arc::new(Schema::new(vec![Field::new(
         "a",
-        DataType::Decimal128(5, 3),
+        DataType::LargeUtf8,
         true,
     )]));
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
     let mut reader = StubBatchReader::new(schema, vec![batch]);
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 2).unwrap();
+    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "12.345\nNULL\n67.891\n.001\n1.000";
+    let expected = "Hello\nNULL\nWorld";
     assert_eq!(expected, actual);
 }
 
 #[test]
-fn insert_decimal_256() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn insert_utf8_view() {
+    // Given a table and a record batch reader returning a batch with a string view column. One
+    // string is short enough to be inlined into the view, the other one is not.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["NUMERIC(5,3)"]).unwrap();
-    let mut builder = Decimal256Builder::new();
-    let mut bytes = [0u8; 32];
-    type I256 = <Decimal256Type as ArrowPrimitiveType>::Native;
-    bytes[0..4].copy_from_slice(12345i32.to_le_bytes().as_slice());
-    builder.append_value(I256::from_le_bytes(bytes));
-    builder.append_null();
-    let array = builder.finish().with_precision_and_scale(5, 3).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "a",
-        DataType::Decimal256(5, 3),
-        true,
-    )]));
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
+    let array = StringViewArray::from(vec![
+        Some("Hello"),
+        None,
+        Some("A string too long to be inlined"),
+    ]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Utf8View, true)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array.clone())]).unwrap();
+    let mut reader = StubBatchReader::new(schema.clone(), vec![batch]);
 
     // When
     insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "12.345\nNULL";
-    assert_eq!(expected, actual);
+    let cursor = conn
+        .execute(&format!("SELECT a FROM {table_name} ORDER BY id"), ())
+        .unwrap()
+        .unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let record_batch = reader.next().unwrap().unwrap();
+    let actual = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringViewArray>()
+        .unwrap();
+    assert_eq!(&array, actual);
 }
 
+/// Rows with an existing key are updated, all others are inserted.
 #[test]
-fn insert_decimal_128_with_negative_scale() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn merge_into_table() {
+    // Given a table with one row, and a batch updating that row and adding another one
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["NUMERIC(5,0)"]).unwrap();
-    let array: Decimal128Array = [Some(123), None, Some(456), Some(1), Some(10)]
-        .into_iter()
-        .collect();
-    let array = array.with_precision_and_scale(3, -2).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "a",
-        DataType::Decimal128(3, -2),
-        true,
-    )]));
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    setup_empty_table(&conn, table_name, &["INTEGER NOT NULL", "VARCHAR(50)"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a, b) VALUES (1, 'old')"),
+        (),
+    )
+    .unwrap();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, false),
+        Field::new("b", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int32Array::from(vec![1, 2])),
+            Arc::new(StringArray::from(vec!["new", "two"])),
+        ],
+    )
+    .unwrap();
 
     // When
-    insert_into_table(&conn, &mut reader, table_name, 2).unwrap();
+    let mut writer = OdbcWriter::new_merge(&conn, table_name, &["a"], &schema, 5).unwrap();
+    writer.write_batch(&batch).unwrap();
+    writer.flush().unwrap();
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "12300\nNULL\n45600\n100\n1000";
-    assert_eq!(expected, actual);
+    let cursor = conn
+        .execute(&format!("SELECT a, b FROM {table_name} ORDER BY a"), ())
+        .unwrap()
+        .unwrap();
+    assert_eq!("1,new\n2,two", cursor_to_string(cursor));
 }
 
 #[test]
-fn insert_decimal_256_with_negative_scale() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn paginated_reader() {
+    // Given a table with five rows
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["NUMERIC(5,0)"]).unwrap();
-    let mut builder = Decimal256Builder::new();
-    let mut bytes = [0u8; 32];
-    type I256 = <Decimal256Type as ArrowPrimitiveType>::Native;
-    bytes[0..4].copy_from_slice(123i32.to_le_bytes().as_slice());
-    builder.append_value(I256::from_le_bytes(bytes));
-    builder.append_null();
-    let array = builder.finish().with_precision_and_scale(3, -2).unwrap();
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "a",
-        DataType::Decimal256(3, -2),
-        true,
-    )]));
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let mut reader = StubBatchReader::new(schema, vec![batch]);
+    setup_empty_table(&conn, table_name, &["INTEGER NOT NULL"]).unwrap();
+    conn.execute(
+        &format!("INSERT INTO {table_name} (a) VALUES (1), (2), (3), (4), (5)"),
+        (),
+    )
+    .unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
 
-    // When
-    insert_into_table(&conn, &mut reader, table_name, 5).unwrap();
+    // When reading it in pages of two rows
+    let sql = format!("SELECT a FROM {table_name} ORDER BY a");
+    let reader =
+        OdbcPaginatedReader::new(&conn, &sql, schema, 2, &OdbcReaderBuilder::new()).unwrap();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
 
-    // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "12300\nNULL";
-    assert_eq!(expected, actual);
+    // Then each page is emitted as a batch, with the last one holding the remainder
+    let values: Vec<Vec<i32>> = batches
+        .iter()
+        .map(|batch| {
+            batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap()
+                .values()
+                .to_vec()
+        })
+        .collect();
+    assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5]], values);
 }
 
+/// A page size of zero would never make progress through the result set.
 #[test]
-fn insert_taking_ownership_of_connection() {
-    // Given a table and a record batch reader returning a batch with a text column.
-    let table_name = function_name!().rsplit_once(':').unwrap().1;
+fn paginated_reader_rejects_zero_batch_size() {
+    // Given
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
-    let array = StringArray::from(vec![Some("Hello"), None, Some("World")]);
-    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Utf8, true)]));
-    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
-    let reader = StubBatchReader::new(schema.clone(), vec![batch]);
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
 
     // When
-    let mut inserter = {
-        let conn = ENV
-            .connect_with_connection_string(MSSQL, Default::default())
-            .unwrap();
-        let row_capacity = 50;
-        OdbcWriter::from_connection(conn, &schema, table_name, row_capacity).unwrap()
-    };
-    inserter.write_all(reader).unwrap();
+    let result = OdbcPaginatedReader::new(
+        &conn,
+        "SELECT 1 AS a ORDER BY a",
+        schema,
+        0,
+        &OdbcReaderBuilder::new(),
+    );
 
     // Then
-    let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "Hello\nNULL\nWorld";
-    assert_eq!(expected, actual);
+    assert!(matches!(result, Err(Error::ZeroPageSize)));
 }
 
 #[test]
-fn insert_large_text() {
-    // Given a table and a record batch reader returning a batch with a text column.
+fn insert_binary_view() {
+    // Given a table and a record batch reader returning a batch with a binary view column.
     let table_name = function_name!().rsplit_once(':').unwrap().1;
     let conn = ENV
         .connect_with_connection_string(MSSQL, Default::default())
         .unwrap();
-    setup_empty_table(&conn, table_name, &["VARCHAR(4096)"]).unwrap();
-    let array = LargeStringArray::from(vec![Some("Hello"), None, Some("World")]);
+    setup_empty_table(&conn, table_name, &["VARBINARY(4096)"]).unwrap();
+    let array = BinaryViewArray::from(vec![
+        Some([1, 2].as_slice()),
+        None,
+        Some([3, 4, 5, 6, 7].as_slice()),
+    ]);
     let schema = Arc::new(Schema::new(vec![Field::new(
         "a",
-        DataType::LargeUtf8,
+        DataType::BinaryView,
         true,
     )]));
     let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
@@ -1982,7 +5295,7 @@ fn insert_large_text() {
 
     // Then
     let actual = table_to_string(&conn, table_name, &["a"]);
-    let expected = "Hello\nNULL\nWorld";
+    let expected = "0102\nNULL\n0304050607";
     assert_eq!(expected, actual);
 }
 
@@ -0,0 +1,337 @@
+//! Tests running against an in-memory SQLite database. In contrast to the tests in
+//! `integration.rs` these do not require a database server to be running, only the SQLite ODBC
+//! driver (<http://www.ch-werner.de/sqliteodbc/>) to be installed and registered under the name
+//! `SQLite3`. On Ubuntu this is the case after running `sudo apt-get install libsqliteodbc`.
+//!
+//! Run these tests with `cargo test --features integration-tests --test integration_sqlite`.
+#![cfg(feature = "integration-tests")]
+
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Date32Array, Float32Array, Float64Array, Int16Array,
+        Int32Array, Int64Array, LargeStringArray, StringArray, TimestampSecondArray, UInt64Array,
+    },
+    datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit},
+    record_batch::{RecordBatch, RecordBatchReader},
+};
+use lazy_static::lazy_static;
+
+use arrow_odbc::{
+    odbc_api::{Connection, ConnectionOptions, Environment},
+    ColumnFailure, Error, OdbcReaderBuilder, SpannerCompatibility,
+};
+
+/// Each connection to this data source creates a fresh, empty database living in memory.
+const SQLITE: &str = "Driver={SQLite3};Database=:memory:;";
+
+// Rust by default executes tests in parallel. Yet only one environment is allowed at a time.
+lazy_static! {
+    static ref ENV: Environment = Environment::new().unwrap();
+}
+
+/// Fetch a table containing a column for every Arrow type supported by the reader and compare the
+/// result with the expected record batch.
+#[test]
+fn fetch_all_supported_types() {
+    // Given a table with one row of values and one row of NULLs
+    let conn = connect();
+    conn.execute(
+        "CREATE TABLE AllTypes (\
+            a BIT, b SMALLINT, c INTEGER, d BIGINT, e REAL, f DOUBLE, g VARCHAR(10), h DATE, \
+            i TIMESTAMP\
+        )",
+        (),
+    )
+    .unwrap();
+    conn.execute(
+        "INSERT INTO AllTypes VALUES \
+            (1, 16, 32, 64, 1.5, 2.5, 'Hello', '2021-04-09', '2021-04-09 18:57:50'), \
+            (NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL)",
+        (),
+    )
+    .unwrap();
+
+    // When reading it with an explicit schema
+    let schema = all_types_schema();
+    let cursor = conn
+        .execute("SELECT a, b, c, d, e, f, g, h, i FROM AllTypes", ())
+        .unwrap()
+        .unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(10)
+        .with_schema(schema.clone())
+        .build(cursor)
+        .unwrap();
+    let actual = reader.next().unwrap().unwrap();
+
+    // Then the record batch must match the table contents exactly
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(BooleanArray::from(vec![Some(true), None])),
+        Arc::new(Int16Array::from(vec![Some(16), None])),
+        Arc::new(Int32Array::from(vec![Some(32), None])),
+        Arc::new(Int64Array::from(vec![Some(64), None])),
+        Arc::new(Float32Array::from(vec![Some(1.5), None])),
+        Arc::new(Float64Array::from(vec![Some(2.5), None])),
+        Arc::new(StringArray::from(vec![Some("Hello"), None])),
+        // 2021-04-09
+        Arc::new(Date32Array::from(vec![Some(18726), None])),
+        // 2021-04-09 18:57:50
+        Arc::new(TimestampSecondArray::from(vec![Some(1_617_994_670), None])),
+    ];
+    let expected = RecordBatch::try_new(schema, columns).unwrap();
+    assert_eq!(expected, actual);
+    assert!(reader.next().is_none());
+}
+
+/// A query returning no rows must yield no batches, but still report the schema.
+#[test]
+fn fetch_empty_result_set() {
+    // Given an empty table
+    let conn = connect();
+    conn.execute("CREATE TABLE Empty (a INTEGER)", ()).unwrap();
+
+    // When reading it
+    let cursor = conn.execute("SELECT a FROM Empty", ()).unwrap().unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(10)
+        .build(cursor)
+        .unwrap();
+
+    // Then no batch is emitted
+    assert_eq!(1, reader.schema().fields().len());
+    assert!(reader.next().is_none());
+}
+
+/// NULL indicators of a nullable `BIT` column must result in nulls at exactly the same positions,
+/// rather than being materialized as `false`.
+#[test]
+fn fetch_nullable_boolean_null_positions() {
+    // Given a BIT column with NULLs at the first, in between and at the last position
+    let conn = connect();
+    conn.execute("CREATE TABLE Flags (id INTEGER, a BIT)", ())
+        .unwrap();
+    conn.execute(
+        "INSERT INTO Flags VALUES (1, NULL), (2, 1), (3, 0), (4, NULL), (5, 0), (6, NULL)",
+        (),
+    )
+    .unwrap();
+
+    // When reading it into a nullable boolean field
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Boolean, true)]));
+    let cursor = conn
+        .execute("SELECT a FROM Flags ORDER BY id", ())
+        .unwrap()
+        .unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(10)
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then nulls are reported at exactly the positions of the NULL values
+    let expected = BooleanArray::from(vec![None, Some(true), Some(false), None, Some(false), None]);
+    let actual = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<BooleanArray>()
+        .unwrap();
+    assert_eq!(&expected, actual);
+    assert_eq!(3, actual.null_count());
+}
+
+/// Columns of a JSON type are inferred as `Utf8` annotated with the canonical `arrow.json`
+/// extension type, so downstream engines are aware the text holds JSON documents.
+#[test]
+fn infer_json_column_as_json_extension() {
+    // Given a table with a JSON column
+    let conn = connect();
+    conn.execute("CREATE TABLE Documents (a JSON)", ()).unwrap();
+    conn.execute(
+        "INSERT INTO Documents VALUES ('{\"answer\":42}'), (NULL)",
+        (),
+    )
+    .unwrap();
+
+    // When reading it with an inferred schema
+    let cursor = conn
+        .execute("SELECT a FROM Documents", ())
+        .unwrap()
+        .unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(10)
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then the field is annotated as JSON and the documents are fetched as text
+    let field = batch.schema().field(0).clone();
+    assert_eq!(&DataType::Utf8, field.data_type());
+    assert_eq!(
+        Some("arrow.json"),
+        field
+            .metadata()
+            .get("ARROW:extension:name")
+            .map(String::as_str)
+    );
+    let expected = StringArray::from(vec![Some("{\"answer\":42}"), None]);
+    let actual = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(&expected, actual);
+}
+
+/// Spanner compatibility reads JSON columns as `LargeUtf8` and normalizes the documents, so equal
+/// documents compare equal as text.
+#[test]
+fn normalize_json_documents_with_spanner_compatibility() {
+    // Given a table with a JSON column holding documents with unsorted keys and whitespace
+    let conn = connect();
+    conn.execute("CREATE TABLE Documents (a JSON)", ()).unwrap();
+    conn.execute(
+        "INSERT INTO Documents VALUES ('{ \"b\": 1, \"a\": [true, null] }'), (NULL)",
+        (),
+    )
+    .unwrap();
+
+    // When reading it with Spanner compatibility and normalization enabled
+    let cursor = conn
+        .execute("SELECT a FROM Documents", ())
+        .unwrap()
+        .unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .with_max_num_rows_per_batch(10)
+        .with_spanner_compatibility(SpannerCompatibility {
+            normalize_json: true,
+        })
+        .build(cursor)
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then the field is a JSON annotated `LargeUtf8` holding the canonical documents
+    let field = batch.schema().field(0).clone();
+    assert_eq!(&DataType::LargeUtf8, field.data_type());
+    assert_eq!(
+        Some("arrow.json"),
+        field
+            .metadata()
+            .get("ARROW:extension:name")
+            .map(String::as_str)
+    );
+    let expected = LargeStringArray::from(vec![Some("{\"a\":[true,null],\"b\":1}"), None]);
+    let actual = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!(&expected, actual);
+}
+
+/// Casting infinity or NaN to an integer would silently yield a bogus value, so fetching them into
+/// a decimal must fail instead.
+#[test]
+fn fetch_infinite_double_into_decimal() {
+    // Given a DOUBLE column holding positive infinity. SQLite yields it for literals too large to
+    // represent.
+    let conn = connect();
+    conn.execute("CREATE TABLE Measurements (a DOUBLE)", ())
+        .unwrap();
+    conn.execute("INSERT INTO Measurements VALUES (9e999)", ())
+        .unwrap();
+
+    // When reading it into a decimal field
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Decimal128(10, 2),
+        true,
+    )]));
+    let cursor = conn
+        .execute("SELECT a FROM Measurements", ())
+        .unwrap()
+        .unwrap();
+    let mut reader = OdbcReaderBuilder::new()
+        .with_schema(schema)
+        .build(cursor)
+        .unwrap();
+    let result = reader.next().unwrap();
+
+    // Then an error is reported rather than a value
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("'inf'"), "{error}");
+}
+
+/// The SQLite driver reports columns declared `UNSIGNED` as such. Their values are reinterpreted,
+/// so a negative number in the signed transit buffer becomes a large unsigned one.
+#[test]
+fn infer_unsigned_bigint_as_uint64() {
+    // Given a BIGINT UNSIGNED column. SQLite stores 64 Bit signed integers, so `-1` stands in for
+    // the maximum of the unsigned type.
+    let conn = connect();
+    conn.execute("CREATE TABLE Counters (id INTEGER, a BIGINT UNSIGNED)", ())
+        .unwrap();
+    conn.execute("INSERT INTO Counters VALUES (1, 42), (2, -1)", ())
+        .unwrap();
+
+    // When
+    let cursor = conn
+        .execute("SELECT a FROM Counters ORDER BY id", ())
+        .unwrap()
+        .unwrap();
+    let mut reader = OdbcReaderBuilder::new().build(cursor).unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    // Then
+    assert_eq!(&DataType::UInt64, reader.schema().field(0).data_type());
+    let array = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap();
+    assert_eq!([42, u64::MAX], array.values()[..]);
+}
+
+/// Fetching an unsigned column into a signed field would turn large values negative.
+#[test]
+fn fetch_unsigned_bigint_into_int64_is_rejected() {
+    // Given
+    let conn = connect();
+    conn.execute("CREATE TABLE Counters (a BIGINT UNSIGNED)", ())
+        .unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    let cursor = conn.execute("SELECT a FROM Counters", ()).unwrap().unwrap();
+
+    // When
+    let result = OdbcReaderBuilder::new().with_schema(schema).build(cursor);
+
+    // Then
+    assert!(matches!(
+        result,
+        Err(Error::ColumnFailure {
+            source: ColumnFailure::SignedUnsignedMismatch,
+            ..
+        })
+    ));
+}
+
+fn connect() -> Connection<'static> {
+    ENV.connect_with_connection_string(SQLITE, ConnectionOptions::default())
+        .unwrap()
+}
+
+fn all_types_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Boolean, true),
+        Field::new("b", DataType::Int16, true),
+        Field::new("c", DataType::Int32, true),
+        Field::new("d", DataType::Int64, true),
+        Field::new("e", DataType::Float32, true),
+        Field::new("f", DataType::Float64, true),
+        Field::new("g", DataType::Utf8, true),
+        Field::new("h", DataType::Date32, true),
+        Field::new("i", DataType::Timestamp(TimeUnit::Second, None), true),
+    ]))
+}